@@ -1,23 +1,157 @@
-use anyhow::{Context, Result};
+use crate::pulse_info;
+use crate::virtual_device::{COMBINE_SINK_NAME, VIRTUAL_SINK_NAME};
+use anyhow::{bail, Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use crossbeam_channel::Sender;
+use crossbeam_channel::{Receiver, Sender};
 use log::{info, warn};
 use ringbuf::traits::{Consumer, Observer, Producer, Split};
-use ringbuf::HeapRb;
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use serde::{Deserialize, Serialize};
 
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
-use voidmic_core::DenoiseState;
-use voidmic_core::VoidProcessor;
+use voidmic_core::frame_adapter::MAX_CHANNELS;
+use voidmic_core::processor::GateEvent;
+use voidmic_core::{LinearResampler, PartitionedConvolver, ProcessorControls, VoidProcessor};
 
-fn resolve_device(
-    host: &cpal::Host,
-    name: &str,
-    is_input: bool,
-) -> Result<cpal::Device> {
+/// Which cpal host to open devices on.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioBackend {
+    /// The platform default (ALSA/PulseAudio on Linux, WASAPI, CoreAudio)
+    #[default]
+    Default,
+    /// ALSA directly (Linux); lists hardware devices the default host may hide
+    Alsa,
+    /// JACK, so VoidMic shows up as JACK ports (Linux, `jack` feature)
+    Jack,
+    /// WASAPI (Windows)
+    Wasapi,
+    /// ASIO drivers (Windows, `asio` feature)
+    Asio,
+    /// CoreAudio (macOS)
+    #[value(name = "coreaudio")]
+    CoreAudio,
+}
+
+impl AudioBackend {
+    pub const ALL: [AudioBackend; 6] = [
+        AudioBackend::Default,
+        AudioBackend::Alsa,
+        AudioBackend::Jack,
+        AudioBackend::Wasapi,
+        AudioBackend::Asio,
+        AudioBackend::CoreAudio,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AudioBackend::Default => "System Default",
+            AudioBackend::Alsa => "ALSA",
+            AudioBackend::Jack => "JACK",
+            AudioBackend::Wasapi => "WASAPI",
+            AudioBackend::Asio => "ASIO",
+            AudioBackend::CoreAudio => "CoreAudio",
+        }
+    }
+
+    /// The cpal host behind this backend, if this build has it; `None` for `Default`.
+    fn host_id(self) -> Option<cpal::HostId> {
+        match self {
+            AudioBackend::Default => None,
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd"))]
+            AudioBackend::Alsa => Some(cpal::HostId::Alsa),
+            #[cfg(all(
+                any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd"),
+                feature = "jack"
+            ))]
+            AudioBackend::Jack => Some(cpal::HostId::Jack),
+            #[cfg(target_os = "windows")]
+            AudioBackend::Wasapi => Some(cpal::HostId::Wasapi),
+            #[cfg(all(target_os = "windows", feature = "asio"))]
+            AudioBackend::Asio => Some(cpal::HostId::Asio),
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            AudioBackend::CoreAudio => Some(cpal::HostId::CoreAudio),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Backends to offer: `Default` plus every host cpal reports as available here.
+    /// Some devices only appear under a specific host (e.g. ASIO interfaces).
+    pub fn available() -> Vec<AudioBackend> {
+        Self::ALL
+            .into_iter()
+            .filter(|backend| backend.is_available())
+            .collect()
+    }
+
+    /// Whether this build and system offer the backend. A saved backend can stop
+    /// being available (e.g. after switching to a build without JACK); `audio_host`
+    /// then falls back to the default host.
+    pub fn is_available(self) -> bool {
+        match self.host_id() {
+            None => self == AudioBackend::Default,
+            Some(id) => cpal::available_hosts().contains(&id),
+        }
+    }
+}
+
+/// Opens the cpal host for `backend`, falling back to the default host (with a
+/// warning) if it isn't compiled in or its server isn't running.
+pub fn audio_host(backend: AudioBackend) -> cpal::Host {
+    if backend == AudioBackend::Default {
+        return cpal::default_host();
+    }
+    match backend.host_id() {
+        Some(id) => match cpal::host_from_id(id) {
+            Ok(host) => return host,
+            Err(e) => warn!(
+                "{} unavailable ({}), falling back to default host",
+                backend.label(),
+                e
+            ),
+        },
+        None => warn!(
+            "Built without {} support, falling back to default host",
+            backend.label()
+        ),
+    }
+    cpal::default_host()
+}
+
+/// Ring buffer capacity between the device callbacks and the processing thread.
+pub const ENGINE_BUFFER_MS: u32 = 100;
+/// Ring buffer capacity of the output filter (playback tolerates more delay).
+pub const OUTPUT_FILTER_BUFFER_MS: u32 = 200;
+
+/// One processed frame, as handed to a recording tap.
+pub type RecordFrame = [f32; FRAME_SIZE];
+
+/// Worst-case mic delay implied by the buffer sizes: a full input ring, one processing
+/// frame and a full output ring. The output filter sits on the playback path, so its
+/// buffer doesn't count.
+pub fn estimated_latency_ms() -> f32 {
+    2.0 * ENGINE_BUFFER_MS as f32 + FRAME_SIZE as f32 * 1000.0 / SAMPLE_RATE as f32
+}
+
+/// Splits a comma-separated device list (`"VoidMic_Clean,Headphones"`) into names.
+/// The first entry is the primary device.
+pub fn split_device_list(names: &str) -> Vec<&str> {
+    names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Finds the input or output device called `name` on `host`; `default` is the host's
+/// default device.
+pub fn resolve_device(host: &cpal::Host, name: &str, is_input: bool) -> Result<cpal::Device> {
     if name == "default" {
         if is_input {
             host.default_input_device()
@@ -42,6 +176,431 @@ fn resolve_device(
     }
 }
 
+/// Whether `device` can be opened at 48kHz, the rate the engine runs its streams at.
+/// False if the device can't be queried.
+pub fn supports_engine_rate(device: &cpal::Device, is_input: bool) -> bool {
+    let configs: Vec<cpal::SupportedStreamConfigRange> = if is_input {
+        device.supported_input_configs().map(Iterator::collect)
+    } else {
+        device.supported_output_configs().map(Iterator::collect)
+    }
+    .unwrap_or_default();
+    configs
+        .iter()
+        .any(|c| c.min_sample_rate().0 <= SAMPLE_RATE && c.max_sample_rate().0 >= SAMPLE_RATE)
+}
+
+/// Output channels to open on `device` at 48kHz, so the mono signal can be upmixed
+/// for stereo-only devices. Falls back to mono if the device can't be queried.
+fn output_channels(device: &cpal::Device, dual_mono: bool) -> u16 {
+    let offered: Vec<u16> = device
+        .supported_output_configs()
+        .map(|configs| {
+            configs
+                .filter(|c| {
+                    c.min_sample_rate().0 <= SAMPLE_RATE && c.max_sample_rate().0 >= SAMPLE_RATE
+                })
+                .map(|c| c.channels())
+                .collect()
+        })
+        .unwrap_or_default();
+    pick_output_channels(&offered, dual_mono)
+}
+
+/// Input channels to open on `device` at 48kHz (see [`pick_input_channels`]). Falls back
+/// to mono if the device can't be queried.
+fn input_channels(device: &cpal::Device, stereo: bool) -> u16 {
+    let offered: Vec<u16> = device
+        .supported_input_configs()
+        .map(|configs| {
+            configs
+                .filter(|c| {
+                    c.min_sample_rate().0 <= SAMPLE_RATE && c.max_sample_rate().0 >= SAMPLE_RATE
+                })
+                .map(|c| c.channels())
+                .collect()
+        })
+        .unwrap_or_default();
+    pick_input_channels(&offered, stereo)
+}
+
+/// Picks an input channel count from those a device offers: mono when offered, else the
+/// fewest on offer. With `stereo` set, stereo is preferred when offered, so each side of
+/// a stereo mic is processed (and metered) on its own.
+fn pick_input_channels(offered: &[u16], stereo: bool) -> u16 {
+    if stereo && offered.contains(&2) {
+        return 2;
+    }
+    if offered.contains(&1) {
+        return 1;
+    }
+    offered.iter().copied().min().unwrap_or(1).max(1)
+}
+
+/// Picks a channel count from those a device offers: the fewest, or stereo when
+/// `dual_mono` is set and the device offers exactly that. A mono stream into the stereo virtual
+/// sink is left to the server's channel mapping and can end up on the left only, so
+/// that sink gets the signal duplicated to both channels by [`upmix_mono`] instead.
+fn pick_output_channels(offered: &[u16], dual_mono: bool) -> u16 {
+    if dual_mono && offered.contains(&2) {
+        return 2;
+    }
+    offered.iter().copied().min().unwrap_or(1).max(1)
+}
+
+/// Fills an interleaved buffer with `channels` channels from a mono signal, copying
+/// each sample to every channel. Frames the signal runs out for are silent.
+fn upmix_mono(mono: impl IntoIterator<Item = f32>, interleaved: &mut [f32], channels: usize) {
+    let mut mono = mono.into_iter();
+    for frame in interleaved.chunks_mut(channels.max(1)) {
+        frame.fill(mono.next().unwrap_or(0.0));
+    }
+}
+
+/// RMS of a block of samples; 0 for an empty one.
+fn block_rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// What an output callback plays for samples the processing thread hasn't delivered yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnderrunFill {
+    /// Zeros; clicks when the signal was far from zero
+    #[default]
+    Silence,
+    /// Repeat the last sample played
+    Hold,
+    /// Decay from the last sample to silence over a few milliseconds
+    Fade,
+}
+
+// Time constant of the `UnderrunFill::Fade` decay
+const UNDERRUN_FADE_MS: f32 = 5.0;
+
+impl UnderrunFill {
+    pub const ALL: [UnderrunFill; 3] = [
+        UnderrunFill::Silence,
+        UnderrunFill::Hold,
+        UnderrunFill::Fade,
+    ];
+
+    /// Decodes the value stored in `AudioEngine::underrun_fill`; unknown values fall back
+    /// to `Silence`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => UnderrunFill::Hold,
+            2 => UnderrunFill::Fade,
+            _ => UnderrunFill::Silence,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UnderrunFill::Silence => tr!("advanced.underrun_silence"),
+            UnderrunFill::Hold => tr!("advanced.underrun_hold"),
+            UnderrunFill::Fade => tr!("advanced.underrun_fade"),
+        }
+    }
+}
+
+/// Per-stream state for `UnderrunFill`: the last sample played and the fade so far.
+struct UnderrunFiller {
+    last: f32,
+    gain: f32,
+    // Per-sample decay factor for `Fade` at the stream's rate
+    fade_step: f32,
+}
+
+impl UnderrunFiller {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            last: 0.0,
+            gain: 1.0,
+            fade_step: (-1000.0 / (UNDERRUN_FADE_MS * sample_rate as f32)).exp(),
+        }
+    }
+
+    /// Passes a delivered sample through, or stands in for a missing one.
+    fn next(&mut self, sample: Option<f32>, fill: UnderrunFill) -> f32 {
+        if let Some(sample) = sample {
+            self.last = sample;
+            self.gain = 1.0;
+            return sample;
+        }
+        match fill {
+            UnderrunFill::Silence => 0.0,
+            UnderrunFill::Hold => self.last,
+            UnderrunFill::Fade => {
+                self.gain *= self.fade_step;
+                self.last * self.gain
+            }
+        }
+    }
+}
+
+/// Converts a signed 16-bit sample to f32 in `-1.0..1.0`.
+fn i16_to_f32(sample: i16) -> f32 {
+    sample as f32 / 32768.0
+}
+
+/// Converts an f32 sample to signed 16-bit, clipping anything outside `-1.0..1.0`.
+fn f32_to_i16(sample: f32) -> i16 {
+    (sample * 32768.0).round().clamp(-32768.0, 32767.0) as i16
+}
+
+/// Converts an unsigned 16-bit sample (silence at 32768) to f32 in `-1.0..1.0`.
+fn u16_to_f32(sample: u16) -> f32 {
+    i16_to_f32((sample as i32 - 32768) as i16)
+}
+
+/// Converts an f32 sample to unsigned 16-bit, clipping like `f32_to_i16`.
+fn f32_to_u16(sample: f32) -> u16 {
+    (f32_to_i16(sample) as i32 + 32768) as u16
+}
+
+/// Picks the sample format to open a stream with from those a device supports: f32 when
+/// offered, else a 16-bit format we convert. Anything else is left to cpal to reject.
+fn pick_sample_format(supported: &[cpal::SampleFormat]) -> cpal::SampleFormat {
+    [
+        cpal::SampleFormat::F32,
+        cpal::SampleFormat::I16,
+        cpal::SampleFormat::U16,
+    ]
+    .into_iter()
+    .find(|format| supported.contains(format))
+    .unwrap_or(cpal::SampleFormat::F32)
+}
+
+fn device_sample_format(device: &cpal::Device, input: bool) -> cpal::SampleFormat {
+    let supported: Vec<cpal::SampleFormat> = if input {
+        device
+            .supported_input_configs()
+            .map(|configs| configs.map(|c| c.sample_format()).collect())
+    } else {
+        device
+            .supported_output_configs()
+            .map(|configs| configs.map(|c| c.sample_format()).collect())
+    }
+    .unwrap_or_default();
+    let format = pick_sample_format(&supported);
+    if format != cpal::SampleFormat::F32 {
+        info!(
+            "{} does not take f32 samples, converting from {:?}",
+            device.name().unwrap_or_default(),
+            format
+        );
+    }
+    format
+}
+
+// Longest device block converted in one piece; longer ones are converted in several
+const CONVERSION_BUFFER_MS: usize = 1000;
+
+/// Scratch buffer for converting device blocks to or from f32, allocated up front so the
+/// callbacks never grow it. Holds whole frames of `config`.
+fn conversion_buffer(config: &cpal::StreamConfig) -> Vec<f32> {
+    let frames = config.sample_rate.0 as usize * CONVERSION_BUFFER_MS / 1000;
+    vec![0.0; frames.max(1) * config.channels.max(1) as usize]
+}
+
+/// Opens a capture stream that hands `callback` f32 samples, whatever format the device
+/// delivers. Stream errors are logged prefixed with `error_label`.
+fn build_input_stream_f32(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut callback: impl FnMut(&[f32]) + Send + 'static,
+    error_label: &'static str,
+) -> Result<cpal::Stream> {
+    let on_error = move |err: cpal::StreamError| warn!("{}: {}", error_label, err);
+    let stream = match device_sample_format(device, true) {
+        cpal::SampleFormat::I16 => {
+            let mut converted = conversion_buffer(config);
+            device.build_input_stream(
+                config,
+                move |data: &[i16], _| {
+                    for chunk in data.chunks(converted.len()) {
+                        let block = &mut converted[..chunk.len()];
+                        for (dst, &src) in block.iter_mut().zip(chunk) {
+                            *dst = i16_to_f32(src);
+                        }
+                        callback(block);
+                    }
+                },
+                on_error,
+                None,
+            )?
+        }
+        cpal::SampleFormat::U16 => {
+            let mut converted = conversion_buffer(config);
+            device.build_input_stream(
+                config,
+                move |data: &[u16], _| {
+                    for chunk in data.chunks(converted.len()) {
+                        let block = &mut converted[..chunk.len()];
+                        for (dst, &src) in block.iter_mut().zip(chunk) {
+                            *dst = u16_to_f32(src);
+                        }
+                        callback(block);
+                    }
+                },
+                on_error,
+                None,
+            )?
+        }
+        _ => device.build_input_stream(
+            config,
+            move |data: &[f32], _| callback(data),
+            on_error,
+            None,
+        )?,
+    };
+    Ok(stream)
+}
+
+/// Opens a playback stream that `callback` fills with f32 samples, converted to the
+/// format the device takes. Stream errors are logged prefixed with `error_label`.
+fn build_output_stream_f32(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut callback: impl FnMut(&mut [f32]) + Send + 'static,
+    error_label: &'static str,
+) -> Result<cpal::Stream> {
+    let on_error = move |err: cpal::StreamError| warn!("{}: {}", error_label, err);
+    let stream = match device_sample_format(device, false) {
+        cpal::SampleFormat::I16 => {
+            let mut pending = conversion_buffer(config);
+            device.build_output_stream(
+                config,
+                move |data: &mut [i16], _| {
+                    for chunk in data.chunks_mut(pending.len()) {
+                        let block = &mut pending[..chunk.len()];
+                        callback(block);
+                        for (dst, &src) in chunk.iter_mut().zip(block.iter()) {
+                            *dst = f32_to_i16(src);
+                        }
+                    }
+                },
+                on_error,
+                None,
+            )?
+        }
+        cpal::SampleFormat::U16 => {
+            let mut pending = conversion_buffer(config);
+            device.build_output_stream(
+                config,
+                move |data: &mut [u16], _| {
+                    for chunk in data.chunks_mut(pending.len()) {
+                        let block = &mut pending[..chunk.len()];
+                        callback(block);
+                        for (dst, &src) in chunk.iter_mut().zip(block.iter()) {
+                            *dst = f32_to_u16(src);
+                        }
+                    }
+                },
+                on_error,
+                None,
+            )?
+        }
+        _ => device.build_output_stream(
+            config,
+            move |data: &mut [f32], _| callback(data),
+            on_error,
+            None,
+        )?,
+    };
+    Ok(stream)
+}
+
+/// Opens a capture stream on the reference (speaker monitor) device, returning
+/// the stream together with the consumer side of its ring buffer.
+fn build_reference_stream(
+    host: &cpal::Host,
+    name: &str,
+    config: &cpal::StreamConfig,
+    buffer_size: usize,
+) -> Result<(cpal::Stream, HeapCons<f32>)> {
+    let device = resolve_device(host, name, true)?;
+    info!(
+        "Using reference device: {}",
+        device.name().unwrap_or_default()
+    );
+
+    let (mut prod_ref, cons_ref) = HeapRb::<f32>::new(buffer_size).split();
+    let stream = build_input_stream_f32(
+        &device,
+        config,
+        move |data| {
+            let _ = prod_ref.push_slice(data);
+        },
+        "Reference input error",
+    )?;
+    stream.play()?;
+    Ok((stream, cons_ref))
+}
+
+/// Adds which apps hold `device` to a "device busy" error from opening it, so the
+/// user knows what to close. Other errors pass through unchanged.
+fn explain_busy_input(err: anyhow::Error, device: &cpal::Device) -> anyhow::Error {
+    let busy = matches!(
+        err.downcast_ref::<cpal::BuildStreamError>(),
+        Some(cpal::BuildStreamError::DeviceNotAvailable)
+    ) || {
+        let message = err.to_string().to_lowercase();
+        message.contains("busy") || message.contains("in use")
+    };
+    if !busy {
+        return err;
+    }
+    let apps = pulse_info::get_input_users(&device.name().unwrap_or_default());
+    if apps.is_empty() {
+        return err;
+    }
+    err.context(format!("Device in use by: {}", apps.join(", ")))
+}
+
+// Processing threads (engine and output filter) that haven't exited yet
+static AUDIO_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of engine/output filter processing threads still alive. Dropping an engine
+/// joins its thread, so this is 0 once every engine is gone.
+pub fn audio_threads_running() -> usize {
+    AUDIO_THREADS.load(Ordering::Relaxed)
+}
+
+/// Counts a processing thread in `AUDIO_THREADS` for as long as it is held.
+struct AudioThreadCount(&'static AtomicUsize);
+
+impl AudioThreadCount {
+    fn new() -> Self {
+        Self::with_counter(&AUDIO_THREADS)
+    }
+
+    /// Counts in `counter` instead, so tests don't race on the global count.
+    fn with_counter(counter: &'static AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for AudioThreadCount {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Stops a processing thread and waits for it to exit.
+fn join_worker(is_running: &AtomicBool, worker: &mut Option<thread::JoinHandle<()>>) {
+    is_running.store(false, Ordering::Relaxed);
+    if let Some(worker) = worker.take() {
+        if worker.join().is_err() {
+            warn!("Audio processing thread panicked");
+        }
+    }
+}
+
 // Gate timing constants (all in milliseconds)
 
 /// Audio processing engine that combines RNNoise denoising with a smart noise gate.
@@ -49,35 +608,76 @@ fn resolve_device(
 /// The engine runs in a separate thread and processes audio in real-time using VoidProcessor.
 pub struct AudioEngine {
     _input_stream: cpal::Stream,
-    _output_stream: cpal::Stream,
-    _reference_stream: Option<cpal::Stream>,
+    // Primary output first, then any fan-out (monitor) outputs
+    _output_streams: Vec<cpal::Stream>,
+    reference_stream: Option<cpal::Stream>,
+    // Device the reference stream was opened on; it stays open while AEC is off
+    reference_device: Option<String>,
+    // Hands a freshly opened reference buffer to the audio thread; the receiver clone
+    // lets a newer buffer replace one the thread hasn't picked up yet
+    reference_sender: Sender<HeapCons<f32>>,
+    reference_pending: Receiver<HeapCons<f32>>,
+    // Hands a recording tap (or `None` to detach it) to the audio thread
+    record_sender: Sender<Option<Sender<RecordFrame>>>,
+    backend: AudioBackend,
     is_running: Arc<AtomicBool>,
+    worker: Option<thread::JoinHandle<()>>,
+
+    /// Settings shared with the processor (live-updatable from the GUI)
+    pub controls: ProcessorControls,
 
     // Shared state for GUI communication
     pub volume_level: Arc<AtomicU32>,
+    pub channel_levels: Arc<[AtomicU32]>,
+    pub gate_is_open: Arc<AtomicBool>,
+    pub effective_threshold: Arc<AtomicU32>,
+    pub noise_floor: Arc<AtomicU32>,
+    pub dsp_suspended: Arc<AtomicBool>,
     pub calibration_mode: Arc<AtomicBool>,
     pub calibration_result: Arc<AtomicU32>,
-
-    pub vad_sensitivity: Arc<AtomicU32>,
-    pub eq_low_gain: Arc<AtomicU32>,
-    pub eq_mid_gain: Arc<AtomicU32>,
-    pub eq_high_gain: Arc<AtomicU32>,
-
-    pub eq_enabled: Arc<AtomicBool>,
-    pub agc_enabled: Arc<AtomicBool>,
-    pub bypass_enabled: Arc<AtomicBool>,
+    pub gain_calibration_mode: Arc<AtomicBool>,
+    pub gain_calibration_result: Arc<AtomicU32>,
     pub jitter_ewma_us: Arc<AtomicU32>,
-    pub gate_threshold: Arc<AtomicU32>,
-    pub suppression_strength: Arc<AtomicU32>,
-    pub dynamic_threshold_enabled: Arc<AtomicBool>,
+    pub process_time_us: Arc<AtomicU32>,
+    /// Estimated mic-to-output latency in µs: input device block + one processing
+    /// frame + samples queued in the output ring + output device block.
+    pub latency_us: Arc<AtomicU32>,
+    /// Frames run through the processor since start (10ms each)
+    pub frames_processed: Arc<AtomicU64>,
+    /// Largest output sample of the last frame (f32 bits)
+    pub output_peak: Arc<AtomicU32>,
+    /// Frames that clipped since start or the last reset (store 0 to reset)
+    pub clip_count: Arc<AtomicU32>,
+    /// Frames the VAD took for speech (see `VoidProcessor::speech_frames`)
+    pub speech_frames: Arc<AtomicU32>,
+    /// RMS of the last block the first output device played (f32 bits), underrun fill
+    /// included: what actually reaches the device, unlike `volume_level`
+    pub output_level: Arc<AtomicU32>,
+    /// Frames dropped because the output buffer stayed full
+    pub dropped_frames: Arc<AtomicU64>,
+    /// `UnderrunFill` the output callbacks use when the ring runs dry
+    pub underrun_fill: Arc<AtomicU32>,
+    /// Wall-clock time the engine was started
+    pub started_at: SystemTime,
+    /// Why echo cancellation was requested at start but is off (the reference
+    /// device couldn't be opened). `None` if it is running or wasn't requested.
+    pub echo_cancel_error: Option<String>,
+    /// Return path for spectrum/waveform buffers the GUI is done with
+    pub spectrum_recycler: Sender<(Vec<f32>, Vec<f32>)>,
 }
 
 impl AudioEngine {
     /// Starts the audio engine.
+    ///
+    /// Processed audio goes to every device in `output_device_names` (the host default
+    /// if empty). The first is the primary output (latency and backpressure are measured
+    /// on it); the others drop frames rather than stall it. Names are used as is, so
+    /// ALSA names with commas (`hw:CARD=PCH,DEV=0`) work.
     #[allow(clippy::too_many_arguments)]
     pub fn start(
+        backend: AudioBackend,
         input_device_name: &str,
-        output_device_name: &str,
+        output_device_names: &[String],
         gate_threshold: f32,
         suppression_strength: f32,
         echo_cancel_enabled: bool,
@@ -89,9 +689,12 @@ impl AudioEngine {
         agc_enabled: bool,
         agc_target_level: f32,
         bypass_enabled: bool,
+        stereo_input: bool,
         spectrum_sender: Option<Sender<(Vec<f32>, Vec<f32>)>>,
+        waveform_sender: Option<Sender<(Vec<f32>, Vec<f32>)>>,
+        gate_event_sender: Option<Sender<GateEvent>>,
     ) -> Result<Self> {
-        let host = cpal::default_host();
+        let host = audio_host(backend);
         info!("Audio host: {}", host.id().name());
 
         let input_device = resolve_device(&host, input_device_name, true)?;
@@ -100,96 +703,152 @@ impl AudioEngine {
             input_device.name().unwrap_or_default()
         );
 
-        let output_device = resolve_device(&host, output_device_name, false)?;
-        info!(
-            "Using output device: {}",
-            output_device.name().unwrap_or_default()
-        );
-
-        // Resolve reference device for echo cancellation
-        let reference_device = if echo_cancel_enabled {
-            if let Some(ref_name) = reference_device_name {
-                let dev = resolve_device(&host, ref_name, true).ok();
-                if let Some(d) = &dev {
-                    info!("Using reference device: {}", d.name().unwrap_or_default());
-                } else {
-                    warn!("Failed to resolve reference device: {}", ref_name);
-                }
-                dev
-            } else {
-                None
-            }
+        let default_output = ["default".to_string()];
+        let output_names = if output_device_names.is_empty() {
+            &default_output[..]
         } else {
-            None
+            output_device_names
         };
+        let mut output_devices = Vec::with_capacity(output_names.len());
+        for name in output_names {
+            let device = resolve_device(&host, name, false)?;
+            info!("Using output device: {}", device.name().unwrap_or_default());
+            output_devices.push(device);
+        }
 
         let config = cpal::StreamConfig {
             channels: 1,
             sample_rate: cpal::SampleRate(SAMPLE_RATE),
             buffer_size: cpal::BufferSize::Default,
         };
+        // A stereo mic is processed per channel and mixed down to mono for the outputs;
+        // otherwise only the first channel a device delivers is used
+        let input_config = cpal::StreamConfig {
+            channels: input_channels(&input_device, stereo_input),
+            ..config.clone()
+        };
+        let stream_channels = input_config.channels as usize;
+        let channels = if stereo_input {
+            stream_channels.min(MAX_CHANNELS)
+        } else {
+            1
+        };
+        info!(
+            "Input channels: {} (processing {})",
+            stream_channels, channels
+        );
 
-        // Latency management (100ms buffer)
-        let buffer_size = (SAMPLE_RATE as usize) / 10;
+        // Latency management
+        let buffer_size = (SAMPLE_RATE * ENGINE_BUFFER_MS / 1000) as usize;
 
-        // Ring buffers
-        let rb_in = HeapRb::<f32>::new(buffer_size);
+        // Ring buffers (the input one holds interleaved frames)
+        let rb_in = HeapRb::<f32>::new(buffer_size * channels);
         let (mut prod_in, mut cons_in) = rb_in.split();
 
-        let rb_out = HeapRb::<f32>::new(buffer_size);
-        let (mut prod_out, mut cons_out) = rb_out.split();
-
-        // Reference ring buffer for echo cancellation
-        let rb_ref = HeapRb::<f32>::new(buffer_size);
-        let (mut prod_ref, mut cons_ref) = rb_ref.split();
-
-        // Build reference capture stream if echo cancellation is enabled
-        let reference_stream: Option<cpal::Stream> = if let Some(ref_dev) = &reference_device {
-            match ref_dev.build_input_stream(
-                &config,
-                move |data: &[f32], _| {
-                    let _ = prod_ref.push_slice(data);
-                },
-                |err| warn!("Reference input error: {}", err),
-                None,
-            ) {
-                Ok(stream) => Some(stream),
+        // Reference stream for echo cancellation (can be reopened later via
+        // `set_reference_device`, so the audio thread receives its buffer over a channel)
+        let (reference_sender, reference_receiver) = crossbeam_channel::bounded::<HeapCons<f32>>(1);
+        let reference_pending = reference_receiver.clone();
+        let (record_sender, record_receiver) =
+            crossbeam_channel::bounded::<Option<Sender<RecordFrame>>>(4);
+        // Without a reference AEC would do nothing, so it starts off and the error is
+        // kept for the caller instead
+        let mut reference_stream = None;
+        let mut reference_device = None;
+        let mut initial_reference = None;
+        let mut echo_cancel_error = None;
+        if echo_cancel_enabled {
+            match reference_device_name
+                .context("No reference device selected")
+                .and_then(|name| build_reference_stream(&host, name, &config, buffer_size))
+            {
+                Ok((stream, cons)) => {
+                    reference_stream = Some(stream);
+                    initial_reference = Some(cons);
+                    reference_device = reference_device_name.map(str::to_string);
+                }
                 Err(e) => {
-                    warn!("Failed to open reference device for echo cancellation: {}", e);
-                    None
+                    warn!("Echo cancellation disabled: {}", e);
+                    echo_cancel_error = Some(e.to_string());
                 }
             }
-        } else {
-            None
-        };
+        }
+        let echo_cancel_enabled = reference_stream.is_some();
 
-        let input_stream = input_device.build_input_stream(
-            &config,
-            move |data: &[f32], _| {
-                let _ = prod_in.push_slice(data);
-            },
-            |err| warn!("Input error: {}", err),
-            None,
-        )?;
+        // Device callback sizes (in samples) for the latency estimate
+        let input_block = Arc::new(AtomicU32::new(0));
+        let output_block = Arc::new(AtomicU32::new(0));
+        let input_block_cb = input_block.clone();
+        let output_block_cb = output_block.clone();
 
-        let output_stream = output_device.build_output_stream(
-            &config,
-            move |data: &mut [f32], _| {
-                let read = cons_out.pop_slice(data);
-                if read < data.len() {
-                    for sample in data.iter_mut().skip(read) {
-                        *sample = 0.0;
+        let input_stream = build_input_stream_f32(
+            &input_device,
+            &input_config,
+            move |data| {
+                input_block_cb.store((data.len() / stream_channels) as u32, Ordering::Relaxed);
+                // Whole frames only, so a full ring can't shift the channels out of step
+                if stream_channels == channels {
+                    let room = prod_in.vacant_len() / channels * channels;
+                    let _ = prod_in.push_slice(&data[..data.len().min(room)]);
+                } else {
+                    // More channels than the processor takes: keep the first ones
+                    for frame in data.chunks_exact(stream_channels) {
+                        if prod_in.vacant_len() >= channels {
+                            let _ = prod_in.push_slice(&frame[..channels]);
+                        }
                     }
                 }
             },
-            |err| warn!("Output error: {}", err),
-            None,
-        )?;
+            "Input error",
+        )
+        .map_err(|e| explain_busy_input(e, &input_device))?;
+
+        let underrun_fill = Arc::new(AtomicU32::new(UnderrunFill::default() as u32));
+        let output_level = Arc::new(AtomicU32::new(0));
+
+        // One ring per output device
+        let mut output_streams = Vec::with_capacity(output_devices.len());
+        let mut output_producers: Vec<HeapProd<f32>> = Vec::with_capacity(output_devices.len());
+        for (i, (device, name)) in output_devices.iter().zip(output_names).enumerate() {
+            let (prod, mut cons) = HeapRb::<f32>::new(buffer_size).split();
+            let block_cb = (i == 0).then(|| output_block_cb.clone());
+            let level_cb = (i == 0).then(|| output_level.clone());
+            let fill_cb = underrun_fill.clone();
+            let mut filler = UnderrunFiller::new(SAMPLE_RATE);
+            // Stereo-only devices reject a mono stream; open what they offer and upmix.
+            // The virtual sink (or the combined sink `load --monitor` writes to) always
+            // gets dual-mono.
+            let dual_mono = name.contains(VIRTUAL_SINK_NAME) || name.contains(COMBINE_SINK_NAME);
+            let channels = output_channels(device, dual_mono);
+            let output_config = cpal::StreamConfig {
+                channels,
+                ..config.clone()
+            };
+            let stream = build_output_stream_f32(
+                device,
+                &output_config,
+                move |data| {
+                    if let Some(block) = &block_cb {
+                        let frames = data.len() / channels as usize;
+                        block.store(frames as u32, Ordering::Relaxed);
+                    }
+                    let fill = UnderrunFill::from_u32(fill_cb.load(Ordering::Relaxed));
+                    let samples = std::iter::from_fn(|| Some(filler.next(cons.try_pop(), fill)));
+                    upmix_mono(samples, data, channels as usize);
+                    if let Some(level) = &level_cb {
+                        level.store(block_rms(data).to_bits(), Ordering::Relaxed);
+                    }
+                },
+                "Output error",
+            )?;
+            output_streams.push(stream);
+            output_producers.push(prod);
+        }
 
         // Initialize Processor
         // Always pass real EQ params; eq_enabled atomic controls whether EQ runs
         let mut processor = VoidProcessor::new(
-            1, // Mono for App
+            channels,
             vad_sensitivity,
             eq_params,
             agc_target_level,
@@ -197,278 +856,1055 @@ impl AudioEngine {
         );
 
         // Set initial state via atomics (live-updatable from GUI)
-        processor
+        let controls = processor.controls().clone();
+        controls
             .gate_threshold
             .store(gate_threshold.to_bits(), Ordering::Relaxed);
-        processor
+        controls
             .suppression_strength
             .store(suppression_strength.to_bits(), Ordering::Relaxed);
-        processor
+        controls
             .dynamic_threshold_enabled
             .store(dynamic_threshold_enabled, Ordering::Relaxed);
-        processor.eq_enabled.store(eq_enabled, Ordering::Relaxed);
-        processor.agc_enabled.store(agc_enabled, Ordering::Relaxed);
-        processor
+        controls.eq_enabled.store(eq_enabled, Ordering::Relaxed);
+        controls.agc_enabled.store(agc_enabled, Ordering::Relaxed);
+        controls
             .bypass_enabled
             .store(bypass_enabled, Ordering::Relaxed);
         if let Some(sender) = spectrum_sender.clone() {
             processor.spectrum_sender = Some(sender);
         }
+        processor.waveform_sender = waveform_sender;
+        processor.gate_event_sender = gate_event_sender;
 
         // Extract Atomics for GUI
         let volume_level = processor.volume_level.clone();
+        let channel_levels = processor.channel_levels.clone();
+        let gate_is_open = processor.gate_is_open.clone();
+        let clip_count = processor.clip_count.clone();
+        let speech_frames = processor.speech_frames.clone();
+        let effective_threshold = processor.effective_threshold.clone();
+        let noise_floor = processor.noise_floor.clone();
+        let dsp_suspended = processor.dsp_suspended.clone();
         let calibration_mode = processor.calibration_mode.clone();
         let calibration_result = processor.calibration_result.clone();
-        let vad_sensitivity_atomic = processor.vad_sensitivity.clone();
-        let eq_low_atomic = processor.eq_low_gain.clone();
-        let eq_mid_atomic = processor.eq_mid_gain.clone();
-        let eq_high_atomic = processor.eq_high_gain.clone();
-        let eq_enabled_atomic = processor.eq_enabled.clone();
-        let agc_enabled_atomic = processor.agc_enabled.clone();
-        let bypass_enabled_atomic = processor.bypass_enabled.clone();
+        let gain_calibration_mode = processor.gain_calibration_mode.clone();
+        let gain_calibration_result = processor.gain_calibration_result.clone();
         let jitter_atomic = processor.jitter_ewma_us.clone();
-        let gate_threshold_atomic = processor.gate_threshold.clone();
-        let suppression_atomic = processor.suppression_strength.clone();
-        let dynamic_threshold_atomic = processor.dynamic_threshold_enabled.clone();
+        let process_time_atomic = processor.process_time_us.clone();
+        let latency_atomic = Arc::new(AtomicU32::new(0));
+        let latency_report = latency_atomic.clone();
+        let frames_atomic = Arc::new(AtomicU64::new(0));
+        let frames_counter = frames_atomic.clone();
+        let output_peak_atomic = Arc::new(AtomicU32::new(0));
+        let output_peak_report = output_peak_atomic.clone();
+        let dropped_atomic = Arc::new(AtomicU64::new(0));
+        let dropped_counter = dropped_atomic.clone();
+        let spectrum_recycler = processor.spectrum_recycler();
 
         let is_running = Arc::new(AtomicBool::new(true));
         let run_flag = is_running.clone();
+        let thread_count = AudioThreadCount::new();
+
+        let worker = thread::Builder::new()
+            .name("voidmic-audio".into())
+            .spawn(move || {
+                let _thread_count = thread_count;
+                let mut input_interleaved = vec![0.0f32; FRAME_SIZE * channels];
+                let mut input_frames = vec![[0.0f32; FRAME_SIZE]; channels];
+                let mut output_frames = vec![[0.0f32; FRAME_SIZE]; channels];
+                let mut output_frame = [0.0f32; FRAME_SIZE];
+                let mut ref_frame = [0.0f32; FRAME_SIZE];
+                let mut cons_ref = initial_reference;
+                let mut record_tap: Option<Sender<RecordFrame>> = None;
+                let (prod_out, monitor_outs) = output_producers
+                    .split_first_mut()
+                    .expect("at least one output device");
 
-        let has_reference = echo_cancel_enabled && reference_stream.is_some();
+                // Jitter State - EWMA for smoother, more responsive display
+                let mut last_loop_time = std::time::Instant::now();
+                let mut jitter_ewma: f32 = 0.0;
+                let mut process_time_ewma: f32 = 0.0;
+                let mut frames_since_jitter_report = 0u32;
+                let mut queued_out_ewma: f32 = 0.0;
 
-        thread::Builder::new().name("voidmic-audio".into()).spawn(move || {
-            let mut input_frame = [0.0f32; FRAME_SIZE];
-            let mut output_frame = [0.0f32; FRAME_SIZE];
-            let mut ref_frame = [0.0f32; FRAME_SIZE];
+                loop {
+                    if !run_flag.load(Ordering::Relaxed) {
+                        break;
+                    }
 
-            // Jitter State - EWMA for smoother, more responsive display
-            let mut last_loop_time = std::time::Instant::now();
-            let mut jitter_ewma: f32 = 0.0;
-            let mut frames_since_jitter_report = 0u32;
+                    // Process updates
+                    processor.process_updates();
 
-            loop {
-                if !run_flag.load(Ordering::Relaxed) {
-                    break;
-                }
+                    // Pick up a reopened reference stream
+                    if let Ok(cons) = reference_receiver.try_recv() {
+                        cons_ref = Some(cons);
+                    }
+                    while let Ok(tap) = record_receiver.try_recv() {
+                        record_tap = tap;
+                    }
 
-                // Process updates
-                processor.process_updates();
+                    if cons_in.occupied_len() >= FRAME_SIZE * channels {
+                        // Jitter Calculation - skip obviously invalid deltas (e.g. system suspend)
+                        let now = std::time::Instant::now();
+                        let loop_delta = now.duration_since(last_loop_time).as_micros() as u32;
+                        last_loop_time = now;
 
-                if cons_in.occupied_len() >= FRAME_SIZE {
-                    // Jitter Calculation - skip obviously invalid deltas (e.g. system suspend)
-                    let now = std::time::Instant::now();
-                    let loop_delta = now.duration_since(last_loop_time).as_micros() as u32;
-                    last_loop_time = now;
+                        if loop_delta < 100_000 {
+                            let expected = 10_000u32;
+                            let jitter = loop_delta.abs_diff(expected) as f32;
 
-                    if loop_delta < 100_000 {
-                        let expected = 10_000u32;
-                        let jitter = loop_delta.abs_diff(expected) as f32;
+                            // EWMA: alpha=0.1 gives ~10-frame smoothing
+                            jitter_ewma = jitter_ewma * 0.9 + jitter * 0.1;
+                        }
 
-                        // EWMA: alpha=0.1 gives ~10-frame smoothing
-                        jitter_ewma = jitter_ewma * 0.9 + jitter * 0.1;
-                    }
+                        // Report to GUI every 50 frames (~500ms)
+                        frames_since_jitter_report += 1;
+                        if frames_since_jitter_report >= 50 {
+                            processor
+                                .jitter_ewma_us
+                                .store(jitter_ewma as u32, Ordering::Relaxed);
+                            processor
+                                .process_time_us
+                                .store(process_time_ewma as u32, Ordering::Relaxed);
+                            frames_since_jitter_report = 0;
 
-                    // Report to GUI every 50 frames (~500ms)
-                    frames_since_jitter_report += 1;
-                    if frames_since_jitter_report >= 50 {
-                        processor
-                            .jitter_ewma_us
-                            .store(jitter_ewma as u32, Ordering::Relaxed);
-                        frames_since_jitter_report = 0;
-                    }
+                            let latency_samples = input_block.load(Ordering::Relaxed) as f32
+                                + FRAME_SIZE as f32
+                                + queued_out_ewma
+                                + output_block.load(Ordering::Relaxed) as f32;
+                            let latency_us = latency_samples * 1_000_000.0 / SAMPLE_RATE as f32;
+                            latency_report.store(latency_us as u32, Ordering::Relaxed);
+                        }
+
+                        // Read Audio
+                        cons_in.pop_slice(&mut input_interleaved);
+                        for (i, samples) in input_interleaved.chunks_exact(channels).enumerate() {
+                            for (frame, &sample) in input_frames.iter_mut().zip(samples) {
+                                frame[i] = sample;
+                            }
+                        }
 
-                    // Read Audio
-                    cons_in.pop_slice(&mut input_frame);
+                        // Read reference audio for echo cancellation
+                        let ref_frames = match cons_ref.as_mut() {
+                            Some(cons) if cons.occupied_len() >= FRAME_SIZE => {
+                                cons.pop_slice(&mut ref_frame);
+                                Some(&[&ref_frame[..]][..])
+                            }
+                            _ => None,
+                        };
 
-                    // Read reference audio for echo cancellation
-                    let ref_frames = if has_reference && cons_ref.occupied_len() >= FRAME_SIZE {
-                        cons_ref.pop_slice(&mut ref_frame);
-                        Some(&[&ref_frame[..]][..])
-                    } else {
-                        None
-                    };
-
-                    // Process Audio (read live values from atomics)
-                    processor.process_frame(
-                        &[&input_frame],
-                        &mut [&mut output_frame],
-                        ref_frames,
-                        f32::from_bits(processor.suppression_strength.load(Ordering::Relaxed)),
-                        f32::from_bits(processor.gate_threshold.load(Ordering::Relaxed)),
-                        processor.dynamic_threshold_enabled.load(Ordering::Relaxed),
-                    );
-
-                    // Write Audio - retry briefly if output buffer is full
-                    let mut retries = 0;
-                    while prod_out.vacant_len() < FRAME_SIZE {
-                        thread::yield_now();
-                        retries += 1;
-                        if retries > 100 {
-                            break;
+                        // Process Audio (read live values from atomics)
+                        let controls = processor.controls();
+                        let suppression =
+                            f32::from_bits(controls.suppression_strength.load(Ordering::Relaxed));
+                        let threshold =
+                            f32::from_bits(controls.gate_threshold.load(Ordering::Relaxed));
+                        let dynamic_threshold =
+                            controls.dynamic_threshold_enabled.load(Ordering::Relaxed);
+                        // Fixed-size slice tables keep the audio path allocation-free
+                        let mut input_iter = input_frames.iter();
+                        let inputs: [&[f32]; MAX_CHANNELS] =
+                            std::array::from_fn(|_| input_iter.next().map_or(&[][..], |f| &f[..]));
+                        let mut output_iter = output_frames.iter_mut();
+                        let mut outputs: [&mut [f32]; MAX_CHANNELS] = std::array::from_fn(|_| {
+                            output_iter.next().map_or(&mut [][..], |f| &mut f[..])
+                        });
+                        let process_start = std::time::Instant::now();
+                        processor.process_frame(
+                            &inputs[..channels],
+                            &mut outputs[..channels],
+                            ref_frames,
+                            suppression,
+                            threshold,
+                            dynamic_threshold,
+                        );
+                        let process_time = process_start.elapsed().as_micros() as f32;
+                        process_time_ewma = process_time_ewma * 0.9 + process_time * 0.1;
+                        frames_counter.fetch_add(1, Ordering::Relaxed);
+                        for (j, sample) in output_frame.iter_mut().enumerate() {
+                            *sample =
+                                output_frames.iter().map(|f| f[j]).sum::<f32>() / channels as f32;
                         }
+                        let peak = output_frame.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+                        output_peak_report.store(peak.to_bits(), Ordering::Relaxed);
+
+                        // Write Audio - retry briefly if output buffer is full
+                        let mut retries = 0;
+                        while prod_out.vacant_len() < FRAME_SIZE {
+                            thread::yield_now();
+                            retries += 1;
+                            if retries > 100 {
+                                break;
+                            }
+                        }
+                        if prod_out.vacant_len() >= FRAME_SIZE {
+                            prod_out.push_slice(&output_frame);
+                        } else {
+                            dropped_counter.fetch_add(1, Ordering::Relaxed);
+                        }
+                        // A slow disk loses recorded frames, never live ones
+                        if let Some(tap) = &record_tap {
+                            let _ = tap.try_send(output_frame);
+                        }
+                        // Fan-out outputs never block the primary one
+                        for monitor in monitor_outs.iter_mut() {
+                            if monitor.vacant_len() >= FRAME_SIZE {
+                                monitor.push_slice(&output_frame);
+                            }
+                        }
+                        // Smoothed output backlog for the latency estimate
+                        let queued = prod_out.occupied_len() as f32;
+                        queued_out_ewma = queued_out_ewma * 0.9 + queued * 0.1;
+                    } else {
+                        thread::sleep(Duration::from_millis(1));
                     }
-                    if prod_out.vacant_len() >= FRAME_SIZE {
-                        prod_out.push_slice(&output_frame);
-                    }
-                } else {
-                    thread::sleep(Duration::from_millis(1));
                 }
-            }
-        }).context("Failed to spawn audio processing thread")?;
-
-        input_stream.play()?;
-        output_stream.play()?;
-        if let Some(ref ref_stream) = reference_stream {
-            ref_stream.play()?;
-        }
+            })
+            .context("Failed to spawn audio processing thread")?;
 
-        Ok(Self {
+        // Built before the streams start, so a failure below still stops the thread
+        let engine = Self {
             _input_stream: input_stream,
-            _output_stream: output_stream,
-            _reference_stream: reference_stream,
+            _output_streams: output_streams,
+            reference_stream,
+            reference_device,
+            reference_sender,
+            reference_pending,
+            record_sender,
+            backend,
             is_running,
+            worker: Some(worker),
+            controls,
             volume_level,
+            channel_levels,
+            gate_is_open,
+            effective_threshold,
+            noise_floor,
+            dsp_suspended,
             calibration_mode,
             calibration_result,
-            vad_sensitivity: vad_sensitivity_atomic,
-            eq_low_gain: eq_low_atomic,
-            eq_mid_gain: eq_mid_atomic,
-            eq_high_gain: eq_high_atomic,
-            eq_enabled: eq_enabled_atomic,
-            agc_enabled: agc_enabled_atomic,
-            bypass_enabled: bypass_enabled_atomic,
-            gate_threshold: gate_threshold_atomic,
-            suppression_strength: suppression_atomic,
-            dynamic_threshold_enabled: dynamic_threshold_atomic,
+            gain_calibration_mode,
+            gain_calibration_result,
+            echo_cancel_error,
+            spectrum_recycler,
             jitter_ewma_us: jitter_atomic,
-        })
+            process_time_us: process_time_atomic,
+            latency_us: latency_atomic,
+            frames_processed: frames_atomic,
+            output_peak: output_peak_atomic,
+            clip_count,
+            speech_frames,
+            output_level,
+            dropped_frames: dropped_atomic,
+            underrun_fill,
+            started_at: SystemTime::now(),
+        };
+        engine._input_stream.play()?;
+        for stream in &engine._output_streams {
+            stream.play()?;
+        }
+        Ok(engine)
+    }
+
+    /// Time since the engine was started.
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed().unwrap_or_default()
+    }
+
+    /// Turns echo cancellation on or off without restarting the engine.
+    /// Switching off leaves the reference stream open (the audio thread keeps draining
+    /// it), so switching back on is just a flag flip unless the reference device changed.
+    pub fn set_echo_cancel(&mut self, enabled: bool, reference_device_name: &str) -> Result<()> {
+        if enabled && self.reference_device.as_deref() != Some(reference_device_name) {
+            self.set_reference_device(Some(reference_device_name))?;
+        }
+        self.controls
+            .echo_cancel_enabled
+            .store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Tees every processed frame into `tap` (see `recorder::Recording`), or stops
+    /// doing so with `None`. The audio thread only ever `try_send`s to it.
+    pub fn set_record_tap(&self, tap: Option<Sender<RecordFrame>>) -> Result<()> {
+        self.record_sender
+            .try_send(tap)
+            .map_err(|_| anyhow::anyhow!("Audio thread is not accepting a recording"))
+    }
+
+    /// Reopens (or closes, with `None`) just the reference stream, leaving the
+    /// microphone path running. If reopening fails, echo cancellation is switched off.
+    pub fn set_reference_device(&mut self, name: Option<&str>) -> Result<()> {
+        // Drop the old stream first so its buffer stops filling
+        self.reference_stream = None;
+        self.reference_device = None;
+        if let Err(e) = self.open_reference(name) {
+            self.controls
+                .echo_cancel_enabled
+                .store(false, Ordering::Relaxed);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn open_reference(&mut self, name: Option<&str>) -> Result<()> {
+        // A buffer from an earlier switch the audio thread hasn't taken yet is stale now
+        while self.reference_pending.try_recv().is_ok() {}
+        let Some(name) = name else {
+            return Ok(());
+        };
+
+        let config = cpal::StreamConfig {
+            channels: 1,
+            sample_rate: cpal::SampleRate(SAMPLE_RATE),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        let host = audio_host(self.backend);
+        let buffer_size = (SAMPLE_RATE * ENGINE_BUFFER_MS / 1000) as usize;
+        let (stream, cons) = build_reference_stream(&host, name, &config, buffer_size)?;
+        self.reference_sender
+            .try_send(cons)
+            .map_err(|_| anyhow::anyhow!("Audio thread is not accepting a new reference"))?;
+        self.reference_stream = Some(stream);
+        self.reference_device = Some(name.to_string());
+        Ok(())
     }
 }
 
 impl Drop for AudioEngine {
     fn drop(&mut self) {
-        self.is_running.store(false, Ordering::Relaxed);
+        join_worker(&self.is_running, &mut self.worker);
     }
 }
 
+/// Mono rate conversion between a device and the 48kHz processor, on top of the core
+/// `LinearResampler`. Audio passes through untouched when the rates already match.
+struct MonoResampler {
+    resampler: Option<LinearResampler>,
+    output: Vec<f32>,
+}
+
+impl MonoResampler {
+    fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            resampler: (from_rate != to_rate).then(|| LinearResampler::new(from_rate, to_rate)),
+            output: Vec::new(),
+        }
+    }
+
+    /// Converts one block; the result is valid until the next call.
+    fn process<'a>(&'a mut self, input: &'a [f32]) -> &'a [f32] {
+        let Some(resampler) = &mut self.resampler else {
+            return input;
+        };
+        self.output
+            .resize(resampler.max_output_len(input.len()), 0.0);
+        let written = resampler.process(&[input], &mut [self.output.as_mut_slice()]);
+        &self.output[..written]
+    }
+}
+
+/// Reads a WAV impulse response for the output filter's room correction, averaged to
+/// mono and converted to 48kHz.
+pub fn load_impulse_response(path: &Path) -> Result<Vec<f32>> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("Can't read impulse response {}", path.display()))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+    let mono: Vec<f32> = samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    if mono.iter().all(|&s| s == 0.0) {
+        bail!("Impulse response {} is silent", path.display());
+    }
+    Ok(MonoResampler::new(spec.sample_rate, SAMPLE_RATE)
+        .process(&mono)
+        .to_vec())
+}
+
 /// Output filter engine for speaker/headphone denoising.
 ///
-/// Captures audio from a source (e.g., application output) and applies RNNoise
-/// before sending to the actual speakers. Introduces ~100ms latency.
+/// Captures audio from a source (e.g., application output) and runs it through its
+/// own `VoidProcessor` before sending it to the actual speakers, so incoming audio can
+/// use different settings from the microphone. Introduces ~100ms latency.
 pub struct OutputFilterEngine {
     _input_stream: cpal::Stream,
     _output_stream: cpal::Stream,
     is_running: Arc<AtomicBool>,
-    pub suppression_strength: Arc<AtomicU32>,
+    worker: Option<thread::JoinHandle<()>>,
+    /// The filter's own processing settings
+    pub controls: ProcessorControls,
+    pub underrun_fill: Arc<AtomicU32>,
 }
 
 impl OutputFilterEngine {
-    /// Starts the output filter engine.
+    /// Starts the output filter engine. Processing settings start at the processor
+    /// defaults; set them through the public atomics.
+    ///
+    /// Both devices are opened in their own default format. The source is downmixed to
+    /// mono and resampled to 48kHz for the processor, and the result is resampled to the
+    /// sink's rate and copied to all of its channels.
     ///
     /// # Arguments
     /// * `source_name` - Name of the source to filter (e.g., application output monitor)
     /// * `sink_name` - Name of the sink to output filtered audio to (e.g., speakers)
-    /// * `suppression_strength` - Strength of noise suppression (0.0-1.0)
-    pub fn start(source_name: &str, sink_name: &str, suppression_strength: f32) -> Result<Self> {
-        let host = cpal::default_host();
+    /// * `backend` - Audio host to open both devices on
+    /// * `impulse_response` - WAV file to convolve the filtered audio with (room or
+    ///   headphone correction), see [`load_impulse_response`]
+    pub fn start(
+        source_name: &str,
+        sink_name: &str,
+        backend: AudioBackend,
+        impulse_response: Option<&Path>,
+    ) -> Result<Self> {
+        let mut convolver = match impulse_response {
+            Some(path) => {
+                let ir = load_impulse_response(path)?;
+                info!("Output filter: {} sample impulse response", ir.len());
+                Some(PartitionedConvolver::new(&ir))
+            }
+            None => None,
+        };
+        let host = audio_host(backend);
 
         // Use monitor source as input (captures what apps are playing)
         let input_device = resolve_device(&host, source_name, true)?;
         let output_device = resolve_device(&host, sink_name, false)?;
 
-        let config = cpal::StreamConfig {
-            channels: 1,
-            sample_rate: cpal::SampleRate(SAMPLE_RATE),
+        // Monitor sources are often 44.1kHz and/or stereo; assuming 48kHz mono plays
+        // them back at the wrong speed
+        let source_format = input_device
+            .default_input_config()
+            .context("Failed to query output filter source format")?;
+        let sink_format = output_device
+            .default_output_config()
+            .context("Failed to query output filter sink format")?;
+        let source_rate = source_format.sample_rate().0;
+        let sink_rate = sink_format.sample_rate().0;
+        let source_channels = (source_format.channels() as usize).max(1);
+        let sink_channels = (sink_format.channels() as usize).max(1);
+        info!(
+            "Output filter: source {}Hz/{}ch, sink {}Hz/{}ch",
+            source_rate, source_channels, sink_rate, sink_channels
+        );
+
+        let input_config = cpal::StreamConfig {
+            channels: source_channels as u16,
+            sample_rate: cpal::SampleRate(source_rate),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        let output_config = cpal::StreamConfig {
+            channels: sink_channels as u16,
+            sample_rate: cpal::SampleRate(sink_rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
-        // Use larger buffer for output filtering (100ms acceptable latency)
-        let buffer_size = (SAMPLE_RATE as usize) / 5; // 200ms buffer
-
-        let rb_in = HeapRb::<f32>::new(buffer_size);
+        // Use larger buffers for output filtering (mono, at each device's own rate)
+        let rb_in = HeapRb::<f32>::new((source_rate * OUTPUT_FILTER_BUFFER_MS / 1000) as usize);
         let (mut prod_in, mut cons_in) = rb_in.split();
 
-        let rb_out = HeapRb::<f32>::new(buffer_size);
+        let rb_out = HeapRb::<f32>::new((sink_rate * OUTPUT_FILTER_BUFFER_MS / 1000) as usize);
         let (mut prod_out, mut cons_out) = rb_out.split();
 
-        let input_stream = input_device.build_input_stream(
-            &config,
-            move |data: &[f32], _| {
-                let _ = prod_in.push_slice(data);
+        let input_stream = build_input_stream_f32(
+            &input_device,
+            &input_config,
+            move |data| {
+                for frame in data.chunks_exact(source_channels) {
+                    let _ = prod_in.try_push(frame.iter().sum::<f32>() / source_channels as f32);
+                }
             },
-            |err| warn!("Output filter input error: {}", err),
-            None,
+            "Output filter input error",
         )?;
 
-        let output_stream = output_device.build_output_stream(
-            &config,
-            move |data: &mut [f32], _| {
-                let read = cons_out.pop_slice(data);
-                if read < data.len() {
-                    for sample in data.iter_mut().skip(read) {
-                        *sample = 0.0;
-                    }
-                }
+        let underrun_fill = Arc::new(AtomicU32::new(UnderrunFill::default() as u32));
+        let fill_cb = underrun_fill.clone();
+        let mut filler = UnderrunFiller::new(sink_rate);
+        let output_stream = build_output_stream_f32(
+            &output_device,
+            &output_config,
+            move |data| {
+                let fill = UnderrunFill::from_u32(fill_cb.load(Ordering::Relaxed));
+                let samples = std::iter::from_fn(|| Some(filler.next(cons_out.try_pop(), fill)));
+                upmix_mono(samples, data, sink_channels);
             },
-            |err| warn!("Output filter output error: {}", err),
-            None,
+            "Output filter output error",
         )?;
 
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let controls = processor.controls().clone();
+        controls.eq_enabled.store(false, Ordering::Relaxed);
+
         let is_running = Arc::new(AtomicBool::new(true));
         let run_flag = is_running.clone();
-        let suppression_atomic = Arc::new(AtomicU32::new(suppression_strength.to_bits()));
-        let suppression_for_thread = suppression_atomic.clone();
+        let thread_count = AudioThreadCount::new();
 
-        thread::Builder::new().name("voidmic-output-filter".into()).spawn(move || {
-            let mut denoise = DenoiseState::new();
-            let mut input_frame = [0.0f32; FRAME_SIZE];
-            let mut output_frame = [0.0f32; FRAME_SIZE];
+        let worker = thread::Builder::new()
+            .name("voidmic-output-filter".into())
+            .spawn(move || {
+                let _thread_count = thread_count;
+                let mut source_chunk = [0.0f32; FRAME_SIZE];
+                let mut input_frame = [0.0f32; FRAME_SIZE];
+                let mut output_frame = [0.0f32; FRAME_SIZE];
+                let mut to_processor = MonoResampler::new(source_rate, SAMPLE_RATE);
+                let mut to_sink = MonoResampler::new(SAMPLE_RATE, sink_rate);
+                // 48kHz samples waiting for a full frame
+                let mut pending: Vec<f32> = Vec::with_capacity(2 * FRAME_SIZE);
 
-            while run_flag.load(Ordering::Relaxed) {
-                if cons_in.occupied_len() >= FRAME_SIZE {
-                    cons_in.pop_slice(&mut input_frame);
+                while run_flag.load(Ordering::Relaxed) {
+                    if cons_in.occupied_len() >= FRAME_SIZE {
+                        cons_in.pop_slice(&mut source_chunk);
+                        pending.extend_from_slice(to_processor.process(&source_chunk));
 
-                    // Denoise with RNNoise
-                    denoise.process_frame(&mut output_frame, &input_frame);
+                        while pending.len() >= FRAME_SIZE {
+                            input_frame.copy_from_slice(&pending[..FRAME_SIZE]);
+                            pending.drain(..FRAME_SIZE);
 
-                    // Blend based on suppression strength (live-updated from GUI)
-                    let strength = f32::from_bits(suppression_for_thread.load(Ordering::Relaxed));
-                    for i in 0..FRAME_SIZE {
-                        output_frame[i] = input_frame[i] * (1.0 - strength)
-                            + output_frame[i] * strength;
-                    }
+                            // Process with the filter's own settings (live-updated from GUI)
+                            processor.process_updates();
+                            let controls = processor.controls();
+                            let suppression = f32::from_bits(
+                                controls.suppression_strength.load(Ordering::Relaxed),
+                            );
+                            let threshold =
+                                f32::from_bits(controls.gate_threshold.load(Ordering::Relaxed));
+                            processor.process_frame(
+                                &[&input_frame],
+                                &mut [&mut output_frame],
+                                None,
+                                suppression,
+                                threshold,
+                                false,
+                            );
+                            if let Some(convolver) = &mut convolver {
+                                convolver.process_frame(&mut output_frame);
+                            }
 
-                    let mut retries = 0;
-                    while prod_out.vacant_len() < FRAME_SIZE {
-                        thread::yield_now();
-                        retries += 1;
-                        if retries > 100 {
-                            break;
+                            let converted = to_sink.process(&output_frame);
+                            let mut retries = 0;
+                            while prod_out.vacant_len() < converted.len() {
+                                thread::yield_now();
+                                retries += 1;
+                                if retries > 100 {
+                                    break;
+                                }
+                            }
+                            if prod_out.vacant_len() >= converted.len() {
+                                prod_out.push_slice(converted);
+                            }
                         }
+                    } else {
+                        thread::sleep(Duration::from_millis(2));
                     }
-                    if prod_out.vacant_len() >= FRAME_SIZE {
-                        prod_out.push_slice(&output_frame);
-                    }
-                } else {
-                    thread::sleep(Duration::from_millis(2));
                 }
-            }
-        }).context("Failed to spawn output filter thread")?;
-
-        input_stream.play()?;
-        output_stream.play()?;
+            })
+            .context("Failed to spawn output filter thread")?;
 
-        Ok(Self {
+        let filter = Self {
             _input_stream: input_stream,
             _output_stream: output_stream,
             is_running,
-            suppression_strength: suppression_atomic,
-        })
+            worker: Some(worker),
+            controls,
+            underrun_fill,
+        };
+        filter._input_stream.play()?;
+        filter._output_stream.play()?;
+        Ok(filter)
     }
 }
 
 impl Drop for OutputFilterEngine {
     fn drop(&mut self) {
-        self.is_running.store(false, Ordering::Relaxed);
+        join_worker(&self.is_running, &mut self.worker);
+    }
+}
+
+// Test tone: 1kHz sine, 1s long, 20ms fades, peaking at -18 dBFS
+const TEST_TONE_HZ: f32 = 1000.0;
+const TEST_TONE_SECS: f32 = 1.0;
+const TEST_TONE_FADE_SECS: f32 = 0.02;
+const TEST_TONE_DBFS: f32 = -18.0;
+
+/// Sample generator for the test tone, with linear fade in/out.
+struct ToneGenerator {
+    position: usize,
+    total: usize,
+    fade: usize,
+    amplitude: f32,
+    phase_step: f32,
+}
+
+impl ToneGenerator {
+    fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate as f32;
+        Self {
+            position: 0,
+            total: (rate * TEST_TONE_SECS) as usize,
+            fade: (rate * TEST_TONE_FADE_SECS) as usize,
+            amplitude: 10f32.powf(TEST_TONE_DBFS / 20.0),
+            phase_step: std::f32::consts::TAU * TEST_TONE_HZ / rate,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position >= self.total
+    }
+
+    /// Next sample, or silence once the tone is over.
+    fn next_sample(&mut self) -> f32 {
+        if self.is_finished() {
+            return 0.0;
+        }
+        let n = self.position;
+        let remaining = self.total - n;
+        let envelope = if n < self.fade {
+            n as f32 / self.fade as f32
+        } else if remaining < self.fade {
+            remaining as f32 / self.fade as f32
+        } else {
+            1.0
+        };
+        self.position += 1;
+        (n as f32 * self.phase_step).sin() * self.amplitude * envelope
+    }
+}
+
+/// Plays a short 1kHz test tone on an output device, to verify routing.
+///
+/// The stream closes when this is dropped; poll `is_finished` to know when
+/// the tone is over.
+pub struct TestTone {
+    _stream: cpal::Stream,
+    finished: Arc<AtomicBool>,
+}
+
+impl TestTone {
+    pub fn play(backend: AudioBackend, output_device_name: &str) -> Result<Self> {
+        let host = audio_host(backend);
+        let device = resolve_device(&host, output_device_name, false)?;
+        info!(
+            "Playing test tone on: {}",
+            device.name().unwrap_or_default()
+        );
+
+        let channels = output_channels(&device, false);
+        let config = cpal::StreamConfig {
+            channels,
+            sample_rate: cpal::SampleRate(SAMPLE_RATE),
+            buffer_size: cpal::BufferSize::Default,
+        };
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let finished_flag = finished.clone();
+        let mut tone = ToneGenerator::new(SAMPLE_RATE);
+        let stream = build_output_stream_f32(
+            &device,
+            &config,
+            move |data| {
+                let samples = std::iter::from_fn(|| Some(tone.next_sample()));
+                upmix_mono(samples, data, channels as usize);
+                if tone.is_finished() {
+                    finished_flag.store(true, Ordering::Relaxed);
+                }
+            },
+            "Test tone output error",
+        )?;
+        stream.play()?;
+
+        Ok(Self {
+            _stream: stream,
+            finished,
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_device_list() {
+        assert_eq!(split_device_list("VoidMic_Clean"), vec!["VoidMic_Clean"]);
+        assert_eq!(
+            split_device_list("VoidMic_Clean, Headphones ,"),
+            vec!["VoidMic_Clean", "Headphones"]
+        );
+        assert!(split_device_list(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_estimated_latency_ms() {
+        // 100ms input ring + 10ms frame + 100ms output ring
+        assert_eq!(estimated_latency_ms(), 210.0);
+    }
+
+    #[test]
+    fn test_audio_backend_serde() {
+        assert_eq!(
+            serde_json::to_string(&AudioBackend::Jack).unwrap(),
+            "\"jack\""
+        );
+        let backend: AudioBackend = serde_json::from_str("\"default\"").unwrap();
+        assert_eq!(backend, AudioBackend::Default);
+        let backend: AudioBackend = serde_json::from_str("\"coreaudio\"").unwrap();
+        assert_eq!(backend, AudioBackend::CoreAudio);
+    }
+
+    #[test]
+    fn test_available_backends_match_cpal_hosts() {
+        let available = AudioBackend::available();
+        assert_eq!(available.first(), Some(&AudioBackend::Default));
+        let hosts = cpal::available_hosts();
+        for backend in &available[1..] {
+            let id = backend
+                .host_id()
+                .expect("non-default backend without a host");
+            assert!(
+                hosts.contains(&id),
+                "{:?} offered but not available",
+                backend
+            );
+            assert_eq!(audio_host(*backend).id(), id);
+        }
+        for backend in AudioBackend::ALL {
+            assert_eq!(backend.is_available(), available.contains(&backend));
+        }
+    }
+
+    #[test]
+    fn test_audio_host_falls_back_to_default() {
+        assert_eq!(
+            audio_host(AudioBackend::Default).id(),
+            cpal::default_host().id()
+        );
+        // Backends not compiled into this build resolve to the default host
+        for backend in AudioBackend::ALL {
+            if backend.host_id().is_none() {
+                assert_eq!(
+                    audio_host(backend).id(),
+                    cpal::default_host().id(),
+                    "{:?}",
+                    backend
+                );
+            }
+        }
+    }
+
+    // Talks to the real audio servers, so it only runs on request (`cargo test -- --ignored`)
+    #[test]
+    #[ignore]
+    fn test_audio_host_enumerates_devices() {
+        // A backend resolves to its own host when it's available (JACK: when the server
+        // is running), otherwise to the default host; either way the result must be
+        // usable for enumeration
+        for backend in AudioBackend::ALL {
+            let host = audio_host(backend);
+            assert!(
+                host.input_devices().is_ok(),
+                "{:?} input enumeration failed",
+                backend
+            );
+            assert!(
+                host.output_devices().is_ok(),
+                "{:?} output enumeration failed",
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn test_join_worker_waits_for_the_thread() {
+        static THREADS: AtomicUsize = AtomicUsize::new(0);
+        let is_running = Arc::new(AtomicBool::new(true));
+        let run_flag = is_running.clone();
+        let thread_count = AudioThreadCount::with_counter(&THREADS);
+        let mut worker = Some(thread::spawn(move || {
+            let _thread_count = thread_count;
+            while run_flag.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }));
+        assert_eq!(THREADS.load(Ordering::Relaxed), 1);
+        join_worker(&is_running, &mut worker);
+        assert!(worker.is_none());
+        assert_eq!(THREADS.load(Ordering::Relaxed), 0);
+    }
+
+    // Opens real devices and reads the global thread count, so it only runs on request
+    // (`cargo test -- --ignored`), on its own
+    #[test]
+    #[ignore]
+    fn test_stopping_engines_joins_their_threads() {
+        // Stop -> start cycles, as "Reset Audio" does; machines without an input and
+        // output device have nothing to check
+        for _ in 0..3 {
+            let Ok(engine) = AudioEngine::start(
+                AudioBackend::Default,
+                "default",
+                &["default".to_string()],
+                0.015,
+                1.0,
+                false,
+                None,
+                false,
+                2,
+                false,
+                (0.0, 0.0, 0.0),
+                false,
+                0.7,
+                false,
+                false,
+                None,
+                None,
+                None,
+            ) else {
+                return;
+            };
+            assert_eq!(audio_threads_running(), 1);
+            drop(engine);
+            assert_eq!(
+                audio_threads_running(),
+                0,
+                "Engine thread outlived the engine"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_conversion_round_trip() {
+        for sample in i16::MIN..=i16::MAX {
+            assert_eq!(f32_to_i16(i16_to_f32(sample)), sample);
+        }
+        for sample in [0u16, 1, 32767, 32768, 40000, u16::MAX] {
+            assert_eq!(f32_to_u16(u16_to_f32(sample)), sample);
+        }
+        assert_eq!(u16_to_f32(32768), 0.0);
+
+        for i in -100..=100 {
+            let sample = i as f32 / 100.0;
+            assert!((i16_to_f32(f32_to_i16(sample)) - sample).abs() <= 1.0 / 32768.0);
+            assert!((u16_to_f32(f32_to_u16(sample)) - sample).abs() <= 1.0 / 32768.0);
+        }
+
+        // Out of range clips instead of wrapping
+        assert_eq!(f32_to_i16(1.5), i16::MAX);
+        assert_eq!(f32_to_i16(-1.5), i16::MIN);
+        assert_eq!(f32_to_u16(1.5), u16::MAX);
+        assert_eq!(f32_to_u16(-1.5), 0);
+    }
+
+    #[test]
+    fn test_pick_sample_format() {
+        use cpal::SampleFormat::*;
+        assert_eq!(pick_sample_format(&[I16, F32]), F32);
+        assert_eq!(pick_sample_format(&[U16, I16]), I16);
+        assert_eq!(pick_sample_format(&[U8, U16]), U16);
+        // Nothing we can convert: let cpal report the failure
+        assert_eq!(pick_sample_format(&[I32]), F32);
+        assert_eq!(pick_sample_format(&[]), F32);
+    }
+
+    #[test]
+    fn test_upmix_mono() {
+        let frame = [0.1f32, -0.2, 0.3];
+
+        let mut stereo = [1.0f32; 6];
+        upmix_mono(frame, &mut stereo, 2);
+        assert_eq!(stereo, [0.1, 0.1, -0.2, -0.2, 0.3, 0.3]);
+
+        let mut mono = [1.0f32; 3];
+        upmix_mono(frame, &mut mono, 1);
+        assert_eq!(mono, frame);
+
+        // Frames past the end of the signal are silent
+        let mut surround = [1.0f32; 8];
+        upmix_mono(frame[..1].iter().copied(), &mut surround, 4);
+        assert_eq!(surround, [0.1, 0.1, 0.1, 0.1, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_pick_input_channels() {
+        assert_eq!(pick_input_channels(&[1, 2], false), 1);
+        assert_eq!(pick_input_channels(&[2], false), 2);
+        assert_eq!(pick_input_channels(&[1, 2], true), 2);
+        assert_eq!(pick_input_channels(&[1], true), 1);
+        assert_eq!(pick_input_channels(&[4, 1], true), 1);
+        assert_eq!(pick_input_channels(&[4, 6], true), 4);
+        assert_eq!(pick_input_channels(&[], true), 1);
+    }
+
+    #[test]
+    fn test_pick_output_channels() {
+        assert_eq!(pick_output_channels(&[1, 2], false), 1);
+        assert_eq!(pick_output_channels(&[2, 6], false), 2);
+        assert_eq!(pick_output_channels(&[], false), 1);
+        // The virtual sink is fed dual-mono even though it would accept mono
+        assert_eq!(pick_output_channels(&[1, 2], true), 2);
+        // Each config has a fixed channel count, so a 6-channel device can't open 2
+        assert_eq!(pick_output_channels(&[6, 1], true), 1);
+        assert_eq!(pick_output_channels(&[6], true), 6);
+        assert_eq!(pick_output_channels(&[1], true), 1);
+
+        let mono = [0.25f32, -0.5];
+        let mut stereo = [0.0f32; 4];
+        let channels = pick_output_channels(&[1, 2], true) as usize;
+        upmix_mono(mono, &mut stereo, channels);
+        for (frame, sample) in stereo.chunks(2).zip(mono) {
+            assert_eq!(frame, [sample, sample]);
+        }
+    }
+
+    #[test]
+    fn test_load_impulse_response() {
+        let path = std::env::temp_dir().join(format!("voidmic_ir_{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 24_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        // Left and right average to a half-scale impulse at the start
+        for (left, right) in [(i16::MAX, 0), (0, 0), (0, 0), (0, 0)] {
+            writer.write_sample(left).unwrap();
+            writer.write_sample(right).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let ir = load_impulse_response(&path).unwrap();
+        // Upsampled to 48kHz
+        assert_eq!(ir.len(), 8);
+        let peak = ir.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.01, "{}", peak);
+
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        writer.write_sample(0i16).unwrap();
+        writer.write_sample(0i16).unwrap();
+        writer.finalize().unwrap();
+        assert!(load_impulse_response(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+        assert!(load_impulse_response(&path).is_err());
+    }
+
+    #[test]
+    fn test_block_rms() {
+        assert_eq!(block_rms(&[]), 0.0);
+        assert_eq!(block_rms(&[0.0; 480]), 0.0);
+        assert!((block_rms(&[0.5, -0.5, 0.5, -0.5]) - 0.5).abs() < 1e-6);
+        // Upmixing copies samples, so the level doesn't depend on the channel count
+        let mono = [0.1, -0.4, 0.3];
+        let mut stereo = [0.0; 6];
+        upmix_mono(mono, &mut stereo, 2);
+        assert!((block_rms(&stereo) - block_rms(&mono)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_underrun_fill() {
+        let run = |fill: UnderrunFill| {
+            let mut filler = UnderrunFiller::new(SAMPLE_RATE);
+            let played: Vec<f32> = [Some(0.4), Some(0.5), None, None]
+                .into_iter()
+                .map(|sample| filler.next(sample, fill))
+                .collect();
+            // The stream recovers with whatever arrives next
+            assert_eq!(filler.next(Some(0.1), fill), 0.1);
+            played
+        };
+
+        assert_eq!(run(UnderrunFill::Silence), [0.4, 0.5, 0.0, 0.0]);
+        assert_eq!(run(UnderrunFill::Hold), [0.4, 0.5, 0.5, 0.5]);
+        let faded = run(UnderrunFill::Fade);
+        assert!(
+            faded[2] < 0.5 && faded[3] < faded[2] && faded[3] > 0.4,
+            "{:?}",
+            faded
+        );
+
+        // The fade reaches near-silence after a few time constants
+        let mut filler = UnderrunFiller::new(SAMPLE_RATE);
+        filler.next(Some(1.0), UnderrunFill::Fade);
+        let samples = (5.0 * UNDERRUN_FADE_MS * SAMPLE_RATE as f32 / 1000.0) as usize;
+        let tail = (0..samples)
+            .map(|_| filler.next(None, UnderrunFill::Fade))
+            .last()
+            .unwrap();
+        assert!(tail < 0.01, "{}", tail);
+
+        assert_eq!(
+            UnderrunFill::from_u32(UnderrunFill::Fade as u32),
+            UnderrunFill::Fade
+        );
+        assert_eq!(UnderrunFill::from_u32(99), UnderrunFill::Silence);
+    }
+
+    #[test]
+    fn test_mono_resampler_rates() {
+        // Matching rates pass straight through
+        let mut same = MonoResampler::new(SAMPLE_RATE, SAMPLE_RATE);
+        let block = [0.1f32, -0.2, 0.3];
+        assert_eq!(same.process(&block), &block);
+
+        // One second of a 44.1kHz monitor source, through the processor rate and back
+        let step = 0.05f32;
+        let input: Vec<f32> = (0..44_100).map(|i| (i as f32 * step).sin()).collect();
+        let mut up = MonoResampler::new(44_100, SAMPLE_RATE);
+        let mut down = MonoResampler::new(SAMPLE_RATE, 44_100);
+        let mut upsampled = Vec::new();
+        let mut restored = 0;
+        for chunk in input.chunks(FRAME_SIZE) {
+            let converted = up.process(chunk).to_vec();
+            restored += down.process(&converted).len();
+            upsampled.extend(converted);
+        }
+        assert!(upsampled.len().abs_diff(48_000) <= 1, "{}", upsampled.len());
+        assert!(restored.abs_diff(44_100) <= 2, "{}", restored);
+
+        // Same tone at the new rate, not pitched up (output lags the input by one sample)
+        let ratio = 44_100.0 / SAMPLE_RATE as f32;
+        for (k, &sample) in upsampled.iter().enumerate().skip(1) {
+            let expected = ((k as f32 * ratio - 1.0) * step).sin();
+            assert!(
+                (sample - expected).abs() < 0.01,
+                "sample {}: {} vs {}",
+                k,
+                sample,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_tone_level_and_fades() {
+        let mut tone = ToneGenerator::new(SAMPLE_RATE);
+        let samples: Vec<f32> =
+            std::iter::from_fn(|| (!tone.is_finished()).then(|| tone.next_sample())).collect();
+
+        assert_eq!(samples.len(), SAMPLE_RATE as usize);
+        // Peak stays at the -18 dBFS safety level
+        let peak = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        let limit = 10f32.powf(TEST_TONE_DBFS / 20.0);
+        assert!(peak <= limit + 1e-6 && peak > limit * 0.99, "peak {}", peak);
+        // Fades start and end at silence (no clicks)
+        assert_eq!(samples[0], 0.0);
+        assert!(samples.last().unwrap().abs() < limit * 0.01);
+        // Silence after the tone
+        assert_eq!(tone.next_sample(), 0.0);
     }
 }
@@ -1,38 +1,136 @@
+use crate::audio::AudioBackend;
+use crate::i18n::Language;
+use crate::silence_watch::{SilenceAction, SILENCE_DEFAULT_MINS};
+use crate::updater::UpdateChannel;
+use anyhow::Context;
 use directories::ProjectDirs;
 use log::warn;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use voidmic_core::processor::{
+    ProcessorControls, AGC_CEILING_DEFAULT_DB, AGC_DEFAULT_ATTACK_MS, AGC_DEFAULT_RELEASE_MS,
+    AUTO_SUSPEND_DEFAULT_SECS, DYNAMIC_THRESHOLD_DEFAULT_MAX, DYNAMIC_THRESHOLD_DEFAULT_MIN,
+    GATE_HYSTERESIS_DEFAULT,
+};
 
 /// Application configuration for persisting user preferences.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub last_input: String,
     pub last_output: String,
+    /// Extra output that also receives the processed mic (empty = none)
+    #[serde(default)]
+    pub monitor_output: String,
+    #[serde(default)]
+    pub audio_backend: AudioBackend,
+    /// Open a stereo mic in stereo and process each channel on its own (mono otherwise)
+    #[serde(default)]
+    pub stereo_input: bool,
     #[serde(default = "default_gate_threshold")]
     pub gate_threshold: f32,
     #[serde(default = "default_suppression_strength")]
     pub suppression_strength: f32,
     #[serde(default)]
+    pub gate_hold_ms: u32,
+    #[serde(default)]
+    pub gate_mode: u32, // GateMode: 0 = Gate, 1 = Expander, 2 = Off
+    #[serde(default)]
+    pub gate_fade_curve: u32, // FadeCurve: 0 = Cosine, 1 = Linear, 2 = Exponential
+    #[serde(default)]
+    pub noise_floor_speed: u32, // NoiseFloorSpeed: 0 = Normal, 1 = Slow, 2 = Fast
+    #[serde(default = "default_expander_ratio")]
+    pub expander_ratio: f32,
+    #[serde(default = "default_gate_hysteresis")]
+    pub gate_hysteresis: f32, // Close threshold as a fraction of the open threshold
+    #[serde(default = "default_gate_subblocks")]
+    pub gate_subblocks: u32, // Gate decisions per 10 ms frame
+    #[serde(default)]
+    pub gate_key_source: u32, // GateKeySource: 0 = After denoise, 1 = Before denoise
+    #[serde(default)]
+    pub input_gain_db: f32,
+    #[serde(default)]
+    pub speech_gated_suppression: bool,
+    #[serde(default = "default_speech_suppression_floor")]
+    pub speech_suppression_floor: f32,
+    #[serde(default)]
+    pub protect_highs: bool,
+    #[serde(default)]
+    pub spectral_gate: bool,
+    #[serde(default)]
+    pub dither_enabled: bool,
+    #[serde(default)]
     pub start_on_boot: bool,
     #[serde(default)]
     pub output_filter_enabled: bool,
+    // Output filter processing, independent of the microphone settings
+    #[serde(default = "default_suppression_strength")]
+    pub output_filter_suppression: f32,
+    #[serde(default)]
+    pub output_filter_threshold: f32,
+    #[serde(default)]
+    pub output_filter_eq_enabled: bool,
+    #[serde(default)]
+    pub output_filter_eq_low_gain: f32,
+    #[serde(default)]
+    pub output_filter_eq_mid_gain: f32,
+    #[serde(default)]
+    pub output_filter_eq_high_gain: f32,
+    /// WAV impulse response the output filter convolves with (room/headphone
+    /// correction); empty leaves it off
+    #[serde(default)]
+    pub output_filter_ir_path: String,
     #[serde(default)]
     pub echo_cancel_enabled: bool,
     #[serde(default)]
+    pub clean_reference: bool,
+    #[serde(default)]
     pub dynamic_threshold_enabled: bool,
+    /// Range Auto-Gate's threshold is kept in (RMS); validated by the processor
+    #[serde(default = "default_dynamic_threshold_min")]
+    pub dynamic_threshold_min: f32,
+    #[serde(default = "default_dynamic_threshold_max")]
+    pub dynamic_threshold_max: f32,
     #[serde(default)]
     pub start_minimized: bool,
     #[serde(default)]
     pub auto_start_processing: bool,
     #[serde(default)]
+    pub pause_when_idle: bool,
+    /// Skip the DSP chain after this long with the gate closed (Gate mode only)
+    #[serde(default)]
+    pub auto_suspend_enabled: bool,
+    #[serde(default = "default_auto_suspend_secs")]
+    pub auto_suspend_secs: u32,
+    /// What to do after `silence_minutes` of sound without speech ("forgot to mute")
+    #[serde(default)]
+    pub silence_action: SilenceAction,
+    #[serde(default = "default_silence_minutes")]
+    pub silence_minutes: u32,
+    #[serde(default)]
     pub window_x: Option<f32>,
     #[serde(default)]
     pub window_y: Option<f32>,
     #[serde(default = "default_dark_mode")]
     pub dark_mode: bool,
+    /// Pick dark/light from the OS preference instead of `dark_mode`
+    #[serde(default)]
+    pub follow_system_theme: bool,
+    #[serde(default)]
+    pub language: Language,
     #[serde(default = "default_preset")]
     pub preset: String,
+    /// Edits made to built-in presets, keyed by preset name
+    #[serde(default)]
+    pub preset_overrides: HashMap<String, PresetValues>,
+    /// Presets remembered per input device (device name -> preset name); devices without
+    /// an entry use `preset`
+    #[serde(default)]
+    pub per_device_presets: HashMap<String, String>,
     #[serde(default = "default_toggle_hotkey")]
     pub toggle_hotkey: String,
     #[serde(default = "default_first_run")]
@@ -40,6 +138,14 @@ pub struct AppConfig {
     #[serde(default = "default_vad_sensitivity")]
     pub vad_sensitivity: i32,
     #[serde(default)]
+    pub vad_source_channel: u32, // VadSource: 0 = Mix, 1 = Loudest, 2+ = channel (n - 2)
+    #[serde(default = "default_vad_frames")]
+    pub vad_window_frames: u32, // Recent VAD frames the onset count looks at
+    #[serde(default = "default_vad_frames")]
+    pub vad_onset_frames: u32, // Voiced frames among them needed to detect speech
+    #[serde(default)]
+    pub vad_hangover_frames: u32, // Frames speech is held after that
+    #[serde(default)]
     pub eq_enabled: bool,
     #[serde(default)]
     pub eq_low_gain: f32, // dB
@@ -47,29 +153,191 @@ pub struct AppConfig {
     pub eq_mid_gain: f32, // dB
     #[serde(default)]
     pub eq_high_gain: f32, // dB
+    #[serde(default)]
+    pub eq_auto_gain: bool,
+    #[serde(default)]
+    pub eq_tilt: f32, // Warmth macro: -1 (bright) .. 1 (warm)
 
     // Phase 4 field
     #[serde(default)]
     pub agc_enabled: bool,
     #[serde(default = "default_agc_target")]
     pub agc_target_level: f32,
+    #[serde(default = "default_agc_attack")]
+    pub agc_attack_ms: f32,
+    #[serde(default = "default_agc_release")]
+    pub agc_release_ms: f32,
+    /// Freezes the AGC gain while the gate is closed, so it can't wind up on silence
+    #[serde(default = "default_agc_hold_when_gated")]
+    pub agc_hold_when_gated: bool,
+    /// Peak ceiling of the AGC output, dBFS
+    #[serde(default = "default_agc_ceiling")]
+    pub agc_ceiling_db: f32,
+
+    #[serde(default)]
+    pub compressor_enabled: bool,
+    #[serde(default = "default_compressor_threshold")]
+    pub compressor_threshold_db: f32,
+    #[serde(default = "default_compressor_ratio")]
+    pub compressor_ratio: f32,
+    #[serde(default = "default_compressor_attack")]
+    pub compressor_attack_ms: f32,
+    #[serde(default = "default_compressor_release")]
+    pub compressor_release_ms: f32,
+    #[serde(default)]
+    pub compressor_makeup_db: f32,
 
     #[serde(default)]
     pub last_reference: String,
+    /// Leave the virtual sink loaded when VoidMic exits, so apps keep it selected
+    #[serde(default)]
+    pub persist_virtual_sink: bool,
+    /// Write gate open/close events to `gate_events.csv` in the data directory
+    #[serde(default)]
+    pub gate_event_log: bool,
+    /// Serve Prometheus/JSON metrics on 127.0.0.1 at this port; off when unset
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+    /// Where "Record" writes the processed mic; empty picks a new file in the music folder
+    #[serde(default)]
+    pub record_path: String,
 
     // Phase 6
     #[serde(default)]
     pub mini_mode: bool,
+    /// Show every advanced control inline; off tucks the rarely-used ones under "Show more"
+    #[serde(default)]
+    pub advanced_mode: bool,
+
+    // Spectrum display (defaults match the original linear plot)
+    #[serde(default)]
+    pub spectrum_log_frequency: bool,
+    #[serde(default)]
+    pub spectrum_db_scale: bool,
+    #[serde(default = "default_spectrum_db_floor")]
+    pub spectrum_db_floor: f32,
+    #[serde(default)]
+    pub spectrum_peak_hold: bool,
+    /// Show the waveform (oscilloscope) view instead of the spectrum
+    #[serde(default)]
+    pub visualizer_waveform: bool,
+    /// Level meter ballistics: 0 = PPM, 1 = VU, 2 = Instant
+    #[serde(default)]
+    pub meter_ballistics: u32,
+    /// What the output plays when processing falls behind: 0 = Silence, 1 = Hold, 2 = Fade
+    #[serde(default)]
+    pub underrun_fill: u32,
+
+    #[serde(default = "default_check_for_updates")]
+    pub check_for_updates: bool,
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+}
+
+/// The settings a preset controls. Also stored per built-in preset once the user edits
+/// it, so picking that preset again restores the edits instead of the defaults.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct PresetValues {
+    pub gate_threshold: f32,
+    pub suppression_strength: f32,
+    pub dynamic_threshold_enabled: bool,
+    // Added later; overrides saved before then get the app defaults
+    #[serde(default = "default_vad_sensitivity")]
+    pub vad_sensitivity: i32,
+    #[serde(default)]
+    pub eq_enabled: bool,
+    #[serde(default)]
+    pub eq_low_gain: f32,
+    #[serde(default)]
+    pub eq_mid_gain: f32,
+    #[serde(default)]
+    pub eq_high_gain: f32,
+    #[serde(default)]
+    pub agc_enabled: bool,
+    #[serde(default = "default_agc_target")]
+    pub agc_target_level: f32,
+}
+
+fn default_check_for_updates() -> bool {
+    true
+}
+
+fn default_spectrum_db_floor() -> f32 {
+    -80.0
 }
 
 fn default_agc_target() -> f32 {
     0.7 // Approx -3dB
 }
 
+fn default_agc_attack() -> f32 {
+    AGC_DEFAULT_ATTACK_MS
+}
+
+fn default_agc_release() -> f32 {
+    AGC_DEFAULT_RELEASE_MS
+}
+
+fn default_agc_ceiling() -> f32 {
+    AGC_CEILING_DEFAULT_DB
+}
+
+fn default_agc_hold_when_gated() -> bool {
+    true
+}
+
+fn default_dynamic_threshold_min() -> f32 {
+    DYNAMIC_THRESHOLD_DEFAULT_MIN
+}
+
+fn default_dynamic_threshold_max() -> f32 {
+    DYNAMIC_THRESHOLD_DEFAULT_MAX
+}
+
+fn default_expander_ratio() -> f32 {
+    2.0
+}
+
+fn default_gate_hysteresis() -> f32 {
+    GATE_HYSTERESIS_DEFAULT
+}
+
+fn default_gate_subblocks() -> u32 {
+    1
+}
+
+fn default_auto_suspend_secs() -> u32 {
+    AUTO_SUSPEND_DEFAULT_SECS
+}
+
+fn default_silence_minutes() -> u32 {
+    SILENCE_DEFAULT_MINS
+}
+
+fn default_compressor_threshold() -> f32 {
+    -20.0
+}
+
+fn default_compressor_ratio() -> f32 {
+    3.0
+}
+
+fn default_compressor_attack() -> f32 {
+    10.0
+}
+
+fn default_compressor_release() -> f32 {
+    100.0
+}
+
 fn default_vad_sensitivity() -> i32 {
     2 // 0-3, 3 is most aggressive
 }
 
+fn default_vad_frames() -> u32 {
+    1
+}
+
 fn default_first_run() -> bool {
     true
 }
@@ -90,6 +358,10 @@ fn default_suppression_strength() -> f32 {
     1.0
 }
 
+fn default_speech_suppression_floor() -> f32 {
+    0.5
+}
+
 fn default_dark_mode() -> bool {
     true
 }
@@ -99,44 +371,151 @@ impl Default for AppConfig {
         Self {
             last_input: String::new(),
             last_output: String::new(),
+            monitor_output: String::new(),
+            audio_backend: AudioBackend::Default,
+            stereo_input: false,
             gate_threshold: default_gate_threshold(),
             suppression_strength: default_suppression_strength(),
+            gate_hold_ms: 0,
+            gate_mode: 0,
+            gate_fade_curve: 0,
+            noise_floor_speed: 0,
+            expander_ratio: default_expander_ratio(),
+            gate_hysteresis: default_gate_hysteresis(),
+            gate_subblocks: default_gate_subblocks(),
+            gate_key_source: 0,
+            input_gain_db: 0.0,
+            speech_gated_suppression: false,
+            speech_suppression_floor: default_speech_suppression_floor(),
+            protect_highs: false,
+            spectral_gate: false,
+            dither_enabled: false,
             start_on_boot: false,
             output_filter_enabled: false,
+            output_filter_suppression: default_suppression_strength(),
+            output_filter_threshold: 0.0,
+            output_filter_eq_enabled: false,
+            output_filter_eq_low_gain: 0.0,
+            output_filter_eq_mid_gain: 0.0,
+            output_filter_eq_high_gain: 0.0,
+            output_filter_ir_path: String::new(),
             echo_cancel_enabled: false,
+            clean_reference: false,
             dynamic_threshold_enabled: false,
+            dynamic_threshold_min: default_dynamic_threshold_min(),
+            dynamic_threshold_max: default_dynamic_threshold_max(),
             start_minimized: false,
             auto_start_processing: false,
+            pause_when_idle: false,
+            auto_suspend_enabled: false,
+            auto_suspend_secs: default_auto_suspend_secs(),
+            silence_action: SilenceAction::Off,
+            silence_minutes: default_silence_minutes(),
             window_x: None,
             window_y: None,
             dark_mode: true,
+            follow_system_theme: false,
+            language: Language::English,
             preset: default_preset(),
+            preset_overrides: HashMap::new(),
+            per_device_presets: HashMap::new(),
             toggle_hotkey: default_toggle_hotkey(),
             first_run: true,
             vad_sensitivity: default_vad_sensitivity(),
+            vad_source_channel: 0,
+            vad_window_frames: default_vad_frames(),
+            vad_onset_frames: default_vad_frames(),
+            vad_hangover_frames: 0,
             eq_enabled: false,
             eq_low_gain: 0.0,
             eq_mid_gain: 0.0,
             eq_high_gain: 0.0,
+            eq_auto_gain: false,
+            eq_tilt: 0.0,
             agc_enabled: false,
             agc_target_level: default_agc_target(),
+            agc_attack_ms: default_agc_attack(),
+            agc_release_ms: default_agc_release(),
+            agc_hold_when_gated: default_agc_hold_when_gated(),
+            agc_ceiling_db: default_agc_ceiling(),
+            compressor_enabled: false,
+            compressor_threshold_db: default_compressor_threshold(),
+            compressor_ratio: default_compressor_ratio(),
+            compressor_attack_ms: default_compressor_attack(),
+            compressor_release_ms: default_compressor_release(),
+            compressor_makeup_db: 0.0,
             last_reference: String::new(),
+            persist_virtual_sink: false,
+            gate_event_log: false,
+            metrics_port: None,
+            record_path: String::new(),
             mini_mode: false,
+            advanced_mode: false,
+            spectrum_log_frequency: false,
+            spectrum_db_scale: false,
+            spectrum_db_floor: -80.0,
+            spectrum_peak_hold: false,
+            visualizer_waveform: false,
+            meter_ballistics: 0,
+            underrun_fill: 0,
+            check_for_updates: true,
+            update_channel: UpdateChannel::Stable,
         }
     }
 }
 
 impl AppConfig {
+    /// The current values of every preset-controlled setting.
+    pub fn preset_values(&self) -> PresetValues {
+        PresetValues {
+            gate_threshold: self.gate_threshold,
+            suppression_strength: self.suppression_strength,
+            dynamic_threshold_enabled: self.dynamic_threshold_enabled,
+            vad_sensitivity: self.vad_sensitivity,
+            eq_enabled: self.eq_enabled,
+            eq_low_gain: self.eq_low_gain,
+            eq_mid_gain: self.eq_mid_gain,
+            eq_high_gain: self.eq_high_gain,
+            agc_enabled: self.agc_enabled,
+            agc_target_level: self.agc_target_level,
+        }
+    }
+
+    /// Sets every preset-controlled setting from `values`.
+    pub fn set_preset_values(&mut self, values: &PresetValues) {
+        self.gate_threshold = values.gate_threshold;
+        self.suppression_strength = values.suppression_strength;
+        self.dynamic_threshold_enabled = values.dynamic_threshold_enabled;
+        self.vad_sensitivity = values.vad_sensitivity;
+        self.eq_enabled = values.eq_enabled;
+        self.eq_low_gain = values.eq_low_gain;
+        self.eq_mid_gain = values.eq_mid_gain;
+        self.eq_high_gain = values.eq_high_gain;
+        self.agc_enabled = values.agc_enabled;
+        self.agc_target_level = values.agc_target_level;
+    }
+
     /// Loads configuration from disk, or returns default if not found.
     pub fn load() -> Self {
-        if let Some(path) = config_path() {
-            if let Ok(content) = fs::read_to_string(path) {
-                if let Ok(cfg) = serde_json::from_str(&content) {
-                    return cfg;
-                }
-            }
-        }
-        Self::default()
+        config_path()
+            .and_then(|path| Self::read_file(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Checks the config file `load` would read: its path if it is readable and valid,
+    /// `None` if there is none (defaults apply), otherwise why `load` ignores it.
+    pub fn check_file() -> anyhow::Result<Option<PathBuf>> {
+        let Some(path) = config_path().filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+        Self::read_file(&path)?;
+        Ok(Some(path))
+    }
+
+    fn read_file(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
     }
 
     /// Saves configuration to disk in JSON format.
@@ -160,10 +539,232 @@ impl AppConfig {
             Err(e) => warn!("Failed to serialize config: {}", e),
         }
     }
+
+    /// Pushes the processing settings that aren't arguments of `AudioEngine::start` into
+    /// a processor's controls. Used by the GUI and `voidmic run` alike.
+    pub fn apply_to(&self, controls: &ProcessorControls) {
+        controls
+            .input_gain_db
+            .store(self.input_gain_db.to_bits(), Ordering::Relaxed);
+        controls
+            .gate_hold_ms
+            .store(self.gate_hold_ms, Ordering::Relaxed);
+        controls.gate_mode.store(self.gate_mode, Ordering::Relaxed);
+        controls
+            .gate_fade_curve
+            .store(self.gate_fade_curve, Ordering::Relaxed);
+        controls
+            .noise_floor_speed
+            .store(self.noise_floor_speed, Ordering::Relaxed);
+        controls
+            .dynamic_threshold_min
+            .store(self.dynamic_threshold_min.to_bits(), Ordering::Relaxed);
+        controls
+            .dynamic_threshold_max
+            .store(self.dynamic_threshold_max.to_bits(), Ordering::Relaxed);
+        controls
+            .expander_ratio
+            .store(self.expander_ratio.to_bits(), Ordering::Relaxed);
+        controls
+            .gate_hysteresis
+            .store(self.gate_hysteresis.to_bits(), Ordering::Relaxed);
+        controls
+            .gate_subblocks
+            .store(self.gate_subblocks, Ordering::Relaxed);
+        controls
+            .gate_key_source
+            .store(self.gate_key_source, Ordering::Relaxed);
+        controls
+            .vad_source_channel
+            .store(self.vad_source_channel, Ordering::Relaxed);
+        controls
+            .vad_window_frames
+            .store(self.vad_window_frames, Ordering::Relaxed);
+        controls
+            .vad_onset_frames
+            .store(self.vad_onset_frames, Ordering::Relaxed);
+        controls
+            .vad_hangover_frames
+            .store(self.vad_hangover_frames, Ordering::Relaxed);
+        controls
+            .auto_suspend_enabled
+            .store(self.auto_suspend_enabled, Ordering::Relaxed);
+        controls
+            .auto_suspend_secs
+            .store(self.auto_suspend_secs, Ordering::Relaxed);
+        controls
+            .clean_reference
+            .store(self.clean_reference, Ordering::Relaxed);
+        controls
+            .eq_auto_gain
+            .store(self.eq_auto_gain, Ordering::Relaxed);
+        controls
+            .eq_tilt
+            .store(self.eq_tilt.to_bits(), Ordering::Relaxed);
+        controls
+            .agc_attack_ms
+            .store(self.agc_attack_ms.to_bits(), Ordering::Relaxed);
+        controls
+            .agc_release_ms
+            .store(self.agc_release_ms.to_bits(), Ordering::Relaxed);
+        controls
+            .agc_hold_when_gated
+            .store(self.agc_hold_when_gated, Ordering::Relaxed);
+        controls
+            .agc_ceiling_db
+            .store(self.agc_ceiling_db.to_bits(), Ordering::Relaxed);
+        controls
+            .compressor_enabled
+            .store(self.compressor_enabled, Ordering::Relaxed);
+        controls
+            .compressor_threshold_db
+            .store(self.compressor_threshold_db.to_bits(), Ordering::Relaxed);
+        controls
+            .compressor_ratio
+            .store(self.compressor_ratio.to_bits(), Ordering::Relaxed);
+        controls
+            .compressor_attack_ms
+            .store(self.compressor_attack_ms.to_bits(), Ordering::Relaxed);
+        controls
+            .compressor_release_ms
+            .store(self.compressor_release_ms.to_bits(), Ordering::Relaxed);
+        controls
+            .compressor_makeup_db
+            .store(self.compressor_makeup_db.to_bits(), Ordering::Relaxed);
+        controls
+            .speech_gated_suppression
+            .store(self.speech_gated_suppression, Ordering::Relaxed);
+        controls
+            .speech_suppression_floor
+            .store(self.speech_suppression_floor.to_bits(), Ordering::Relaxed);
+        controls
+            .protect_highs
+            .store(self.protect_highs, Ordering::Relaxed);
+        controls
+            .spectral_gate
+            .store(self.spectral_gate, Ordering::Relaxed);
+        controls
+            .dither_enabled
+            .store(self.dither_enabled, Ordering::Relaxed);
+    }
+
+    /// Applies `VOIDMIC_*` environment overrides (see `ENV_OVERRIDES`), so headless runs
+    /// can be configured without a config file. Precedence: env > config file > default.
+    pub fn apply_env_overrides(&mut self) -> anyhow::Result<()> {
+        self.apply_overrides(|name| std::env::var(name).ok())
+    }
+
+    fn apply_overrides(&mut self, var: impl Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+        if let Some(v) = env_value(&var, "VOIDMIC_THRESHOLD", parse_unit)? {
+            self.gate_threshold = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_SUPPRESSION", parse_unit)? {
+            self.suppression_strength = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_VAD", parse_vad)? {
+            self.vad_sensitivity = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_AUTO_GATE", parse_flag)? {
+            self.dynamic_threshold_enabled = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_AGC", parse_flag)? {
+            self.agc_enabled = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_EQ", parse_flag)? {
+            self.eq_enabled = v;
+        }
+        if let Some(v) = env_value(&var, "VOIDMIC_METRICS_PORT", parse_port)? {
+            self.metrics_port = Some(v);
+        }
+        Ok(())
+    }
+
+    /// Preset for the input `device`: the one remembered for it, else the global `preset`.
+    pub fn preset_for_device(&self, device: &str) -> &str {
+        self.per_device_presets
+            .get(device)
+            .map_or(&self.preset, String::as_str)
+    }
+}
+
+/// Environment variables `apply_env_overrides` reads, with the values they accept.
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("VOIDMIC_THRESHOLD", "gate threshold, RMS 0.0-1.0"),
+    ("VOIDMIC_SUPPRESSION", "suppression strength, 0.0-1.0"),
+    ("VOIDMIC_VAD", "VAD sensitivity, 0-3"),
+    ("VOIDMIC_AUTO_GATE", "dynamic threshold, on/off"),
+    ("VOIDMIC_AGC", "automatic gain control, on/off"),
+    ("VOIDMIC_EQ", "equalizer, on/off"),
+    ("VOIDMIC_METRICS_PORT", "metrics endpoint port, 1-65535"),
+];
+
+/// Reads and parses one override; set but unparsable is an error rather than silently
+/// falling back, since nobody watches a headless deployment's config.
+fn env_value<T>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> anyhow::Result<Option<T>> {
+    let Some(raw) = var(name) else {
+        return Ok(None);
+    };
+    let expected = ENV_OVERRIDES
+        .iter()
+        .find(|(var, _)| *var == name)
+        .map_or("", |(_, expected)| expected);
+    parse(raw.trim())
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("Invalid {}='{}' (expected {})", name, raw, expected))
+}
+
+fn parse_unit(s: &str) -> Option<f32> {
+    s.parse().ok().filter(|v: &f32| (0.0..=1.0).contains(v))
+}
+
+fn parse_vad(s: &str) -> Option<i32> {
+    s.parse().ok().filter(|v| (0..=3).contains(v))
+}
+
+fn parse_port(s: &str) -> Option<u16> {
+    s.parse().ok().filter(|port| *port != 0)
+}
+
+fn parse_flag(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Directory from `--config-dir`/`VOIDMIC_CONFIG_DIR`, if any (see `set_config_dir`).
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Points `load`/`save` at `flag` (`--config-dir`) or else `$VOIDMIC_CONFIG_DIR`
+/// instead of the platform config directory, for portable installs and isolated
+/// instances. Call once at startup, before the first `load`.
+pub fn set_config_dir(flag: Option<PathBuf>) {
+    if let Some(dir) = config_dir_override(flag, std::env::var_os("VOIDMIC_CONFIG_DIR")) {
+        let _ = CONFIG_DIR.set(dir);
+    }
+}
+
+/// The flag wins over the environment; an empty variable counts as unset.
+fn config_dir_override(flag: Option<PathBuf>, env: Option<OsString>) -> Option<PathBuf> {
+    flag.or_else(|| env.filter(|dir| !dir.is_empty()).map(PathBuf::from))
+}
+
+/// The directory set by `set_config_dir`, if any.
+pub fn custom_config_dir() -> Option<&'static Path> {
+    CONFIG_DIR.get().map(PathBuf::as_path)
 }
 
 fn config_path() -> Option<PathBuf> {
-    ProjectDirs::from("com", "voidmic", "voidmic").map(|dirs| dirs.config_dir().join("config.json"))
+    match CONFIG_DIR.get() {
+        Some(dir) => Some(dir.join("config.json")),
+        None => ProjectDirs::from("com", "voidmic", "voidmic")
+            .map(|dirs| dirs.config_dir().join("config.json")),
+    }
 }
 
 #[cfg(test)]
@@ -178,6 +779,26 @@ mod tests {
         assert!(!config.start_on_boot);
         assert!(!config.echo_cancel_enabled);
         assert!(!config.dynamic_threshold_enabled);
+        assert!(!config.advanced_mode);
+        assert!(!config.persist_virtual_sink);
+        assert!(!config.gate_event_log);
+        assert_eq!(config.metrics_port, None);
+        assert!(config.record_path.is_empty());
+        assert!(!config.auto_suspend_enabled);
+        assert_eq!(config.auto_suspend_secs, 30);
+        assert_eq!(config.silence_action, SilenceAction::Off);
+        assert_eq!(config.silence_minutes, SILENCE_DEFAULT_MINS);
+        assert_eq!(config.dynamic_threshold_min, 0.005);
+        assert_eq!(config.dynamic_threshold_max, 0.08);
+        assert_eq!(config.gate_hysteresis, 0.8);
+        assert_eq!(config.gate_subblocks, 1);
+        assert_eq!(config.gate_key_source, 0);
+        assert_eq!(config.vad_window_frames, 1);
+        assert_eq!(config.vad_onset_frames, 1);
+        assert_eq!(config.vad_hangover_frames, 0);
+        assert!(!config.dither_enabled);
+        assert!(config.agc_hold_when_gated);
+        assert_eq!(config.agc_ceiling_db, -0.09);
     }
 
     #[test]
@@ -185,29 +806,95 @@ mod tests {
         let config = AppConfig {
             last_input: "Test Mic".to_string(),
             last_output: "Test Output".to_string(),
+            monitor_output: String::new(),
+            audio_backend: AudioBackend::Default,
+            stereo_input: false,
             gate_threshold: 0.02,
             suppression_strength: 0.8,
+            gate_hold_ms: 0,
+            gate_mode: 0,
+            gate_fade_curve: 0,
+            noise_floor_speed: 0,
+            expander_ratio: 2.0,
+            gate_hysteresis: 0.8,
+            gate_subblocks: 1,
+            gate_key_source: 0,
+            input_gain_db: -4.5,
+            speech_gated_suppression: false,
+            speech_suppression_floor: 0.5,
+            protect_highs: false,
+            spectral_gate: false,
+            dither_enabled: false,
             start_on_boot: true,
             output_filter_enabled: false,
+            output_filter_suppression: 1.0,
+            output_filter_threshold: 0.0,
+            output_filter_eq_enabled: false,
+            output_filter_eq_low_gain: 0.0,
+            output_filter_eq_mid_gain: 0.0,
+            output_filter_eq_high_gain: 0.0,
+            output_filter_ir_path: String::new(),
             echo_cancel_enabled: true,
+            clean_reference: false,
             dynamic_threshold_enabled: true,
+            dynamic_threshold_min: 0.005,
+            dynamic_threshold_max: 0.08,
             start_minimized: false,
             auto_start_processing: false,
+            pause_when_idle: false,
+            auto_suspend_enabled: false,
+            auto_suspend_secs: 30,
+            silence_action: SilenceAction::Off,
+            silence_minutes: 5,
             window_x: None,
             window_y: None,
             dark_mode: true,
+            follow_system_theme: false,
+            language: Language::English,
             preset: "Gaming".to_string(),
+            preset_overrides: HashMap::new(),
+            per_device_presets: HashMap::new(),
             toggle_hotkey: "Control+Shift+M".to_string(),
             first_run: true,
             vad_sensitivity: 2,
+            vad_source_channel: 0,
+            vad_window_frames: 1,
+            vad_onset_frames: 1,
+            vad_hangover_frames: 0,
             eq_enabled: true,
             eq_low_gain: 3.0,
             eq_mid_gain: -1.0,
             eq_high_gain: 0.0,
+            eq_auto_gain: false,
+            eq_tilt: 0.0,
             agc_enabled: false,
             agc_target_level: 0.7,
+            agc_attack_ms: 95.0,
+            agc_release_ms: 2000.0,
+            agc_hold_when_gated: true,
+            agc_ceiling_db: -0.09,
+            compressor_enabled: false,
+            compressor_threshold_db: -20.0,
+            compressor_ratio: 3.0,
+            compressor_attack_ms: 10.0,
+            compressor_release_ms: 100.0,
+            compressor_makeup_db: 0.0,
             last_reference: String::new(),
+            persist_virtual_sink: false,
+            gate_event_log: false,
+            metrics_port: None,
+            record_path: String::new(),
             mini_mode: false,
+            advanced_mode: false,
+            spectrum_log_frequency: false,
+            spectrum_db_scale: false,
+            spectrum_db_floor: -80.0,
+            spectrum_peak_hold: false,
+            visualizer_waveform: false,
+            meter_ballistics: 0,
+            underrun_fill: 0,
+            check_for_updates: true,
+            update_channel: UpdateChannel::Stable,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -227,6 +914,22 @@ mod tests {
         assert_eq!(config.gate_threshold, 0.015); // Default
         assert_eq!(config.suppression_strength, 1.0); // Default
         assert!(!config.echo_cancel_enabled); // Default false
+        assert!(config.check_for_updates); // Default true
+        assert_eq!(config.update_channel, UpdateChannel::Stable);
+        assert_eq!(config.language, Language::English);
+        assert_eq!(config.spectrum_db_floor, -80.0); // Default
+        assert_eq!(config.meter_ballistics, 0); // PPM
+        assert_eq!(config.underrun_fill, 0); // Silence
+        assert_eq!(config.gate_key_source, 0); // After denoise
+        assert_eq!(config.vad_source_channel, 0); // Mix
+        assert!(!config.dither_enabled);
+        assert!(config.preset_overrides.is_empty());
+        assert!(config.per_device_presets.is_empty());
+        assert_eq!(config.audio_backend, AudioBackend::Default);
+        assert!(!config.stereo_input); // Mono capture
+        assert_eq!(config.output_filter_suppression, 1.0);
+        assert_eq!(config.output_filter_threshold, 0.0); // Gate off for incoming audio
+        assert!(config.output_filter_ir_path.is_empty()); // No room correction
     }
 
     #[test]
@@ -234,42 +937,342 @@ mod tests {
         let original = AppConfig {
             last_input: "Input".to_string(),
             last_output: "Output".to_string(),
+            monitor_output: "Headphones".to_string(),
+            audio_backend: AudioBackend::Jack,
+            stereo_input: true,
             gate_threshold: 0.025,
             suppression_strength: 0.5,
+            gate_hold_ms: 150,
+            gate_mode: 1,
+            gate_fade_curve: 2,
+            noise_floor_speed: 1,
+            expander_ratio: 3.0,
+            gate_hysteresis: 0.65,
+            gate_subblocks: 4,
+            gate_key_source: 1,
+            input_gain_db: 6.0,
+            speech_gated_suppression: false,
+            speech_suppression_floor: 0.5,
+            protect_highs: true,
+            spectral_gate: true,
+            dither_enabled: true,
             start_on_boot: false,
             output_filter_enabled: true,
+            output_filter_suppression: 0.6,
+            output_filter_threshold: 0.01,
+            output_filter_eq_enabled: true,
+            output_filter_eq_low_gain: -3.0,
+            output_filter_eq_mid_gain: 2.0,
+            output_filter_eq_high_gain: 0.0,
+            output_filter_ir_path: "/home/user/headphones_ir.wav".to_string(),
             echo_cancel_enabled: false,
+            clean_reference: true,
             dynamic_threshold_enabled: true,
+            dynamic_threshold_min: 0.001,
+            dynamic_threshold_max: 0.05,
             start_minimized: true,
             auto_start_processing: true,
+            pause_when_idle: true,
+            auto_suspend_enabled: true,
+            auto_suspend_secs: 90,
+            silence_action: SilenceAction::Mute,
+            silence_minutes: 12,
             window_x: Some(100.0),
             window_y: Some(200.0),
             dark_mode: false,
+            follow_system_theme: true,
+            language: Language::German,
             preset: "Podcast".to_string(),
+            preset_overrides: HashMap::from([(
+                "Podcast".to_string(),
+                PresetValues {
+                    gate_threshold: 0.012,
+                    suppression_strength: 0.8,
+                    dynamic_threshold_enabled: false,
+                    vad_sensitivity: 1,
+                    eq_enabled: true,
+                    eq_low_gain: 2.0,
+                    eq_mid_gain: 0.0,
+                    eq_high_gain: 1.5,
+                    agc_enabled: true,
+                    agc_target_level: 0.6,
+                },
+            )]),
+            per_device_presets: HashMap::from([(
+                "USB Podcast Mic".to_string(),
+                "Music".to_string(),
+            )]),
             toggle_hotkey: "Control+Shift+K".to_string(),
             first_run: false,
             vad_sensitivity: 3,
+            vad_source_channel: 3,
+            vad_window_frames: 5,
+            vad_onset_frames: 3,
+            vad_hangover_frames: 20,
             eq_enabled: false,
             eq_low_gain: 0.0,
             eq_mid_gain: 0.0,
             eq_high_gain: 0.0,
+            eq_auto_gain: false,
+            eq_tilt: 0.4,
             agc_enabled: true,
             agc_target_level: 0.8,
+            agc_attack_ms: 20.0,
+            agc_release_ms: 500.0,
+            agc_hold_when_gated: false,
+            agc_ceiling_db: -1.0,
+            compressor_enabled: true,
+            compressor_threshold_db: -24.0,
+            compressor_ratio: 4.0,
+            compressor_attack_ms: 5.0,
+            compressor_release_ms: 150.0,
+            compressor_makeup_db: 6.0,
             last_reference: "Monitor of Speakers".to_string(),
+            persist_virtual_sink: true,
+            gate_event_log: true,
+            metrics_port: Some(9464),
+            record_path: "/tmp/stream_mic.wav".to_string(),
             mini_mode: true,
+            advanced_mode: true,
+            spectrum_log_frequency: true,
+            spectrum_db_scale: true,
+            spectrum_db_floor: -60.0,
+            spectrum_peak_hold: true,
+            visualizer_waveform: true,
+            meter_ballistics: 1,
+            underrun_fill: 2,
+            check_for_updates: false,
+            update_channel: UpdateChannel::Beta,
         };
 
         let json = serde_json::to_string(&original).unwrap();
         let restored: AppConfig = serde_json::from_str(&json).unwrap();
 
         assert_eq!(original.gate_threshold, restored.gate_threshold);
+        assert_eq!(original.gate_hold_ms, restored.gate_hold_ms);
+        assert_eq!(original.gate_mode, restored.gate_mode);
+        assert_eq!(original.gate_fade_curve, restored.gate_fade_curve);
+        assert_eq!(original.noise_floor_speed, restored.noise_floor_speed);
+        assert_eq!(original.protect_highs, restored.protect_highs);
+        assert_eq!(original.spectral_gate, restored.spectral_gate);
+        assert_eq!(original.dither_enabled, restored.dither_enabled);
+        assert_eq!(original.expander_ratio, restored.expander_ratio);
+        assert_eq!(original.gate_hysteresis, restored.gate_hysteresis);
+        assert_eq!(original.gate_subblocks, restored.gate_subblocks);
+        assert_eq!(original.gate_key_source, restored.gate_key_source);
+        assert_eq!(original.vad_source_channel, restored.vad_source_channel);
+        assert_eq!(original.vad_window_frames, restored.vad_window_frames);
+        assert_eq!(original.vad_onset_frames, restored.vad_onset_frames);
+        assert_eq!(original.vad_hangover_frames, restored.vad_hangover_frames);
+        assert_eq!(original.input_gain_db, restored.input_gain_db);
         assert_eq!(
             original.dynamic_threshold_enabled,
             restored.dynamic_threshold_enabled
         );
+        assert_eq!(
+            original.dynamic_threshold_min,
+            restored.dynamic_threshold_min
+        );
+        assert_eq!(
+            original.dynamic_threshold_max,
+            restored.dynamic_threshold_max
+        );
         assert_eq!(
             original.output_filter_enabled,
             restored.output_filter_enabled
         );
+        assert_eq!(original.check_for_updates, restored.check_for_updates);
+        assert_eq!(original.update_channel, restored.update_channel);
+        assert_eq!(original.language, restored.language);
+        assert_eq!(
+            original.spectrum_log_frequency,
+            restored.spectrum_log_frequency
+        );
+        assert_eq!(original.spectrum_db_scale, restored.spectrum_db_scale);
+        assert_eq!(original.spectrum_db_floor, restored.spectrum_db_floor);
+        assert_eq!(original.spectrum_peak_hold, restored.spectrum_peak_hold);
+        assert_eq!(original.visualizer_waveform, restored.visualizer_waveform);
+        assert_eq!(original.meter_ballistics, restored.meter_ballistics);
+        assert_eq!(original.underrun_fill, restored.underrun_fill);
+        assert_eq!(original.advanced_mode, restored.advanced_mode);
+        assert_eq!(original.persist_virtual_sink, restored.persist_virtual_sink);
+        assert_eq!(original.gate_event_log, restored.gate_event_log);
+        assert_eq!(original.metrics_port, restored.metrics_port);
+        assert_eq!(original.record_path, restored.record_path);
+        assert_eq!(original.audio_backend, restored.audio_backend);
+        assert_eq!(original.stereo_input, restored.stereo_input);
+        assert_eq!(original.eq_tilt, restored.eq_tilt);
+        assert_eq!(original.monitor_output, restored.monitor_output);
+        assert_eq!(
+            original.output_filter_suppression,
+            restored.output_filter_suppression
+        );
+        assert_eq!(
+            original.output_filter_threshold,
+            restored.output_filter_threshold
+        );
+        assert_eq!(
+            original.output_filter_eq_low_gain,
+            restored.output_filter_eq_low_gain
+        );
+        assert_eq!(original.agc_attack_ms, restored.agc_attack_ms);
+        assert_eq!(original.agc_release_ms, restored.agc_release_ms);
+        assert_eq!(original.agc_hold_when_gated, restored.agc_hold_when_gated);
+        assert_eq!(original.agc_ceiling_db, restored.agc_ceiling_db);
+        assert_eq!(original.compressor_enabled, restored.compressor_enabled);
+        assert_eq!(
+            original.compressor_threshold_db,
+            restored.compressor_threshold_db
+        );
+        assert_eq!(original.compressor_ratio, restored.compressor_ratio);
+        assert_eq!(original.compressor_makeup_db, restored.compressor_makeup_db);
+        assert_eq!(original.preset_overrides, restored.preset_overrides);
+        assert_eq!(original.auto_suspend_enabled, restored.auto_suspend_enabled);
+        assert_eq!(original.auto_suspend_secs, restored.auto_suspend_secs);
+        assert_eq!(original.silence_action, restored.silence_action);
+        assert_eq!(original.silence_minutes, restored.silence_minutes);
+        assert_eq!(original.per_device_presets, restored.per_device_presets);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let env = HashMap::from([
+            ("VOIDMIC_THRESHOLD", " 0.03 "),
+            ("VOIDMIC_VAD", "1"),
+            ("VOIDMIC_AGC", "on"),
+            ("VOIDMIC_AUTO_GATE", "false"),
+            ("VOIDMIC_METRICS_PORT", "9464"),
+        ]);
+        let mut config = AppConfig {
+            dynamic_threshold_enabled: true,
+            suppression_strength: 0.6,
+            ..AppConfig::default()
+        };
+        config
+            .apply_overrides(|name| env.get(name).map(|v| v.to_string()))
+            .unwrap();
+        assert_eq!(config.gate_threshold, 0.03);
+        assert_eq!(config.vad_sensitivity, 1);
+        assert!(config.agc_enabled);
+        assert!(!config.dynamic_threshold_enabled);
+        assert_eq!(config.metrics_port, Some(9464));
+        // Unset variables keep the config value
+        assert_eq!(config.suppression_strength, 0.6);
+        assert!(!config.eq_enabled);
+
+        for (name, value) in [
+            ("VOIDMIC_SUPPRESSION", "1.5"),
+            ("VOIDMIC_VAD", "4"),
+            ("VOIDMIC_EQ", "maybe"),
+            ("VOIDMIC_THRESHOLD", "loud"),
+            ("VOIDMIC_METRICS_PORT", "0"),
+            ("VOIDMIC_METRICS_PORT", "70000"),
+        ] {
+            let err = AppConfig::default()
+                .apply_overrides(|var| (var == name).then(|| value.to_string()))
+                .unwrap_err();
+            assert!(err.to_string().contains(name), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_read_file_reports_invalid_config() {
+        let path = std::env::temp_dir().join(format!("voidmic_config_{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{"gate_threshold": 0.02, "last_input": "Podcast Mic", "last_output": "default"}"#,
+        )
+        .unwrap();
+        let config = AppConfig::read_file(&path).unwrap();
+        assert_eq!(config.last_input, "Podcast Mic");
+
+        fs::write(&path, r#"{"gate_threshold": "loud"}"#).unwrap();
+        let Err(err) = AppConfig::read_file(&path) else {
+            panic!("A string threshold should be rejected");
+        };
+        // Names the file and where in it the problem is
+        let message = format!("{:#}", err);
+        assert!(message.contains(&*path.to_string_lossy()), "{}", message);
+        assert!(message.contains("line 1"), "{}", message);
+        let _ = fs::remove_file(&path);
+        assert!(AppConfig::read_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_config_dir_override() {
+        let flag = Some(PathBuf::from("/media/usb/voidmic"));
+        let env = Some(OsString::from("/opt/voidmic"));
+        assert_eq!(config_dir_override(flag.clone(), env.clone()), flag);
+        assert_eq!(
+            config_dir_override(None, env),
+            Some(PathBuf::from("/opt/voidmic"))
+        );
+        assert_eq!(config_dir_override(None, Some(OsString::new())), None);
+        assert_eq!(config_dir_override(None, None), None);
+    }
+
+    #[test]
+    fn test_preset_for_device() {
+        let config = AppConfig {
+            preset: "Gaming".to_string(),
+            per_device_presets: HashMap::from([("Podcast Mic".to_string(), "Podcast".to_string())]),
+            ..AppConfig::default()
+        };
+        assert_eq!(config.preset_for_device("Podcast Mic"), "Podcast");
+        // No mapping: the global preset
+        assert_eq!(config.preset_for_device("Headset"), "Gaming");
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""per_device_presets":{"Podcast Mic":"Podcast"}"#));
+        let restored: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.preset_for_device("Podcast Mic"), "Podcast");
+    }
+
+    #[test]
+    fn test_preset_values_cover_vad_eq_and_agc() {
+        let mut config = AppConfig::default();
+        let values = PresetValues {
+            gate_threshold: 0.008,
+            suppression_strength: 0.6,
+            dynamic_threshold_enabled: true,
+            vad_sensitivity: 1,
+            eq_enabled: true,
+            eq_low_gain: 2.0,
+            eq_mid_gain: -1.0,
+            eq_high_gain: 3.0,
+            agc_enabled: true,
+            agc_target_level: 0.5,
+        };
+        config.set_preset_values(&values);
+        assert_eq!(config.vad_sensitivity, 1);
+        assert_eq!(config.eq_high_gain, 3.0);
+        assert!(config.agc_enabled);
+        assert_eq!(config.preset_values(), values);
+
+        // Overrides saved with only the original three fields get the defaults for the rest
+        let json = r#"{"gate_threshold":0.02,"suppression_strength":0.9,"dynamic_threshold_enabled":false}"#;
+        let old: PresetValues = serde_json::from_str(json).unwrap();
+        assert_eq!(old.gate_threshold, 0.02);
+        assert_eq!(old.vad_sensitivity, 2);
+        assert!(!old.eq_enabled && !old.agc_enabled);
+        assert_eq!(old.agc_target_level, 0.7);
+    }
+
+    #[test]
+    fn test_apply_to_reaches_processor_controls() {
+        let config = AppConfig {
+            gate_hold_ms: 250,
+            expander_ratio: 3.0,
+            spectral_gate: true,
+            ..AppConfig::default()
+        };
+        let controls = ProcessorControls::new(2, (0.0, 0.0, 0.0), 0.7, false);
+        config.apply_to(&controls);
+        assert_eq!(controls.gate_hold_ms.load(Ordering::Relaxed), 250);
+        assert_eq!(
+            f32::from_bits(controls.expander_ratio.load(Ordering::Relaxed)),
+            3.0
+        );
+        assert!(controls.spectral_gate.load(Ordering::Relaxed));
     }
 }
@@ -1,16 +1,87 @@
 //! Daemon management for VoidMic.
 //!
-//! Provides PID file management for graceful shutdown of background processes.
+//! Provides PID file management for graceful shutdown of background processes, and a
+//! status file the running engine refreshes so `voidmic status` can report on it.
 
 use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const PID_FILENAME: &str = "daemon.pid";
+const STATUS_FILENAME: &str = "daemon.status";
+/// How often a running engine rewrites its status file
+pub const STATUS_INTERVAL_SECS: u64 = 1;
+// A status file not refreshed for this long belongs to an engine that is gone
+const STATUS_STALE_SECS: u64 = 5;
+
+/// Snapshot written by a running `voidmic run` process.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    /// Unix time (seconds) the engine started
+    pub started_at: u64,
+    /// Unix time (seconds) of the last refresh
+    pub updated_at: u64,
+    pub frames_processed: u64,
+    /// Threshold the gate currently uses (the Auto-Gate one when that is on)
+    #[serde(default)]
+    pub threshold: f32,
+    /// Auto-Gate's noise floor estimate; 0 while Auto-Gate is off
+    #[serde(default)]
+    pub noise_floor: f32,
+    /// Threshold suggested by the last `run --calibrate`
+    #[serde(default)]
+    pub calibrated_threshold: Option<f32>,
+}
+
+impl DaemonStatus {
+    /// Whether the writer refreshed this recently enough to still be running.
+    pub fn is_fresh(&self, now: u64) -> bool {
+        now.saturating_sub(self.updated_at) <= STATUS_STALE_SECS
+    }
+
+    pub fn uptime_secs(&self, now: u64) -> u64 {
+        now.saturating_sub(self.started_at)
+    }
+}
+
+/// Current Unix time in seconds.
+pub fn unix_now() -> u64 {
+    unix_secs(SystemTime::now())
+}
+
+pub fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Formats a duration as `1h 02m 03s`, `4m 05s` or `7s`.
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Directory for the PID and status files: the `--config-dir` one if set, so separately
+/// configured instances keep their own, else the user data directory.
+fn data_dir() -> Option<PathBuf> {
+    match crate::config::custom_config_dir() {
+        Some(dir) => Some(dir.to_path_buf()),
+        None => {
+            ProjectDirs::from("com", "voidmic", "voidmic").map(|dirs| dirs.data_dir().to_path_buf())
+        }
+    }
+}
 
 /// Gets the path to the PID file.
 fn pid_file_path() -> Option<PathBuf> {
-    ProjectDirs::from("com", "voidmic", "voidmic").map(|dirs| dirs.data_dir().join(PID_FILENAME))
+    data_dir().map(|dir| dir.join(PID_FILENAME))
 }
 
 /// Writes the given process ID to the PID file.
@@ -47,6 +118,40 @@ pub fn remove_pid_file() -> Result<(), String> {
     Ok(())
 }
 
+fn status_file_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(STATUS_FILENAME))
+}
+
+/// Writes the engine status for `voidmic status` to pick up.
+pub fn write_status_file(status: &DaemonStatus) -> Result<(), String> {
+    let path = status_file_path().ok_or("Could not determine data directory")?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+
+    let json =
+        serde_json::to_string(status).map_err(|e| format!("Failed to serialize status: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write status file: {}", e))
+}
+
+/// Reads the last status written by a running engine.
+pub fn read_status_file() -> Option<DaemonStatus> {
+    let path = status_file_path()?;
+    let content = fs::read_to_string(&path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Removes the status file.
+pub fn remove_status_file() -> Result<(), String> {
+    if let Some(path) = status_file_path() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove status file: {}", e))?;
+        }
+    }
+    Ok(())
+}
 
 /// Stops the running daemon by sending SIGTERM.
 #[cfg(target_os = "linux")]
@@ -70,6 +175,34 @@ pub fn stop_daemon() -> Result<(), String> {
     Err("Daemon management not supported on this platform".to_string())
 }
 
+/// Fallback for a missing PID file: stops the `voidmic run` processes of this instance,
+/// i.e. those started with the same `--config-dir` (or none, for the default one).
+#[cfg(target_os = "linux")]
+pub fn kill_instance_runs() {
+    use std::process::Command;
+    let Ok(output) = Command::new("pgrep").args(["-af", "voidmic run"]).output() else {
+        return;
+    };
+    let config_dir = crate::config::custom_config_dir();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((pid, cmdline)) = line.split_once(' ') else {
+            continue;
+        };
+        if is_instance_run(cmdline, config_dir) && pid != std::process::id().to_string() {
+            let _ = Command::new("kill").args(["-TERM", pid]).output();
+        }
+    }
+}
+
+/// Whether a `voidmic run` command line belongs to the instance using `config_dir`.
+#[cfg(target_os = "linux")]
+fn is_instance_run(cmdline: &str, config_dir: Option<&std::path::Path>) -> bool {
+    match config_dir {
+        Some(dir) => format!("{} ", cmdline).contains(&format!("--config-dir {} ", dir.display())),
+        None => !cmdline.contains("--config-dir"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +212,50 @@ mod tests {
         // Should return Some path on most systems
         assert!(pid_file_path().is_some());
     }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_instance_runs_are_told_apart() {
+        use std::path::Path;
+        let default = "/usr/bin/voidmic run --backend default -i mic -o VoidMic_Clean";
+        let usb = "/usr/bin/voidmic run --backend default --config-dir /media/usb -i mic";
+        assert!(is_instance_run(default, None));
+        assert!(!is_instance_run(usb, None));
+        assert!(is_instance_run(usb, Some(Path::new("/media/usb"))));
+        assert!(!is_instance_run(usb, Some(Path::new("/media/us"))));
+        assert!(!is_instance_run(default, Some(Path::new("/media/usb"))));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(7), "7s");
+        assert_eq!(format_duration(245), "4m 05s");
+        assert_eq!(format_duration(3723), "1h 02m 03s");
+        assert_eq!(format_duration(90_000), "25h 00m 00s");
+    }
+
+    #[test]
+    fn test_status_freshness() {
+        let status = DaemonStatus {
+            pid: 42,
+            started_at: 1_000,
+            updated_at: 1_060,
+            frames_processed: 6_000,
+            threshold: 0.015,
+            noise_floor: 0.0,
+            calibrated_threshold: None,
+        };
+        assert!(status.is_fresh(1_060 + STATUS_STALE_SECS));
+        assert!(!status.is_fresh(1_061 + STATUS_STALE_SECS));
+        assert_eq!(status.uptime_secs(1_060), 60);
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(serde_json::from_str::<DaemonStatus>(&json).unwrap(), status);
+
+        // Written by a build that didn't report threshold or calibration yet
+        let old = r#"{"pid":42,"started_at":1000,"updated_at":1060,"frames_processed":6000}"#;
+        let old: DaemonStatus = serde_json::from_str(old).unwrap();
+        assert_eq!(old.threshold, 0.0);
+        assert_eq!(old.calibrated_threshold, None);
+    }
 }
@@ -0,0 +1,227 @@
+//! `voidmic doctor`: checks what VoidMic needs from the system (sound server, devices,
+//! sample rate, virtual sink, config) and says how to fix whatever is missing.
+
+use cpal::traits::DeviceTrait;
+
+use crate::audio::{self, AudioBackend};
+use crate::config::AppConfig;
+#[cfg(target_os = "linux")]
+use crate::{pulse_info, virtual_device};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but probably not the way the user wants
+    Warn,
+    Fail,
+}
+
+/// One line of the report.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Runs every check against `backend`'s audio host.
+pub fn run_checks(backend: AudioBackend) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    checks.push(match pulse_info::get_server_name() {
+        Ok(name) => Check::pass("Sound server", name),
+        Err(e) => Check::fail(
+            "Sound server",
+            e,
+            "Install pactl (pulseaudio-utils) and make sure PipeWire (with pipewire-pulse) \
+             or PulseAudio is running",
+        ),
+    });
+
+    let (config, config_check) = check_config();
+    checks.push(config_check);
+
+    let host = audio::audio_host(backend);
+    checks.push(Check::pass("Audio host", host.id().name()));
+    checks.extend(check_device(&host, true, &config.last_input));
+    checks.extend(check_device(&host, false, &config.last_output));
+
+    #[cfg(target_os = "linux")]
+    checks.push(if virtual_device::virtual_sink_exists() {
+        Check::pass(
+            "Virtual sink",
+            format!(
+                "Loaded; apps should use {} as their mic",
+                virtual_device::get_monitor_source_name()
+            ),
+        )
+    } else {
+        Check::warn(
+            "Virtual sink",
+            "Not loaded",
+            "Start processing in the GUI or run `voidmic load`; both create it",
+        )
+    });
+
+    checks
+}
+
+/// Validates the config file and the `VOIDMIC_*` overrides, returning the config the
+/// other checks should use.
+fn check_config() -> (AppConfig, Check) {
+    let mut config = AppConfig::load();
+    let check = match AppConfig::check_file() {
+        Ok(path) => {
+            let file = path.map_or("none, using defaults".to_string(), |path| {
+                path.display().to_string()
+            });
+            match config.apply_env_overrides() {
+                Ok(()) => Check::pass("Config", file),
+                Err(e) => Check::fail(
+                    "Config",
+                    format!("{:#}", e),
+                    "Fix or unset the environment variable",
+                ),
+            }
+        }
+        Err(e) => Check::fail(
+            "Config",
+            format!("{:#}", e),
+            "Fix or delete the file; VoidMic ignores it and uses the defaults until then",
+        ),
+    };
+    (config, check)
+}
+
+/// Checks the default input or output and, if one is remembered in the config, that
+/// device too: that they resolve and can run at 48kHz.
+fn check_device(host: &cpal::Host, is_input: bool, remembered: &str) -> Vec<Check> {
+    let (name, rate_name, remembered_name) = if is_input {
+        ("Default input", "Input sample rate", "Last used input")
+    } else {
+        ("Default output", "Output sample rate", "Last used output")
+    };
+    let mut checks = Vec::new();
+
+    let device = match audio::resolve_device(host, "default", is_input) {
+        Ok(device) => device,
+        Err(e) => {
+            checks.push(Check::fail(
+                name,
+                format!("{:#}", e),
+                "Connect a device or set a default in your sound settings (e.g. pavucontrol)",
+            ));
+            return checks;
+        }
+    };
+    let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+    checks.push(Check::pass(name, device_name.clone()));
+    checks.push(if audio::supports_engine_rate(&device, is_input) {
+        Check::pass(rate_name, format!("{} supports 48 kHz", device_name))
+    } else {
+        Check::fail(
+            rate_name,
+            format!("{} doesn't offer 48 kHz", device_name),
+            "Pick another device, or allow 48000 in PipeWire's default.clock.allowed-rates",
+        )
+    });
+
+    if !remembered.is_empty() && remembered != "default" {
+        checks.push(match audio::resolve_device(host, remembered, is_input) {
+            Ok(_) => Check::pass(remembered_name, remembered),
+            Err(_) => Check::warn(
+                remembered_name,
+                format!("{} not found", remembered),
+                "Plug it in or pick another device; `voidmic list` shows what is available",
+            ),
+        });
+    }
+    checks
+}
+
+/// Formats `checks` as a pass/fail list with hints, followed by a summary line.
+pub fn format_report(checks: &[Check]) -> String {
+    let mut report = String::new();
+    for check in checks {
+        let mark = match check.status {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+        };
+        report.push_str(&format!("{} {}: {}\n", mark, check.name, check.detail));
+        if let Some(hint) = &check.hint {
+            report.push_str(&format!("    → {}\n", hint));
+        }
+    }
+    let count = |status: CheckStatus| checks.iter().filter(|c| c.status == status).count();
+    let (failed, warned) = (count(CheckStatus::Fail), count(CheckStatus::Warn));
+    report.push_str(&match (failed, warned) {
+        (0, 0) => "\nAll checks passed".to_string(),
+        (0, _) => format!("\nNo problems found ({} warning(s))", warned),
+        _ => format!("\n{} problem(s) found ({} warning(s))", failed, warned),
+    });
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report() {
+        let mut checks = vec![
+            Check::pass("Sound server", "PulseAudio (on PipeWire 1.0.5)"),
+            Check::warn("Virtual sink", "Not loaded", "Run `voidmic load`"),
+        ];
+        let report = format_report(&checks);
+        assert!(report.starts_with("✓ Sound server: PulseAudio (on PipeWire 1.0.5)\n"));
+        assert!(report.contains("! Virtual sink: Not loaded\n    → Run `voidmic load`\n"));
+        assert!(report.ends_with("No problems found (1 warning(s))"));
+
+        checks.push(Check::fail(
+            "Default input",
+            "No default input found",
+            "Connect a device",
+        ));
+        let report = format_report(&checks);
+        assert!(report.contains("✗ Default input: No default input found\n"));
+        assert!(report.ends_with("1 problem(s) found (1 warning(s))"));
+
+        assert_eq!(
+            format_report(&checks[..1]).lines().last(),
+            Some("All checks passed")
+        );
+    }
+}
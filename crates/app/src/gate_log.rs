@@ -0,0 +1,128 @@
+//! Optional CSV log of gate open/close events, for finding out why the gate chatters.
+//!
+//! The processor only sends an event when the gate flips, and only while a log is
+//! attached; writing happens on a separate thread.
+
+use anyhow::{Context, Result};
+use crossbeam_channel::Sender;
+use directories::ProjectDirs;
+use log::warn;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use voidmic_core::processor::GateEvent;
+
+const GATE_LOG_FILENAME: &str = "gate_events.csv";
+// Transitions buffered between the audio thread and the writer
+const GATE_LOG_QUEUE: usize = 256;
+
+/// Where the GUI writes the log (next to the status and PID files).
+pub fn gate_log_path() -> Option<PathBuf> {
+    ProjectDirs::from("com", "voidmic", "voidmic")
+        .map(|dirs| dirs.data_dir().join(GATE_LOG_FILENAME))
+}
+
+/// One CSV row: seconds since the Unix epoch, `open`/`close`, RMS, threshold.
+fn format_event(event: &GateEvent) -> String {
+    let secs = event
+        .time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    format!(
+        "{:.3},{},{:.5},{:.5}",
+        secs,
+        if event.open { "open" } else { "close" },
+        event.rms,
+        event.threshold
+    )
+}
+
+/// Creates (truncates) the log file at `path` and starts a writer thread for it.
+/// Attach the returned sender to `VoidProcessor::gate_event_sender`; the thread ends
+/// when the processor (and with it the sender) is dropped.
+pub fn start(path: &Path) -> Result<Sender<GateEvent>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Can't create {}", parent.display()))?;
+    }
+    let file = File::create(path).with_context(|| format!("Can't create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "time,event,rms,threshold")?;
+    writer.flush()?;
+
+    let (tx, rx) = crossbeam_channel::bounded::<GateEvent>(GATE_LOG_QUEUE);
+    std::thread::Builder::new()
+        .name("voidmic-gate-log".into())
+        .spawn(move || {
+            for event in rx {
+                // Flush per event so the file can be followed live
+                let written =
+                    writeln!(writer, "{}", format_event(&event)).and_then(|()| writer.flush());
+                if let Err(e) = written {
+                    warn!("Gate event log stopped: {}", e);
+                    return;
+                }
+            }
+        })
+        .context("Failed to spawn gate log thread")?;
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn event(secs: u64, open: bool) -> GateEvent {
+        GateEvent {
+            time: SystemTime::UNIX_EPOCH + Duration::from_millis(secs * 1000 + 250),
+            open,
+            rms: 0.02,
+            threshold: 0.015,
+        }
+    }
+
+    #[test]
+    fn test_format_event() {
+        assert_eq!(
+            format_event(&event(1_700_000_000, true)),
+            "1700000000.250,open,0.02000,0.01500"
+        );
+        assert_eq!(
+            format_event(&event(5, false)),
+            "5.250,close,0.02000,0.01500"
+        );
+    }
+
+    #[test]
+    fn test_log_file_gets_header_and_events() {
+        let path = std::env::temp_dir()
+            .join(format!("voidmic_gate_log_{}", std::process::id()))
+            .join(GATE_LOG_FILENAME);
+        let tx = start(&path).unwrap();
+        tx.send(event(1, true)).unwrap();
+        tx.send(event(2, false)).unwrap();
+        drop(tx);
+
+        // The writer flushes per event; give it a moment to drain the queue
+        let mut content = String::new();
+        for _ in 0..100 {
+            content = std::fs::read_to_string(&path).unwrap_or_default();
+            if content.lines().count() == 3 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(
+            content.lines().collect::<Vec<_>>(),
+            [
+                "time,event,rms,threshold",
+                "1.250,open,0.02000,0.01500",
+                "2.250,close,0.02000,0.01500"
+            ]
+        );
+    }
+}
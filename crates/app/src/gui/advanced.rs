@@ -1,36 +1,42 @@
-use crate::audio::OutputFilterEngine;
+use crate::audio::{estimated_latency_ms, UnderrunFill, ENGINE_BUFFER_MS};
+use crate::gate_log;
 use eframe::egui;
 use std::sync::atomic::Ordering;
+use voidmic_core::processor::{
+    VadSource, AGC_CEILING_MIN_DB, VAD_MAX_HANGOVER_FRAMES, VAD_MAX_WINDOW_FRAMES,
+};
+use voidmic_ui::widgets::{self, MeterBallistics};
 
 use super::app::VoidMicApp;
 
 impl VoidMicApp {
     /// Renders advanced features (output filter, echo cancellation, VAD, EQ, AGC, bypass, spectrum).
+    /// Outside advanced mode, VAD, the detailed EQ and the latency readouts sit under "Show more".
     pub(super) fn render_advanced_features(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Advanced Features");
+        ui.horizontal(|ui| {
+            ui.heading(tr!("advanced.heading"));
+            if ui
+                .checkbox(&mut self.config.advanced_mode, tr!("advanced.mode"))
+                .on_hover_text(tr!("advanced.mode_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+            }
+        });
 
         ui.horizontal(|ui| {
             if ui
                 .checkbox(
                     &mut self.config.output_filter_enabled,
-                    "Filter Output (Speaker Denoising)",
+                    tr!("advanced.filter_output"),
                 )
                 .changed()
             {
                 self.mark_config_dirty();
                 if self.config.output_filter_enabled {
                     if self.engine.is_some() && self.output_filter_engine.is_none() {
-                        match OutputFilterEngine::start(
-                            &self.selected_reference,
-                            &self.selected_output,
-                            self.config.suppression_strength,
-                        ) {
-                            Ok(filter) => self.output_filter_engine = Some(filter),
-                            Err(e) => {
-                                log::error!("Output filter failed to start: {}", e);
-                                self.status_msg = format!("Output filter error: {}", e);
-                                self.config.output_filter_enabled = false;
-                            }
+                        if let Err(e) = self.start_output_filter() {
+                            self.status_msg = tr!("status.output_filter_error", error = e);
                         }
                     }
                 } else {
@@ -38,32 +44,53 @@ impl VoidMicApp {
                 }
             }
             ui.label(
-                egui::RichText::new("⚠️ ~100ms latency")
+                egui::RichText::new(tr!("advanced.latency_warning"))
                     .size(10.0)
                     .color(egui::Color32::YELLOW),
             );
         });
 
+        if self.config.output_filter_enabled {
+            self.render_output_filter_controls(ui);
+        }
+
         ui.horizontal(|ui| {
             if ui
-                .checkbox(&mut self.config.echo_cancel_enabled, "Echo Cancellation")
+                .checkbox(
+                    &mut self.config.echo_cancel_enabled,
+                    tr!("advanced.echo_cancel"),
+                )
                 .changed()
             {
                 self.mark_config_dirty();
-                if self.engine.is_some() {
-                    let prev_echo = !self.config.echo_cancel_enabled;
-                    self.stop_engine();
-                    self.start_engine();
-                    if self.engine.is_none() {
-                        self.config.echo_cancel_enabled = prev_echo;
-                    }
-                }
+                self.apply_echo_cancel();
             }
         });
 
+        if self.config.echo_cancel_enabled {
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(
+                        &mut self.config.clean_reference,
+                        tr!("advanced.denoise_reference"),
+                    )
+                    .on_hover_text(tr!("advanced.denoise_reference_hint"))
+                    .changed()
+                {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        engine
+                            .controls
+                            .clean_reference
+                            .store(self.config.clean_reference, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
         if self.config.echo_cancel_enabled || self.config.output_filter_enabled {
             ui.horizontal(|ui| {
-                ui.label("Reference Input (Monitor):");
+                ui.label(tr!("advanced.reference_input"));
                 let prev_ref = self.selected_reference.clone();
                 egui::ComboBox::from_id_salt("ref_combo")
                     .selected_text(&self.selected_reference)
@@ -76,14 +103,423 @@ impl VoidMicApp {
                     });
                 if self.selected_reference != prev_ref {
                     self.mark_config_dirty();
+                    self.apply_reference_device();
                 }
-                ui.label(egui::RichText::new("ℹ️ Select speaker monitor").size(10.0));
+                ui.label(egui::RichText::new(tr!("advanced.select_monitor")).size(10.0));
             });
         }
 
         ui.separator();
 
-        // VAD Controls
+        if self.config.advanced_mode {
+            self.render_vad_controls(ui);
+            ui.separator();
+        }
+
+        // Tone macro (drives the EQ shelves, works without the detailed EQ)
+        ui.horizontal(|ui| {
+            ui.label(tr!("advanced.tone"));
+            ui.label(egui::RichText::new(tr!("advanced.bright")).size(10.0));
+            if ui
+                .add(egui::Slider::new(&mut self.config.eq_tilt, -1.0..=1.0).show_value(false))
+                .on_hover_text(tr!("advanced.tone_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .eq_tilt
+                        .store(self.config.eq_tilt.to_bits(), Ordering::Relaxed);
+                }
+            }
+            ui.label(egui::RichText::new(tr!("advanced.warm")).size(10.0));
+            if self.config.eq_tilt != 0.0 && ui.small_button(tr!("advanced.reset")).clicked() {
+                self.config.eq_tilt = 0.0;
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .eq_tilt
+                        .store(0.0f32.to_bits(), Ordering::Relaxed);
+                }
+            }
+        });
+
+        if self.config.advanced_mode {
+            self.render_equalizer_controls(ui);
+        }
+
+        // Compressor
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui
+                .checkbox(
+                    &mut self.config.compressor_enabled,
+                    tr!("advanced.compressor"),
+                )
+                .on_hover_text(tr!("advanced.compressor_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .compressor_enabled
+                        .store(self.config.compressor_enabled, Ordering::Relaxed);
+                }
+            }
+        });
+
+        if self.config.compressor_enabled {
+            egui::Grid::new("compressor_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("advanced.threshold"));
+                    let slider =
+                        egui::Slider::new(&mut self.config.compressor_threshold_db, -60.0..=0.0)
+                            .text("dB");
+                    if ui.add(slider).changed() {
+                        self.mark_config_dirty();
+                        if let Some(engine) = &self.engine {
+                            let bits = self.config.compressor_threshold_db.to_bits();
+                            engine
+                                .controls
+                                .compressor_threshold_db
+                                .store(bits, Ordering::Relaxed);
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(tr!("advanced.ratio"));
+                    let slider = egui::Slider::new(&mut self.config.compressor_ratio, 1.0..=20.0)
+                        .text(":1")
+                        .logarithmic(true);
+                    if ui.add(slider).changed() {
+                        self.mark_config_dirty();
+                        if let Some(engine) = &self.engine {
+                            let bits = self.config.compressor_ratio.to_bits();
+                            engine
+                                .controls
+                                .compressor_ratio
+                                .store(bits, Ordering::Relaxed);
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(tr!("advanced.attack"));
+                    let slider =
+                        egui::Slider::new(&mut self.config.compressor_attack_ms, 0.1..=100.0)
+                            .text("ms")
+                            .logarithmic(true);
+                    if ui.add(slider).changed() {
+                        self.mark_config_dirty();
+                        if let Some(engine) = &self.engine {
+                            let bits = self.config.compressor_attack_ms.to_bits();
+                            engine
+                                .controls
+                                .compressor_attack_ms
+                                .store(bits, Ordering::Relaxed);
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(tr!("advanced.release"));
+                    let slider =
+                        egui::Slider::new(&mut self.config.compressor_release_ms, 10.0..=1000.0)
+                            .text("ms")
+                            .logarithmic(true);
+                    if ui.add(slider).changed() {
+                        self.mark_config_dirty();
+                        if let Some(engine) = &self.engine {
+                            let bits = self.config.compressor_release_ms.to_bits();
+                            engine
+                                .controls
+                                .compressor_release_ms
+                                .store(bits, Ordering::Relaxed);
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(tr!("advanced.makeup"));
+                    let slider =
+                        egui::Slider::new(&mut self.config.compressor_makeup_db, 0.0..=24.0)
+                            .text("dB");
+                    if ui.add(slider).changed() {
+                        self.mark_config_dirty();
+                        if let Some(engine) = &self.engine {
+                            let bits = self.config.compressor_makeup_db.to_bits();
+                            engine
+                                .controls
+                                .compressor_makeup_db
+                                .store(bits, Ordering::Relaxed);
+                        }
+                    }
+                    ui.end_row();
+                });
+        }
+
+        // AGC + Bypass
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui
+                .checkbox(&mut self.config.agc_enabled, tr!("advanced.agc"))
+                .on_hover_text(tr!("advanced.agc_hint"))
+                .changed()
+            {
+                self.record_preset_edit();
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .agc_enabled
+                        .store(self.config.agc_enabled, Ordering::Relaxed);
+                }
+            }
+        });
+
+        if self.config.agc_enabled {
+            egui::Grid::new("agc_grid").num_columns(2).show(ui, |ui| {
+                ui.label(tr!("advanced.attack"));
+                let slider = egui::Slider::new(&mut self.config.agc_attack_ms, 10.0..=1000.0)
+                    .text("ms")
+                    .logarithmic(true);
+                if ui.add(slider).changed() {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        let bits = self.config.agc_attack_ms.to_bits();
+                        engine.controls.agc_attack_ms.store(bits, Ordering::Relaxed);
+                    }
+                }
+                ui.end_row();
+
+                ui.label(tr!("advanced.release"));
+                let slider = egui::Slider::new(&mut self.config.agc_release_ms, 100.0..=10_000.0)
+                    .text("ms")
+                    .logarithmic(true);
+                if ui.add(slider).changed() {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        let bits = self.config.agc_release_ms.to_bits();
+                        engine
+                            .controls
+                            .agc_release_ms
+                            .store(bits, Ordering::Relaxed);
+                    }
+                }
+                ui.end_row();
+
+                ui.label(tr!("advanced.ceiling"));
+                let slider =
+                    egui::Slider::new(&mut self.config.agc_ceiling_db, AGC_CEILING_MIN_DB..=0.0)
+                        .text("dB")
+                        .fixed_decimals(1);
+                if ui
+                    .add(slider)
+                    .on_hover_text(tr!("advanced.ceiling_hint"))
+                    .changed()
+                {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        let bits = self.config.agc_ceiling_db.to_bits();
+                        engine
+                            .controls
+                            .agc_ceiling_db
+                            .store(bits, Ordering::Relaxed);
+                    }
+                }
+                ui.end_row();
+            });
+
+            if ui
+                .checkbox(
+                    &mut self.config.agc_hold_when_gated,
+                    tr!("advanced.agc_hold"),
+                )
+                .on_hover_text(tr!("advanced.agc_hold_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .agc_hold_when_gated
+                        .store(self.config.agc_hold_when_gated, Ordering::Relaxed);
+                }
+            }
+        }
+
+        ui.add_space(5.0);
+
+        // BIG BYPASS BUTTON
+        let bypass_enabled = if let Some(engine) = &self.engine {
+            engine.controls.bypass_enabled.load(Ordering::Relaxed)
+        } else {
+            false
+        };
+        let bypass_text = if bypass_enabled {
+            tr!("advanced.bypassed")
+        } else {
+            tr!("advanced.processing_active")
+        };
+        let bypass_btn = ui
+            .add_sized(
+                [ui.available_width(), 30.0],
+                egui::Button::new(egui::RichText::new(bypass_text).strong().size(14.0)).fill(
+                    if bypass_enabled {
+                        egui::Color32::DARK_RED
+                    } else {
+                        egui::Color32::DARK_GREEN
+                    },
+                ),
+            )
+            .on_hover_text(tr!("app.bypass_hint"));
+        widgets::describe_button(&bypass_btn, tr!("app.processing"), Some(!bypass_enabled));
+        if bypass_btn.clicked() {
+            self.toggle_bypass();
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("advanced.meter"));
+            let current = MeterBallistics::from_u32(self.config.meter_ballistics);
+            egui::ComboBox::from_id_salt("meter_ballistics_combo")
+                .selected_text(meter_ballistics_label(current))
+                .show_ui(ui, |ui| {
+                    for ballistics in MeterBallistics::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.meter_ballistics,
+                                ballistics as u32,
+                                meter_ballistics_label(ballistics),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("advanced.meter_hint"));
+        });
+
+        // Spectrum / Waveform Visualizer
+        if self.engine.is_some() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let mut changed = ui
+                    .selectable_value(
+                        &mut self.config.visualizer_waveform,
+                        false,
+                        tr!("advanced.spectrum"),
+                    )
+                    .changed();
+                changed |= ui
+                    .selectable_value(
+                        &mut self.config.visualizer_waveform,
+                        true,
+                        tr!("advanced.waveform"),
+                    )
+                    .on_hover_text(tr!("advanced.waveform_hint"))
+                    .changed();
+                if changed {
+                    self.mark_config_dirty();
+                    self.waveform_history.0.clear();
+                    self.waveform_history.1.clear();
+                    if let Some(engine) = &self.engine {
+                        engine
+                            .controls
+                            .waveform_view
+                            .store(self.config.visualizer_waveform, Ordering::Relaxed);
+                    }
+                }
+            });
+            if self.config.visualizer_waveform {
+                self.render_waveform(ui);
+            } else {
+                self.render_spectrum(ui);
+                ui.horizontal(|ui| {
+                    let mut changed = ui
+                        .checkbox(
+                            &mut self.config.spectrum_log_frequency,
+                            tr!("advanced.log_freq"),
+                        )
+                        .on_hover_text(tr!("advanced.log_freq_hint"))
+                        .changed();
+                    changed |= ui
+                        .checkbox(&mut self.config.spectrum_db_scale, "dB")
+                        .on_hover_text(tr!("advanced.db_hint"))
+                        .changed();
+                    if self.config.spectrum_db_scale {
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(
+                                    &mut self.config.spectrum_db_floor,
+                                    -120.0..=-20.0,
+                                )
+                                .suffix(" dB")
+                                .step_by(5.0),
+                            )
+                            .on_hover_text(tr!("advanced.db_floor_hint"))
+                            .changed();
+                    }
+                    changed |= ui
+                        .checkbox(
+                            &mut self.config.spectrum_peak_hold,
+                            tr!("advanced.peak_hold"),
+                        )
+                        .on_hover_text(tr!("advanced.peak_hold_hint"))
+                        .changed();
+                    if changed {
+                        self.mark_config_dirty();
+                    }
+                });
+            }
+
+            // Gate Activity
+            ui.add_space(5.0);
+            ui.label(tr!("advanced.gate_activity"))
+                .on_hover_text(tr!("advanced.gate_activity_hint"));
+            self.render_gate_history(ui);
+            if ui
+                .checkbox(&mut self.config.gate_event_log, tr!("advanced.gate_log"))
+                .on_hover_ui(|ui| {
+                    let path = gate_log::gate_log_path()
+                        .map_or_else(|| "-".to_string(), |path| path.display().to_string());
+                    ui.label(tr!("advanced.gate_log_hint", path = path));
+                })
+                .changed()
+            {
+                self.mark_config_dirty();
+                // The log is attached when the engine starts
+                self.stop_engine();
+                self.start_engine();
+            }
+
+            if self.config.advanced_mode {
+                self.render_latency_health(ui);
+                self.render_underrun_fill(ui);
+            }
+        }
+
+        // Rarely-used controls stay reachable but out of the way unless in advanced mode
+        if !self.config.advanced_mode {
+            ui.add_space(5.0);
+            egui::CollapsingHeader::new(tr!("advanced.show_more"))
+                .id_salt("advanced_show_more")
+                .show(ui, |ui| {
+                    self.render_vad_controls(ui);
+                    ui.separator();
+                    self.render_equalizer_controls(ui);
+                    self.render_latency_health(ui);
+                    self.render_underrun_fill(ui);
+                });
+        }
+    }
+
+    /// WebRTC VAD aggressiveness picker.
+    fn render_vad_controls(&mut self, ui: &mut egui::Ui) {
         const VAD_MODES: &[(i32, &str, &str)] = &[
             (0, "Quality", "Quality (Likely Speech)"),
             (1, "Low Bitrate", "Low Bitrate"),
@@ -91,7 +527,7 @@ impl VoidMicApp {
             (3, "Very Aggressive", "Very Aggressive"),
         ];
         ui.horizontal(|ui| {
-            ui.label("VAD Sensitivity:");
+            ui.label(tr!("advanced.vad_sensitivity"));
             let current_label = VAD_MODES
                 .iter()
                 .find(|(v, _, _)| *v == self.config.vad_sensitivity)
@@ -105,9 +541,11 @@ impl VoidMicApp {
                             .selectable_value(&mut self.config.vad_sensitivity, *value, *label)
                             .clicked()
                         {
+                            self.record_preset_edit();
                             self.mark_config_dirty();
                             if let Some(engine) = &self.engine {
                                 engine
+                                    .controls
                                     .vad_sensitivity
                                     .store(self.config.vad_sensitivity as u32, Ordering::Relaxed);
                             }
@@ -115,148 +553,351 @@ impl VoidMicApp {
                     }
                 });
             ui.label(egui::RichText::new("ℹ️ WebRTC VAD").size(10.0))
-                .on_hover_text("Voice Activity Detection - filters non-speech sounds");
+                .on_hover_text(tr!("advanced.vad_hint"));
         });
 
-        ui.separator();
+        // Which channel the VAD hears; only matters with stereo capture
+        ui.horizontal(|ui| {
+            ui.label(tr!("advanced.vad_source"));
+            let current = VadSource::from_u32(self.config.vad_source_channel);
+            egui::ComboBox::from_id_salt("vad_source_combo")
+                .selected_text(vad_source_label(current))
+                .show_ui(ui, |ui| {
+                    for source in VAD_SOURCES {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.vad_source_channel,
+                                source.to_u32(),
+                                vad_source_label(source),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                            if let Some(engine) = &self.engine {
+                                engine
+                                    .controls
+                                    .vad_source_channel
+                                    .store(self.config.vad_source_channel, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("advanced.vad_source_hint"));
+        });
 
-        // Equalizer Controls
+        // Smoothing: speech needs `onset` voiced frames out of the last `window`
+        ui.label(tr!("advanced.vad_smoothing"))
+            .on_hover_text(tr!("advanced.vad_smoothing_hint"));
+        egui::Grid::new("vad_smoothing_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let mut changed = false;
+                ui.label(tr!("advanced.vad_window"));
+                let slider = egui::Slider::new(
+                    &mut self.config.vad_window_frames,
+                    1..=VAD_MAX_WINDOW_FRAMES,
+                )
+                .text(tr!("advanced.frames"));
+                changed |= ui.add(slider).changed();
+                ui.end_row();
+
+                ui.label(tr!("advanced.vad_onset"));
+                let window = self.config.vad_window_frames;
+                self.config.vad_onset_frames = self.config.vad_onset_frames.clamp(1, window);
+                let slider = egui::Slider::new(&mut self.config.vad_onset_frames, 1..=window)
+                    .text(tr!("advanced.frames"));
+                changed |= ui.add(slider).changed();
+                ui.end_row();
+
+                ui.label(tr!("advanced.vad_hangover"));
+                let slider = egui::Slider::new(
+                    &mut self.config.vad_hangover_frames,
+                    0..=VAD_MAX_HANGOVER_FRAMES,
+                )
+                .text(tr!("advanced.frames"));
+                changed |= ui.add(slider).changed();
+                ui.end_row();
+
+                if changed {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        let controls = &engine.controls;
+                        let window = self.config.vad_window_frames;
+                        controls.vad_window_frames.store(window, Ordering::Relaxed);
+                        let onset = self.config.vad_onset_frames;
+                        controls.vad_onset_frames.store(onset, Ordering::Relaxed);
+                        let hangover = self.config.vad_hangover_frames;
+                        controls
+                            .vad_hangover_frames
+                            .store(hangover, Ordering::Relaxed);
+                    }
+                }
+            });
+    }
+
+    /// Detailed 3-band EQ (gains per band and auto gain), on top of the tone macro.
+    fn render_equalizer_controls(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             if ui
-                .checkbox(&mut self.config.eq_enabled, "Equalizer (3-Band)")
+                .checkbox(&mut self.config.eq_enabled, tr!("advanced.equalizer_3band"))
                 .changed()
             {
+                self.record_preset_edit();
                 self.mark_config_dirty();
                 if let Some(engine) = &self.engine {
-                    engine.eq_enabled.store(self.config.eq_enabled, Ordering::Relaxed);
+                    engine
+                        .controls
+                        .eq_enabled
+                        .store(self.config.eq_enabled, Ordering::Relaxed);
                 }
             }
         });
 
         if self.config.eq_enabled {
             egui::Grid::new("eq_grid").num_columns(2).show(ui, |ui| {
-                ui.label("Low (Bass):");
+                ui.label(tr!("advanced.eq_low"));
                 if ui
                     .add(egui::Slider::new(&mut self.config.eq_low_gain, -10.0..=10.0).text("dB"))
                     .changed()
                 {
+                    self.record_preset_edit();
                     self.mark_config_dirty();
                     if let Some(engine) = &self.engine {
                         engine
+                            .controls
                             .eq_low_gain
                             .store(self.config.eq_low_gain.to_bits(), Ordering::Relaxed);
                     }
                 }
                 ui.end_row();
 
-                ui.label("Mid (Voice):");
+                ui.label(tr!("advanced.eq_mid"));
                 if ui
                     .add(egui::Slider::new(&mut self.config.eq_mid_gain, -10.0..=10.0).text("dB"))
                     .changed()
                 {
+                    self.record_preset_edit();
                     self.mark_config_dirty();
                     if let Some(engine) = &self.engine {
                         engine
+                            .controls
                             .eq_mid_gain
                             .store(self.config.eq_mid_gain.to_bits(), Ordering::Relaxed);
                     }
                 }
                 ui.end_row();
 
-                ui.label("High (Treble):");
+                ui.label(tr!("advanced.eq_high"));
                 if ui
                     .add(egui::Slider::new(&mut self.config.eq_high_gain, -10.0..=10.0).text("dB"))
                     .changed()
                 {
+                    self.record_preset_edit();
                     self.mark_config_dirty();
                     if let Some(engine) = &self.engine {
                         engine
+                            .controls
                             .eq_high_gain
                             .store(self.config.eq_high_gain.to_bits(), Ordering::Relaxed);
                     }
                 }
                 ui.end_row();
             });
-        }
 
-        // AGC + Bypass
-        ui.separator();
-
-        ui.horizontal(|ui| {
             if ui
-                .checkbox(&mut self.config.agc_enabled, "Automatic Gain Control (AGC)")
-                .on_hover_text("Normalizes volume to prevent clipping and boost quiet speech")
+                .checkbox(&mut self.config.eq_auto_gain, tr!("advanced.eq_auto_gain"))
+                .on_hover_text(tr!("advanced.eq_auto_gain_hint"))
                 .changed()
             {
                 self.mark_config_dirty();
                 if let Some(engine) = &self.engine {
                     engine
-                        .agc_enabled
-                        .store(self.config.agc_enabled, Ordering::Relaxed);
+                        .controls
+                        .eq_auto_gain
+                        .store(self.config.eq_auto_gain, Ordering::Relaxed);
                 }
             }
-        });
+        }
+    }
 
+    /// Jitter and latency readouts for the running engine.
+    fn render_latency_health(&self, ui: &mut egui::Ui) {
+        const JITTER_GOOD_US: u32 = 1000;
+        const JITTER_WARN_US: u32 = 5000;
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        let jitter = engine.jitter_ewma_us.load(Ordering::Relaxed);
+        let process_time = engine.process_time_us.load(Ordering::Relaxed);
+        let latency_ms = engine.latency_us.load(Ordering::Relaxed) as f32 / 1000.0;
         ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label(tr!("advanced.latency_health"));
+            let color = if jitter < JITTER_GOOD_US {
+                egui::Color32::GREEN
+            } else if jitter < JITTER_WARN_US {
+                egui::Color32::YELLOW
+            } else {
+                egui::Color32::RED
+            };
+            ui.colored_label(color, format!("{} µs jitter", jitter))
+                .on_hover_text(tr!("advanced.jitter_hint"));
+            ui.label(format!("{} µs/frame", process_time))
+                .on_hover_text(tr!("advanced.process_time_hint"));
+            ui.label(format!("≈ {:.1} ms latency", latency_ms))
+                .on_hover_text(tr!("advanced.latency_hint"));
+        });
+        ui.label(tr!(
+            "advanced.estimated_latency",
+            max = format!("{:.0}", estimated_latency_ms())
+        ))
+        .on_hover_text(tr!(
+            "advanced.estimated_latency_hint",
+            buffer = ENGINE_BUFFER_MS
+        ));
+    }
 
-        // BIG BYPASS BUTTON
-        let bypass_enabled = if let Some(engine) = &self.engine {
-            engine.bypass_enabled.load(Ordering::Relaxed)
-        } else {
-            false
-        };
-        let bypass_text = if bypass_enabled {
-            "🔴 BYPASSED (Raw Audio)"
-        } else {
-            "🟢 Processing Active"
-        };
-        if ui
-            .add_sized(
-                [ui.available_width(), 30.0],
-                egui::Button::new(egui::RichText::new(bypass_text).strong().size(14.0)).fill(
-                    if bypass_enabled {
-                        egui::Color32::DARK_RED
-                    } else {
-                        egui::Color32::DARK_GREEN
-                    },
-                ),
-            )
-            .clicked()
-        {
-            if let Some(engine) = &self.engine {
-                let current = engine.bypass_enabled.load(Ordering::Relaxed);
-                engine.bypass_enabled.store(!current, Ordering::Relaxed);
+    /// What the outputs play when processing falls behind; applies to the output filter too.
+    fn render_underrun_fill(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("advanced.underrun"));
+            let current = UnderrunFill::from_u32(self.config.underrun_fill);
+            egui::ComboBox::from_id_salt("underrun_fill_combo")
+                .selected_text(current.label())
+                .show_ui(ui, |ui| {
+                    for fill in UnderrunFill::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.underrun_fill,
+                                fill as u32,
+                                fill.label(),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                            if let Some(engine) = &self.engine {
+                                engine.underrun_fill.store(fill as u32, Ordering::Relaxed);
+                            }
+                            if let Some(filter) = &self.output_filter_engine {
+                                filter.underrun_fill.store(fill as u32, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("advanced.underrun_hint"));
+        });
+    }
+
+    /// Settings for the output filter, independent of the microphone chain.
+    fn render_output_filter_controls(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        ui.indent("output_filter_controls", |ui| {
+            egui::Grid::new("output_filter_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("controls.suppression"));
+                    let pct = (self.config.output_filter_suppression * 100.0) as i32;
+                    changed |= ui
+                        .add(
+                            egui::Slider::new(
+                                &mut self.config.output_filter_suppression,
+                                0.0..=1.0,
+                            )
+                            .text(format!("{}%", pct))
+                            .show_value(false),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label(tr!("controls.gate_threshold"));
+                    changed |= ui
+                        .add(
+                            egui::Slider::new(&mut self.config.output_filter_threshold, 0.0..=0.05)
+                                .fixed_decimals(3),
+                        )
+                        .on_hover_text(tr!("advanced.gate_open_hint"))
+                        .changed();
+                    ui.end_row();
+                });
+
+            changed |= ui
+                .checkbox(
+                    &mut self.config.output_filter_eq_enabled,
+                    tr!("advanced.equalizer"),
+                )
+                .changed();
+            if self.config.output_filter_eq_enabled {
+                egui::Grid::new("output_filter_eq_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (label, gain) in [
+                            (
+                                tr!("advanced.eq_low"),
+                                &mut self.config.output_filter_eq_low_gain,
+                            ),
+                            (
+                                tr!("advanced.eq_mid"),
+                                &mut self.config.output_filter_eq_mid_gain,
+                            ),
+                            (
+                                tr!("advanced.eq_high"),
+                                &mut self.config.output_filter_eq_high_gain,
+                            ),
+                        ] {
+                            ui.label(label);
+                            changed |= ui
+                                .add(egui::Slider::new(gain, -10.0..=10.0).text("dB"))
+                                .changed();
+                            ui.end_row();
+                        }
+                    });
             }
-        }
 
-        // Spectrum Visualizer
-        if self.engine.is_some() {
-            ui.add_space(10.0);
-            ui.label("📊 Spectrum Analysis");
-            self.render_spectrum(ui);
-
-            // Jitter Monitor
-            const JITTER_GOOD_US: u32 = 1000;
-            const JITTER_WARN_US: u32 = 5000;
-            let jitter = self
-                .engine
-                .as_ref()
-                .unwrap()
-                .jitter_ewma_us
-                .load(Ordering::Relaxed);
-            ui.add_space(5.0);
-            ui.horizontal(|ui| {
-                ui.label("Latency Health:");
-                let color = if jitter < JITTER_GOOD_US {
-                    egui::Color32::GREEN
-                } else if jitter < JITTER_WARN_US {
-                    egui::Color32::YELLOW
-                } else {
-                    egui::Color32::RED
-                };
-                ui.colored_label(color, format!("{} µs jitter", jitter))
-                    .on_hover_text("< 1ms = excellent | 1-5ms = acceptable | > 5ms = may cause audio glitches");
-            });
+            if let Some(filter) = &self.output_filter_engine {
+                let mut bypass = filter.controls.bypass_enabled.load(Ordering::Relaxed);
+                if ui
+                    .checkbox(&mut bypass, tr!("advanced.bypass_output_filter"))
+                    .changed()
+                {
+                    filter
+                        .controls
+                        .bypass_enabled
+                        .store(bypass, Ordering::Relaxed);
+                }
+            }
+        });
+
+        if changed {
+            self.mark_config_dirty();
+            if let Some(filter) = &self.output_filter_engine {
+                self.apply_output_filter_settings(filter);
+            }
         }
     }
 }
+
+/// VAD sources offered in the picker: stereo capture gives at most two channels.
+const VAD_SOURCES: [VadSource; 4] = [
+    VadSource::Mix,
+    VadSource::Loudest,
+    VadSource::Channel(0),
+    VadSource::Channel(1),
+];
+
+fn vad_source_label(source: VadSource) -> String {
+    match source {
+        VadSource::Mix => tr!("advanced.vad_source_mix").to_string(),
+        VadSource::Loudest => tr!("advanced.vad_source_loudest").to_string(),
+        VadSource::Channel(ch) => tr!("advanced.vad_source_channel", n = ch + 1),
+    }
+}
+
+fn meter_ballistics_label(ballistics: MeterBallistics) -> &'static str {
+    match ballistics {
+        MeterBallistics::Ppm => tr!("advanced.meter_ppm"),
+        MeterBallistics::Vu => tr!("advanced.meter_vu"),
+        MeterBallistics::Instant => tr!("advanced.meter_instant"),
+    }
+}
@@ -1,19 +1,34 @@
-use crate::audio::{AudioEngine, OutputFilterEngine};
+use crate::audio::{AudioBackend, AudioEngine, OutputFilterEngine, TestTone};
 use crate::config::AppConfig;
+use crate::daemon::format_duration;
+use crate::i18n::{self, Language};
+use crate::metrics::MetricsServer;
+use crate::recorder::Recording;
+use crate::silence_watch::{SilenceAction, SilenceWatch, SILENCE_MAX_MINS};
 use crate::updater::{self, UpdateInfo};
 use crossbeam_channel::Receiver;
 use eframe::egui;
 use global_hotkey::hotkey::HotKey;
 use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager};
+use std::collections::VecDeque;
 use std::sync::atomic::Ordering;
 use tray_icon::TrayIcon;
+use voidmic_core::constants::FRAME_SIZE;
+use voidmic_core::processor::{rescale_threshold, AUTO_SUSPEND_MAX_SECS};
+use voidmic_ui::visualizer::GateSample;
 use voidmic_ui::{theme, visualizer, widgets};
 
-
 use super::devices::get_devices;
-use super::tray::{load_icon, QUIT_ID, SHOW_ID, TOGGLE_ID};
+use super::offline::{OfflineJob, OfflineResult};
+use super::tray::{TrayIcons, TrayState, QUIT_ID, SHOW_ID, TOGGLE_ID, TOOLTIP_LEVEL_INTERVAL};
 use super::wizard::WizardStep;
 
+// Span of the gate activity strip chart, and a cap in case the repaint rate spikes
+const GATE_HISTORY_SECS: f64 = 10.0;
+const GATE_HISTORY_MAX_SAMPLES: usize = 1024;
+// Samples kept for the waveform view: 25 frame snapshots, about one second of updates
+const WAVEFORM_HISTORY_SAMPLES: usize = 25 * FRAME_SIZE;
+
 /// Runs the VoidMic GUI application.
 ///
 /// # Arguments
@@ -25,7 +40,6 @@ pub fn run_gui() -> eframe::Result<()> {
     // Load config early to determine if we should start minimized
     let config = AppConfig::load();
     let start_minimized = config.start_minimized;
-    let dark_mode = config.dark_mode;
 
     // Build viewport with saved position if available
     let mut viewport = egui::ViewportBuilder::default()
@@ -45,34 +59,74 @@ pub fn run_gui() -> eframe::Result<()> {
         "VoidMic",
         options,
         Box::new(move |cc| {
-            theme::setup_custom_style(&cc.egui_ctx, dark_mode);
-            Ok(Box::new(VoidMicApp::new_with_config(config)))
+            let app = VoidMicApp::new_with_config(config);
+            theme::setup_custom_style(&cc.egui_ctx, app.applied_dark_mode);
+            Ok(Box::new(app))
         }),
     )
 }
 
+/// Queries the OS color scheme preference, if the platform reports one.
+fn system_prefers_dark() -> Option<bool> {
+    match dark_light::detect() {
+        Ok(dark_light::Mode::Dark) => Some(true),
+        Ok(dark_light::Mode::Light) => Some(false),
+        _ => None,
+    }
+}
+
+/// Resolves the theme to use, falling back to the explicit toggle when the
+/// OS preference is unknown.
+fn effective_dark_mode(config: &AppConfig) -> bool {
+    if config.follow_system_theme {
+        system_prefers_dark().unwrap_or(config.dark_mode)
+    } else {
+        config.dark_mode
+    }
+}
+
 pub(super) struct VoidMicApp {
     pub(super) input_devices: Vec<String>,
     pub(super) output_devices: Vec<String>,
     pub(super) selected_input: String,
     pub(super) selected_output: String,
     pub(super) engine: Option<AudioEngine>,
+    // Test tone currently playing on the output device (only while the engine is stopped)
+    pub(super) test_tone: Option<TestTone>,
     pub(super) status_msg: String,
     pub(super) config: AppConfig,
     pub(super) config_dirty: bool,
     #[allow(dead_code)] // Kept alive for tray icon
     pub(super) tray_icon: Option<TrayIcon>,
+    pub(super) tray_icons: TrayIcons,
+    // State the tray icon/tooltip currently show
+    pub(super) tray_state: TrayState,
+    pub(super) last_tray_tooltip: std::time::Instant,
     pub(super) is_quitting: bool,
     pub(super) is_calibrating: bool,
-    pub(super) update_receiver: Option<std::sync::mpsc::Receiver<Option<UpdateInfo>>>,
+    // Auto-Gate's noise floor is held; not saved, since the estimate itself isn't
+    pub(super) noise_floor_frozen: bool,
+    // Second calibration phase (speech level -> input gain) is running
+    pub(super) is_gain_calibrating: bool,
+    pub(super) update_receiver:
+        Option<std::sync::mpsc::Receiver<Result<Option<UpdateInfo>, String>>>,
     pub(super) update_info: Option<UpdateInfo>,
+    // Set when the user pressed "Check Now" and expects feedback either way
+    pub(super) manual_update_check: bool,
     pub(super) virtual_sink_module_id: Option<u32>,
     pub(super) connected_apps: Vec<String>,
     pub(super) last_app_refresh: std::time::Instant,
     pub(super) virtual_sink_cached: bool,
     pub(super) last_sink_check: std::time::Instant,
+    // Refreshed with the sink check, to warn when the output goes to real speakers
+    pub(super) pulse_sinks: Vec<crate::pulse_info::DeviceInfo>,
+    pub(super) default_sink: Option<String>,
     // Output Filter (Speaker Denoising)
     pub(super) output_filter_engine: Option<OutputFilterEngine>,
+    // Metrics endpoint, when `metrics_port` is set; reports the engine while it runs
+    pub(super) metrics_server: Option<MetricsServer>,
+    // Processed mic being written to a WAV file
+    pub(super) recording: Option<Recording>,
     // Echo Cancellation
     pub(super) selected_reference: String,
     // Global Hotkeys
@@ -85,36 +139,74 @@ pub(super) struct VoidMicApp {
     // Phase 6
     pub(super) spectrum_receiver: Option<Receiver<(Vec<f32>, Vec<f32>)>>,
     pub(super) last_spectrum_data: (Vec<f32>, Vec<f32>),
+    pub(super) waveform_receiver: Option<Receiver<(Vec<f32>, Vec<f32>)>>,
+    // Rolling (input, output) samples shown by the waveform view
+    pub(super) waveform_history: (VecDeque<f32>, VecDeque<f32>),
+    // Recent gate state, one sample per repaint while the engine runs
+    pub(super) gate_history: VecDeque<GateSample>,
     // Track mini mode resize so we only send the command once
     pub(super) mini_mode_resized: bool,
     // Periodic auto-save for dirty config
     pub(super) last_config_save: std::time::Instant,
+    // Idle pause (engine suspended while hidden and unused)
+    pub(super) window_visible: bool,
+    pub(super) idle_paused: bool,
+    pub(super) last_idle_check: std::time::Instant,
+    // "Forgot to mute" watch, restarted with each engine
+    pub(super) silence_watch: Option<SilenceWatch>,
+    // Banner shown once it fired, until dismissed or speech is heard again
+    pub(super) silence_alert: Option<SilenceAction>,
+    // When the current engine was started; toggles are ignored until it processes audio
+    pub(super) engine_starting: Option<std::time::Instant>,
+    // "Reset Audio" was clicked; runs next frame so the status shows it first
+    pub(super) audio_reset_pending: bool,
+    // Theme currently applied to the context (may follow the OS)
+    pub(super) applied_dark_mode: bool,
+    pub(super) last_theme_check: std::time::Instant,
+    // WAV file dropped onto the window: being processed, waiting to be saved, or failed
+    pub(super) offline_job: Option<OfflineJob>,
+    pub(super) offline_result: Option<OfflineResult>,
+    pub(super) offline_error: Option<String>,
 }
 
 impl VoidMicApp {
     pub(super) fn new_with_config(config: AppConfig) -> Self {
+        // Before anything builds text, including the tray menu
+        i18n::set_language(config.language);
+
         // Tray Setup
         let tray_menu = tray_icon::menu::Menu::new();
         let toggle_item =
-            tray_icon::menu::MenuItem::with_id(TOGGLE_ID, "Enable", true, None);
+            tray_icon::menu::MenuItem::with_id(TOGGLE_ID, tr!("tray.enable"), true, None);
         let show_item =
-            tray_icon::menu::MenuItem::with_id(SHOW_ID, "Show/Hide", true, None);
-        let quit_item =
-            tray_icon::menu::MenuItem::with_id(QUIT_ID, "Quit", true, None);
+            tray_icon::menu::MenuItem::with_id(SHOW_ID, tr!("tray.show_hide"), true, None);
+        let quit_item = tray_icon::menu::MenuItem::with_id(QUIT_ID, tr!("tray.quit"), true, None);
         let _ = tray_menu.append_items(&[&toggle_item, &show_item, &quit_item]);
 
-        let icon = load_icon();
+        let tray_icons = TrayIcons::new();
         let tray_icon = tray_icon::TrayIconBuilder::new()
             .with_menu(Box::new(tray_menu))
-            .with_tooltip("VoidMic")
-            .with_icon(icon)
+            .with_tooltip(TrayState::Disabled.tooltip())
+            .with_icon(tray_icons.get(TrayState::Disabled))
             .build()
             .ok();
 
-        // Start async update check
-        let update_receiver = Some(updater::check_for_updates_async());
+        // Start async update check (opt-out for offline/privacy-conscious setups)
+        let update_receiver = config
+            .check_for_updates
+            .then(|| updater::check_for_updates_async(config.update_channel));
 
-        let (inputs, outputs) = get_devices();
+        let (inputs, outputs) = get_devices(config.audio_backend);
+        // Keep the saved choice so it applies again once the host is back, but say
+        // that the default host is used meanwhile
+        let status_msg = if config.audio_backend.is_available() {
+            tr!("status.ready").to_string()
+        } else {
+            tr!(
+                "app.backend_unavailable",
+                backend = config.audio_backend.label()
+            )
+        };
 
         let default_in = if inputs.contains(&config.last_input) {
             config.last_input.clone()
@@ -134,17 +226,26 @@ impl VoidMicApp {
                 .unwrap_or_else(|| "default".to_string())
         };
 
-        let default_ref = if !config.last_reference.is_empty() && inputs.contains(&config.last_reference) {
-            config.last_reference.clone()
-        } else {
-            inputs
-                .first()
-                .cloned()
-                .unwrap_or_else(|| "default".to_string())
-        };
+        let default_ref =
+            if !config.last_reference.is_empty() && inputs.contains(&config.last_reference) {
+                config.last_reference.clone()
+            } else {
+                inputs
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "default".to_string())
+            };
 
         let auto_start = config.auto_start_processing;
         let show_wizard = config.first_run;
+        let window_visible = !config.start_minimized;
+        let applied_dark_mode = effective_dark_mode(&config);
+        // Monitoring is optional; a busy port shouldn't keep the app from starting
+        let metrics_server = config.metrics_port.and_then(|port| {
+            MetricsServer::start(port)
+                .map_err(|e| log::warn!("Metrics endpoint disabled: {:#}", e))
+                .ok()
+        });
 
         let mut app = Self {
             input_devices: inputs,
@@ -152,20 +253,31 @@ impl VoidMicApp {
             selected_input: default_in,
             selected_output: default_out,
             engine: None,
-            status_msg: "Ready".to_string(),
+            test_tone: None,
+            status_msg,
             config,
             config_dirty: false,
             tray_icon,
+            tray_icons,
+            tray_state: TrayState::Disabled,
+            last_tray_tooltip: std::time::Instant::now(),
             is_quitting: false,
             is_calibrating: false,
+            noise_floor_frozen: false,
+            is_gain_calibrating: false,
             update_receiver,
             update_info: None,
+            manual_update_check: false,
             virtual_sink_module_id: None,
             connected_apps: Vec::new(),
             output_filter_engine: None,
+            metrics_server,
+            recording: None,
             last_app_refresh: std::time::Instant::now(),
             virtual_sink_cached: false,
             last_sink_check: std::time::Instant::now() - std::time::Duration::from_secs(5),
+            pulse_sinks: Vec::new(),
+            default_sink: None,
             selected_reference: default_ref,
             hotkey_manager: match GlobalHotKeyManager::new() {
                 Ok(m) => Some(m),
@@ -179,8 +291,23 @@ impl VoidMicApp {
             wizard_step: WizardStep::Welcome,
             spectrum_receiver: None,
             last_spectrum_data: (Vec::new(), Vec::new()),
+            waveform_receiver: None,
+            waveform_history: (VecDeque::new(), VecDeque::new()),
+            gate_history: VecDeque::new(),
             mini_mode_resized: false,
             last_config_save: std::time::Instant::now(),
+            window_visible,
+            idle_paused: false,
+            engine_starting: None,
+            audio_reset_pending: false,
+            last_idle_check: std::time::Instant::now(),
+            silence_watch: None,
+            silence_alert: None,
+            applied_dark_mode,
+            last_theme_check: std::time::Instant::now(),
+            offline_job: None,
+            offline_result: None,
+            offline_error: None,
         };
 
         // Register Hotkey
@@ -216,6 +343,15 @@ impl VoidMicApp {
         }
     }
 
+    /// Re-applies the theme if the resolved dark/light choice changed.
+    pub(super) fn apply_theme(&mut self, ctx: &egui::Context) {
+        let dark_mode = effective_dark_mode(&self.config);
+        if dark_mode != self.applied_dark_mode {
+            self.applied_dark_mode = dark_mode;
+            theme::setup_custom_style(ctx, dark_mode);
+        }
+    }
+
     pub(super) fn save_config_now(&mut self) {
         self.config.last_input = self.selected_input.clone();
         self.config.last_output = self.selected_output.clone();
@@ -233,12 +369,15 @@ impl VoidMicApp {
             ui.horizontal(|ui| {
                 ui.colored_label(
                     egui::Color32::GOLD,
-                    format!("🎉 Update available: {}", version),
+                    tr!("app.update_available", version = version),
                 );
-                if ui.small_button("Download").clicked() {
+                if ui.small_button(tr!("app.download")).clicked() {
                     let _ = open::that(&url);
                 }
-                if ui.small_button("✕").clicked() {
+                if ui
+                    .small_button(format!("✕ {}", tr!("app.dismiss")))
+                    .clicked()
+                {
                     dismiss = true;
                 }
             });
@@ -247,6 +386,44 @@ impl VoidMicApp {
         dismiss
     }
 
+    /// Renders the "forgot to mute" banner once the silence watch fired.
+    pub(super) fn render_silence_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(action) = self.silence_alert else {
+            return;
+        };
+        let minutes = self.config.silence_minutes;
+        ui.horizontal(|ui| {
+            if action == SilenceAction::Mute {
+                ui.colored_label(
+                    egui::Color32::GOLD,
+                    tr!("app.silence_muted", minutes = minutes),
+                );
+                if ui.small_button(tr!("app.unmute")).clicked() {
+                    self.end_silence_alert();
+                }
+            } else {
+                ui.colored_label(
+                    egui::Color32::GOLD,
+                    tr!("app.silence_warning", minutes = minutes),
+                );
+                if ui.small_button(tr!("app.mute")).clicked() {
+                    if let Some(engine) = &self.engine {
+                        engine.controls.muted.store(true, Ordering::Relaxed);
+                    }
+                    // Unmutes like an automatic mute once speech is heard again
+                    self.silence_alert = Some(SilenceAction::Mute);
+                }
+                if ui
+                    .small_button(format!("✕ {}", tr!("app.dismiss")))
+                    .clicked()
+                {
+                    self.end_silence_alert();
+                }
+            }
+        });
+        ui.separator();
+    }
+
     /// Renders the volume meter with dB scaling and threshold marker.
     pub(super) fn render_volume_meter(&self, ui: &mut egui::Ui) {
         let volume = if let Some(engine) = &self.engine {
@@ -254,35 +431,227 @@ impl VoidMicApp {
         } else {
             0.0
         };
-        widgets::render_volume_meter(ui, volume, self.config.gate_threshold);
+        let ballistics = widgets::MeterBallistics::from_u32(self.config.meter_ballistics);
+        widgets::render_volume_meter(ui, volume, self.config.gate_threshold, ballistics);
+        if self
+            .engine
+            .as_ref()
+            .is_some_and(|engine| engine.dsp_suspended.load(Ordering::Relaxed))
+        {
+            ui.weak(tr!("app.dsp_suspended"));
+        }
+
+        // Separate meters only make sense for multichannel input
+        if let Some(engine) = self.engine.as_ref().filter(|e| e.channel_levels.len() > 1) {
+            let levels: Vec<f32> = engine
+                .channel_levels
+                .iter()
+                .map(|level| f32::from_bits(level.load(Ordering::Relaxed)))
+                .collect();
+            widgets::render_channel_meters(ui, &levels, self.config.gate_threshold, ballistics);
+        }
+
+        // What reaches the device; stays low while the main meter moves if the output
+        // ring runs dry
+        if let Some(engine) = &self.engine {
+            let level = f32::from_bits(engine.output_level.load(Ordering::Relaxed));
+            widgets::render_output_meter(
+                ui,
+                tr!("app.output_level"),
+                tr!("app.output_level_hint"),
+                level,
+                ballistics,
+            );
+
+            let clips = engine.clip_count.load(Ordering::Relaxed);
+            ui.horizontal(|ui| {
+                let text = tr!("app.clips", count = clips);
+                let label = if clips > 0 {
+                    ui.colored_label(egui::Color32::RED, text)
+                } else {
+                    ui.weak(text)
+                };
+                label.on_hover_text(tr!("app.clips_hint"));
+                if clips > 0 && ui.small_button(tr!("app.clips_reset")).clicked() {
+                    engine.clip_count.store(0, Ordering::Relaxed);
+                }
+            });
+        }
     }
 
     pub(super) fn render_spectrum(&mut self, ui: &mut egui::Ui) {
         // Receive new data
         if let Some(rx) = &self.spectrum_receiver {
             while let Ok(data) = rx.try_recv() {
-                self.last_spectrum_data = data;
+                let old = std::mem::replace(&mut self.last_spectrum_data, data);
+                // Hand the buffers back so the audio thread can reuse them
+                if let Some(engine) = &self.engine {
+                    let _ = engine.spectrum_recycler.try_send(old);
+                }
             }
         }
+        let display = visualizer::SpectrumDisplayConfig {
+            log_frequency: self.config.spectrum_log_frequency,
+            db_scale: self.config.spectrum_db_scale,
+            db_floor: self.config.spectrum_db_floor,
+            peak_hold: self.config.spectrum_peak_hold,
+        };
         let (in_data, out_data) = &self.last_spectrum_data;
-        visualizer::render_spectrum(ui, in_data, out_data);
+        visualizer::render_spectrum(ui, in_data, out_data, &display);
+    }
+
+    pub(super) fn render_waveform(&mut self, ui: &mut egui::Ui) {
+        if let Some(rx) = &self.waveform_receiver {
+            while let Ok(data) = rx.try_recv() {
+                let (input, output) = &mut self.waveform_history;
+                input.extend(&data.0);
+                output.extend(&data.1);
+                for history in [input, output] {
+                    let excess = history.len().saturating_sub(WAVEFORM_HISTORY_SAMPLES);
+                    history.drain(..excess);
+                }
+                if let Some(engine) = &self.engine {
+                    let _ = engine.spectrum_recycler.try_send(data);
+                }
+            }
+        }
+        let (input, output) = &mut self.waveform_history;
+        visualizer::render_waveform(ui, input.make_contiguous(), output.make_contiguous());
+    }
+
+    /// Appends the engine's current gate state to the activity history and drops samples
+    /// older than `GATE_HISTORY_SECS`.
+    fn record_gate_history(&mut self, now: f64) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        self.gate_history.push_back(GateSample {
+            time: now,
+            rms: f32::from_bits(engine.volume_level.load(Ordering::Relaxed)),
+            threshold: f32::from_bits(engine.effective_threshold.load(Ordering::Relaxed)),
+            open: engine.gate_is_open.load(Ordering::Relaxed),
+        });
+        while self.gate_history.len() > GATE_HISTORY_MAX_SAMPLES
+            || self
+                .gate_history
+                .front()
+                .is_some_and(|sample| now - sample.time > GATE_HISTORY_SECS)
+        {
+            self.gate_history.pop_front();
+        }
+    }
+
+    pub(super) fn render_gate_history(&self, ui: &mut egui::Ui) {
+        visualizer::render_gate_history(ui, &self.gate_history, GATE_HISTORY_SECS);
+    }
+
+    /// Swaps the tray icon and tooltip when the engine state changed since the last frame.
+    /// While the engine runs, the tooltip also shows the input level, refreshed every
+    /// `TOOLTIP_LEVEL_INTERVAL` so minimized users can see audio is flowing.
+    fn sync_tray_state(&mut self) {
+        let state = match &self.engine {
+            None => TrayState::Disabled,
+            Some(engine) if engine.controls.bypass_enabled.load(Ordering::Relaxed) => {
+                TrayState::Bypassed
+            }
+            Some(_) => TrayState::Active,
+        };
+        let changed = state != self.tray_state;
+        let Some(tray) = &self.tray_icon else {
+            self.tray_state = state;
+            return;
+        };
+        if changed {
+            self.tray_state = state;
+            let _ = tray.set_icon(Some(self.tray_icons.get(state)));
+        }
+        // Throttled, so the tray isn't rebuilt every frame
+        let refresh = changed || self.last_tray_tooltip.elapsed() >= TOOLTIP_LEVEL_INTERVAL;
+        match &self.engine {
+            Some(engine) if refresh => {
+                let rms = f32::from_bits(engine.volume_level.load(Ordering::Relaxed));
+                let _ = tray.set_tooltip(Some(state.tooltip_with_level(rms)));
+                self.last_tray_tooltip = std::time::Instant::now();
+            }
+            None if changed => {
+                let _ = tray.set_tooltip(Some(state.tooltip()));
+            }
+            _ => {}
+        }
     }
 
     /// Checks and handles calibration results.
+    ///
+    /// Calibration runs in two phases: a quiet phase that sets the gate threshold,
+    /// then a speaking phase that sets the input gain.
     pub(super) fn check_calibration_result(&mut self) {
-        if self.is_calibrating {
-            if let Some(engine) = &self.engine {
-                if !engine.calibration_mode.load(Ordering::Relaxed) {
-                    let result = f32::from_bits(engine.calibration_result.load(Ordering::Relaxed));
-                    if result > 0.0 {
-                        self.config.gate_threshold = result;
-                        engine.gate_threshold.store(result.to_bits(), Ordering::Relaxed);
-                        self.save_config_now();
-                        self.status_msg = format!("Calibrated! Threshold set to {:.3}", result);
-                    }
-                    self.is_calibrating = false;
-                }
+        if !self.is_calibrating {
+            return;
+        }
+        let Some(engine) = &self.engine else {
+            self.is_calibrating = false;
+            self.is_gain_calibrating = false;
+            return;
+        };
+
+        if self.is_gain_calibrating {
+            if !engine.gain_calibration_mode.load(Ordering::Relaxed) {
+                let gain_db =
+                    f32::from_bits(engine.gain_calibration_result.load(Ordering::Relaxed));
+                // The threshold from the first phase was measured at the old gain
+                let threshold = rescale_threshold(
+                    self.config.gate_threshold,
+                    self.config.input_gain_db,
+                    gain_db,
+                );
+                self.config.gate_threshold = threshold;
+                engine
+                    .controls
+                    .gate_threshold
+                    .store(threshold.to_bits(), Ordering::Relaxed);
+                self.config.input_gain_db = gain_db;
+                engine
+                    .controls
+                    .input_gain_db
+                    .store(gain_db.to_bits(), Ordering::Relaxed);
+                self.save_config_now();
+                self.status_msg = tr!(
+                    "app.calibrated",
+                    threshold = format!("{:.3}", self.config.gate_threshold),
+                    gain = format!("{:+.1}", gain_db),
+                );
+                self.is_gain_calibrating = false;
+                self.is_calibrating = false;
             }
+        } else if !engine.calibration_mode.load(Ordering::Relaxed) {
+            let result = f32::from_bits(engine.calibration_result.load(Ordering::Relaxed));
+            engine.gain_calibration_mode.store(true, Ordering::Relaxed);
+            if result > 0.0 {
+                engine
+                    .controls
+                    .gate_threshold
+                    .store(result.to_bits(), Ordering::Relaxed);
+                self.config.gate_threshold = result;
+                self.record_preset_edit();
+                self.save_config_now();
+            }
+            self.is_gain_calibrating = true;
+            self.status_msg = tr!("app.speak_now").to_string();
+        }
+    }
+
+    /// Switches between the compact window and the full one.
+    pub(super) fn set_mini_mode(&mut self, ctx: &egui::Context, mini: bool) {
+        if mini == self.config.mini_mode {
+            return;
+        }
+        self.config.mini_mode = mini;
+        self.mark_config_dirty();
+        if mini {
+            // Shrunk on the next frame by `update`
+            self.mini_mode_resized = false;
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize([450.0, 450.0].into()));
         }
     }
 
@@ -294,13 +663,9 @@ impl VoidMicApp {
                 ui.horizontal(|ui| {
                     ui.label("🌌 VoidMic");
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("⛶").on_hover_text("Expand").clicked() {
-                            self.config.mini_mode = false;
-                            self.mark_config_dirty();
+                        if ui.button(format!("⛶ {}", tr!("app.expand"))).clicked() {
+                            self.set_mini_mode(ctx, false);
                             expanded = true;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
-                                [450.0, 450.0].into(),
-                            ));
                         }
                     });
                 });
@@ -315,14 +680,18 @@ impl VoidMicApp {
                     } else {
                         egui::Color32::RED
                     },
-                    if active { "Active" } else { "Inactive" },
+                    if active {
+                        tr!("app.active")
+                    } else {
+                        tr!("app.inactive")
+                    },
                 );
 
                 ui.add_space(5.0);
 
                 // Bypass Button
                 let bypass_enabled = if let Some(engine) = &self.engine {
-                    engine.bypass_enabled.load(Ordering::Relaxed)
+                    engine.controls.bypass_enabled.load(Ordering::Relaxed)
                 } else {
                     false
                 };
@@ -333,19 +702,17 @@ impl VoidMicApp {
                     egui::Color32::DARK_GREEN
                 };
                 let btn_text = if bypass_enabled {
-                    "Stopped"
+                    tr!("app.stopped")
                 } else {
-                    "Processing"
+                    tr!("app.processing")
                 };
 
-                if ui
+                let btn = ui
                     .add_sized([80.0, 30.0], egui::Button::new(btn_text).fill(btn_color))
-                    .clicked()
-                {
-                    if let Some(engine) = &self.engine {
-                        let current = engine.bypass_enabled.load(Ordering::Relaxed);
-                        engine.bypass_enabled.store(!current, Ordering::Relaxed);
-                    }
+                    .on_hover_text(tr!("app.bypass_hint"));
+                widgets::describe_button(&btn, tr!("app.processing"), Some(!bypass_enabled));
+                if btn.clicked() {
+                    self.toggle_bypass();
                 }
 
                 ui.add_space(5.0);
@@ -364,6 +731,7 @@ impl eframe::App for VoidMicApp {
                 self.is_quitting = true;
                 ctx.send_viewport_cmd(egui::ViewportCommand::Close);
             } else if event.id.0 == SHOW_ID {
+                self.window_visible = true;
                 ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
                 ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
             } else if event.id.0 == TOGGLE_ID {
@@ -387,10 +755,43 @@ impl eframe::App for VoidMicApp {
                 self.config.window_y = Some(pos.y);
                 self.save_config_now();
             }
+            self.window_visible = false;
             ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
             ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
         }
 
+        if self.audio_reset_pending {
+            self.reset_audio();
+        }
+
+        // Suspend/resume processing when hidden and no app is listening
+        self.update_idle_pause();
+
+        // Warn about (or mute) a mic that sends sound but hasn't heard speech in a while
+        self.update_silence_watch(ctx);
+
+        // The visualizer's FFTs only run while the full window can show them
+        if let Some(engine) = &self.engine {
+            let minimized = ctx.input(|i| i.viewport().minimized).unwrap_or(false);
+            let shown =
+                self.window_visible && !minimized && !self.config.mini_mode && !self.show_wizard;
+            engine
+                .controls
+                .spectrum_enabled
+                .store(shown, Ordering::Relaxed);
+        }
+
+        // Reflect active/bypassed/disabled in the tray icon
+        self.sync_tray_state();
+
+        self.record_gate_history(ctx.input(|i| i.time));
+
+        // Pick up OS theme changes
+        if self.config.follow_system_theme && self.last_theme_check.elapsed().as_secs() >= 2 {
+            self.last_theme_check = std::time::Instant::now();
+            self.apply_theme(ctx);
+        }
+
         // Repaint rate
         if self.engine.is_some() {
             ctx.request_repaint_after(std::time::Duration::from_millis(33));
@@ -406,12 +807,34 @@ impl eframe::App for VoidMicApp {
 
         // Check for update result
         if let Some(ref rx) = self.update_receiver {
-            if let Ok(update) = rx.try_recv() {
-                self.update_info = update;
+            if let Ok(result) = rx.try_recv() {
                 self.update_receiver = None;
+                match result {
+                    Ok(update) => {
+                        if self.manual_update_check && update.is_none() {
+                            self.status_msg = tr!("app.up_to_date").to_string();
+                        }
+                        self.update_info = update;
+                    }
+                    Err(e) => {
+                        log::warn!("Update check failed: {}", e);
+                        if self.manual_update_check {
+                            self.status_msg = tr!("status.update_check_failed", error = e);
+                        }
+                    }
+                }
+                self.manual_update_check = false;
             }
         }
 
+        // Recordings dropped onto the window are denoised in the background
+        self.handle_dropped_files(ctx);
+        self.render_offline_processing(ctx);
+
+        if !self.show_wizard {
+            self.handle_shortcuts(ctx);
+        }
+
         if self.show_wizard {
             self.render_wizard(ctx);
             return;
@@ -431,142 +854,370 @@ impl eframe::App for VoidMicApp {
             if self.render_update_banner(ui) {
                 self.update_info = None;
             }
+            self.render_silence_banner(ui);
 
-            egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
-                ui.heading("VoidMic 🌌");
-                ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Hybrid Noise Reduction").size(10.0).weak());
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("➖").on_hover_text("Compact Mode").clicked() {
-                            self.config.mini_mode = true;
-                            self.mini_mode_resized = false;
-                            self.mark_config_dirty();
-                        }
+            egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    ui.heading("VoidMic 🌌");
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(tr!("app.subtitle")).size(10.0).weak());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .button(format!("➖ {}", tr!("app.compact_mode")))
+                                .clicked()
+                            {
+                                self.set_mini_mode(ui.ctx(), true);
+                            }
+                        });
                     });
-                });
-                ui.separator();
-                ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
 
-                // Volume meter
-                self.render_volume_meter(ui);
-                ui.add_space(20.0);
+                    // Volume meter
+                    self.render_volume_meter(ui);
+                    ui.add_space(20.0);
 
-                // Device selectors
-                self.render_device_selectors(ui);
-                ui.add_space(20.0);
+                    // Device selectors
+                    self.render_device_selectors(ui);
+                    ui.add_space(20.0);
 
-                // Threshold and suppression controls
-                self.render_threshold_controls(ui);
-                self.check_calibration_result();
+                    // Threshold and suppression controls
+                    self.render_threshold_controls(ui);
+                    self.check_calibration_result();
 
-                // Advanced Features
-                ui.add_space(10.0);
-                self.render_advanced_features(ui);
-                ui.add_space(10.0);
+                    // Advanced Features
+                    ui.add_space(10.0);
+                    self.render_advanced_features(ui);
+                    ui.add_space(10.0);
 
-                // Connected Apps display
-                #[cfg(target_os = "linux")]
-                {
-                    if self.engine.is_some() && self.last_app_refresh.elapsed().as_secs() >= 2 {
-                        self.connected_apps = crate::pulse_info::get_connected_apps()
-                            .into_iter()
-                            .map(|a| a.name)
-                            .collect();
-                        self.last_app_refresh = std::time::Instant::now();
-                    }
+                    // Connected Apps display
+                    #[cfg(target_os = "linux")]
+                    {
+                        if self.engine.is_some() && self.last_app_refresh.elapsed().as_secs() >= 2 {
+                            self.connected_apps = crate::pulse_info::get_connected_apps()
+                                .into_iter()
+                                .map(|a| a.name)
+                                .collect();
+                            self.last_app_refresh = std::time::Instant::now();
+                        }
 
-                    if !self.connected_apps.is_empty() {
-                        ui.add_space(10.0);
-                        egui::CollapsingHeader::new(format!("📱 Connected Apps ({})", self.connected_apps.len()))
+                        if !self.connected_apps.is_empty() {
+                            ui.add_space(10.0);
+                            egui::CollapsingHeader::new(tr!(
+                                "app.connected_apps",
+                                count = self.connected_apps.len()
+                            ))
                             .default_open(true)
                             .show(ui, |ui| {
                                 for app in &self.connected_apps {
                                     ui.label(format!("  • {}", app));
                                 }
                             });
+                        }
                     }
-                }
 
-                let is_running = self.engine.is_some();
-                let btn_text = if is_running { "STOP ENGINE" } else { "ACTIVATE VOIDMIC" };
+                    let is_running = self.engine.is_some();
+                    let btn_text = if is_running {
+                        tr!("app.stop_engine")
+                    } else {
+                        tr!("app.activate")
+                    };
 
-                let btn = ui.add_sized([ui.available_width(), 50.0], egui::Button::new(
-                    egui::RichText::new(btn_text).size(18.0).strong()
-                ));
-                if btn.clicked() {
-                    self.toggle_engine();
-                }
+                    let btn = ui.add_sized(
+                        [ui.available_width(), 50.0],
+                        egui::Button::new(egui::RichText::new(btn_text).size(18.0).strong()),
+                    );
+                    // The text names the action; announce what it switches and whether it's on
+                    widgets::describe_button(&btn, tr!("app.engine"), Some(is_running));
+                    if btn.on_hover_text(tr!("app.shortcuts_hint")).clicked() {
+                        self.toggle_engine();
+                    }
 
-                ui.add_space(10.0);
-                ui.label(format!("Status: {}", self.status_msg));
+                    let reset_btn = egui::Button::new(tr!("app.reset_audio"))
+                        .min_size(egui::vec2(ui.available_width(), 28.0));
+                    let reset = ui.add_enabled(!self.audio_reset_pending, reset_btn);
+                    if reset.on_hover_text(tr!("app.reset_audio_hint")).clicked() {
+                        self.audio_reset_pending = true;
+                        self.status_msg = tr!("app.resetting_audio").to_string();
+                        ctx.request_repaint();
+                    }
 
-                ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
-                    ui.horizontal(|ui| {
-                        if ui.button("🛠️ Install Virtual Cable").clicked() {
-                            match super::devices::install_virtual_cable() {
-                                Ok(msg) => {
-                                    self.status_msg = msg;
-                                    let (inputs, outputs) = get_devices();
-                                    self.input_devices = inputs;
-                                    self.output_devices = outputs;
+                    ui.add_space(10.0);
+                    ui.label(tr!("app.status", status = self.status_msg));
+                    if let Some(engine) = &self.engine {
+                        ui.label(tr!(
+                            "app.uptime",
+                            uptime = format_duration(engine.uptime().as_secs()),
+                            frames = engine.frames_processed.load(Ordering::Relaxed),
+                        ))
+                        .on_hover_text(tr!("app.frames_hint"));
+                    }
+                    self.render_record_controls(ui);
+
+                    ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button(tr!("app.install_cable")).clicked() {
+                                match super::devices::install_virtual_cable() {
+                                    Ok(msg) => {
+                                        self.status_msg = msg;
+                                        let (inputs, outputs) =
+                                            get_devices(self.config.audio_backend);
+                                        self.input_devices = inputs;
+                                        self.output_devices = outputs;
+                                    }
+                                    Err(e) => {
+                                        self.status_msg =
+                                            tr!("status.virtual_cable_error", error = e);
+                                    }
                                 }
-                                Err(e) => {
-                                    self.status_msg = format!("Virtual Cable Error: {}", e);
+                            }
+                        });
+                        ui.separator();
+
+                        // Start on Boot
+                        let mut start_on_boot = self.config.start_on_boot;
+                        if ui
+                            .checkbox(&mut start_on_boot, tr!("app.start_on_boot"))
+                            .changed()
+                        {
+                            self.config.start_on_boot = start_on_boot;
+                            if start_on_boot {
+                                if let Err(e) = crate::autostart::enable_autostart() {
+                                    self.status_msg = tr!("status.autostart_error", error = e);
+                                    self.config.start_on_boot = false;
+                                } else {
+                                    self.status_msg = tr!("app.autostart_enabled").to_string();
                                 }
+                            } else if let Err(e) = crate::autostart::disable_autostart() {
+                                self.status_msg = tr!("status.autostart_error", error = e);
+                            } else {
+                                self.status_msg = tr!("app.autostart_disabled").to_string();
                             }
+                            self.save_config_now();
                         }
-                    });
-                    ui.separator();
 
-                    // Start on Boot
-                    let mut start_on_boot = self.config.start_on_boot;
-                    if ui.checkbox(&mut start_on_boot, "Start on Boot").changed() {
-                        self.config.start_on_boot = start_on_boot;
-                        if start_on_boot {
-                            if let Err(e) = crate::autostart::enable_autostart() {
-                                self.status_msg = format!("Autostart error: {}", e);
-                                self.config.start_on_boot = false;
-                            } else {
-                                self.status_msg = "Autostart enabled".to_string();
+                        // Start Minimized
+                        let mut start_minimized = self.config.start_minimized;
+                        if ui
+                            .checkbox(&mut start_minimized, tr!("app.start_minimized"))
+                            .changed()
+                        {
+                            self.config.start_minimized = start_minimized;
+                            self.save_config_now();
+                        }
+
+                        // Auto-Start Processing
+                        let mut auto_start = self.config.auto_start_processing;
+                        if ui
+                            .checkbox(&mut auto_start, tr!("app.auto_start"))
+                            .changed()
+                        {
+                            self.config.auto_start_processing = auto_start;
+                            self.save_config_now();
+                        }
+
+                        // Pause When Idle
+                        let mut pause_when_idle = self.config.pause_when_idle;
+                        if ui
+                            .checkbox(&mut pause_when_idle, tr!("app.pause_when_idle"))
+                            .on_hover_text(tr!("app.pause_when_idle_hint"))
+                            .changed()
+                        {
+                            self.config.pause_when_idle = pause_when_idle;
+                            self.save_config_now();
+                        }
+
+                        // Auto-Suspend DSP
+                        ui.horizontal(|ui| {
+                            let enabled = &mut self.config.auto_suspend_enabled;
+                            let mut changed = ui
+                                .checkbox(enabled, tr!("app.auto_suspend"))
+                                .on_hover_text(tr!("app.auto_suspend_hint"))
+                                .changed();
+                            changed |= ui
+                                .add_enabled(
+                                    self.config.auto_suspend_enabled,
+                                    egui::DragValue::new(&mut self.config.auto_suspend_secs)
+                                        .range(1..=AUTO_SUSPEND_MAX_SECS)
+                                        .suffix(" s"),
+                                )
+                                .changed();
+                            if changed {
+                                self.mark_config_dirty();
+                                if let Some(engine) = &self.engine {
+                                    engine
+                                        .controls
+                                        .auto_suspend_enabled
+                                        .store(self.config.auto_suspend_enabled, Ordering::Relaxed);
+                                    engine
+                                        .controls
+                                        .auto_suspend_secs
+                                        .store(self.config.auto_suspend_secs, Ordering::Relaxed);
+                                }
                             }
-                        } else if let Err(e) = crate::autostart::disable_autostart() {
-                            self.status_msg = format!("Autostart error: {}", e);
-                        } else {
-                            self.status_msg = "Autostart disabled".to_string();
+                        });
+
+                        // Forgot-to-mute watch
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("app.silence_action"))
+                                .on_hover_text(tr!("app.silence_action_hint"));
+                            let prev_action = self.config.silence_action;
+                            egui::ComboBox::from_id_salt("silence_action_combo")
+                                .selected_text(self.config.silence_action.label())
+                                .show_ui(ui, |ui| {
+                                    for action in SilenceAction::ALL {
+                                        ui.selectable_value(
+                                            &mut self.config.silence_action,
+                                            action,
+                                            action.label(),
+                                        );
+                                    }
+                                });
+                            let minutes_changed = ui
+                                .add_enabled(
+                                    self.config.silence_action != SilenceAction::Off,
+                                    egui::DragValue::new(&mut self.config.silence_minutes)
+                                        .range(1..=SILENCE_MAX_MINS)
+                                        .suffix(" min"),
+                                )
+                                .changed();
+                            if self.config.silence_action != prev_action {
+                                self.end_silence_alert();
+                                self.save_config_now();
+                            } else if minutes_changed {
+                                self.mark_config_dirty();
+                            }
+                        });
+
+                        // Audio Backend
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("app.audio_backend"));
+                            let prev_backend = self.config.audio_backend;
+                            egui::ComboBox::from_id_salt("audio_backend_combo")
+                                .selected_text(if self.config.audio_backend.is_available() {
+                                    self.config.audio_backend.label().to_string()
+                                } else {
+                                    tr!(
+                                        "app.backend_unavailable_label",
+                                        backend = self.config.audio_backend.label()
+                                    )
+                                })
+                                .show_ui(ui, |ui| {
+                                    for backend in AudioBackend::available() {
+                                        ui.selectable_value(
+                                            &mut self.config.audio_backend,
+                                            backend,
+                                            backend.label(),
+                                        );
+                                    }
+                                });
+                            if self.config.audio_backend != prev_backend {
+                                self.save_config_now();
+                                self.apply_audio_backend();
+                            }
+                        });
+
+                        // Language
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("app.language"));
+                            let prev_language = self.config.language;
+                            egui::ComboBox::from_id_salt("language_combo")
+                                .selected_text(self.config.language.label())
+                                .show_ui(ui, |ui| {
+                                    for language in Language::ALL {
+                                        ui.selectable_value(
+                                            &mut self.config.language,
+                                            language,
+                                            language.label(),
+                                        );
+                                    }
+                                });
+                            if self.config.language != prev_language {
+                                i18n::set_language(self.config.language);
+                                self.save_config_now();
+                            }
+                        });
+
+                        // Theme
+                        let mut follow_system = self.config.follow_system_theme;
+                        if ui
+                            .checkbox(&mut follow_system, tr!("app.follow_system_theme"))
+                            .changed()
+                        {
+                            self.config.follow_system_theme = follow_system;
+                            self.save_config_now();
+                            self.apply_theme(ui.ctx());
                         }
-                        self.save_config_now();
-                    }
 
-                    // Start Minimized
-                    let mut start_minimized = self.config.start_minimized;
-                    if ui.checkbox(&mut start_minimized, "Start Minimized to Tray").changed() {
-                        self.config.start_minimized = start_minimized;
-                        self.save_config_now();
-                    }
+                        let mut dark_mode = self.config.dark_mode;
+                        if ui
+                            .add_enabled(
+                                !follow_system,
+                                egui::Checkbox::new(&mut dark_mode, tr!("app.dark_mode")),
+                            )
+                            .changed()
+                        {
+                            self.config.dark_mode = dark_mode;
+                            self.save_config_now();
+                            self.apply_theme(ui.ctx());
+                        }
 
-                    // Auto-Start Processing
-                    let mut auto_start = self.config.auto_start_processing;
-                    if ui.checkbox(&mut auto_start, "Auto-Start Processing").changed() {
-                        self.config.auto_start_processing = auto_start;
-                        self.save_config_now();
-                    }
+                        // Updates
+                        let mut check_updates = self.config.check_for_updates;
+                        if ui
+                            .checkbox(&mut check_updates, tr!("app.check_updates"))
+                            .changed()
+                        {
+                            self.config.check_for_updates = check_updates;
+                            self.save_config_now();
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("app.update_channel"));
+                            let prev_channel = self.config.update_channel;
+                            egui::ComboBox::from_id_salt("update_channel_combo")
+                                .selected_text(self.config.update_channel.label())
+                                .show_ui(ui, |ui| {
+                                    for channel in updater::UpdateChannel::ALL {
+                                        ui.selectable_value(
+                                            &mut self.config.update_channel,
+                                            channel,
+                                            channel.label(),
+                                        );
+                                    }
+                                });
+                            if self.config.update_channel != prev_channel {
+                                self.save_config_now();
+                            }
 
-                    // Dark Mode
-                    let mut dark_mode = self.config.dark_mode;
-                    if ui.checkbox(&mut dark_mode, "Dark Mode").changed() {
-                        self.config.dark_mode = dark_mode;
-                        self.save_config_now();
-                        theme::setup_custom_style(ui.ctx(), dark_mode);
-                    }
+                            let checking = self.update_receiver.is_some();
+                            if ui
+                                .add_enabled(!checking, egui::Button::new(tr!("app.check_now")))
+                                .clicked()
+                            {
+                                self.manual_update_check = true;
+                                self.status_msg = tr!("app.checking_updates").to_string();
+                                self.update_receiver = Some(updater::check_for_updates_async(
+                                    self.config.update_channel,
+                                ));
+                            }
+                        });
 
-                    ui.add_space(5.0);
-                    ui.horizontal(|ui| {
-                        ui.label("Global Hotkey:");
-                        ui.code(self.config.toggle_hotkey.as_str());
-                        ui.label(egui::RichText::new("ℹ️ Edit in config.json").size(10.0));
+                        ui.add_space(5.0);
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("app.global_hotkey"));
+                            ui.code(self.config.toggle_hotkey.as_str());
+                            ui.label(egui::RichText::new(tr!("app.edit_in_config")).size(10.0));
+                        });
                     });
-                });
-            }); // ScrollArea
+                }); // ScrollArea
         });
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_config();
+        // Stop streaming into the sink before it goes away
+        self.stop_engine();
+        self.release_virtual_sink();
+    }
 }
@@ -1,153 +1,749 @@
 use eframe::egui;
 use std::sync::atomic::Ordering;
+use voidmic_core::processor::{
+    dynamic_threshold_range, FadeCurve, GateKeySource, GateMode, NoiseFloorSpeed,
+    DYNAMIC_THRESHOLD_HIGHEST, DYNAMIC_THRESHOLD_LOWEST, GATE_HOLD_MAX_MS, GATE_HYSTERESIS_MIN,
+    INPUT_GAIN_MAX_DB, INPUT_GAIN_MIN_DB,
+};
 
 use super::app::VoidMicApp;
+use crate::config::PresetValues;
 
+/// A built-in preset. Covers the whole sound (gate, suppression, VAD, EQ, AGC) so
+/// switching presets leaves nothing behind from the previous one.
 pub(super) struct Preset {
     pub name: &'static str,
-    gate_threshold: f32,
-    suppression_strength: f32,
-    dynamic_threshold_enabled: bool,
+    values: PresetValues,
 }
 
 pub(super) const PRESETS: &[Preset] = &[
     Preset {
         name: "Standard",
-        gate_threshold: 0.015,
-        suppression_strength: 1.0,
-        dynamic_threshold_enabled: true,
+        values: PresetValues {
+            gate_threshold: 0.015,
+            suppression_strength: 1.0,
+            dynamic_threshold_enabled: true,
+            vad_sensitivity: 2,
+            eq_enabled: false,
+            eq_low_gain: 0.0,
+            eq_mid_gain: 0.0,
+            eq_high_gain: 0.0,
+            agc_enabled: false,
+            agc_target_level: 0.7,
+        },
     },
     Preset {
         name: "Gaming",
-        gate_threshold: 0.030,
-        suppression_strength: 1.0,
-        dynamic_threshold_enabled: true,
+        values: PresetValues {
+            gate_threshold: 0.030,
+            suppression_strength: 1.0,
+            dynamic_threshold_enabled: true,
+            vad_sensitivity: 3,
+            eq_enabled: false,
+            eq_low_gain: 0.0,
+            eq_mid_gain: 0.0,
+            eq_high_gain: 0.0,
+            agc_enabled: true,
+            agc_target_level: 0.7,
+        },
     },
     Preset {
         name: "Podcast",
-        gate_threshold: 0.008,
-        suppression_strength: 0.6,
-        dynamic_threshold_enabled: true,
+        values: PresetValues {
+            gate_threshold: 0.008,
+            suppression_strength: 0.6,
+            dynamic_threshold_enabled: true,
+            vad_sensitivity: 1,
+            eq_enabled: true,
+            eq_low_gain: 2.0,
+            eq_mid_gain: 0.0,
+            eq_high_gain: 2.0,
+            agc_enabled: true,
+            agc_target_level: 0.7,
+        },
     },
     Preset {
         name: "Noisy Office",
-        gate_threshold: 0.020,
-        suppression_strength: 1.0,
-        dynamic_threshold_enabled: true,
+        values: PresetValues {
+            gate_threshold: 0.020,
+            suppression_strength: 1.0,
+            dynamic_threshold_enabled: true,
+            vad_sensitivity: 3,
+            eq_enabled: true,
+            eq_low_gain: -4.0,
+            eq_mid_gain: 1.0,
+            eq_high_gain: 0.0,
+            agc_enabled: true,
+            agc_target_level: 0.7,
+        },
     },
     Preset {
         name: "Music",
-        gate_threshold: 0.002,
-        suppression_strength: 0.3,
-        dynamic_threshold_enabled: false,
+        values: PresetValues {
+            gate_threshold: 0.002,
+            suppression_strength: 0.3,
+            dynamic_threshold_enabled: false,
+            vad_sensitivity: 0,
+            eq_enabled: false,
+            eq_low_gain: 0.0,
+            eq_mid_gain: 0.0,
+            eq_high_gain: 0.0,
+            agc_enabled: false,
+            agc_target_level: 0.7,
+        },
     },
 ];
 
 impl VoidMicApp {
     pub(super) fn apply_preset(&mut self, preset_name: &str) {
         if let Some(preset) = PRESETS.iter().find(|p| p.name == preset_name) {
-            self.config.gate_threshold = preset.gate_threshold;
-            self.config.suppression_strength = preset.suppression_strength;
-            self.config.dynamic_threshold_enabled = preset.dynamic_threshold_enabled;
-            self.config.preset = preset_name.to_string();
+            // The user's edits to this preset win over its built-in values
+            let values = self
+                .config
+                .preset_overrides
+                .get(preset_name)
+                .copied()
+                .unwrap_or(preset.values);
+            self.config.set_preset_values(&values);
+            self.set_active_preset(preset_name);
             self.save_config_now();
 
             // Update running engine immediately
             if let Some(engine) = &self.engine {
-                engine.gate_threshold.store(self.config.gate_threshold.to_bits(), Ordering::Relaxed);
-                engine.suppression_strength.store(self.config.suppression_strength.to_bits(), Ordering::Relaxed);
-                engine.dynamic_threshold_enabled.store(self.config.dynamic_threshold_enabled, Ordering::Relaxed);
+                engine
+                    .controls
+                    .gate_threshold
+                    .store(self.config.gate_threshold.to_bits(), Ordering::Relaxed);
+                engine.controls.suppression_strength.store(
+                    self.config.suppression_strength.to_bits(),
+                    Ordering::Relaxed,
+                );
+                engine
+                    .controls
+                    .dynamic_threshold_enabled
+                    .store(self.config.dynamic_threshold_enabled, Ordering::Relaxed);
+                engine
+                    .controls
+                    .vad_sensitivity
+                    .store(self.config.vad_sensitivity as u32, Ordering::Relaxed);
+                engine
+                    .controls
+                    .eq_enabled
+                    .store(self.config.eq_enabled, Ordering::Relaxed);
+                engine
+                    .controls
+                    .eq_low_gain
+                    .store(self.config.eq_low_gain.to_bits(), Ordering::Relaxed);
+                engine
+                    .controls
+                    .eq_mid_gain
+                    .store(self.config.eq_mid_gain.to_bits(), Ordering::Relaxed);
+                engine
+                    .controls
+                    .eq_high_gain
+                    .store(self.config.eq_high_gain.to_bits(), Ordering::Relaxed);
+                engine
+                    .controls
+                    .agc_enabled
+                    .store(self.config.agc_enabled, Ordering::Relaxed);
+                engine
+                    .controls
+                    .agc_target
+                    .store(self.config.agc_target_level.to_bits(), Ordering::Relaxed);
             }
         }
     }
 
+    /// Preset in effect for the selected mic: the one remembered for it, else the global one.
+    fn active_preset(&self) -> &str {
+        self.config.preset_for_device(&self.selected_input)
+    }
+
+    /// Makes `name` the active preset: the selected mic's if it has one remembered,
+    /// otherwise the global preset.
+    fn set_active_preset(&mut self, name: &str) {
+        match self.config.per_device_presets.get_mut(&self.selected_input) {
+            Some(preset) => *preset = name.to_string(),
+            None => self.config.preset = name.to_string(),
+        }
+    }
+
+    /// Applies the preset for a newly selected input device.
+    pub(super) fn apply_device_preset(&mut self) {
+        let name = self.active_preset().to_string();
+        self.apply_preset(&name);
+    }
+
+    /// Records a manual change to a preset-controlled setting. Built-in presets keep it as
+    /// their override (dropped again if it matches the defaults); otherwise it's "Custom".
+    pub(super) fn record_preset_edit(&mut self) {
+        let Some(preset) = PRESETS.iter().find(|p| p.name == self.active_preset()) else {
+            self.set_active_preset("Custom");
+            return;
+        };
+        let values = self.config.preset_values();
+        if values == preset.values {
+            self.config.preset_overrides.remove(preset.name);
+        } else {
+            self.config
+                .preset_overrides
+                .insert(preset.name.to_string(), values);
+        }
+    }
+
+    /// Threshold calibration needs a running engine and a fixed (not Auto-Gate) threshold.
+    pub(super) fn can_calibrate(&self) -> bool {
+        self.engine.is_some() && !self.is_calibrating && !self.config.dynamic_threshold_enabled
+    }
+
+    pub(super) fn start_calibration(&mut self) {
+        if let Some(engine) = &self.engine {
+            engine.calibration_mode.store(true, Ordering::Relaxed);
+            self.is_calibrating = true;
+            self.status_msg = tr!("controls.calibrating").to_string();
+        }
+    }
+
+    fn preset_label(&self, name: &str) -> String {
+        if name == "Custom" {
+            tr!("controls.custom").to_string()
+        } else if self.config.preset_overrides.contains_key(name) {
+            tr!("controls.edited", name = name)
+        } else {
+            name.to_string()
+        }
+    }
+
     /// Renders the threshold and suppression controls.
     pub(super) fn render_threshold_controls(&mut self, ui: &mut egui::Ui) {
         // Presets Dropdown
         ui.horizontal(|ui| {
-            ui.label("Preset:");
+            ui.label(tr!("controls.preset"));
             egui::ComboBox::from_id_salt("preset_combo")
-                .selected_text(&self.config.preset)
+                .selected_text(self.preset_label(self.active_preset()))
                 .show_ui(ui, |ui| {
                     if ui
-                        .selectable_label(self.config.preset == "Custom", "Custom")
+                        .selectable_label(self.active_preset() == "Custom", tr!("controls.custom"))
                         .clicked()
                     {
-                        self.config.preset = "Custom".to_string();
+                        self.set_active_preset("Custom");
                         self.save_config_now();
                     }
                     ui.separator();
                     for preset in PRESETS {
+                        let label = self.preset_label(preset.name);
                         if ui
-                            .selectable_label(self.config.preset == preset.name, preset.name)
+                            .selectable_label(self.active_preset() == preset.name, label)
                             .clicked()
                         {
                             self.apply_preset(preset.name);
                         }
                     }
                 });
+
+            if self
+                .config
+                .preset_overrides
+                .contains_key(self.active_preset())
+                && ui
+                    .small_button(format!("↺ {}", tr!("controls.restore")))
+                    .on_hover_text(tr!("controls.restore_preset"))
+                    .clicked()
+            {
+                let name = self.active_preset().to_string();
+                self.config.preset_overrides.remove(&name);
+                self.apply_preset(&name);
+            }
+
+            // Remembering starts from the preset in effect; forgetting falls back to
+            // the global preset
+            let mut remember = self
+                .config
+                .per_device_presets
+                .contains_key(&self.selected_input);
+            if ui
+                .checkbox(&mut remember, tr!("controls.remember_preset"))
+                .on_hover_text(tr!("controls.remember_preset_hint"))
+                .changed()
+            {
+                if remember {
+                    let name = self.config.preset.clone();
+                    self.config
+                        .per_device_presets
+                        .insert(self.selected_input.clone(), name);
+                    self.save_config_now();
+                } else {
+                    self.config.per_device_presets.remove(&self.selected_input);
+                    self.apply_device_preset();
+                }
+            }
         });
 
         ui.add_space(5.0);
 
         ui.horizontal(|ui| {
             if ui
-                .checkbox(&mut self.config.dynamic_threshold_enabled, "Auto-Gate")
-                .on_hover_text("Automatically adjusts gate based on ambient noise floor")
+                .checkbox(
+                    &mut self.config.dynamic_threshold_enabled,
+                    tr!("controls.auto_gate"),
+                )
+                .on_hover_text(tr!("controls.auto_gate_hint"))
                 .changed()
             {
-                self.config.preset = "Custom".to_string();
+                self.record_preset_edit();
                 self.mark_config_dirty();
                 if let Some(engine) = &self.engine {
-                    engine.dynamic_threshold_enabled.store(self.config.dynamic_threshold_enabled, Ordering::Relaxed);
+                    engine
+                        .controls
+                        .dynamic_threshold_enabled
+                        .store(self.config.dynamic_threshold_enabled, Ordering::Relaxed);
                 }
             }
 
             ui.add_enabled_ui(!self.config.dynamic_threshold_enabled, |ui| {
-                ui.label("Gate Threshold:");
+                ui.label(tr!("controls.gate_threshold"));
                 let slider = egui::Slider::new(&mut self.config.gate_threshold, 0.005..=0.05)
                     .text("")
                     .fixed_decimals(3);
                 if ui.add(slider).changed() {
-                    self.config.preset = "Custom".to_string();
+                    self.record_preset_edit();
                     self.mark_config_dirty();
                     if let Some(engine) = &self.engine {
-                        engine.gate_threshold.store(self.config.gate_threshold.to_bits(), Ordering::Relaxed);
+                        engine
+                            .controls
+                            .gate_threshold
+                            .store(self.config.gate_threshold.to_bits(), Ordering::Relaxed);
                     }
                 }
             });
 
-            let calibrate_enabled = self.engine.is_some()
-                && !self.is_calibrating
-                && !self.config.dynamic_threshold_enabled;
             if ui
-                .add_enabled(calibrate_enabled, egui::Button::new("🎯 Calibrate"))
+                .add_enabled(
+                    self.can_calibrate(),
+                    egui::Button::new(tr!("controls.calibrate")),
+                )
                 .clicked()
             {
+                self.start_calibration();
+            }
+        });
+
+        if self.config.dynamic_threshold_enabled {
+            ui.horizontal(|ui| {
+                ui.label(tr!("controls.adaptation"));
+                let current = NoiseFloorSpeed::from_u32(self.config.noise_floor_speed);
+                egui::ComboBox::from_id_salt("noise_floor_speed_combo")
+                    .selected_text(noise_floor_speed_label(current))
+                    .show_ui(ui, |ui| {
+                        for speed in NoiseFloorSpeed::ALL {
+                            if ui
+                                .selectable_value(
+                                    &mut self.config.noise_floor_speed,
+                                    speed as u32,
+                                    noise_floor_speed_label(speed),
+                                )
+                                .clicked()
+                            {
+                                self.mark_config_dirty();
+                                if let Some(engine) = &self.engine {
+                                    engine
+                                        .controls
+                                        .noise_floor_speed
+                                        .store(self.config.noise_floor_speed, Ordering::Relaxed);
+                                }
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("controls.adaptation_hint"));
+
+                if ui
+                    .add_enabled(
+                        self.engine.is_some(),
+                        egui::SelectableLabel::new(self.noise_floor_frozen, tr!("controls.freeze")),
+                    )
+                    .on_hover_text(tr!("controls.freeze_hint"))
+                    .clicked()
+                {
+                    self.noise_floor_frozen = !self.noise_floor_frozen;
+                    if let Some(engine) = &self.engine {
+                        engine
+                            .controls
+                            .noise_floor_frozen
+                            .store(self.noise_floor_frozen, Ordering::Relaxed);
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(tr!("controls.auto_gate_range"))
+                    .on_hover_text(tr!("controls.auto_gate_range_hint"));
+                let bounds = DYNAMIC_THRESHOLD_LOWEST..=DYNAMIC_THRESHOLD_HIGHEST;
+                let mut changed = ui
+                    .add(
+                        egui::DragValue::new(&mut self.config.dynamic_threshold_min)
+                            .range(bounds.clone())
+                            .speed(0.0005)
+                            .fixed_decimals(4),
+                    )
+                    .changed();
+                ui.label("–");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.config.dynamic_threshold_max)
+                            .range(bounds)
+                            .speed(0.0005)
+                            .fixed_decimals(4),
+                    )
+                    .changed();
+                if changed {
+                    (
+                        self.config.dynamic_threshold_min,
+                        self.config.dynamic_threshold_max,
+                    ) = dynamic_threshold_range(
+                        self.config.dynamic_threshold_min,
+                        self.config.dynamic_threshold_max,
+                    );
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        let min = self.config.dynamic_threshold_min.to_bits();
+                        let max = self.config.dynamic_threshold_max.to_bits();
+                        engine
+                            .controls
+                            .dynamic_threshold_min
+                            .store(min, Ordering::Relaxed);
+                        engine
+                            .controls
+                            .dynamic_threshold_max
+                            .store(max, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.gate_mode"));
+            let current = GateMode::from_u32(self.config.gate_mode);
+            egui::ComboBox::from_id_salt("gate_mode_combo")
+                .selected_text(gate_mode_label(current))
+                .show_ui(ui, |ui| {
+                    for mode in GateMode::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.gate_mode,
+                                mode as u32,
+                                gate_mode_label(mode),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                            if let Some(engine) = &self.engine {
+                                engine
+                                    .controls
+                                    .gate_mode
+                                    .store(self.config.gate_mode, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("controls.gate_mode_hint"));
+
+            if current == GateMode::Expander {
+                let slider = egui::Slider::new(&mut self.config.expander_ratio, 1.0..=10.0)
+                    .text(":1")
+                    .fixed_decimals(1);
+                if ui.add(slider).changed() {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        engine
+                            .controls
+                            .expander_ratio
+                            .store(self.config.expander_ratio.to_bits(), Ordering::Relaxed);
+                    }
+                }
+            }
+
+            if current == GateMode::Gate {
+                let curve = FadeCurve::from_u32(self.config.gate_fade_curve);
+                egui::ComboBox::from_id_salt("gate_fade_combo")
+                    .selected_text(fade_curve_label(curve))
+                    .show_ui(ui, |ui| {
+                        for curve in FadeCurve::ALL {
+                            if ui
+                                .selectable_value(
+                                    &mut self.config.gate_fade_curve,
+                                    curve as u32,
+                                    fade_curve_label(curve),
+                                )
+                                .clicked()
+                            {
+                                self.mark_config_dirty();
+                                if let Some(engine) = &self.engine {
+                                    engine
+                                        .controls
+                                        .gate_fade_curve
+                                        .store(self.config.gate_fade_curve, Ordering::Relaxed);
+                                }
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("controls.fade_curve_hint"));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.gate_hold"));
+            let slider = egui::Slider::new(&mut self.config.gate_hold_ms, 0..=GATE_HOLD_MAX_MS / 2)
+                .text("ms");
+            if ui
+                .add(slider)
+                .on_hover_text(tr!("controls.gate_hold_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
                 if let Some(engine) = &self.engine {
-                    engine.calibration_mode.store(true, Ordering::Relaxed);
-                    self.is_calibrating = true;
-                    self.status_msg = "Calibrating... stay quiet for 3 seconds".to_string();
+                    engine
+                        .controls
+                        .gate_hold_ms
+                        .store(self.config.gate_hold_ms, Ordering::Relaxed);
                 }
             }
         });
 
         ui.horizontal(|ui| {
-            ui.label("Suppression:");
+            ui.label(tr!("controls.gate_hysteresis"));
+            let slider =
+                egui::Slider::new(&mut self.config.gate_hysteresis, GATE_HYSTERESIS_MIN..=1.0)
+                    .text("×")
+                    .fixed_decimals(2);
+            if ui
+                .add(slider)
+                .on_hover_text(tr!("controls.gate_hysteresis_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .gate_hysteresis
+                        .store(self.config.gate_hysteresis.to_bits(), Ordering::Relaxed);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.gate_resolution"));
+            let block_ms = |subblocks: u32| format!("{} ms", 10.0 / subblocks as f32);
+            egui::ComboBox::from_id_salt("gate_resolution_combo")
+                .selected_text(block_ms(self.config.gate_subblocks))
+                .show_ui(ui, |ui| {
+                    for subblocks in [1, 2, 4, 8] {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.gate_subblocks,
+                                subblocks,
+                                block_ms(subblocks),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                            if let Some(engine) = &self.engine {
+                                engine
+                                    .controls
+                                    .gate_subblocks
+                                    .store(self.config.gate_subblocks, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("controls.gate_resolution_hint"));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.gate_key"));
+            let current = GateKeySource::from_u32(self.config.gate_key_source);
+            egui::ComboBox::from_id_salt("gate_key_combo")
+                .selected_text(gate_key_source_label(current))
+                .show_ui(ui, |ui| {
+                    for source in GateKeySource::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut self.config.gate_key_source,
+                                source as u32,
+                                gate_key_source_label(source),
+                            )
+                            .clicked()
+                        {
+                            self.mark_config_dirty();
+                            if let Some(engine) = &self.engine {
+                                engine
+                                    .controls
+                                    .gate_key_source
+                                    .store(self.config.gate_key_source, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("controls.gate_key_hint"));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.input_gain"));
+            let slider = egui::Slider::new(
+                &mut self.config.input_gain_db,
+                INPUT_GAIN_MIN_DB..=INPUT_GAIN_MAX_DB,
+            )
+            .text("dB")
+            .fixed_decimals(1);
+            if ui
+                .add(slider)
+                .on_hover_text(tr!("controls.input_gain_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine
+                        .controls
+                        .input_gain_db
+                        .store(self.config.input_gain_db.to_bits(), Ordering::Relaxed);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("controls.suppression"));
             let pct = (self.config.suppression_strength * 100.0) as i32;
             let slider = egui::Slider::new(&mut self.config.suppression_strength, 0.0..=1.0)
                 .text(format!("{}%", pct))
                 .fixed_decimals(0);
             if ui.add(slider).changed() {
-                self.config.preset = "Custom".to_string();
+                self.record_preset_edit();
+                self.mark_config_dirty();
+                if let Some(engine) = &self.engine {
+                    engine.controls.suppression_strength.store(
+                        self.config.suppression_strength.to_bits(),
+                        Ordering::Relaxed,
+                    );
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .checkbox(
+                    &mut self.config.speech_gated_suppression,
+                    tr!("controls.natural_speech"),
+                )
+                .on_hover_text(tr!("controls.natural_speech_hint"))
+                .changed()
+            {
                 self.mark_config_dirty();
                 if let Some(engine) = &self.engine {
-                    engine.suppression_strength.store(self.config.suppression_strength.to_bits(), Ordering::Relaxed);
+                    engine
+                        .controls
+                        .speech_gated_suppression
+                        .store(self.config.speech_gated_suppression, Ordering::Relaxed);
                 }
-                if let Some(filter) = &self.output_filter_engine {
-                    filter.suppression_strength.store(self.config.suppression_strength.to_bits(), Ordering::Relaxed);
+            }
+            if self.config.speech_gated_suppression {
+                let pct = (self.config.speech_suppression_floor * 100.0) as i32;
+                let slider =
+                    egui::Slider::new(&mut self.config.speech_suppression_floor, 0.0..=1.0)
+                        .text(tr!("controls.while_speaking", pct = pct))
+                        .show_value(false);
+                if ui.add(slider).changed() {
+                    self.mark_config_dirty();
+                    if let Some(engine) = &self.engine {
+                        engine.controls.speech_suppression_floor.store(
+                            self.config.speech_suppression_floor.to_bits(),
+                            Ordering::Relaxed,
+                        );
+                    }
                 }
             }
         });
+
+        if ui
+            .checkbox(
+                &mut self.config.protect_highs,
+                tr!("controls.protect_highs"),
+            )
+            .on_hover_text(tr!("controls.protect_highs_hint"))
+            .changed()
+        {
+            self.mark_config_dirty();
+            if let Some(engine) = &self.engine {
+                engine
+                    .controls
+                    .protect_highs
+                    .store(self.config.protect_highs, Ordering::Relaxed);
+            }
+        }
+
+        if ui
+            .checkbox(
+                &mut self.config.spectral_gate,
+                tr!("controls.spectral_gate"),
+            )
+            .on_hover_text(tr!("controls.spectral_gate_hint"))
+            .changed()
+        {
+            self.mark_config_dirty();
+            if let Some(engine) = &self.engine {
+                engine
+                    .controls
+                    .spectral_gate
+                    .store(self.config.spectral_gate, Ordering::Relaxed);
+            }
+        }
+
+        if ui
+            .checkbox(&mut self.config.dither_enabled, tr!("controls.dither"))
+            .on_hover_text(tr!("controls.dither_hint"))
+            .changed()
+        {
+            self.mark_config_dirty();
+            if let Some(engine) = &self.engine {
+                engine
+                    .controls
+                    .dither_enabled
+                    .store(self.config.dither_enabled, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+fn gate_mode_label(mode: GateMode) -> &'static str {
+    match mode {
+        GateMode::Gate => tr!("controls.gate_mode_gate"),
+        GateMode::Expander => tr!("controls.gate_mode_expander"),
+        GateMode::Off => tr!("controls.gate_mode_off"),
+    }
+}
+
+fn fade_curve_label(curve: FadeCurve) -> &'static str {
+    match curve {
+        FadeCurve::Cosine => tr!("controls.fade_cosine"),
+        FadeCurve::Linear => tr!("controls.fade_linear"),
+        FadeCurve::Exponential => tr!("controls.fade_exponential"),
+    }
+}
+
+fn noise_floor_speed_label(speed: NoiseFloorSpeed) -> &'static str {
+    match speed {
+        NoiseFloorSpeed::Slow => tr!("controls.adaptation_slow"),
+        NoiseFloorSpeed::Normal => tr!("controls.adaptation_normal"),
+        NoiseFloorSpeed::Fast => tr!("controls.adaptation_fast"),
+    }
+}
+
+fn gate_key_source_label(source: GateKeySource) -> &'static str {
+    match source {
+        GateKeySource::PostDenoise => tr!("controls.gate_key_post_denoise"),
+        GateKeySource::PreDenoise => tr!("controls.gate_key_pre_denoise"),
     }
 }
@@ -1,4 +1,5 @@
-use crate::virtual_device;
+use crate::audio::{audio_host, AudioBackend, TestTone};
+use crate::{pulse_info, virtual_device};
 use cpal::traits::{DeviceTrait, HostTrait};
 use eframe::egui;
 
@@ -8,7 +9,7 @@ impl VoidMicApp {
     /// Renders the device selection dropdowns.
     pub(super) fn render_device_selectors(&mut self, ui: &mut egui::Ui) {
         egui::Grid::new("device_grid").striped(true).show(ui, |ui| {
-            ui.label("Microphone:");
+            ui.label(tr!("devices.microphone"));
             egui::ComboBox::from_id_salt("input_combo")
                 .selected_text(&self.selected_input)
                 .width(250.0)
@@ -24,11 +25,24 @@ impl VoidMicApp {
                     }
                     if changed {
                         self.mark_config_dirty();
+                        self.apply_device_preset();
                     }
                 });
+            if ui
+                .checkbox(&mut self.config.stereo_input, tr!("devices.stereo_input"))
+                .on_hover_text(tr!("devices.stereo_input_hint"))
+                .changed()
+            {
+                self.mark_config_dirty();
+                // The input channel count is fixed at start
+                if self.engine.is_some() {
+                    self.stop_engine();
+                    self.start_engine();
+                }
+            }
             ui.end_row();
 
-            ui.label("Output Sink:");
+            ui.label(tr!("devices.output_sink"));
             egui::ComboBox::from_id_salt("output_combo")
                 .selected_text(&self.selected_output)
                 .width(250.0)
@@ -46,6 +60,58 @@ impl VoidMicApp {
                         self.mark_config_dirty();
                     }
                 });
+
+            if self
+                .test_tone
+                .as_ref()
+                .is_some_and(|tone| tone.is_finished())
+            {
+                self.test_tone = None;
+            }
+            let tone_enabled = self.engine.is_none() && self.test_tone.is_none();
+            if ui
+                .add_enabled(tone_enabled, egui::Button::new(tr!("devices.test_tone")))
+                .on_hover_text(tr!("devices.test_tone_hint"))
+                .on_disabled_hover_text(tr!("devices.test_tone_disabled"))
+                .clicked()
+            {
+                match TestTone::play(self.config.audio_backend, &self.selected_output) {
+                    Ok(tone) => self.test_tone = Some(tone),
+                    Err(e) => self.status_msg = tr!("status.test_tone_error", error = e),
+                }
+            }
+            ui.end_row();
+
+            ui.label(tr!("devices.monitor_on"));
+            let prev_monitor = self.config.monitor_output.clone();
+            let monitor_text = if self.config.monitor_output.is_empty() {
+                tr!("devices.none")
+            } else {
+                self.config.monitor_output.as_str()
+            };
+            egui::ComboBox::from_id_salt("monitor_combo")
+                .selected_text(monitor_text)
+                .width(250.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut self.config.monitor_output,
+                        String::new(),
+                        tr!("devices.none"),
+                    );
+                    for dev in &self.output_devices {
+                        ui.selectable_value(&mut self.config.monitor_output, dev.clone(), dev);
+                    }
+                })
+                .response
+                .on_hover_text(tr!("devices.monitor_hint"));
+            if self.config.monitor_output != prev_monitor {
+                self.mark_config_dirty();
+                // Output streams are fixed at start, so restart to pick up the change
+                if self.engine.is_some() {
+                    self.stop_engine();
+                    self.start_engine();
+                }
+            }
             ui.end_row();
         });
 
@@ -54,53 +120,103 @@ impl VoidMicApp {
         // One-Click Setup Section
         if self.last_sink_check.elapsed().as_secs() >= 5 {
             self.virtual_sink_cached = virtual_device::virtual_sink_exists();
+            if self.virtual_sink_cached {
+                self.pulse_sinks = pulse_info::get_sinks();
+                self.default_sink = pulse_info::get_default_sink();
+            }
             self.last_sink_check = std::time::Instant::now();
         }
         ui.horizontal(|ui| {
             let sink_exists = self.virtual_sink_cached;
 
             if sink_exists {
-                ui.colored_label(egui::Color32::GREEN, "✔ Virtual Mic Active");
-                if ui.button("Destroy").clicked() {
+                ui.colored_label(egui::Color32::GREEN, tr!("devices.virtual_mic_active"));
+                if ui.button(tr!("devices.destroy")).clicked() {
                     if let Some(id) = self.virtual_sink_module_id {
                         let _ = virtual_device::destroy_virtual_sink(id);
                     } else {
                         let _ = virtual_device::destroy_virtual_sink(0);
                     }
                     self.virtual_sink_module_id = None;
-                    let (inputs, outputs) = get_devices();
+                    self.virtual_sink_cached = false;
+                    let (inputs, outputs) = get_devices(self.config.audio_backend);
                     self.input_devices = inputs;
                     self.output_devices = outputs;
                 }
-                ui.label(egui::RichText::new("ℹ️ Select 'VoidMic_Clean' in Discord").size(10.0));
+                let keep = &mut self.config.persist_virtual_sink;
+                if ui
+                    .checkbox(keep, tr!("devices.keep_virtual_mic"))
+                    .on_hover_text(tr!("devices.keep_virtual_mic_hint"))
+                    .changed()
+                {
+                    self.mark_config_dirty();
+                }
+                ui.label(egui::RichText::new(tr!("devices.select_in_discord")).size(10.0));
             } else if ui
-                .button("✨ Create Virtual Mic")
-                .on_hover_text("Creates a virtual device for Discord/Zoom")
+                .button(tr!("devices.create_virtual_mic"))
+                .on_hover_text(tr!("devices.create_virtual_mic_hint"))
                 .clicked()
             {
                 match virtual_device::create_virtual_sink() {
                     Ok(device) => {
                         self.virtual_sink_module_id = Some(device.module_id);
-                        let (inputs, outputs) = get_devices();
+                        let (inputs, outputs) = get_devices(self.config.audio_backend);
                         self.input_devices = inputs;
                         self.output_devices = outputs;
                         if self.output_devices.contains(&device.sink_name) {
                             self.selected_output = device.sink_name;
                             self.mark_config_dirty();
                         }
-                        self.status_msg = "Virtual Mic Created!".to_string();
+                        self.status_msg = tr!("devices.virtual_mic_created").to_string();
                     }
                     Err(e) => {
-                        self.status_msg = format!("Failed to create sink: {}", e);
+                        self.status_msg = tr!("status.create_sink_failed", error = e);
                     }
                 }
             }
         });
+
+        // Non-blocking hint: the processed mic is going to the speakers, not the virtual mic
+        if self.virtual_sink_cached
+            && pulse_info::is_physical_output(
+                &self.selected_output,
+                &self.pulse_sinks,
+                self.default_sink.as_deref(),
+            )
+        {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    tr!("devices.physical_output_warning"),
+                )
+                .on_hover_text(tr!("devices.physical_output_hint"));
+                let sink_name = virtual_device::VIRTUAL_SINK_NAME;
+                if let Some(sink) = self.output_devices.iter().find(|d| d.contains(sink_name)) {
+                    if ui.small_button(tr!("devices.use_virtual_mic")).clicked() {
+                        self.selected_output = sink.clone();
+                        self.mark_config_dirty();
+                    }
+                }
+            });
+        }
+    }
+
+    /// Unloads the virtual sink on exit if this session created it and it isn't kept
+    /// between runs. A sink that was already there at startup (module ID unknown) stays.
+    pub(super) fn release_virtual_sink(&mut self) {
+        if self.config.persist_virtual_sink {
+            return;
+        }
+        if let Some(id) = self.virtual_sink_module_id.take().filter(|&id| id != 0) {
+            if let Err(e) = virtual_device::destroy_virtual_sink(id) {
+                log::warn!("Failed to remove virtual sink: {}", e);
+            }
+        }
     }
 }
 
-pub(super) fn get_devices() -> (Vec<String>, Vec<String>) {
-    let host = cpal::default_host();
+pub(super) fn get_devices(backend: AudioBackend) -> (Vec<String>, Vec<String>) {
+    let host = audio_host(backend);
     let inputs = host
         .input_devices()
         .map(|devs| {
@@ -123,21 +239,18 @@ pub(super) fn get_devices() -> (Vec<String>, Vec<String>) {
 pub(super) fn install_virtual_cable() -> Result<String, String> {
     if cfg!(target_os = "linux") {
         match virtual_device::create_virtual_sink() {
-            Ok(_) => Ok(
-                "Virtual sink 'VoidMic_Clean' created! Select 'Monitor of VoidMic_Clean' in your apps."
-                    .to_string(),
-            ),
+            Ok(_) => Ok(tr!("status.virtual_cable_created").to_string()),
             Err(e) => Err(e),
         }
     } else if cfg!(target_os = "windows") {
         open::that("https://vb-audio.com/Cable/")
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
-        Ok("Opening VB-Cable download page...".to_string())
+            .map_err(|e| tr!("status.browser_failed", error = e))?;
+        Ok(tr!("status.opening_vb_cable").to_string())
     } else if cfg!(target_os = "macos") {
         open::that("https://github.com/ExistentialAudio/BlackHole")
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
-        Ok("Opening BlackHole download page...".to_string())
+            .map_err(|e| tr!("status.browser_failed", error = e))?;
+        Ok(tr!("status.opening_blackhole").to_string())
     } else {
-        Err("Unsupported platform".to_string())
+        Err(tr!("status.unsupported_platform").to_string())
     }
 }
@@ -1,16 +1,29 @@
-use crate::audio::{AudioEngine, OutputFilterEngine};
+use crate::audio::{audio_threads_running, AudioEngine, OutputFilterEngine};
+use crate::gate_log;
+use crate::metrics::MetricsSource;
+use crate::silence_watch::{silence_limit, SilenceAction, SilenceEvent, SilenceWatch};
 use crate::virtual_device;
+use eframe::egui;
+use std::sync::atomic::Ordering;
 
 use super::app::VoidMicApp;
 use super::devices::get_devices;
 
+// Longest a start may count as in progress, in case the device never delivers audio
+const ENGINE_START_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
 impl VoidMicApp {
     pub(super) fn start_engine(&mut self) {
         if self.engine.is_some() {
             return;
         }
+        self.engine_starting = Some(std::time::Instant::now());
+        // Free the output device for the engine
+        self.test_tone = None;
 
-        self.status_msg = "Initializing Hybrid Engine...".to_string();
+        self.status_msg = tr!("status.initializing").to_string();
+        // A fresh engine has no noise floor estimate worth holding
+        self.noise_floor_frozen = false;
 
         // Auto-create virtual sink on Linux
         #[cfg(target_os = "linux")]
@@ -19,7 +32,7 @@ impl VoidMicApp {
                 match virtual_device::create_virtual_sink() {
                     Ok(device) => {
                         self.virtual_sink_module_id = Some(device.module_id);
-                        let (inputs, outputs) = get_devices();
+                        let (inputs, outputs) = get_devices(self.config.audio_backend);
                         self.input_devices = inputs;
                         self.output_devices = outputs.clone();
                         if let Some(sink) = outputs.iter().find(|d| d.contains("VoidMic_Clean")) {
@@ -27,21 +40,51 @@ impl VoidMicApp {
                         }
                     }
                     Err(e) => {
-                        self.status_msg = format!("Virtual sink warning: {}", e);
+                        self.status_msg = tr!("status.virtual_sink_warning", error = e);
                     }
                 }
             }
         }
 
         let (tx, rx) = crossbeam_channel::bounded(2);
+        let (waveform_tx, waveform_rx) = crossbeam_channel::bounded(2);
+
+        // A log that can't be opened shouldn't keep the mic from starting
+        let gate_events = if self.config.gate_event_log {
+            let started = gate_log::gate_log_path()
+                .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))
+                .and_then(|path| gate_log::start(&path));
+            match started {
+                Ok(sender) => Some(sender),
+                Err(e) => {
+                    log::warn!("Gate event log disabled: {:#}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Fan out to the monitor device as well, if one is picked
+        let mut outputs = vec![self.selected_output.clone()];
+        if !self.config.monitor_output.is_empty()
+            && self.config.monitor_output != self.selected_output
+        {
+            outputs.push(self.config.monitor_output.clone());
+        }
 
         match AudioEngine::start(
+            self.config.audio_backend,
             &self.selected_input,
-            &self.selected_output,
+            &outputs,
             self.config.gate_threshold,
             self.config.suppression_strength,
             self.config.echo_cancel_enabled,
-            if self.config.echo_cancel_enabled { Some(self.selected_reference.as_str()) } else { None },
+            if self.config.echo_cancel_enabled {
+                Some(self.selected_reference.as_str())
+            } else {
+                None
+            },
             self.config.dynamic_threshold_enabled,
             self.config.vad_sensitivity,
             self.config.eq_enabled,
@@ -53,65 +96,341 @@ impl VoidMicApp {
             self.config.agc_enabled,
             self.config.agc_target_level,
             false,
+            self.config.stereo_input,
             Some(tx),
+            Some(waveform_tx),
+            gate_events,
         ) {
             Ok(engine) => {
+                self.apply_live_settings(&engine);
+                self.status_msg = match &engine.echo_cancel_error {
+                    Some(e) => {
+                        // Keep the toggle honest: AEC isn't running without a reference
+                        self.config.echo_cancel_enabled = false;
+                        tr!("status.active_without_aec", error = e)
+                    }
+                    None => tr!("status.active").to_string(),
+                };
+                if let Some(server) = &self.metrics_server {
+                    server.set_source(Some(MetricsSource::from_engine(&engine)));
+                }
                 self.engine = Some(engine);
                 self.spectrum_receiver = Some(rx);
-                self.status_msg = "Active (RNNoise + Gate)".to_string();
+                self.waveform_receiver = Some(waveform_rx);
                 self.save_config();
 
                 // Start output filter AFTER main engine succeeds
                 if self.config.output_filter_enabled {
-                    match OutputFilterEngine::start(
-                        &self.selected_reference,
-                        &self.selected_output,
-                        self.config.suppression_strength,
-                    ) {
-                        Ok(filter) => self.output_filter_engine = Some(filter),
-                        Err(e) => {
-                            log::error!("Output filter failed to start: {}", e);
-                            self.status_msg = format!("Active (output filter error: {})", e);
-                            self.config.output_filter_enabled = false;
-                        }
+                    if let Err(e) = self.start_output_filter() {
+                        self.status_msg = tr!("status.active_filter_error", error = e);
                     }
                 }
             }
             Err(e) => {
                 let error_str = e.to_string();
                 self.status_msg = if error_str.contains("No default") {
-                    "Error: No audio device found. Check your system settings.".to_string()
+                    tr!("status.no_device").to_string()
                 } else if error_str.contains("not found") {
-                    "Error: Selected device not found. Try refreshing or selecting another device.".to_string()
+                    tr!("status.device_not_found").to_string()
                 } else if error_str.contains("permission") || error_str.contains("access") {
-                    "Error: Permission denied. Check audio device permissions.".to_string()
+                    tr!("status.permission_denied").to_string()
+                } else if error_str.starts_with("Device in use by") {
+                    // Already names the apps to close
+                    tr!("status.error", error = error_str)
                 } else if error_str.contains("in use") || error_str.contains("busy") {
-                    "Error: Device is busy. Close other audio applications.".to_string()
+                    tr!("status.device_busy").to_string()
                 } else {
-                    format!("Error: {}", e)
+                    tr!("status.error", error = e)
                 };
                 log::error!("Failed to start engine: {}", e);
             }
         }
     }
 
+    /// Pushes settings that aren't part of `AudioEngine::start` into the engine's atomics.
+    fn apply_live_settings(&self, engine: &AudioEngine) {
+        engine
+            .underrun_fill
+            .store(self.config.underrun_fill, Ordering::Relaxed);
+        self.config.apply_to(&engine.controls);
+        engine
+            .controls
+            .waveform_view
+            .store(self.config.visualizer_waveform, Ordering::Relaxed);
+    }
+
+    /// Starts the output filter with its own settings from the config. On failure the
+    /// filter is switched off in the config and the error is returned for display.
+    pub(super) fn start_output_filter(&mut self) -> anyhow::Result<()> {
+        let ir_path = &self.config.output_filter_ir_path;
+        match OutputFilterEngine::start(
+            &self.selected_reference,
+            &self.selected_output,
+            self.config.audio_backend,
+            (!ir_path.is_empty()).then(|| std::path::Path::new(ir_path)),
+        ) {
+            Ok(filter) => {
+                self.apply_output_filter_settings(&filter);
+                self.output_filter_engine = Some(filter);
+                Ok(())
+            }
+            Err(e) => {
+                log::error!("Output filter failed to start: {}", e);
+                self.config.output_filter_enabled = false;
+                Err(e)
+            }
+        }
+    }
+
+    /// Pushes the output filter's settings from the config into its atomics.
+    pub(super) fn apply_output_filter_settings(&self, filter: &OutputFilterEngine) {
+        filter
+            .underrun_fill
+            .store(self.config.underrun_fill, Ordering::Relaxed);
+        let controls = &filter.controls;
+        controls.suppression_strength.store(
+            self.config.output_filter_suppression.to_bits(),
+            Ordering::Relaxed,
+        );
+        controls.gate_threshold.store(
+            self.config.output_filter_threshold.to_bits(),
+            Ordering::Relaxed,
+        );
+        controls
+            .eq_enabled
+            .store(self.config.output_filter_eq_enabled, Ordering::Relaxed);
+        controls.eq_low_gain.store(
+            self.config.output_filter_eq_low_gain.to_bits(),
+            Ordering::Relaxed,
+        );
+        controls.eq_mid_gain.store(
+            self.config.output_filter_eq_mid_gain.to_bits(),
+            Ordering::Relaxed,
+        );
+        controls.eq_high_gain.store(
+            self.config.output_filter_eq_high_gain.to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
     pub(super) fn stop_engine(&mut self) {
+        self.stop_recording();
+        if let Some(server) = &self.metrics_server {
+            server.set_source(None);
+        }
         self.engine = None;
         self.output_filter_engine = None;
-        self.status_msg = "Stopped".to_string();
+        self.status_msg = tr!("app.stopped").to_string();
+    }
+
+    /// Applies the echo cancellation toggle to the running engine without restarting it.
+    pub(super) fn apply_echo_cancel(&mut self) {
+        let Some(engine) = self.engine.as_mut() else {
+            return;
+        };
+        let enabled = self.config.echo_cancel_enabled;
+        if let Err(e) = engine.set_echo_cancel(enabled, &self.selected_reference) {
+            log::error!("Failed to switch echo cancellation: {}", e);
+            self.status_msg = tr!("status.echo_cancel_error", error = e);
+            self.config.echo_cancel_enabled = false;
+            let _ = engine.set_echo_cancel(false, &self.selected_reference);
+        }
+    }
+
+    /// Reopens just the reference stream after the reference device changed.
+    pub(super) fn apply_reference_device(&mut self) {
+        if !self.config.echo_cancel_enabled {
+            return;
+        }
+        if let Some(engine) = self.engine.as_mut() {
+            if let Err(e) = engine.set_reference_device(Some(&self.selected_reference)) {
+                log::error!("Failed to open reference device: {}", e);
+                self.status_msg = tr!("status.echo_cancel_off", error = e);
+                // The engine has switched AEC off; reflect that in the toggle
+                self.config.echo_cancel_enabled = false;
+                self.mark_config_dirty();
+            }
+        }
+    }
+
+    /// Re-enumerates devices on the newly selected backend and restarts the engine
+    /// on it if it was running.
+    pub(super) fn apply_audio_backend(&mut self) {
+        self.refresh_devices();
+        if self.engine.is_some() {
+            self.stop_engine();
+            self.start_engine();
+        }
+    }
+
+    /// One-click recovery from a confused audio state: drops every stream, waits for
+    /// the processing threads to exit, re-enumerates devices and starts again with the
+    /// current settings.
+    pub(super) fn reset_audio(&mut self) {
+        self.audio_reset_pending = false;
+        self.test_tone = None;
+        self.engine_starting = None;
+        self.stop_engine();
+        let lingering = audio_threads_running();
+        if lingering > 0 {
+            log::warn!(
+                "Audio reset: {} processing thread(s) still running",
+                lingering
+            );
+        }
+        self.refresh_devices();
+        self.start_engine();
+        self.status_msg = tr!("app.audio_reset", status = self.status_msg);
+    }
+
+    /// Re-enumerates devices, falling back to "default" for selections that are gone.
+    fn refresh_devices(&mut self) {
+        let (inputs, outputs) = get_devices(self.config.audio_backend);
+        if !inputs.contains(&self.selected_input) {
+            self.selected_input = "default".to_string();
+            self.apply_device_preset();
+        }
+        if !outputs.contains(&self.selected_output) {
+            self.selected_output = "default".to_string();
+        }
+        if !inputs.contains(&self.selected_reference) {
+            self.selected_reference = "default".to_string();
+        }
+        self.input_devices = inputs;
+        self.output_devices = outputs;
+    }
+
+    /// Flips bypass on the running engine; does nothing while it's stopped.
+    pub(super) fn toggle_bypass(&self) {
+        if let Some(engine) = &self.engine {
+            let current = engine.controls.bypass_enabled.load(Ordering::Relaxed);
+            engine
+                .controls
+                .bypass_enabled
+                .store(!current, Ordering::Relaxed);
+        }
     }
 
     pub(super) fn toggle_engine(&mut self) {
+        // Clicks and hotkey presses queued up during a slow device init would otherwise
+        // stop and restart the engine before the device is free again ("device busy")
+        if self.engine_start_pending() {
+            log::debug!("Ignoring toggle while the engine is starting");
+            return;
+        }
+        // A manual toggle always overrides the idle pause
+        self.idle_paused = false;
+        // The tray icon and tooltip follow in `sync_tray_state`
         if self.engine.is_some() {
             self.stop_engine();
-            if let Some(ref tray) = self.tray_icon {
-                let _ = tray.set_tooltip(Some("VoidMic - Disabled"));
-            }
         } else {
             self.start_engine();
-            if let Some(ref tray) = self.tray_icon {
-                let _ = tray.set_tooltip(Some("VoidMic - Active"));
+        }
+    }
+
+    /// Whether the last start is still in progress: the engine exists but hasn't processed
+    /// a frame yet, for at most `ENGINE_START_TIMEOUT`.
+    fn engine_start_pending(&mut self) -> bool {
+        let Some(started) = self.engine_starting else {
+            return false;
+        };
+        let pending = self
+            .engine
+            .as_ref()
+            .is_some_and(|engine| engine.frames_processed.load(Ordering::Relaxed) == 0)
+            && started.elapsed() < ENGINE_START_TIMEOUT;
+        if !pending {
+            self.engine_starting = None;
+        }
+        pending
+    }
+
+    /// Suspends the engine while the window is hidden and no app is connected to the
+    /// virtual mic (`pause_when_idle`), resuming once an app connects or the window is shown.
+    pub(super) fn update_idle_pause(&mut self) {
+        if !self.config.pause_when_idle {
+            if self.idle_paused {
+                self.idle_paused = false;
+                self.start_engine();
             }
+            return;
+        }
+
+        if self.idle_paused && self.window_visible {
+            self.idle_paused = false;
+            self.start_engine();
+            return;
+        }
+
+        if self.window_visible || self.last_idle_check.elapsed().as_secs() < 2 {
+            return;
+        }
+        self.last_idle_check = std::time::Instant::now();
+
+        let has_listeners = !crate::pulse_info::get_connected_apps().is_empty();
+        if self.idle_paused {
+            if has_listeners {
+                log::info!("App connected to virtual mic, resuming processing");
+                self.idle_paused = false;
+                self.start_engine();
+            }
+        } else if self.engine.is_some() && !has_listeners {
+            log::info!("Window hidden and no apps connected, pausing processing");
+            self.stop_engine();
+            self.idle_paused = true;
+            self.status_msg = tr!("status.paused_idle").to_string();
+        }
+    }
+
+    /// Warns about, or mutes, a mic that has sent sound without speech for the
+    /// configured time, and lifts that again once speech is heard.
+    pub(super) fn update_silence_watch(&mut self, ctx: &egui::Context) {
+        let Some(engine) = self
+            .engine
+            .as_ref()
+            .filter(|_| self.config.silence_action != SilenceAction::Off)
+        else {
+            // A new engine starts unmuted
+            self.silence_watch = None;
+            self.silence_alert = None;
+            return;
+        };
+
+        let limit = silence_limit(self.config.silence_minutes);
+        let watch = self
+            .silence_watch
+            .get_or_insert_with(|| SilenceWatch::new(limit));
+        watch.set_limit(limit);
+        match watch.poll(engine) {
+            Some(SilenceEvent::Silent) => {
+                let action = self.config.silence_action;
+                log::info!(
+                    "No speech for {} min while sending sound ({:?})",
+                    self.config.silence_minutes,
+                    action
+                );
+                if action == SilenceAction::Mute {
+                    engine.controls.muted.store(true, Ordering::Relaxed);
+                }
+                self.silence_alert = Some(action);
+                ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                    egui::UserAttentionType::Informational,
+                ));
+            }
+            Some(SilenceEvent::Speech) => self.end_silence_alert(),
+            None => {}
+        }
+    }
+
+    /// Clears the silence banner, unmuting if it muted, and restarts the timer.
+    pub(super) fn end_silence_alert(&mut self) {
+        if self.silence_alert.take() == Some(SilenceAction::Mute) {
+            if let Some(engine) = &self.engine {
+                engine.controls.muted.store(false, Ordering::Relaxed);
+            }
+        }
+        if let Some(watch) = &mut self.silence_watch {
+            watch.reset();
         }
     }
 }
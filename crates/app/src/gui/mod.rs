@@ -5,6 +5,9 @@ mod app;
 mod controls;
 mod devices;
 mod engine;
+mod offline;
+mod record;
+mod shortcuts;
 mod tray;
 mod wizard;
 
@@ -0,0 +1,179 @@
+use crate::offline::{self, WavAudio};
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+
+use super::app::VoidMicApp;
+
+/// A dropped recording being denoised on a background thread.
+pub(super) struct OfflineJob {
+    source: PathBuf,
+    // Finished fraction as `f32` bits
+    progress: Arc<AtomicU32>,
+    receiver: Receiver<anyhow::Result<WavAudio>>,
+}
+
+/// A denoised recording waiting to be saved or discarded.
+pub(super) struct OfflineResult {
+    source: PathBuf,
+    audio: WavAudio,
+    save_path: String,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+impl VoidMicApp {
+    /// Starts denoising a `.wav` dropped onto the window; anything else gets an error popup.
+    pub(super) fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        for path in dropped.into_iter().filter_map(|file| file.path) {
+            if !offline::is_wav(&path) {
+                self.offline_error = Some(tr!("offline.not_wav", name = file_name(&path)));
+            } else if self.offline_job.is_some() || self.offline_result.is_some() {
+                self.offline_error = Some(tr!("offline.busy").to_string());
+            } else {
+                self.start_offline_job(path);
+            }
+        }
+    }
+
+    fn start_offline_job(&mut self, source: PathBuf) {
+        let progress = Arc::new(AtomicU32::new(0));
+        let (tx, rx) = mpsc::channel();
+        let config = self.config.clone();
+        let path = source.clone();
+        let thread_progress = progress.clone();
+        std::thread::spawn(move || {
+            let result = offline::read_wav(&path)
+                .map(|audio| offline::process(&audio, &config, &thread_progress));
+            let _ = tx.send(result);
+        });
+        self.offline_job = Some(OfflineJob {
+            source,
+            progress,
+            receiver: rx,
+        });
+    }
+
+    /// Picks up a finished job and shows the drop hint, progress, save prompt and errors.
+    pub(super) fn render_offline_processing(&mut self, ctx: &egui::Context) {
+        if let Some(job) = &self.offline_job {
+            match job.receiver.try_recv() {
+                Ok(Ok(audio)) => {
+                    self.offline_result = Some(OfflineResult {
+                        save_path: offline::processed_path(&job.source).display().to_string(),
+                        source: job.source.clone(),
+                        audio,
+                    });
+                    self.offline_job = None;
+                }
+                Ok(Err(e)) => {
+                    self.offline_error = Some(tr!(
+                        "offline.failed",
+                        name = file_name(&job.source),
+                        error = e
+                    ));
+                    self.offline_job = None;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    // The worker panicked
+                    self.offline_error = Some(tr!(
+                        "offline.failed",
+                        name = file_name(&job.source),
+                        error = "processing stopped unexpectedly"
+                    ));
+                    self.offline_job = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let screen = ctx.screen_rect();
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("offline_drop_hint"),
+            ));
+            painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(180));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                tr!("offline.drop_hint"),
+                egui::FontId::proportional(18.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        if let Some(job) = &self.offline_job {
+            let done = f32::from_bits(job.progress.load(Ordering::Relaxed));
+            egui::Window::new(tr!("offline.processing_title"))
+                .id(egui::Id::new("offline_progress"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label(file_name(&job.source));
+                    ui.add(egui::ProgressBar::new(done).show_percentage());
+                });
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+
+        if let Some(result) = &mut self.offline_result {
+            let mut save = false;
+            let mut discard = false;
+            egui::Window::new(tr!("offline.done_title"))
+                .id(egui::Id::new("offline_save"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label(tr!("offline.done", name = file_name(&result.source)));
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("offline.save_as"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut result.save_path).desired_width(300.0),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        save = ui.button(tr!("offline.save")).clicked();
+                        discard = ui.button(tr!("offline.discard")).clicked();
+                    });
+                });
+            if save {
+                let path = PathBuf::from(result.save_path.trim());
+                match offline::write_wav(&path, &result.audio) {
+                    Ok(()) => {
+                        self.status_msg = tr!("offline.saved", path = path.display());
+                        self.offline_result = None;
+                    }
+                    // Keep the result so another path can be tried
+                    Err(e) => self.offline_error = Some(format!("{:#}", e)),
+                }
+            } else if discard {
+                self.offline_result = None;
+            }
+        }
+
+        if let Some(error) = &self.offline_error {
+            let mut dismiss = false;
+            egui::Window::new(tr!("offline.error_title"))
+                .id(egui::Id::new("offline_error"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -20.0))
+                .show(ctx, |ui| {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error.as_str());
+                    dismiss = ui.button(tr!("offline.ok")).clicked();
+                });
+            if dismiss {
+                self.offline_error = None;
+            }
+        }
+    }
+}
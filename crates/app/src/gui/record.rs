@@ -0,0 +1,90 @@
+use crate::daemon::format_duration;
+use crate::recorder::{self, Recording};
+use eframe::egui;
+use std::path::PathBuf;
+
+use super::app::VoidMicApp;
+
+impl VoidMicApp {
+    fn start_recording(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        let path = match self.config.record_path.trim() {
+            "" => recorder::default_record_path(),
+            path => PathBuf::from(path),
+        };
+        let started = Recording::start(&path).and_then(|recording| {
+            engine
+                .set_record_tap(Some(recording.tap()))
+                .map(|()| recording)
+        });
+        match started {
+            Ok(recording) => self.recording = Some(recording),
+            Err(e) => self.status_msg = tr!("record.failed", error = format!("{:#}", e)),
+        }
+    }
+
+    /// Finalizes the file; also runs when the engine stops.
+    pub(super) fn stop_recording(&mut self) {
+        let Some(recording) = self.recording.take() else {
+            return;
+        };
+        if let Some(engine) = &self.engine {
+            let _ = engine.set_record_tap(None);
+        }
+        let path = recording.path().display().to_string();
+        self.status_msg = match recording.stop() {
+            Ok(duration) => tr!(
+                "record.saved",
+                path = path,
+                duration = format_duration(duration.as_secs())
+            ),
+            Err(e) => tr!("record.failed", error = format!("{:#}", e)),
+        };
+    }
+
+    /// Record button with the running length and size, or the target path while idle.
+    pub(super) fn render_record_controls(&mut self, ui: &mut egui::Ui) {
+        if self.engine.is_none() {
+            return;
+        }
+        // A write error (e.g. a full disk) ends the recording, keeping what was written
+        if self.recording.as_ref().is_some_and(|r| r.error().is_some()) {
+            self.stop_recording();
+        }
+
+        ui.horizontal(|ui| {
+            if let Some(recording) = &self.recording {
+                let stop = ui
+                    .button(tr!("record.stop"))
+                    .on_hover_text(recording.path().display().to_string());
+                ui.label(tr!(
+                    "record.progress",
+                    duration = format_duration(recording.duration().as_secs()),
+                    size = format!("{:.1}", recording.bytes_written() as f64 / 1_000_000.0),
+                ));
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_millis(500));
+                if stop.clicked() {
+                    self.stop_recording();
+                }
+            } else {
+                let start = ui
+                    .button(tr!("record.start"))
+                    .on_hover_text(tr!("record.hint"));
+                let path = ui.add(
+                    egui::TextEdit::singleline(&mut self.config.record_path)
+                        .hint_text(tr!("record.path_hint"))
+                        .desired_width(ui.available_width()),
+                );
+                if path.changed() {
+                    self.mark_config_dirty();
+                }
+                if start.clicked() {
+                    self.start_recording();
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,60 @@
+//! In-window keyboard shortcuts.
+
+use eframe::egui;
+
+use super::app::VoidMicApp;
+
+/// An action bound to a key in the main window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Shortcut {
+    ToggleEngine,
+    Bypass,
+    MiniMode,
+    Calibrate,
+}
+
+impl Shortcut {
+    const ALL: [Shortcut; 4] = [
+        Shortcut::ToggleEngine,
+        Shortcut::Bypass,
+        Shortcut::MiniMode,
+        Shortcut::Calibrate,
+    ];
+
+    fn key(self) -> egui::Key {
+        match self {
+            Shortcut::ToggleEngine => egui::Key::Space,
+            Shortcut::Bypass => egui::Key::B,
+            Shortcut::MiniMode => egui::Key::M,
+            Shortcut::Calibrate => egui::Key::C,
+        }
+    }
+}
+
+impl VoidMicApp {
+    /// Runs the shortcuts pressed since the last frame. Nothing fires while a text field
+    /// has focus, and keys held with a modifier are left to the OS and other widgets.
+    pub(super) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        for shortcut in Shortcut::ALL {
+            if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, shortcut.key())) {
+                self.run_shortcut(ctx, shortcut);
+            }
+        }
+    }
+
+    fn run_shortcut(&mut self, ctx: &egui::Context, shortcut: Shortcut) {
+        match shortcut {
+            Shortcut::ToggleEngine => self.toggle_engine(),
+            Shortcut::Bypass => self.toggle_bypass(),
+            Shortcut::MiniMode => self.set_mini_mode(ctx, !self.config.mini_mode),
+            Shortcut::Calibrate => {
+                if self.can_calibrate() {
+                    self.start_calibration();
+                }
+            }
+        }
+    }
+}
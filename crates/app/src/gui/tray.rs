@@ -1,16 +1,100 @@
+use std::time::Duration;
 use tray_icon::Icon;
 
 pub(super) const QUIT_ID: &str = "quit";
 pub(super) const SHOW_ID: &str = "show";
 pub(super) const TOGGLE_ID: &str = "toggle";
+// How often the tooltip's level readout is refreshed while the engine runs
+pub(super) const TOOLTIP_LEVEL_INTERVAL: Duration = Duration::from_millis(500);
 
-pub(super) fn load_icon() -> Icon {
+/// What the tray icon is currently showing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TrayState {
+    /// Engine running and processing
+    Active,
+    /// Engine running with raw audio passed through
+    Bypassed,
+    /// Engine stopped (disabled or idle-paused)
+    Disabled,
+}
+
+impl TrayState {
+    pub(super) fn tooltip(self) -> &'static str {
+        match self {
+            TrayState::Active => tr!("tray.active"),
+            TrayState::Bypassed => tr!("tray.bypassed"),
+            TrayState::Disabled => tr!("tray.disabled"),
+        }
+    }
+
+    /// The tooltip with the current input level appended, e.g. "VoidMic - Active, -22 dB".
+    pub(super) fn tooltip_with_level(self, rms: f32) -> String {
+        tr!("tray.level", state = self.tooltip(), db = level_db(rms))
+    }
+}
+
+/// Whole dB for the tooltip, floored at -60 like the volume meter.
+fn level_db(rms: f32) -> i32 {
+    if rms > 0.001 {
+        (20.0 * rms.log10()).round() as i32
+    } else {
+        -60
+    }
+}
+
+// Recolor targets for the non-active variants
+const BYPASSED_TINT: [u8; 3] = [255, 170, 0];
+const DISABLED_TINT: [u8; 3] = [110, 110, 110];
+
+/// The three tray icon variants, rendered once from the base icon at startup.
+pub(super) struct TrayIcons {
+    active: Icon,
+    bypassed: Icon,
+    disabled: Icon,
+}
+
+impl TrayIcons {
+    pub(super) fn new() -> Self {
+        let (rgba, width, height) = load_icon_rgba();
+        Self {
+            active: make_icon(rgba.clone(), width, height),
+            bypassed: make_icon(tint_rgba(&rgba, BYPASSED_TINT), width, height),
+            disabled: make_icon(tint_rgba(&rgba, DISABLED_TINT), width, height),
+        }
+    }
+
+    pub(super) fn get(&self, state: TrayState) -> Icon {
+        match state {
+            TrayState::Active => self.active.clone(),
+            TrayState::Bypassed => self.bypassed.clone(),
+            TrayState::Disabled => self.disabled.clone(),
+        }
+    }
+}
+
+fn load_icon_rgba() -> (Vec<u8>, u32, u32) {
     let icon_bytes = include_bytes!("../../assets/icon_32.png");
     let image = image::load_from_memory(icon_bytes)
         .expect("Failed to load icon asset")
         .into_rgba8();
     let (width, height) = image.dimensions();
-    let rgba = image.into_raw();
+    (image.into_raw(), width, height)
+}
+
+fn make_icon(rgba: Vec<u8>, width: u32, height: u32) -> Icon {
     Icon::from_rgba(rgba, width, height)
         .unwrap_or_else(|_| Icon::from_rgba(vec![0; 32 * 32 * 4], 32, 32).unwrap())
 }
+
+/// Recolors an RGBA image to `tint`, keeping each pixel's brightness (HSV value)
+/// and alpha, so the shape and shading of the base icon survive.
+fn tint_rgba(rgba: &[u8], tint: [u8; 3]) -> Vec<u8> {
+    let mut out = rgba.to_vec();
+    for px in out.chunks_exact_mut(4) {
+        let value = px[0].max(px[1]).max(px[2]) as u16;
+        for (c, &t) in px[..3].iter_mut().zip(tint.iter()) {
+            *c = (value * t as u16 / 255) as u8;
+        }
+    }
+    out
+}
@@ -0,0 +1,698 @@
+//! Lightweight localization for GUI strings.
+//!
+//! Strings are looked up by key with `tr!("key")`, in the language picked with
+//! `set_language`. A key the language doesn't translate falls back to English, and an
+//! unknown key shows up as the key itself. `{name}` placeholders are filled in with
+//! `tr!("key", name = value)`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// GUI language.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Decodes a value from `Language as u32`; unknown values fall back to English.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    /// Name of the language in that language, for the picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::German => GERMAN,
+        }
+    }
+}
+
+static CURRENT_LANGUAGE: AtomicU32 = AtomicU32::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT_LANGUAGE.store(language as u32, Ordering::Relaxed);
+}
+
+pub fn current_language() -> Language {
+    Language::from_u32(CURRENT_LANGUAGE.load(Ordering::Relaxed))
+}
+
+/// Looks `key` up in the current language. Use `tr!` instead of calling this directly.
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(current_language(), key)
+}
+
+fn lookup(language: Language, key: &'static str) -> &'static str {
+    find(language.table(), key)
+        .or_else(|| find(ENGLISH, key))
+        .unwrap_or(key)
+}
+
+fn find(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, text)| *text)
+}
+
+/// Translates a GUI string key; with `name = value` pairs, fills in `{name}` placeholders
+/// and returns a `String`.
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut text = $crate::i18n::tr($key).to_string();
+        $(
+            text = text.replace(concat!("{", stringify!($name), "}"), &$value.to_string());
+        )+
+        text
+    }};
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("app.subtitle", "Hybrid Noise Reduction"),
+    ("app.expand", "Expand"),
+    ("app.compact_mode", "Compact Mode"),
+    ("app.dismiss", "Dismiss"),
+    ("app.engine", "VoidMic engine"),
+    ("app.bypass_hint", "Switch between processed and raw mic audio (B)"),
+    ("app.active", "Active"),
+    ("app.inactive", "Inactive"),
+    ("app.stopped", "Stopped"),
+    ("app.processing", "Processing"),
+    ("app.stop_engine", "STOP ENGINE"),
+    ("app.activate", "ACTIVATE VOIDMIC"),
+    ("app.reset_audio", "🔄 Reset Audio"),
+    ("app.reset_audio_hint", "Glitching, stuck or lost a device? Closes all audio streams, rescans devices and starts again with your current settings"),
+    ("app.shortcuts_hint", "Shortcuts: Space start/stop · B bypass · M compact mode · C calibrate"),
+    ("app.resetting_audio", "Resetting audio..."),
+    ("app.frames_hint", "A steadily rising frame count confirms audio is flowing"),
+    ("app.install_cable", "🛠️ Install Virtual Cable"),
+    ("app.start_on_boot", "Start on Boot"),
+    ("app.autostart_enabled", "Autostart enabled"),
+    ("app.autostart_disabled", "Autostart disabled"),
+    ("app.start_minimized", "Start Minimized to Tray"),
+    ("app.auto_start", "Auto-Start Processing"),
+    ("app.pause_when_idle", "Pause When Hidden & Unused"),
+    ("app.pause_when_idle_hint", "Stops processing while the window is hidden and no app is using the virtual mic"),
+    ("app.silence_action", "When Silent:"),
+    ("app.silence_action_hint", "After this long with the mic sending sound but no speech (forgot to mute?)"),
+    ("app.silence_off", "Do Nothing"),
+    ("app.silence_notify", "Warn"),
+    ("app.silence_mute", "Mute"),
+    ("app.silence_warning", "⚠ No speech for {minutes} min, but the mic is sending sound. Forgot to mute?"),
+    ("app.silence_muted", "🔇 Muted after {minutes} min without speech. Speak to unmute."),
+    ("app.mute", "Mute"),
+    ("app.unmute", "Unmute"),
+    ("app.auto_suspend", "Sleep DSP in Silence after"),
+    ("app.auto_suspend_hint", "Saves CPU in long quiet periods: once the gate has been closed this long, denoise/EQ/AGC are skipped and only the input level is watched. Speaking above the threshold wakes it up within one frame. Gate mode only."),
+    ("app.dsp_suspended", "💤 Sleeping until you speak"),
+    ("app.output_level", "Out"),
+    ("app.output_level_hint", "Level the output device is actually playing"),
+    ("app.clips", "Clips: {count}"),
+    ("app.clips_hint", "Frames that reached full scale since processing started; lower the input gain if this keeps climbing"),
+    ("app.clips_reset", "Reset"),
+    ("app.audio_backend", "Audio Backend:"),
+    ("app.backend_unavailable", "{backend} is not available, using the system default"),
+    ("app.backend_unavailable_label", "{backend} (unavailable)"),
+    ("app.language", "Language:"),
+    ("app.follow_system_theme", "Follow System Theme"),
+    ("app.dark_mode", "Dark Mode"),
+    ("app.check_updates", "Check for Updates on Startup"),
+    ("app.update_channel", "Update Channel:"),
+    ("app.check_now", "Check Now"),
+    ("app.checking_updates", "Checking for updates..."),
+    ("app.up_to_date", "VoidMic is up to date"),
+    ("app.global_hotkey", "Global Hotkey:"),
+    ("app.edit_in_config", "ℹ️ Edit in config.json"),
+    ("app.download", "Download"),
+    ("app.speak_now", "Now speak normally for 3 seconds..."),
+    ("controls.preset", "Preset:"),
+    ("controls.restore", "Restore"),
+    ("controls.restore_preset", "Restore this preset's built-in values"),
+    ("controls.remember_preset", "Remember for this mic"),
+    ("controls.remember_preset_hint", "Switch to this preset whenever this microphone is selected. Mics without a remembered preset use the global one."),
+    ("controls.auto_gate", "Auto-Gate"),
+    ("controls.auto_gate_hint", "Automatically adjusts gate based on ambient noise floor"),
+    ("controls.gate_threshold", "Gate Threshold:"),
+    ("controls.calibrate", "🎯 Calibrate"),
+    ("controls.calibrating", "Calibrating... stay quiet for 3 seconds, then speak when asked"),
+    ("controls.adaptation", "Adaptation:"),
+    ("controls.adaptation_hint", "How fast Auto-Gate follows the room's noise floor: Slow rides out intermittent noise, Fast adapts quickly to a changing environment"),
+    ("controls.freeze", "❄ Freeze"),
+    ("controls.freeze_hint", "Hold the current noise floor estimate, e.g. once Auto-Gate has settled in a quiet room, so others talking nearby can't raise the gate"),
+    ("controls.auto_gate_range", "Range:"),
+    ("controls.auto_gate_range_hint", "Lowest and highest threshold Auto-Gate may pick. Lower the minimum for very quiet rooms"),
+    ("controls.gate_mode", "Gate Mode:"),
+    ("controls.gate_mode_hint", "Gate mutes below the threshold; Expander turns it down gradually instead"),
+    ("controls.fade_curve_hint", "Shape of the fade when the gate closes; Cosine is the smoothest"),
+    ("controls.gate_mode_gate", "Gate"),
+    ("controls.gate_mode_expander", "Expander"),
+    ("controls.gate_mode_off", "Off"),
+    ("controls.fade_cosine", "Cosine"),
+    ("controls.fade_linear", "Linear"),
+    ("controls.fade_exponential", "Exponential"),
+    ("controls.adaptation_slow", "Slow"),
+    ("controls.adaptation_normal", "Normal"),
+    ("controls.adaptation_fast", "Fast"),
+    ("controls.gate_hold", "Gate Hold:"),
+    ("controls.gate_hold_hint", "Keeps the gate fully open this long after you stop talking, before the release fade starts"),
+    ("controls.gate_hysteresis", "Close At:"),
+    ("controls.gate_hysteresis_hint", "Once open, the gate only closes when the level drops below the threshold times this. Lower stops fluttering on borderline levels; 1.00 closes at the threshold itself"),
+    ("controls.gate_resolution", "Gate Step:"),
+    ("controls.gate_resolution_hint", "How often the gate checks the level. Shorter steps open and close closer to where speech starts and stops; noise suppression and the VAD still work in 10 ms frames"),
+    ("controls.gate_key", "Gate Listens:"),
+    ("controls.gate_key_post_denoise", "After Denoise"),
+    ("controls.gate_key_pre_denoise", "Before Denoise"),
+    ("controls.gate_key_hint", "Which signal the gate and VAD measure. Before Denoise can open more reliably on quiet speech that noise suppression thins out, but background noise counts toward the level too"),
+    ("controls.input_gain", "Input Gain:"),
+    ("controls.input_gain_hint", "Set automatically by 🎯 Calibrate (speak when asked)"),
+    ("controls.suppression", "Suppression:"),
+    ("controls.natural_speech", "Natural Speech"),
+    ("controls.natural_speech_hint", "Eases off suppression while you talk (less robotic consonants) and applies it fully in pauses"),
+    ("controls.protect_highs", "Protect Highs"),
+    ("controls.protect_highs_hint", "Keeps everything above 8 kHz unsuppressed, for music or crisp sibilance"),
+    ("controls.spectral_gate", "Spectral Gate"),
+    ("controls.spectral_gate_hint", "Gates steady noise bands (hum, fans) per frequency while the voice band passes. Adds 10 ms of latency."),
+    ("controls.dither", "Dither Output"),
+    ("controls.dither_hint", "Adds inaudible noise at 16-bit level so quiet tails don't turn gritty when the output is converted to 16-bit. Silence stays silent."),
+    ("devices.microphone", "Microphone:"),
+    ("devices.stereo_input", "Stereo"),
+    ("devices.stereo_input_hint", "Open the mic in stereo and process each side on its own, with a meter per channel to spot a dead or weak side. Costs twice the CPU; the outputs get the mono mix."),
+    ("devices.output_sink", "Output Sink:"),
+    ("devices.test_tone", "🔊 Test Tone"),
+    ("devices.test_tone_hint", "Plays a short 1kHz tone on this output to check routing"),
+    ("devices.test_tone_disabled", "Stop processing to play a test tone"),
+    ("devices.monitor_on", "Monitor On:"),
+    ("devices.none", "None"),
+    ("devices.monitor_hint", "Also play the processed mic here, e.g. headphones while streaming"),
+    ("devices.virtual_mic_active", "✔ Virtual Mic Active"),
+    ("devices.destroy", "🗑 Remove Virtual Mic"),
+    ("devices.keep_virtual_mic", "Keep after exit"),
+    ("devices.keep_virtual_mic_hint", "Leave the virtual mic loaded when VoidMic quits, so apps keep it selected. It lingers as a silent device until removed."),
+    ("devices.select_in_discord", "ℹ️ Select 'VoidMic_Clean' in Discord"),
+    ("devices.create_virtual_mic", "✨ Create Virtual Mic"),
+    ("devices.create_virtual_mic_hint", "Creates a virtual device for Discord/Zoom"),
+    ("devices.virtual_mic_created", "Virtual Mic Created!"),
+    ("devices.physical_output_warning", "⚠ Output goes to a real device, not the virtual mic"),
+    ("devices.physical_output_hint", "Apps only hear VoidMic through 'VoidMic_Clean'. Playing the processed mic on speakers or headphones is usually a mistake, unless you're monitoring on purpose."),
+    ("devices.use_virtual_mic", "Use VoidMic_Clean"),
+    ("wizard.welcome", "✨ Welcome to VoidMic ✨"),
+    ("wizard.intro", "Let's get your audio set up for crystal clear communication."),
+    ("wizard.explain_ai", "VoidMic uses AI to remove background noise from your microphone."),
+    ("wizard.explain_steps", "This short wizard will help you select your devices and calibrate the noise gate."),
+    ("wizard.get_started", "Get Started ➡"),
+    ("wizard.select_mic", "🎤 Select Microphone"),
+    ("wizard.select_mic_hint", "Choose the microphone you want to clean up:"),
+    ("wizard.next", "Next ➡"),
+    ("wizard.back", "⬅ Back"),
+    ("wizard.select_output", "🔊 Select Output"),
+    ("wizard.select_output_hint", "Choose where you want to hear the processed audio (or your speakers):"),
+    ("wizard.calibration", "🎛️ Calibration"),
+    ("wizard.stay_quiet", "Stay quiet for 3 seconds to measure background noise."),
+    ("wizard.start_engine", "▶ Start Audio Engine"),
+    ("wizard.start_calibration", "🎯 Start Calibration"),
+    ("wizard.calibrating", "Calibrating... stay quiet"),
+    ("wizard.finish", "Finish ✅"),
+    ("wizard.all_set", "🎉 All Set!"),
+    ("wizard.ready", "VoidMic is ready to use."),
+    ("wizard.open_main", "Open Main Interface"),
+    ("advanced.heading", "Advanced Features"),
+    ("advanced.mode", "Advanced mode"),
+    ("advanced.mode_hint", "Show every control inline instead of tucking the rarely-used ones under Show more"),
+    ("advanced.show_more", "Show more"),
+    ("advanced.filter_output", "Filter Output (Speaker Denoising)"),
+    ("advanced.latency_warning", "⚠️ ~100ms latency"),
+    ("advanced.echo_cancel", "Echo Cancellation"),
+    ("advanced.denoise_reference", "Denoise Reference"),
+    ("advanced.denoise_reference_hint", "Runs RNNoise on the speaker monitor before echo cancellation. Helps when the monitored output itself is noisy."),
+    ("advanced.reference_input", "Reference Input (Monitor):"),
+    ("advanced.select_monitor", "ℹ️ Select speaker monitor"),
+    ("advanced.vad_sensitivity", "VAD Sensitivity:"),
+    ("advanced.vad_hint", "Voice Activity Detection - filters non-speech sounds"),
+    ("advanced.vad_source", "VAD Listens To:"),
+    ("advanced.vad_source_hint", "Which input channel the VAD decides on. Only matters with stereo capture; a channel the device doesn't have falls back to the mix."),
+    ("advanced.vad_source_mix", "Mix of all channels"),
+    ("advanced.vad_source_loudest", "Loudest channel"),
+    ("advanced.vad_source_channel", "Channel {n}"),
+    ("advanced.vad_smoothing", "VAD Smoothing:"),
+    ("advanced.vad_smoothing_hint", "Speech is detected once enough of the recent 10 ms frames sound like speech, and held for the hangover after that. Steadies a fluttering gate; 1 of 1 with no hangover turns it off."),
+    ("advanced.vad_window", "Window:"),
+    ("advanced.vad_onset", "Voiced frames needed:"),
+    ("advanced.vad_hangover", "Hangover:"),
+    ("advanced.frames", "× 10 ms"),
+    ("advanced.tone", "Tone:"),
+    ("advanced.bright", "Bright"),
+    ("advanced.warm", "Warm"),
+    ("advanced.tone_hint", "Warm boosts lows and cuts highs; bright does the opposite"),
+    ("advanced.reset", "Reset"),
+    ("advanced.equalizer_3band", "Equalizer (3-Band)"),
+    ("advanced.eq_low", "Low (Bass):"),
+    ("advanced.eq_mid", "Mid (Voice):"),
+    ("advanced.eq_high", "High (Treble):"),
+    ("advanced.eq_auto_gain", "EQ Auto-Gain"),
+    ("advanced.eq_auto_gain_hint", "Trims the output so boosting or cutting bands keeps overall loudness steady"),
+    ("advanced.compressor", "Compressor"),
+    ("advanced.compressor_hint", "Evens out loud and soft syllables for a steady, broadcast-style voice (runs after the EQ, before AGC)"),
+    ("advanced.threshold", "Threshold:"),
+    ("advanced.ratio", "Ratio:"),
+    ("advanced.attack", "Attack:"),
+    ("advanced.release", "Release:"),
+    ("advanced.ceiling", "Ceiling:"),
+    ("advanced.agc_hold", "Hold gain while gated"),
+    ("advanced.agc_hold_hint", "Freezes the AGC while the gate is closed, so it doesn't turn up the silence and blast your next word"),
+    ("advanced.ceiling_hint", "Peak level the AGC output never exceeds; -1 dB leaves headroom for lossy codecs"),
+    ("advanced.makeup", "Makeup:"),
+    ("advanced.agc", "Automatic Gain Control (AGC)"),
+    ("advanced.agc_hint", "Normalizes volume to prevent clipping and boost quiet speech"),
+    ("advanced.bypassed", "🔴 BYPASSED (Raw Audio)"),
+    ("advanced.processing_active", "🟢 Processing Active"),
+    ("advanced.spectrum", "📊 Spectrum"),
+    ("advanced.waveform", "〰 Waveform"),
+    ("advanced.waveform_hint", "Oscilloscope view of input (red) vs. output (green)"),
+    ("advanced.meter", "Meter:"),
+    ("advanced.meter_ppm", "PPM"),
+    ("advanced.meter_vu", "VU"),
+    ("advanced.meter_instant", "Instant"),
+    ("advanced.meter_hint", "How the level meters move: PPM rises fast and falls slowly, VU averages over 300 ms, Instant shows the raw level"),
+    ("advanced.underrun", "On Dropout:"),
+    ("advanced.underrun_silence", "Silence"),
+    ("advanced.underrun_hold", "Hold"),
+    ("advanced.underrun_fade", "Fade"),
+    ("advanced.underrun_hint", "What the output plays when processing falls behind. Silence can click, Hold repeats the last sample and Fade lets it die away over a few milliseconds"),
+    ("advanced.log_freq", "Log Freq"),
+    ("advanced.log_freq_hint", "Log-scaled frequency axis (20Hz - 20kHz)"),
+    ("advanced.db_hint", "Show magnitudes in decibels"),
+    ("advanced.db_floor_hint", "Bottom of the dB scale"),
+    ("advanced.peak_hold", "Peak Hold"),
+    ("advanced.peak_hold_hint", "Keep a slowly falling trace of recent output peaks"),
+    ("advanced.gate_activity", "🚪 Gate Activity (last 10s)"),
+    ("advanced.gate_activity_hint", "Level vs. threshold (dashed); green where the gate was open"),
+    ("advanced.gate_log", "Log gate events"),
+    ("advanced.latency_health", "Latency Health:"),
+    ("advanced.jitter_hint", "< 1ms = excellent | 1-5ms = acceptable | > 5ms = may cause audio glitches"),
+    ("advanced.latency_hint", "Estimated mic-to-output delay: input device buffer + 10 ms processing frame + queued output + output device buffer"),
+    ("advanced.estimated_latency", "Estimated latency: up to {max} ms"),
+    ("advanced.estimated_latency_hint", "Worst case from the buffer sizes, once both fill up: {buffer} ms input buffer + 10 ms processing frame + {buffer} ms output buffer"),
+    ("advanced.process_time_hint", "Average CPU time to process one 10 ms frame. Anything near 10000 µs can't keep up. The spectrum is skipped while the window is hidden, minimized or compact"),
+    ("advanced.gate_open_hint", "0 keeps the gate open (denoise only)"),
+    ("advanced.equalizer", "Equalizer"),
+    ("advanced.bypass_output_filter", "Bypass Output Filter"),
+    ("app.status", "Status: {status}"),
+    ("app.audio_reset", "Audio reset. {status}"),
+    ("status.ready", "Ready"),
+    ("status.initializing", "Initializing Hybrid Engine..."),
+    ("status.active", "Active (RNNoise + Gate)"),
+    ("status.active_without_aec", "Active (echo cancellation off: {error})"),
+    ("status.active_filter_error", "Active (output filter error: {error})"),
+    ("status.paused_idle", "Paused (idle)"),
+    ("status.no_device", "Error: No audio device found. Check your system settings."),
+    ("status.device_not_found", "Error: Selected device not found. Try refreshing or selecting another device."),
+    ("status.permission_denied", "Error: Permission denied. Check audio device permissions."),
+    ("status.device_busy", "Error: Device is busy. Close other audio applications."),
+    ("status.error", "Error: {error}"),
+    ("status.virtual_sink_warning", "Virtual sink warning: {error}"),
+    ("status.echo_cancel_error", "Echo cancellation error: {error}"),
+    ("status.echo_cancel_off", "Echo cancellation off: {error}"),
+    ("status.output_filter_error", "Output filter error: {error}"),
+    ("status.update_check_failed", "Update check failed: {error}"),
+    ("status.autostart_error", "Autostart error: {error}"),
+    ("status.test_tone_error", "Test tone error: {error}"),
+    ("status.create_sink_failed", "Failed to create sink: {error}"),
+    ("status.virtual_cable_error", "Virtual Cable Error: {error}"),
+    ("status.virtual_cable_created", "Virtual sink 'VoidMic_Clean' created! Select 'Monitor of VoidMic_Clean' in your apps."),
+    ("status.opening_vb_cable", "Opening VB-Cable download page..."),
+    ("status.opening_blackhole", "Opening BlackHole download page..."),
+    ("status.browser_failed", "Failed to open browser: {error}"),
+    ("status.unsupported_platform", "Unsupported platform"),
+    ("app.uptime", "Uptime: {uptime} · {frames} frames processed"),
+    ("app.update_available", "🎉 Update available: {version}"),
+    ("app.connected_apps", "📱 Connected Apps ({count})"),
+    ("app.calibrated", "Calibrated! Threshold {threshold}, input gain {gain} dB"),
+    ("controls.custom", "Custom"),
+    ("controls.edited", "{name} (edited)"),
+    ("controls.while_speaking", "{pct}% while speaking"),
+    ("tray.level", "{state}, {db} dB"),
+    ("advanced.gate_log_hint", "Writes each gate open/close with its time, level and threshold to {path}, to see whether the gate chatters around the threshold. Restarts processing."),
+    ("offline.not_wav", "{name} is not a WAV file"),
+    ("offline.failed", "Couldn't process {name}: {error}"),
+    ("offline.done", "{name} is ready."),
+    ("offline.saved", "Saved {path}"),
+    ("offline.drop_hint", "Drop a WAV file to denoise it"),
+    ("offline.processing_title", "Denoising recording"),
+    ("offline.done_title", "Recording denoised"),
+    ("offline.save_as", "Save as:"),
+    ("offline.save", "Save"),
+    ("offline.discard", "Discard"),
+    ("offline.busy", "Finish the current recording first"),
+    ("offline.error_title", "Can't process file"),
+    ("offline.ok", "OK"),
+    ("record.start", "⏺ Record"),
+    ("record.stop", "⏹ Stop"),
+    ("record.hint", "Save the processed mic to a WAV file while you use it"),
+    ("record.path_hint", "WAV file (empty: new file in your Music folder)"),
+    ("record.progress", "Recording {duration} · {size} MB"),
+    ("record.saved", "Recording saved: {path} ({duration})"),
+    ("record.failed", "Recording failed: {error}"),
+    ("tray.active", "VoidMic - Active"),
+    ("tray.bypassed", "VoidMic - Bypassed"),
+    ("tray.disabled", "VoidMic - Disabled"),
+    ("tray.enable", "Enable"),
+    ("tray.show_hide", "Show/Hide"),
+    ("tray.quit", "Quit"),
+];
+
+// Keys missing here (e.g. terms German uses unchanged) fall back to English
+const GERMAN: &[(&str, &str)] = &[
+    ("app.subtitle", "Hybride Rauschunterdrückung"),
+    ("app.expand", "Vergrößern"),
+    ("app.compact_mode", "Kompaktmodus"),
+    ("app.dismiss", "Ausblenden"),
+    ("app.engine", "VoidMic-Engine"),
+    ("app.bypass_hint", "Zwischen bearbeitetem und rohem Mikrofonton wechseln (B)"),
+    ("app.active", "Aktiv"),
+    ("app.inactive", "Inaktiv"),
+    ("app.stopped", "Gestoppt"),
+    ("app.processing", "Verarbeitung"),
+    ("app.stop_engine", "ENGINE STOPPEN"),
+    ("app.activate", "VOIDMIC AKTIVIEREN"),
+    ("app.reset_audio", "🔄 Audio zurücksetzen"),
+    ("app.reset_audio_hint", "Aussetzer, hängt oder Gerät verloren? Schließt alle Audiostreams, sucht Geräte neu und startet mit deinen aktuellen Einstellungen neu"),
+    ("app.shortcuts_hint", "Tastenkürzel: Leertaste Start/Stopp · B Bypass · M Kompaktmodus · C Kalibrieren"),
+    ("app.resetting_audio", "Audio wird zurückgesetzt..."),
+    ("app.frames_hint", "Eine stetig steigende Frame-Zahl bestätigt, dass Audio fließt"),
+    ("app.install_cable", "🛠️ Virtuelles Kabel installieren"),
+    ("app.start_on_boot", "Beim Systemstart starten"),
+    ("app.autostart_enabled", "Autostart aktiviert"),
+    ("app.autostart_disabled", "Autostart deaktiviert"),
+    ("app.start_minimized", "Minimiert im Infobereich starten"),
+    ("app.auto_start", "Verarbeitung automatisch starten"),
+    ("app.pause_when_idle", "Pausieren, wenn verborgen & ungenutzt"),
+    ("app.pause_when_idle_hint", "Hält die Verarbeitung an, solange das Fenster verborgen ist und keine App das virtuelle Mikrofon nutzt"),
+    ("app.silence_action", "Bei Stille:"),
+    ("app.silence_action_hint", "Nach so langer Zeit, in der das Mikrofon Ton sendet, aber niemand spricht (vergessen stummzuschalten?)"),
+    ("app.silence_off", "Nichts tun"),
+    ("app.silence_notify", "Warnen"),
+    ("app.silence_mute", "Stummschalten"),
+    ("app.silence_warning", "⚠ Seit {minutes} min keine Sprache, aber das Mikrofon sendet Ton. Vergessen stummzuschalten?"),
+    ("app.silence_muted", "🔇 Nach {minutes} min ohne Sprache stummgeschaltet. Sprich, um die Stummschaltung aufzuheben."),
+    ("app.mute", "Stummschalten"),
+    ("app.unmute", "Stummschaltung aufheben"),
+    ("app.auto_suspend", "DSP bei Stille schlafen legen nach"),
+    ("app.auto_suspend_hint", "Spart CPU in langen Ruhephasen: Ist das Gate so lange geschlossen, werden Rauschunterdrückung/EQ/AGC übersprungen und nur noch der Eingangspegel beobachtet. Sprechen über der Schwelle weckt es innerhalb eines Frames. Nur im Gate-Modus."),
+    ("app.dsp_suspended", "💤 Schläft, bis du sprichst"),
+    ("app.output_level", "Ausg."),
+    ("app.output_level_hint", "Pegel, den das Ausgabegerät tatsächlich abspielt"),
+    ("app.clips", "Übersteuert: {count}"),
+    ("app.clips_hint", "Frames, die seit dem Start der Verarbeitung Vollaussteuerung erreicht haben; senke die Eingangsverstärkung, wenn der Wert weiter steigt"),
+    ("app.clips_reset", "Zurücksetzen"),
+    ("app.audio_backend", "Audio-Backend:"),
+    ("app.backend_unavailable", "{backend} ist nicht verfügbar, Systemstandard wird verwendet"),
+    ("app.backend_unavailable_label", "{backend} (nicht verfügbar)"),
+    ("app.language", "Sprache:"),
+    ("app.follow_system_theme", "Systemdesign folgen"),
+    ("app.dark_mode", "Dunkler Modus"),
+    ("app.check_updates", "Beim Start nach Updates suchen"),
+    ("app.update_channel", "Update-Kanal:"),
+    ("app.check_now", "Jetzt prüfen"),
+    ("app.checking_updates", "Suche nach Updates..."),
+    ("app.up_to_date", "VoidMic ist aktuell"),
+    ("app.global_hotkey", "Globales Tastenkürzel:"),
+    ("app.edit_in_config", "ℹ️ In config.json bearbeiten"),
+    ("app.download", "Herunterladen"),
+    ("app.speak_now", "Jetzt 3 Sekunden lang normal sprechen..."),
+    ("controls.preset", "Voreinstellung:"),
+    ("controls.restore", "Wiederherstellen"),
+    ("controls.restore_preset", "Die eingebauten Werte dieser Voreinstellung wiederherstellen"),
+    ("controls.remember_preset", "Für dieses Mikrofon merken"),
+    ("controls.remember_preset_hint", "Wechselt zu dieser Voreinstellung, sobald dieses Mikrofon gewählt wird. Mikrofone ohne gemerkte Voreinstellung nutzen die globale."),
+    ("controls.auto_gate_hint", "Passt das Gate automatisch an das Umgebungsrauschen an"),
+    ("controls.gate_threshold", "Gate-Schwelle:"),
+    ("controls.calibrate", "🎯 Kalibrieren"),
+    ("controls.calibrating", "Kalibrierung... 3 Sekunden still sein, dann auf Aufforderung sprechen"),
+    ("controls.adaptation", "Anpassung:"),
+    ("controls.adaptation_hint", "Wie schnell Auto-Gate dem Grundrauschen des Raums folgt: Langsam übersteht zeitweiligen Lärm, Schnell passt sich rasch an eine wechselnde Umgebung an"),
+    ("controls.freeze", "❄ Einfrieren"),
+    ("controls.freeze_hint", "Die aktuelle Rauschschätzung festhalten, z. B. nachdem sich Auto-Gate in einem ruhigen Raum eingependelt hat, damit Gespräche in der Nähe das Gate nicht anheben"),
+    ("controls.auto_gate_range", "Bereich:"),
+    ("controls.auto_gate_range_hint", "Niedrigste und höchste Schwelle, die Auto-Gate wählen darf. Für sehr leise Räume das Minimum senken"),
+    ("controls.gate_mode", "Gate-Modus:"),
+    ("controls.gate_mode_hint", "Gate schaltet unterhalb der Schwelle stumm; Expander regelt stattdessen allmählich herunter"),
+    ("controls.fade_curve_hint", "Form der Ausblendung beim Schließen des Gates; Kosinus ist am weichsten"),
+    ("controls.gate_mode_off", "Aus"),
+    ("controls.fade_cosine", "Kosinus"),
+    ("controls.fade_exponential", "Exponentiell"),
+    ("controls.adaptation_slow", "Langsam"),
+    ("controls.adaptation_fast", "Schnell"),
+    ("controls.gate_hold", "Gate-Haltezeit:"),
+    ("controls.gate_hold_hint", "Hält das Gate nach dem Sprechen so lange ganz offen, bevor die Ausblendung beginnt"),
+    ("controls.gate_hysteresis", "Schließen bei:"),
+    ("controls.gate_hysteresis_hint", "Einmal offen, schließt das Gate erst, wenn der Pegel unter Schwelle mal diesen Wert fällt. Niedriger verhindert Flattern bei grenzwertigen Pegeln; 1,00 schließt genau an der Schwelle"),
+    ("controls.gate_resolution", "Gate-Raster:"),
+    ("controls.gate_resolution_hint", "Wie oft das Gate den Pegel prüft. Kürzere Schritte öffnen und schließen näher am Beginn und Ende der Sprache; Rauschunterdrückung und VAD arbeiten weiter in 10-ms-Frames"),
+    ("controls.gate_key", "Gate hört auf:"),
+    ("controls.gate_key_post_denoise", "Nach der Rauschunterdrückung"),
+    ("controls.gate_key_pre_denoise", "Vor der Rauschunterdrückung"),
+    ("controls.gate_key_hint", "Welches Signal Gate und VAD messen. Vor der Rauschunterdrückung öffnet zuverlässiger bei leiser Sprache, die die Unterdrückung ausdünnt, zählt aber auch Hintergrundrauschen zum Pegel"),
+    ("controls.input_gain", "Eingangsverstärkung:"),
+    ("controls.input_gain_hint", "Wird von 🎯 Kalibrieren automatisch gesetzt (auf Aufforderung sprechen)"),
+    ("controls.suppression", "Unterdrückung:"),
+    ("controls.natural_speech", "Natürliche Sprache"),
+    ("controls.natural_speech_hint", "Lockert die Unterdrückung beim Sprechen (weniger roboterhafte Konsonanten) und wendet sie in Pausen voll an"),
+    ("controls.protect_highs", "Höhen schützen"),
+    ("controls.protect_highs_hint", "Lässt alles über 8 kHz ungefiltert, für Musik oder klare Zischlaute"),
+    ("controls.spectral_gate", "Spektrales Gate"),
+    ("controls.spectral_gate_hint", "Dämpft gleichmäßige Störbänder (Brummen, Lüfter) pro Frequenz, während das Sprachband durchkommt. Erhöht die Latenz um 10 ms."),
+    ("controls.dither", "Ausgang dithern"),
+    ("controls.dither_hint", "Fügt unhörbares Rauschen auf 16-Bit-Niveau hinzu, damit leise Ausklänge bei der Wandlung in 16 Bit nicht kratzig werden. Stille bleibt still."),
+    ("devices.microphone", "Mikrofon:"),
+    ("devices.stereo_input", "Stereo"),
+    ("devices.stereo_input_hint", "Das Mikrofon in Stereo öffnen und jede Seite einzeln bearbeiten, mit einer Anzeige pro Kanal, um eine tote oder schwache Seite zu erkennen. Kostet doppelt so viel CPU; die Ausgänge bekommen die Monomischung."),
+    ("devices.output_sink", "Ausgabegerät:"),
+    ("devices.test_tone", "🔊 Testton"),
+    ("devices.test_tone_hint", "Spielt einen kurzen 1-kHz-Ton auf diesem Ausgang, um das Routing zu prüfen"),
+    ("devices.test_tone_disabled", "Verarbeitung stoppen, um einen Testton abzuspielen"),
+    ("devices.monitor_on", "Mithören auf:"),
+    ("devices.none", "Keins"),
+    ("devices.monitor_hint", "Das bearbeitete Mikrofon zusätzlich hier abspielen, z. B. auf Kopfhörern beim Streamen"),
+    ("devices.virtual_mic_active", "✔ Virtuelles Mikrofon aktiv"),
+    ("devices.destroy", "🗑 Virtuelles Mikrofon entfernen"),
+    ("devices.keep_virtual_mic", "Nach dem Beenden behalten"),
+    ("devices.keep_virtual_mic_hint", "Das virtuelle Mikrofon bleibt nach dem Beenden geladen, damit Apps es ausgewählt lassen. Bis zum Entfernen bleibt es als stilles Gerät bestehen."),
+    ("devices.select_in_discord", "ℹ️ 'VoidMic_Clean' in Discord auswählen"),
+    ("devices.create_virtual_mic", "✨ Virtuelles Mikrofon erstellen"),
+    ("devices.create_virtual_mic_hint", "Erstellt ein virtuelles Gerät für Discord/Zoom"),
+    ("devices.virtual_mic_created", "Virtuelles Mikrofon erstellt!"),
+    ("devices.physical_output_warning", "⚠ Ausgabe geht an ein echtes Gerät, nicht an das virtuelle Mikrofon"),
+    ("devices.physical_output_hint", "Apps hören VoidMic nur über 'VoidMic_Clean'. Das bearbeitete Mikrofon auf Lautsprechern oder Kopfhörern abzuspielen ist meist ein Versehen, außer du hörst absichtlich mit."),
+    ("devices.use_virtual_mic", "VoidMic_Clean verwenden"),
+    ("wizard.welcome", "✨ Willkommen bei VoidMic ✨"),
+    ("wizard.intro", "Richten wir dein Audio für glasklare Kommunikation ein."),
+    ("wizard.explain_ai", "VoidMic entfernt mit KI Hintergrundgeräusche aus deinem Mikrofon."),
+    ("wizard.explain_steps", "Dieser kurze Assistent hilft dir, deine Geräte auszuwählen und das Noise-Gate zu kalibrieren."),
+    ("wizard.get_started", "Los geht's ➡"),
+    ("wizard.select_mic", "🎤 Mikrofon auswählen"),
+    ("wizard.select_mic_hint", "Wähle das Mikrofon, das bereinigt werden soll:"),
+    ("wizard.next", "Weiter ➡"),
+    ("wizard.back", "⬅ Zurück"),
+    ("wizard.select_output", "🔊 Ausgabe auswählen"),
+    ("wizard.select_output_hint", "Wähle, wo du das bearbeitete Audio hören möchtest (oder deine Lautsprecher):"),
+    ("wizard.calibration", "🎛️ Kalibrierung"),
+    ("wizard.stay_quiet", "Bleib 3 Sekunden still, um das Hintergrundrauschen zu messen."),
+    ("wizard.start_engine", "▶ Audio-Engine starten"),
+    ("wizard.start_calibration", "🎯 Kalibrierung starten"),
+    ("wizard.calibrating", "Kalibrierung... bitte still sein"),
+    ("wizard.finish", "Fertig ✅"),
+    ("wizard.all_set", "🎉 Alles bereit!"),
+    ("wizard.ready", "VoidMic ist einsatzbereit."),
+    ("wizard.open_main", "Hauptfenster öffnen"),
+    ("advanced.heading", "Erweiterte Funktionen"),
+    ("advanced.mode", "Expertenmodus"),
+    ("advanced.mode_hint", "Alle Regler direkt anzeigen, statt selten genutzte unter Mehr anzeigen einzuklappen"),
+    ("advanced.show_more", "Mehr anzeigen"),
+    ("advanced.filter_output", "Ausgabe filtern (Lautsprecher-Entrauschung)"),
+    ("advanced.latency_warning", "⚠️ ~100 ms Latenz"),
+    ("advanced.echo_cancel", "Echounterdrückung"),
+    ("advanced.denoise_reference", "Referenz entrauschen"),
+    ("advanced.denoise_reference_hint", "Wendet RNNoise vor der Echounterdrückung auf den Lautsprecher-Monitor an. Hilft, wenn die überwachte Ausgabe selbst verrauscht ist."),
+    ("advanced.reference_input", "Referenzeingang (Monitor):"),
+    ("advanced.select_monitor", "ℹ️ Lautsprecher-Monitor auswählen"),
+    ("advanced.vad_sensitivity", "VAD-Empfindlichkeit:"),
+    ("advanced.vad_hint", "Sprachaktivitätserkennung - filtert Geräusche, die keine Sprache sind"),
+    ("advanced.vad_source", "VAD hört auf:"),
+    ("advanced.vad_source_hint", "Auf welchem Eingangskanal die VAD entscheidet. Nur bei Stereo-Aufnahme relevant; fehlt dem Gerät der Kanal, wird die Mischung verwendet."),
+    ("advanced.vad_source_mix", "Mischung aller Kanäle"),
+    ("advanced.vad_source_loudest", "Lautester Kanal"),
+    ("advanced.vad_source_channel", "Kanal {n}"),
+    ("advanced.vad_smoothing", "VAD-Glättung:"),
+    ("advanced.vad_smoothing_hint", "Sprache gilt als erkannt, sobald genug der letzten 10-ms-Frames nach Sprache klingen, und wird danach für die Nachlaufzeit gehalten. Beruhigt ein flatterndes Gate; 1 von 1 ohne Nachlauf schaltet sie ab."),
+    ("advanced.vad_window", "Fenster:"),
+    ("advanced.vad_onset", "Nötige Sprach-Frames:"),
+    ("advanced.vad_hangover", "Nachlauf:"),
+    ("advanced.frames", "× 10 ms"),
+    ("advanced.tone", "Klang:"),
+    ("advanced.bright", "Hell"),
+    ("advanced.tone_hint", "Warm hebt Tiefen an und senkt Höhen; Hell macht das Gegenteil"),
+    ("advanced.reset", "Zurücksetzen"),
+    ("advanced.equalizer_3band", "Equalizer (3 Bänder)"),
+    ("advanced.eq_low", "Tiefen (Bass):"),
+    ("advanced.eq_mid", "Mitten (Stimme):"),
+    ("advanced.eq_high", "Höhen:"),
+    ("advanced.eq_auto_gain", "EQ-Autopegel"),
+    ("advanced.eq_auto_gain_hint", "Gleicht die Ausgabe aus, damit die Gesamtlautstärke beim Anheben oder Absenken von Bändern gleich bleibt"),
+    ("advanced.compressor", "Kompressor"),
+    ("advanced.compressor_hint", "Gleicht laute und leise Silben für eine gleichmäßige Stimme in Radioqualität aus (nach dem EQ, vor der AGC)"),
+    ("advanced.threshold", "Schwelle:"),
+    ("advanced.ratio", "Verhältnis:"),
+    ("advanced.makeup", "Aufholverstärkung:"),
+    ("advanced.agc", "Automatische Pegelregelung (AGC)"),
+    ("advanced.agc_hint", "Normalisiert die Lautstärke, um Übersteuerung zu vermeiden und leise Sprache anzuheben"),
+    ("advanced.ceiling", "Obergrenze:"),
+    ("advanced.agc_hold", "Verstärkung bei geschlossenem Gate halten"),
+    ("advanced.agc_hold_hint", "Friert die AGC ein, solange das Gate geschlossen ist, damit sie nicht die Stille hochregelt und dein nächstes Wort zu laut wird"),
+    ("advanced.ceiling_hint", "Spitzenpegel, den die AGC-Ausgabe nie überschreitet; -1 dB lässt Reserve für verlustbehaftete Codecs"),
+    ("advanced.bypassed", "🔴 UMGANGEN (Rohaudio)"),
+    ("advanced.processing_active", "🟢 Verarbeitung aktiv"),
+    ("advanced.spectrum", "📊 Spektrum"),
+    ("advanced.waveform", "〰 Wellenform"),
+    ("advanced.waveform_hint", "Oszilloskop-Ansicht von Eingang (rot) und Ausgang (grün)"),
+    ("advanced.meter", "Pegelanzeige:"),
+    ("advanced.meter_instant", "Direkt"),
+    ("advanced.meter_hint", "Wie sich die Pegelanzeigen bewegen: PPM steigt schnell und fällt langsam, VU mittelt über 300 ms, Direkt zeigt den Rohpegel"),
+    ("advanced.underrun", "Bei Aussetzern:"),
+    ("advanced.underrun_silence", "Stille"),
+    ("advanced.underrun_hold", "Halten"),
+    ("advanced.underrun_fade", "Ausblenden"),
+    ("advanced.underrun_hint", "Was die Ausgabe spielt, wenn die Verarbeitung nicht nachkommt. Stille kann knacken, Halten wiederholt das letzte Sample und Ausblenden lässt es über wenige Millisekunden ausklingen"),
+    ("advanced.log_freq", "Log. Frequenz"),
+    ("advanced.log_freq_hint", "Logarithmische Frequenzachse (20 Hz - 20 kHz)"),
+    ("advanced.db_hint", "Pegel in Dezibel anzeigen"),
+    ("advanced.db_floor_hint", "Untergrenze der dB-Skala"),
+    ("advanced.peak_hold", "Spitzenwert halten"),
+    ("advanced.peak_hold_hint", "Eine langsam abfallende Spur der letzten Ausgangsspitzen anzeigen"),
+    ("advanced.gate_activity", "🚪 Gate-Aktivität (letzte 10 s)"),
+    ("advanced.gate_activity_hint", "Pegel gegenüber Schwelle (gestrichelt); grün, wo das Gate offen war"),
+    ("advanced.gate_log", "Gate-Ereignisse protokollieren"),
+    ("advanced.latency_health", "Latenzzustand:"),
+    ("advanced.jitter_hint", "< 1 ms = ausgezeichnet | 1-5 ms = akzeptabel | > 5 ms = kann Aussetzer verursachen"),
+    ("advanced.latency_hint", "Geschätzte Verzögerung vom Mikrofon zur Ausgabe: Puffer des Eingabegeräts + 10-ms-Verarbeitungsframe + Ausgabewarteschlange + Puffer des Ausgabegeräts"),
+    ("advanced.estimated_latency", "Geschätzte Latenz: bis zu {max} ms"),
+    ("advanced.estimated_latency_hint", "Schlimmster Fall laut Puffergrößen, wenn beide volllaufen: {buffer} ms Eingangspuffer + 10-ms-Verarbeitungsframe + {buffer} ms Ausgangspuffer"),
+    ("advanced.process_time_hint", "Durchschnittliche CPU-Zeit für einen 10-ms-Frame. Werte nahe 10000 µs kommen nicht mehr hinterher. Das Spektrum wird übersprungen, solange das Fenster versteckt, minimiert oder kompakt ist"),
+    ("advanced.gate_open_hint", "0 hält das Gate offen (nur Entrauschen)"),
+    ("advanced.bypass_output_filter", "Ausgabefilter umgehen"),
+    ("app.audio_reset", "Audio zurückgesetzt. {status}"),
+    ("status.ready", "Bereit"),
+    ("status.initializing", "Hybrid-Engine wird gestartet..."),
+    ("status.active", "Aktiv (RNNoise + Gate)"),
+    ("status.active_without_aec", "Aktiv (Echounterdrückung aus: {error})"),
+    ("status.active_filter_error", "Aktiv (Fehler im Ausgangsfilter: {error})"),
+    ("status.paused_idle", "Pausiert (ungenutzt)"),
+    ("status.no_device", "Fehler: Kein Audiogerät gefunden. Prüfe deine Systemeinstellungen."),
+    ("status.device_not_found", "Fehler: Gewähltes Gerät nicht gefunden. Aktualisiere die Liste oder wähle ein anderes Gerät."),
+    ("status.permission_denied", "Fehler: Zugriff verweigert. Prüfe die Berechtigungen für Audiogeräte."),
+    ("status.device_busy", "Fehler: Gerät ist belegt. Schließe andere Audioanwendungen."),
+    ("status.error", "Fehler: {error}"),
+    ("status.virtual_sink_warning", "Warnung zum virtuellen Ausgang: {error}"),
+    ("status.echo_cancel_error", "Fehler bei der Echounterdrückung: {error}"),
+    ("status.echo_cancel_off", "Echounterdrückung aus: {error}"),
+    ("status.output_filter_error", "Fehler im Ausgangsfilter: {error}"),
+    ("status.update_check_failed", "Suche nach Updates fehlgeschlagen: {error}"),
+    ("status.autostart_error", "Autostart-Fehler: {error}"),
+    ("status.test_tone_error", "Fehler beim Testton: {error}"),
+    ("status.create_sink_failed", "Virtueller Ausgang konnte nicht erstellt werden: {error}"),
+    ("status.virtual_cable_error", "Fehler beim virtuellen Kabel: {error}"),
+    ("status.virtual_cable_created", "Virtueller Ausgang 'VoidMic_Clean' erstellt! Wähle 'Monitor of VoidMic_Clean' in deinen Apps."),
+    ("status.opening_vb_cable", "Downloadseite von VB-Cable wird geöffnet..."),
+    ("status.opening_blackhole", "Downloadseite von BlackHole wird geöffnet..."),
+    ("status.browser_failed", "Browser konnte nicht geöffnet werden: {error}"),
+    ("status.unsupported_platform", "Nicht unterstützte Plattform"),
+    ("app.uptime", "Laufzeit: {uptime} · {frames} Frames verarbeitet"),
+    ("app.update_available", "🎉 Update verfügbar: {version}"),
+    ("app.connected_apps", "📱 Verbundene Apps ({count})"),
+    ("app.calibrated", "Kalibriert! Schwelle {threshold}, Eingangsverstärkung {gain} dB"),
+    ("controls.custom", "Benutzerdefiniert"),
+    ("controls.edited", "{name} (bearbeitet)"),
+    ("controls.while_speaking", "{pct}% beim Sprechen"),
+    ("tray.level", "{state}, {db} dB"),
+    ("advanced.gate_log_hint", "Schreibt jedes Öffnen/Schließen des Gates mit Zeit, Pegel und Schwelle nach {path}, um zu sehen, ob das Gate um die Schwelle flattert. Startet die Verarbeitung neu."),
+    ("offline.not_wav", "{name} ist keine WAV-Datei"),
+    ("offline.failed", "{name} konnte nicht verarbeitet werden: {error}"),
+    ("offline.done", "{name} ist fertig."),
+    ("offline.saved", "{path} gespeichert"),
+    ("offline.drop_hint", "WAV-Datei hier ablegen, um sie zu entrauschen"),
+    ("offline.processing_title", "Aufnahme wird entrauscht"),
+    ("offline.done_title", "Aufnahme entrauscht"),
+    ("offline.save_as", "Speichern unter:"),
+    ("offline.save", "Speichern"),
+    ("offline.discard", "Verwerfen"),
+    ("offline.busy", "Bitte zuerst die aktuelle Aufnahme abschließen"),
+    ("offline.error_title", "Datei kann nicht verarbeitet werden"),
+    ("record.start", "⏺ Aufnehmen"),
+    ("record.stop", "⏹ Stopp"),
+    ("record.hint", "Speichert das bereinigte Mikrofon als WAV-Datei, während du es benutzt"),
+    ("record.path_hint", "WAV-Datei (leer: neue Datei in deinem Musik-Ordner)"),
+    ("record.progress", "Aufnahme {duration} · {size} MB"),
+    ("record.saved", "Aufnahme gespeichert: {path} ({duration})"),
+    ("record.failed", "Aufnahme fehlgeschlagen: {error}"),
+    ("tray.active", "VoidMic - Aktiv"),
+    ("tray.bypassed", "VoidMic - Umgangen"),
+    ("tray.disabled", "VoidMic - Deaktiviert"),
+    ("tray.enable", "Aktivieren"),
+    ("tray.show_hide", "Anzeigen/Verbergen"),
+    ("tray.quit", "Beenden"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_key_falls_back_to_english() {
+        assert!(find(GERMAN, "advanced.attack").is_none());
+        assert_eq!(lookup(Language::German, "advanced.attack"), "Attack:");
+        assert_eq!(lookup(Language::German, "app.dark_mode"), "Dunkler Modus");
+        // Unknown keys show up as themselves rather than blank
+        assert_eq!(lookup(Language::German, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_translations_match_english_keys() {
+        for (key, _) in GERMAN {
+            assert!(
+                find(ENGLISH, key).is_some(),
+                "German key {} has no English text",
+                key
+            );
+        }
+        for (i, (key, _)) in ENGLISH.iter().enumerate() {
+            assert!(find(&ENGLISH[..i], key).is_none(), "Duplicate key {}", key);
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_filled() {
+        set_language(Language::English);
+        let text = tr!("app.uptime", uptime = "1m 05s", frames = 6500);
+        assert_eq!(text, "Uptime: 1m 05s · 6500 frames processed");
+        assert_eq!(Language::from_u32(99), Language::English);
+    }
+}
@@ -1,17 +1,30 @@
+use crate::silence_watch::{silence_limit, SilenceAction, SilenceEvent, SilenceWatch};
 use anyhow::{anyhow, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use cpal::traits::{DeviceTrait, HostTrait};
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
 
+// First, so `tr!` is visible to the modules below
+#[macro_use]
+mod i18n;
 mod audio;
 mod autostart;
 mod config;
 mod daemon;
+mod doctor;
+mod gate_log;
 #[cfg(feature = "gui")]
 mod gui;
+mod metrics;
+#[cfg(feature = "gui")]
+mod offline;
 mod pulse_info;
+#[cfg(feature = "gui")]
+mod recorder;
+mod silence_watch;
 mod updater;
 mod virtual_device;
 
@@ -19,6 +32,13 @@ mod virtual_device;
 #[command(name = "voidmic")]
 #[command(about = "VoidMic: Hybrid AI noise reduction", long_about = None)]
 struct Cli {
+    /// Audio host to use (`jack` falls back to the default host if unavailable)
+    #[arg(long, global = true, value_enum, default_value_t = audio::AudioBackend::Default)]
+    backend: audio::AudioBackend,
+    /// Read and save the config here instead of the user config directory (portable
+    /// installs, separate instances); also `VOIDMIC_CONFIG_DIR`
+    #[arg(long, global = true)]
+    config_dir: Option<std::path::PathBuf>,
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -31,16 +51,38 @@ enum Commands {
     Run {
         #[arg(short, long, default_value = "default")]
         input: String,
+        /// Output device; separate several with commas to send to all of them
         #[arg(short, long, default_value = "default")]
         output: String,
+        /// Write gate open/close events (time, RMS, threshold) to this CSV file
+        #[arg(long)]
+        gate_log: Option<std::path::PathBuf>,
+        /// Measure the room for 3 seconds (stay quiet), then use and save the suggested
+        /// gate threshold
+        #[arg(long)]
+        calibrate: bool,
     },
     /// Load VoidMic: create virtual sink and start processing (daemonize)
     Load {
         #[arg(short, long, default_value = "default")]
         input: String,
+        /// Also play the clean mic on this sink (e.g. headphones) via a combined sink
+        #[arg(short, long)]
+        monitor: Option<String>,
+    },
+    /// Unload VoidMic: stop processing and destroy the virtual sink (unless it is kept
+    /// with `persist_virtual_sink`)
+    Unload {
+        /// Remove the virtual sink even if `persist_virtual_sink` is set
+        #[arg(long)]
+        remove_sink: bool,
     },
-    /// Unload VoidMic: destroy virtual sink
-    Unload,
+    /// Show whether a `run`/`load` engine is running, its uptime, frames processed and
+    /// gate threshold
+    Status,
+    /// Check the sound server, devices, sample rate, virtual sink and config, with
+    /// hints for fixing what fails
+    Doctor,
     #[cfg(feature = "gui")]
     /// Launch the graphical interface
     Gui,
@@ -49,29 +91,76 @@ enum Commands {
 fn main() -> Result<()> {
     env_logger::init();
     let cli = Cli::parse();
+    config::set_config_dir(cli.config_dir.clone());
 
     match cli.command {
         Some(Commands::List) => {
-            list_devices()?;
+            list_devices(cli.backend)?;
         }
-        Some(Commands::Run { input, output }) => {
-            let _engine = audio::AudioEngine::start(
+        Some(Commands::Run {
+            input,
+            output,
+            gate_log,
+            calibrate,
+        }) => {
+            // Precedence: VOIDMIC_* env > config file > default
+            let mut config = config::AppConfig::load();
+            config.apply_env_overrides()?;
+            if calibrate && config.dynamic_threshold_enabled {
+                return Err(anyhow!(
+                    "Calibration sets a fixed threshold; turn off Auto-Gate (VOIDMIC_AUTO_GATE=off)"
+                ));
+            }
+            let gate_events = gate_log.as_deref().map(gate_log::start).transpose()?;
+            let outputs: Vec<String> = audio::split_device_list(&output)
+                .into_iter()
+                .map(String::from)
+                .collect();
+            let engine = audio::AudioEngine::start(
+                cli.backend,
                 &input,
-                &output,
-                0.015,
-                1.0,
-                false,
+                &outputs,
+                config.gate_threshold,
+                config.suppression_strength,
+                false, // No reference device in CLI mode
                 None,
-                false,
-                2,               // Default VAD sensitivity (Aggressive)
-                false,           // Default EQ disabled
-                (0.0, 0.0, 0.0), // Default EQ gains
-                false,           // AGC Disabled for CLI
-                0.7,             // AGC Target
-                false,           // Bypass Disabled
-                None,            // No spectrum visualizer in CLI mode
+                config.dynamic_threshold_enabled,
+                config.vad_sensitivity,
+                config.eq_enabled,
+                (config.eq_low_gain, config.eq_mid_gain, config.eq_high_gain),
+                config.agc_enabled,
+                config.agc_target_level,
+                false, // Bypass Disabled
+                config.stereo_input,
+                None, // No spectrum visualizer in CLI mode
+                None, // ...nor waveform view
+                gate_events,
             )?;
+            // The rest of the saved settings, as the GUI applies them
+            engine
+                .underrun_fill
+                .store(config.underrun_fill, Ordering::Relaxed);
+            config.apply_to(&engine.controls);
+            // Nothing to draw: skip the visualizer work outright
+            engine
+                .controls
+                .spectrum_enabled
+                .store(false, Ordering::Relaxed);
+            let _metrics = match config.metrics_port {
+                Some(port) => {
+                    let server = metrics::MetricsServer::start(port)?;
+                    server.set_source(Some(metrics::MetricsSource::from_engine(&engine)));
+                    Some(server)
+                }
+                None => None,
+            };
             println!("VoidMic Active (Hybrid). Press Ctrl+C to stop.");
+            if calibrate {
+                engine.calibration_mode.store(true, Ordering::Relaxed);
+                println!("Calibrating: stay quiet for 3 seconds...");
+            }
+            let mut calibrating = calibrate;
+            let mut calibrated_threshold = None;
 
             // Graceful shutdown handling
             let running = Arc::new(AtomicBool::new(true));
@@ -82,35 +171,120 @@ fn main() -> Result<()> {
                 r.store(false, Ordering::Relaxed);
             })?;
 
+            // Publish status for `voidmic status` while running
+            let started_at = daemon::unix_secs(engine.started_at);
+            let status_every = daemon::STATUS_INTERVAL_SECS * 10; // Loop ticks are 100ms
+            let mut ticks = 0u64;
+            // "Forgot to mute" watch; with no banner to click, Notify only prints
+            let silence_action = config.silence_action;
+            let mut silence_watch = (silence_action != SilenceAction::Off)
+                .then(|| SilenceWatch::new(silence_limit(config.silence_minutes)));
             while running.load(Ordering::Relaxed) {
+                // Same as the GUI: the processor clears the flag once it has a result
+                if calibrating && !engine.calibration_mode.load(Ordering::Relaxed) {
+                    calibrating = false;
+                    let result = f32::from_bits(engine.calibration_result.load(Ordering::Relaxed));
+                    engine
+                        .controls
+                        .gate_threshold
+                        .store(result.to_bits(), Ordering::Relaxed);
+                    // Saved without this run's env overrides
+                    let mut saved = config::AppConfig::load();
+                    saved.gate_threshold = result;
+                    saved.save();
+                    println!("✓ Calibrated: gate threshold {:.4} (saved)", result);
+                    calibrated_threshold = Some(result);
+                }
+                if ticks.is_multiple_of(status_every) {
+                    let status = daemon::DaemonStatus {
+                        pid: std::process::id(),
+                        started_at,
+                        updated_at: daemon::unix_now(),
+                        frames_processed: engine.frames_processed.load(Ordering::Relaxed),
+                        threshold: f32::from_bits(
+                            engine.effective_threshold.load(Ordering::Relaxed),
+                        ),
+                        noise_floor: f32::from_bits(engine.noise_floor.load(Ordering::Relaxed)),
+                        calibrated_threshold,
+                    };
+                    if let Err(e) = daemon::write_status_file(&status) {
+                        log::warn!("{}", e);
+                    }
+                }
+                match silence_watch.as_mut().and_then(|watch| watch.poll(&engine)) {
+                    Some(SilenceEvent::Silent) => {
+                        let minutes = config.silence_minutes;
+                        if silence_action == SilenceAction::Mute {
+                            engine.controls.muted.store(true, Ordering::Relaxed);
+                            println!("No speech for {} min: muted until you speak again", minutes);
+                        } else {
+                            println!(
+                                "No speech for {} min while sending sound. Forgot to mute?",
+                                minutes
+                            );
+                        }
+                    }
+                    Some(SilenceEvent::Speech) => {
+                        if engine.controls.muted.swap(false, Ordering::Relaxed) {
+                            println!("Speech detected, unmuted");
+                        }
+                    }
+                    None => {}
+                }
+                ticks += 1;
                 std::thread::sleep(std::time::Duration::from_millis(100));
             }
 
+            let _ = daemon::remove_status_file();
             println!("VoidMic stopped.");
         }
-        Some(Commands::Load { input }) => {
+        Some(Commands::Load { input, monitor }) => {
             // NoiseTorch-like workflow: create virtual sink, start processing, daemonize
             #[cfg(target_os = "linux")]
             {
                 use std::process::Command;
 
-                // Create virtual sink
+                // Create virtual sink (or reuse one kept from an earlier run)
                 match virtual_device::create_virtual_sink() {
                     Ok(device) => {
                         println!(
-                            "✓ Virtual sink '{}' created",
-                            virtual_device::VIRTUAL_SINK_NAME
+                            "✓ Virtual sink '{}' {}",
+                            virtual_device::VIRTUAL_SINK_NAME,
+                            if device.module_id == 0 {
+                                "reused"
+                            } else {
+                                "created"
+                            }
                         );
 
                         // Get the monitor source name (this is what apps should use as input)
-                        let monitor = virtual_device::get_monitor_source_name();
+                        let monitor_source = virtual_device::get_monitor_source_name();
 
                         // Spawn background process
                         let exe = std::env::current_exe()?;
-                        let output_sink = virtual_device::VIRTUAL_SINK_NAME.to_string();
+                        let mut output_sink = virtual_device::VIRTUAL_SINK_NAME.to_string();
+
+                        // Fan out to the monitor sink through a combined sink
+                        if let Some(monitor_sink) = &monitor {
+                            let slaves = [virtual_device::VIRTUAL_SINK_NAME, monitor_sink.as_str()];
+                            match virtual_device::create_combine_sink(&slaves) {
+                                Ok(combined) => {
+                                    println!("✓ Monitoring on '{}'", monitor_sink);
+                                    output_sink = combined.sink_name;
+                                }
+                                Err(e) => eprintln!("Warning: monitor disabled: {}", e),
+                            }
+                        }
 
-                        let child = Command::new(&exe)
-                            .args(["run", "-i", &input, "-o", &output_sink])
+                        let backend = cli.backend.to_possible_value().expect("no skipped values");
+                        // `run` stays first so `voidmic unload` can still find it with pgrep
+                        let mut command = Command::new(&exe);
+                        command.args(["run", "--backend", backend.get_name()]);
+                        if let Some(dir) = config::custom_config_dir() {
+                            command.arg("--config-dir").arg(dir);
+                        }
+                        let child = command
+                            .args(["-i", &input, "-o", &output_sink])
                             .stdin(std::process::Stdio::null())
                             .stdout(std::process::Stdio::null())
                             .stderr(std::process::Stdio::null())
@@ -126,14 +300,17 @@ fn main() -> Result<()> {
                                 println!("✓ VoidMic started in background (PID: {})", child_pid);
                                 println!(
                                     "\n📢 Select '{}' as your microphone in applications",
-                                    monitor
+                                    monitor_source
                                 );
                                 println!("\nTo stop: voidmic unload");
                             }
                             Err(e) => {
                                 eprintln!("Failed to start background process: {}", e);
-                                // Cleanup sink
-                                let _ = virtual_device::destroy_virtual_sink(device.module_id);
+                                // Cleanup sinks (a reused sink predates this run, so it stays)
+                                let _ = virtual_device::destroy_combine_sink();
+                                if device.module_id != 0 {
+                                    let _ = virtual_device::destroy_virtual_sink(device.module_id);
+                                }
                             }
                         }
                     }
@@ -146,37 +323,97 @@ fn main() -> Result<()> {
 
             #[cfg(not(target_os = "linux"))]
             {
-                let _ = input;
+                let _ = (input, monitor);
                 println!("Load mode is only supported on Linux.");
                 println!("Use 'voidmic run' on other platforms.");
             }
         }
-        Some(Commands::Unload) => {
+        Some(Commands::Unload { remove_sink }) => {
             #[cfg(target_os = "linux")]
             {
                 // Try graceful shutdown using PID file first
                 match daemon::stop_daemon() {
                     Ok(_) => println!("✓ Daemon stopped gracefully"),
-                    Err(_) => {
-                        // Fallback: Kill any running voidmic processes
-                        let _ = std::process::Command::new("pkill")
-                            .args(["-f", "voidmic run"])
-                            .output();
-                    }
+                    // Fallback: kill this instance's `voidmic run`, leaving other
+                    // `--config-dir` instances alone
+                    Err(_) => daemon::kill_instance_runs(),
                 }
 
-                // Destroy virtual sink
-                match virtual_device::destroy_virtual_sink(0) {
-                    Ok(_) => println!("✓ VoidMic unloaded"),
-                    Err(e) => eprintln!("Warning: {}", e),
+                let _ = daemon::remove_status_file();
+
+                // Destroy the combined (monitor) sink first, it feeds into the virtual sink
+                let _ = virtual_device::destroy_combine_sink();
+
+                // Destroy virtual sink, unless it is kept so apps don't lose it
+                if config::AppConfig::load().persist_virtual_sink && !remove_sink {
+                    println!("✓ VoidMic unloaded");
+                    println!(
+                        "Virtual sink '{}' kept (persist_virtual_sink)",
+                        virtual_device::VIRTUAL_SINK_NAME
+                    );
+                    println!("To remove it: voidmic unload --remove-sink");
+                } else {
+                    match virtual_device::destroy_virtual_sink(0) {
+                        Ok(_) => println!("✓ VoidMic unloaded"),
+                        Err(e) => eprintln!("Warning: {}", e),
+                    }
                 }
             }
 
             #[cfg(not(target_os = "linux"))]
             {
+                let _ = remove_sink;
                 println!("Unload mode is only supported on Linux.");
             }
         }
+        Some(Commands::Status) => {
+            let now = daemon::unix_now();
+            match daemon::read_status_file() {
+                Some(status) if status.is_fresh(now) => {
+                    let audio_secs =
+                        status.frames_processed * FRAME_SIZE as u64 / SAMPLE_RATE as u64;
+                    println!("✓ VoidMic running (PID: {})", status.pid);
+                    println!(
+                        "  Uptime: {}",
+                        daemon::format_duration(status.uptime_secs(now))
+                    );
+                    println!(
+                        "  Frames processed: {} ({} of audio)",
+                        status.frames_processed,
+                        daemon::format_duration(audio_secs)
+                    );
+                    if status.noise_floor > 0.0 {
+                        println!(
+                            "  Gate threshold: {:.4} (Auto-Gate, noise floor {:.4})",
+                            status.threshold, status.noise_floor
+                        );
+                    } else {
+                        println!("  Gate threshold: {:.4}", status.threshold);
+                    }
+                    if let Some(threshold) = status.calibrated_threshold {
+                        println!("  Calibrated threshold: {:.4}", threshold);
+                    }
+                }
+                Some(status) => {
+                    println!(
+                        "VoidMic is not running (last seen {} ago, PID: {})",
+                        daemon::format_duration(now.saturating_sub(status.updated_at)),
+                        status.pid
+                    );
+                }
+                None => println!("VoidMic is not running"),
+            }
+        }
+        Some(Commands::Doctor) => {
+            let checks = doctor::run_checks(cli.backend);
+            println!("{}", doctor::format_report(&checks));
+            if checks
+                .iter()
+                .any(|check| check.status == doctor::CheckStatus::Fail)
+            {
+                return Err(anyhow!("Some checks failed, see the hints above"));
+            }
+        }
         #[cfg(feature = "gui")]
         Some(Commands::Gui) => {
             gui::run_gui().map_err(|e| anyhow!("GUI Error: {}", e))?;
@@ -195,9 +432,15 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn list_devices() -> Result<()> {
-    let host = cpal::default_host();
+fn list_devices(backend: audio::AudioBackend) -> Result<()> {
+    let host = audio::audio_host(backend);
     println!("Audio Host: {}", host.id().name());
+    let available: Vec<String> = audio::AudioBackend::available()
+        .into_iter()
+        .filter_map(|b| b.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    println!("Available backends: {}", available.join(", "));
     println!("\nInput Devices:");
     for device in host.input_devices()? {
         println!("  - {}", device.name().unwrap_or("Unknown".to_string()));
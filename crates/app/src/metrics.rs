@@ -0,0 +1,337 @@
+//! Optional metrics endpoint for monitoring a running engine (Prometheus, Grafana).
+//!
+//! Serves `/metrics` (Prometheus text format) and `/metrics.json` on 127.0.0.1 only.
+//! Off unless `metrics_port` is set, and the HTTP server is only compiled in with the
+//! `metrics` feature. Readings come straight from the engine's atomics.
+
+// Without the feature only `start` (which refuses) and `set_source` are reachable
+#![cfg_attr(not(feature = "metrics"), allow(dead_code))]
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
+
+use crate::audio::AudioEngine;
+
+// Time budget for processing one frame, in µs
+const FRAME_BUDGET_US: f32 = FRAME_SIZE as f32 * 1_000_000.0 / SAMPLE_RATE as f32;
+
+/// The engine atomics the endpoint reads.
+#[derive(Clone)]
+pub struct MetricsSource {
+    volume_level: Arc<AtomicU32>,
+    output_peak: Arc<AtomicU32>,
+    gate_is_open: Arc<AtomicBool>,
+    jitter_ewma_us: Arc<AtomicU32>,
+    process_time_us: Arc<AtomicU32>,
+    dropped_frames: Arc<AtomicU64>,
+    frames_processed: Arc<AtomicU64>,
+}
+
+impl MetricsSource {
+    pub fn from_engine(engine: &AudioEngine) -> Self {
+        Self {
+            volume_level: engine.volume_level.clone(),
+            output_peak: engine.output_peak.clone(),
+            gate_is_open: engine.gate_is_open.clone(),
+            jitter_ewma_us: engine.jitter_ewma_us.clone(),
+            process_time_us: engine.process_time_us.clone(),
+            dropped_frames: engine.dropped_frames.clone(),
+            frames_processed: engine.frames_processed.clone(),
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let process_time = self.process_time_us.load(Ordering::Relaxed) as f32;
+        MetricsSnapshot {
+            running: true,
+            rms: f32::from_bits(self.volume_level.load(Ordering::Relaxed)),
+            peak: f32::from_bits(self.output_peak.load(Ordering::Relaxed)),
+            gate_open: self.gate_is_open.load(Ordering::Relaxed),
+            jitter_us: self.jitter_ewma_us.load(Ordering::Relaxed),
+            dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            cpu_load: process_time / FRAME_BUDGET_US,
+        }
+    }
+}
+
+/// One reading of every metric; all zero (and `running` false) while no engine runs.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub running: bool,
+    /// Processed level (RMS, linear)
+    pub rms: f32,
+    /// Largest output sample of the last frame (linear)
+    pub peak: f32,
+    pub gate_open: bool,
+    pub jitter_us: u32,
+    /// Frames that didn't fit into the output buffer since the engine started
+    pub dropped_frames: u64,
+    pub frames_processed: u64,
+    /// Processing time as a fraction of the 10 ms a frame may take
+    pub cpu_load: f32,
+}
+
+impl MetricsSnapshot {
+    /// Prometheus text exposition format.
+    pub fn to_prometheus(self) -> String {
+        let flag = |on: bool| if on { 1.0 } else { 0.0 };
+        let metrics: [(&str, &str, &str, f64); 8] = [
+            (
+                "voidmic_up",
+                "gauge",
+                "Whether the audio engine is running",
+                flag(self.running),
+            ),
+            (
+                "voidmic_level_rms",
+                "gauge",
+                "Processed level (RMS, linear)",
+                self.rms as f64,
+            ),
+            (
+                "voidmic_output_peak",
+                "gauge",
+                "Peak output sample of the last frame",
+                self.peak as f64,
+            ),
+            (
+                "voidmic_gate_open",
+                "gauge",
+                "Whether the noise gate is open",
+                flag(self.gate_open),
+            ),
+            (
+                "voidmic_jitter_microseconds",
+                "gauge",
+                "Processing loop jitter",
+                self.jitter_us as f64,
+            ),
+            (
+                "voidmic_dropped_frames_total",
+                "counter",
+                "Frames dropped because the output buffer was full",
+                self.dropped_frames as f64,
+            ),
+            (
+                "voidmic_frames_processed_total",
+                "counter",
+                "Frames processed since the engine started",
+                self.frames_processed as f64,
+            ),
+            (
+                "voidmic_cpu_load",
+                "gauge",
+                "Processing time per 10 ms frame budget",
+                self.cpu_load as f64,
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+        out
+    }
+}
+
+/// Engine currently being reported; swapped as the engine starts and stops.
+type SharedSource = Arc<Mutex<Option<MetricsSource>>>;
+
+fn current_snapshot(source: &SharedSource) -> MetricsSnapshot {
+    source
+        .lock()
+        .ok()
+        .and_then(|source| source.as_ref().map(MetricsSource::snapshot))
+        .unwrap_or_default()
+}
+
+/// The running HTTP endpoint. Stops when dropped.
+pub struct MetricsServer {
+    source: SharedSource,
+    #[cfg(feature = "metrics")]
+    server: Arc<tiny_http::Server>,
+    #[cfg(feature = "metrics")]
+    worker: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Starts serving on 127.0.0.1:`port` (0 picks a free port).
+    #[cfg(feature = "metrics")]
+    pub fn start(port: u16) -> Result<Self> {
+        let server = tiny_http::Server::http(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("Can't serve metrics on port {}: {}", port, e))?;
+        let server = Arc::new(server);
+        let source: SharedSource = Arc::new(Mutex::new(None));
+
+        let worker = {
+            let server = server.clone();
+            let source = source.clone();
+            std::thread::Builder::new()
+                .name("voidmic-metrics".into())
+                .spawn(move || {
+                    for request in server.incoming_requests() {
+                        let response = respond(request.url(), &current_snapshot(&source));
+                        if let Err(e) = request.respond(response) {
+                            log::debug!("Metrics response failed: {}", e);
+                        }
+                    }
+                })?
+        };
+        log::info!("Serving metrics on http://{}/metrics", server.server_addr());
+        Ok(Self {
+            source,
+            server,
+            worker: Some(worker),
+        })
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    pub fn start(_port: u16) -> Result<Self> {
+        anyhow::bail!("This build has no metrics endpoint (enable the `metrics` feature)")
+    }
+
+    /// Reports `source` from now on; `None` while no engine runs.
+    pub fn set_source(&self, source: Option<MetricsSource>) {
+        if let Ok(mut current) = self.source.lock() {
+            *current = source;
+        }
+    }
+
+    /// Port actually bound, for servers started on port 0.
+    #[cfg(all(feature = "metrics", test))]
+    pub fn port(&self) -> Option<u16> {
+        self.server.server_addr().to_ip().map(|addr| addr.port())
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn respond(url: &str, snapshot: &MetricsSnapshot) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let (body, content_type) = match url.split('?').next() {
+        Some("/metrics") => (snapshot.to_prometheus(), "text/plain; version=0.0.4"),
+        Some("/metrics.json") => (
+            serde_json::to_string(snapshot).unwrap_or_default(),
+            "application/json",
+        ),
+        _ => {
+            return tiny_http::Response::from_string("Not found: try /metrics or /metrics.json")
+                .with_status_code(404)
+        }
+    };
+    let header = tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
+        .expect("static header is valid");
+    tiny_http::Response::from_string(body).with_header(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Metric name -> value from Prometheus text, checking every sample has HELP and TYPE
+    fn parse_prometheus(text: &str) -> Vec<(String, f64)> {
+        let mut samples = Vec::new();
+        for line in text.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line.split_once(' ').expect("name and value");
+            assert!(
+                text.contains(&format!("# HELP {} ", name)),
+                "{} has no HELP",
+                name
+            );
+            assert!(
+                text.contains(&format!("# TYPE {} ", name)),
+                "{} has no TYPE",
+                name
+            );
+            samples.push((name.to_string(), value.parse().expect("numeric value")));
+        }
+        samples
+    }
+
+    fn value(samples: &[(String, f64)], name: &str) -> f64 {
+        samples
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| *v)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prometheus_format() {
+        let snapshot = MetricsSnapshot {
+            running: true,
+            rms: 0.25,
+            peak: 0.5,
+            gate_open: true,
+            jitter_us: 420,
+            dropped_frames: 3,
+            frames_processed: 1000,
+            cpu_load: 0.125,
+        };
+        let samples = parse_prometheus(&snapshot.to_prometheus());
+        assert_eq!(samples.len(), 8);
+        assert_eq!(value(&samples, "voidmic_up"), 1.0);
+        assert_eq!(value(&samples, "voidmic_level_rms"), 0.25);
+        assert_eq!(value(&samples, "voidmic_gate_open"), 1.0);
+        assert_eq!(value(&samples, "voidmic_jitter_microseconds"), 420.0);
+        assert_eq!(value(&samples, "voidmic_dropped_frames_total"), 3.0);
+        assert_eq!(value(&samples, "voidmic_cpu_load"), 0.125);
+
+        // Stopped engine: everything reads zero
+        let samples = parse_prometheus(&MetricsSnapshot::default().to_prometheus());
+        assert!(samples.iter().all(|(_, v)| *v == 0.0));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_endpoint_serves_metrics() {
+        use std::io::{Read, Write as _};
+
+        fn get(port: u16, path: &str) -> (String, String) {
+            let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+            write!(
+                stream,
+                "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+                path
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").expect("headers and body");
+            (head.to_string(), body.to_string())
+        }
+
+        let server = MetricsServer::start(0).unwrap();
+        let port = server.port().unwrap();
+
+        let (head, body) = get(port, "/metrics");
+        assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+        let samples = parse_prometheus(&body);
+        assert_eq!(value(&samples, "voidmic_up"), 0.0);
+
+        let (_, body) = get(port, "/metrics.json");
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["running"], false);
+        assert_eq!(json["frames_processed"], 0);
+
+        let (head, _) = get(port, "/");
+        assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+    }
+}
@@ -0,0 +1,360 @@
+//! Offline processing: runs a WAV recording through `VoidProcessor` with the current
+//! settings, for recordings dropped onto the GUI.
+
+use crate::config::AppConfig;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
+use voidmic_core::frame_adapter::MAX_CHANNELS;
+use voidmic_core::{LinearResampler, VoidProcessor};
+
+/// Interleaved samples (-1.0..1.0) plus the format they were read in and are written back in.
+pub struct WavAudio {
+    pub spec: hound::WavSpec,
+    pub samples: Vec<f32>,
+}
+
+/// True for paths with a `.wav` extension (any case).
+pub fn is_wav(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+}
+
+/// Where the processed copy goes by default: `take.wav` -> `take_voidmic.wav`.
+pub fn processed_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}_voidmic.wav", stem))
+}
+
+pub fn read_wav(path: &Path) -> Result<WavAudio> {
+    let mut reader =
+        hound::WavReader::open(path).with_context(|| format!("Can't read {}", path.display()))?;
+    let spec = reader.spec();
+    if spec.channels == 0 || spec.channels as usize > MAX_CHANNELS {
+        bail!("Unsupported channel count: {}", spec.channels);
+    }
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+    Ok(WavAudio { spec, samples })
+}
+
+/// Writes `audio` in its own format; integer formats are clipped to full scale.
+pub fn write_wav(path: &Path, audio: &WavAudio) -> Result<()> {
+    let mut writer = hound::WavWriter::create(path, audio.spec)
+        .with_context(|| format!("Can't write {}", path.display()))?;
+    match audio.spec.sample_format {
+        hound::SampleFormat::Float => {
+            for &sample in &audio.samples {
+                writer.write_sample(sample)?;
+            }
+        }
+        hound::SampleFormat::Int => {
+            let max = ((1i64 << (audio.spec.bits_per_sample - 1)) - 1) as f32;
+            for &sample in &audio.samples {
+                writer.write_sample((sample.clamp(-1.0, 1.0) * max).round() as i32)?;
+            }
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Denoises `audio` with a fresh processor set up from `config`, keeping its length, rate
+/// and channel count. `progress` receives the finished fraction (`f32` bits, 0..1).
+pub fn process(audio: &WavAudio, config: &AppConfig, progress: &AtomicU32) -> WavAudio {
+    let channels = audio.spec.channels as usize;
+    let rate = audio.spec.sample_rate;
+    let len = audio.samples.len() / channels;
+
+    let mut planar: Vec<Vec<f32>> = (0..channels)
+        .map(|ch| {
+            audio
+                .samples
+                .iter()
+                .skip(ch)
+                .step_by(channels)
+                .copied()
+                .collect()
+        })
+        .collect();
+    if rate != SAMPLE_RATE {
+        planar = resample(&planar, rate, SAMPLE_RATE);
+    }
+
+    let processor = configured_processor(channels, config);
+    let mut processed = run_processor(processor, planar, config, progress);
+
+    if rate != SAMPLE_RATE {
+        processed = resample(&processed, SAMPLE_RATE, rate);
+    }
+    for ch in &mut processed {
+        ch.resize(len, 0.0);
+    }
+
+    let mut samples = Vec::with_capacity(len * channels);
+    for i in 0..len {
+        samples.extend(processed.iter().map(|ch| ch[i]));
+    }
+    progress.store(1.0f32.to_bits(), Ordering::Relaxed);
+    WavAudio {
+        spec: audio.spec,
+        samples,
+    }
+}
+
+fn configured_processor(channels: usize, config: &AppConfig) -> VoidProcessor {
+    let mut processor = VoidProcessor::new(
+        channels,
+        config.vad_sensitivity,
+        (config.eq_low_gain, config.eq_mid_gain, config.eq_high_gain),
+        config.agc_target_level,
+        false,
+    );
+    let controls = processor.controls();
+    controls
+        .eq_enabled
+        .store(config.eq_enabled, Ordering::Relaxed);
+    controls
+        .agc_enabled
+        .store(config.agc_enabled, Ordering::Relaxed);
+    controls
+        .agc_hold_when_gated
+        .store(config.agc_hold_when_gated, Ordering::Relaxed);
+    let store_f32 =
+        |atomic: &AtomicU32, value: f32| atomic.store(value.to_bits(), Ordering::Relaxed);
+    store_f32(&controls.input_gain_db, config.input_gain_db);
+    controls
+        .gate_hold_ms
+        .store(config.gate_hold_ms, Ordering::Relaxed);
+    controls
+        .gate_mode
+        .store(config.gate_mode, Ordering::Relaxed);
+    controls
+        .gate_fade_curve
+        .store(config.gate_fade_curve, Ordering::Relaxed);
+    controls
+        .noise_floor_speed
+        .store(config.noise_floor_speed, Ordering::Relaxed);
+    store_f32(
+        &controls.dynamic_threshold_min,
+        config.dynamic_threshold_min,
+    );
+    store_f32(
+        &controls.dynamic_threshold_max,
+        config.dynamic_threshold_max,
+    );
+    store_f32(&controls.expander_ratio, config.expander_ratio);
+    store_f32(&controls.gate_hysteresis, config.gate_hysteresis);
+    controls
+        .gate_subblocks
+        .store(config.gate_subblocks, Ordering::Relaxed);
+    controls
+        .gate_key_source
+        .store(config.gate_key_source, Ordering::Relaxed);
+    controls
+        .vad_source_channel
+        .store(config.vad_source_channel, Ordering::Relaxed);
+    controls
+        .vad_window_frames
+        .store(config.vad_window_frames, Ordering::Relaxed);
+    controls
+        .vad_onset_frames
+        .store(config.vad_onset_frames, Ordering::Relaxed);
+    controls
+        .vad_hangover_frames
+        .store(config.vad_hangover_frames, Ordering::Relaxed);
+    controls
+        .eq_auto_gain
+        .store(config.eq_auto_gain, Ordering::Relaxed);
+    store_f32(&controls.eq_tilt, config.eq_tilt);
+    store_f32(&controls.agc_attack_ms, config.agc_attack_ms);
+    store_f32(&controls.agc_release_ms, config.agc_release_ms);
+    store_f32(&controls.agc_ceiling_db, config.agc_ceiling_db);
+    controls
+        .compressor_enabled
+        .store(config.compressor_enabled, Ordering::Relaxed);
+    store_f32(
+        &controls.compressor_threshold_db,
+        config.compressor_threshold_db,
+    );
+    store_f32(&controls.compressor_ratio, config.compressor_ratio);
+    store_f32(&controls.compressor_attack_ms, config.compressor_attack_ms);
+    store_f32(
+        &controls.compressor_release_ms,
+        config.compressor_release_ms,
+    );
+    store_f32(&controls.compressor_makeup_db, config.compressor_makeup_db);
+    controls
+        .speech_gated_suppression
+        .store(config.speech_gated_suppression, Ordering::Relaxed);
+    store_f32(
+        &controls.speech_suppression_floor,
+        config.speech_suppression_floor,
+    );
+    controls
+        .protect_highs
+        .store(config.protect_highs, Ordering::Relaxed);
+    controls
+        .spectral_gate
+        .store(config.spectral_gate, Ordering::Relaxed);
+    controls
+        .dither_enabled
+        .store(config.dither_enabled, Ordering::Relaxed);
+    processor.process_updates();
+    // Nothing calls `process_updates` again, so don't leave the EQ mid-ramp
+    processor.snap_eq();
+    processor
+}
+
+/// Processes 48kHz planar audio frame by frame, zero-padding the tail.
+fn run_processor(
+    mut processor: VoidProcessor,
+    mut planar: Vec<Vec<f32>>,
+    config: &AppConfig,
+    progress: &AtomicU32,
+) -> Vec<Vec<f32>> {
+    let len = planar.first().map_or(0, Vec::len);
+    // The spectral gate delays the output by a frame; run one more and drop it from the front
+    let latency = if config.spectral_gate { FRAME_SIZE } else { 0 };
+    let frames = (len + latency).div_ceil(FRAME_SIZE);
+    for ch in &mut planar {
+        ch.resize(frames * FRAME_SIZE, 0.0);
+    }
+
+    let mut processed = vec![vec![0.0f32; frames * FRAME_SIZE]; planar.len()];
+    for frame in 0..frames {
+        let range = frame * FRAME_SIZE..(frame + 1) * FRAME_SIZE;
+        let input: Vec<&[f32]> = planar.iter().map(|ch| &ch[range.clone()]).collect();
+        let mut output: Vec<&mut [f32]> = processed
+            .iter_mut()
+            .map(|ch| &mut ch[range.clone()])
+            .collect();
+        processor.process_frame(
+            &input,
+            &mut output,
+            None,
+            config.suppression_strength,
+            config.gate_threshold,
+            config.dynamic_threshold_enabled,
+        );
+        // The last bit of progress is the conversion back
+        let done = (frame + 1) as f32 / frames as f32 * 0.99;
+        progress.store(done.to_bits(), Ordering::Relaxed);
+    }
+
+    for ch in &mut processed {
+        ch.drain(..latency);
+        ch.truncate(len);
+    }
+    processed
+}
+
+fn resample(planar: &[Vec<f32>], from_rate: u32, to_rate: u32) -> Vec<Vec<f32>> {
+    let mut resampler = LinearResampler::new(from_rate, to_rate);
+    let len = planar.first().map_or(0, Vec::len);
+    let mut resampled = vec![vec![0.0f32; resampler.max_output_len(len)]; planar.len()];
+    let written = {
+        let input: Vec<&[f32]> = planar.iter().map(Vec::as_slice).collect();
+        let mut output: Vec<&mut [f32]> = resampled.iter_mut().map(Vec::as_mut_slice).collect();
+        resampler.process(&input, &mut output)
+    };
+    for ch in &mut resampled {
+        ch.truncate(written);
+    }
+    resampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voidmic_core::processor::eq_tilt_gains;
+
+    #[test]
+    fn test_wav_detection_and_output_name() {
+        assert!(is_wav(Path::new("/tmp/take.wav")));
+        assert!(is_wav(Path::new("TAKE.WAV")));
+        assert!(!is_wav(Path::new("take.mp3")));
+        assert!(!is_wav(Path::new("wav")));
+        assert_eq!(
+            processed_path(Path::new("/tmp/take.wav")),
+            PathBuf::from("/tmp/take_voidmic.wav")
+        );
+    }
+
+    #[test]
+    fn test_process_keeps_format_and_length() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 44_100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        // 0.3s of stereo tone, deliberately not a whole number of frames
+        let samples: Vec<f32> = (0..13_231 * 2)
+            .map(|i| ((i / 2) as f32 * 0.05).sin() * 0.3)
+            .collect();
+        let audio = WavAudio { spec, samples };
+        let config = AppConfig {
+            spectral_gate: true,
+            ..AppConfig::default()
+        };
+
+        let progress = AtomicU32::new(0);
+        let processed = process(&audio, &config, &progress);
+        assert_eq!(processed.spec, spec);
+        assert_eq!(processed.samples.len(), audio.samples.len());
+        assert!(processed.samples.iter().all(|s| s.is_finite()));
+        assert_eq!(f32::from_bits(progress.load(Ordering::Relaxed)), 1.0);
+
+        let path = std::env::temp_dir().join(format!("voidmic_test_{}.wav", std::process::id()));
+        write_wav(&path, &processed).unwrap();
+        let restored = read_wav(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(restored.spec, spec);
+        assert_eq!(restored.samples.len(), processed.samples.len());
+    }
+
+    #[test]
+    fn test_eq_reaches_target_gains() {
+        let (tilt_low, tilt_high) = eq_tilt_gains(0.5);
+        // Saved band gains only count with the EQ on; the tilt always does
+        let mut config = AppConfig {
+            eq_enabled: false,
+            eq_low_gain: 6.0,
+            eq_mid_gain: -3.0,
+            eq_high_gain: 4.0,
+            eq_tilt: 0.5,
+            ..AppConfig::default()
+        };
+        let processor = configured_processor(1, &config);
+        assert_eq!(processor.eq_gains(), [tilt_low, 0.0, tilt_high]);
+
+        config.eq_enabled = true;
+        let processor = configured_processor(1, &config);
+        assert_eq!(
+            processor.eq_gains(),
+            [6.0 + tilt_low, -3.0, 4.0 + tilt_high]
+        );
+    }
+
+    #[test]
+    fn test_non_wav_file_is_an_error() {
+        let path = std::env::temp_dir().join(format!("voidmic_test_{}.txt", std::process::id()));
+        std::fs::write(&path, "not audio").unwrap();
+        let result = read_wav(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! PulseAudio information queries for VoidMic.
 //!
-//! Provides utilities to query connected apps using VoidMic's virtual source.
+//! Provides utilities to query connected apps using VoidMic's virtual source, to tell
+//! real output devices from virtual sinks, and to find out which apps hold an input.
 
 use std::process::Command;
 
@@ -37,6 +38,256 @@ pub fn get_connected_apps() -> Vec<ConnectedApp> {
     }
 }
 
+/// A PulseAudio/PipeWire sink or source, as listed by `pactl list sinks`/`sources`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceInfo {
+    pub index: u32,
+    pub name: String,
+    pub description: String,
+    /// Backed by hardware (ALSA, Bluetooth, ...) rather than a null or combine sink
+    pub physical: bool,
+}
+
+/// Gets all sinks, classified as physical or virtual. Empty without `pactl`.
+pub fn get_sinks() -> Vec<DeviceInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        match Command::new("pactl").args(["list", "sinks"]).output() {
+            Ok(output) if output.status.success() => {
+                parse_sinks(&String::from_utf8_lossy(&output.stdout))
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Gets the name of the server's default sink.
+pub fn get_default_sink() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let output = Command::new("pactl")
+            .arg("get-default-sink")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Gets the sound server's name from `pactl info`, e.g. "PulseAudio (on PipeWire 1.0.5)".
+/// The error says whether `pactl` is missing or no server answered.
+pub fn get_server_name() -> Result<String, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let output = Command::new("pactl").arg("info").output().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "pactl not found".to_string()
+            } else {
+                format!("Failed to run pactl: {}", e)
+            }
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("pactl info failed: {}", stderr.trim()));
+        }
+        parse_server_name(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| "pactl info reported no server name".to_string())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Only available on Linux".to_string())
+    }
+}
+
+/// Names the apps recording from the input device `device`, for a "device busy" error.
+/// Empty if nothing is found or `pactl` isn't available.
+pub fn get_input_users(device: &str) -> Vec<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let pactl = |args: &[&str]| {
+            Command::new("pactl")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        };
+        let (Some(sources), Some(recordings)) = (
+            pactl(&["list", "sources"]),
+            pactl(&["list", "source-outputs"]),
+        ) else {
+            return Vec::new();
+        };
+        let default_source = pactl(&["get-default-source"]).map(|name| name.trim().to_string());
+        apps_using_input(
+            device,
+            &parse_devices(&sources, "Source #"),
+            &parse_recordings(&recordings),
+            default_source.as_deref(),
+        )
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = device;
+        Vec::new()
+    }
+}
+
+/// An app's recording stream, from `pactl list source-outputs`.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, PartialEq)]
+struct Recording {
+    app: String,
+    /// The source's index (name on older servers)
+    source: String,
+}
+
+/// Apps recording from the source behind `device`: found by name or description like
+/// `is_physical_output` does. ALSA card devices (`hw:CARD=...`) can't be mapped to one
+/// source, so any hardware source counts; the sound server holds the card for them.
+#[cfg(any(target_os = "linux", test))]
+fn apps_using_input(
+    device: &str,
+    sources: &[DeviceInfo],
+    recordings: &[Recording],
+    default_source: Option<&str>,
+) -> Vec<String> {
+    let name = match device {
+        "default" | "pulse" | "pipewire" => default_source.unwrap_or_default(),
+        _ => device,
+    };
+    let targets: Vec<&DeviceInfo> = match sources
+        .iter()
+        .find(|s| s.name == name || s.description == name)
+    {
+        Some(source) => vec![source],
+        None if device.contains("CARD=") => sources
+            .iter()
+            .filter(|s| s.physical && !s.name.ends_with(".monitor"))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut apps: Vec<String> = Vec::new();
+    for recording in recordings {
+        let on_target = targets
+            .iter()
+            .any(|s| recording.source == s.index.to_string() || recording.source == s.name);
+        if on_target && !apps.contains(&recording.app) {
+            apps.push(recording.app.clone());
+        }
+    }
+    apps
+}
+
+/// Heuristic: whether the output device named `output` ends up on real speakers or
+/// headphones. ALSA card devices always do; the generic `default`/`pulse`/`pipewire`
+/// devices follow the default sink; anything else is looked up by sink name or
+/// description. Unknown devices count as not physical, so no false warnings.
+pub fn is_physical_output(output: &str, sinks: &[DeviceInfo], default_sink: Option<&str>) -> bool {
+    if output.contains("CARD=") {
+        return true;
+    }
+    let name = match output {
+        "default" | "pulse" | "pipewire" => match default_sink {
+            Some(name) => name,
+            None => return false,
+        },
+        _ => output,
+    };
+    sinks
+        .iter()
+        .find(|sink| sink.name == name || sink.description == name)
+        .is_some_and(|sink| sink.physical)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_sinks(text: &str) -> Vec<DeviceInfo> {
+    parse_devices(text, "Sink #")
+}
+
+/// Parses `pactl list sinks` or `sources`, whose entries start with `header`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_devices(text: &str, header: &str) -> Vec<DeviceInfo> {
+    let mut devices: Vec<DeviceInfo> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if let Some(index) = line.strip_prefix(header) {
+            devices.push(DeviceInfo {
+                index: index.trim().parse().unwrap_or_default(),
+                name: String::new(),
+                description: String::new(),
+                physical: false,
+            });
+            continue;
+        }
+        let Some(device) = devices.last_mut() else {
+            continue;
+        };
+        if let Some(name) = line.strip_prefix("Name: ") {
+            device.name = name.to_string();
+        } else if let Some(description) = line.strip_prefix("Description: ") {
+            device.description = description.to_string();
+        } else if let Some(driver) = line.strip_prefix("Driver: ") {
+            // PulseAudio names the module that backs the device
+            if driver.starts_with("module-alsa") || driver.starts_with("module-bluez") {
+                device.physical = true;
+            }
+        } else if line.starts_with("device.api = ") || line.starts_with("alsa.card = ") {
+            // Hardware devices carry these on both PulseAudio and PipeWire; null sinks don't
+            device.physical = true;
+        }
+    }
+
+    devices
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_server_name(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|line| line.trim().strip_prefix("Server Name: "))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_recordings(text: &str) -> Vec<Recording> {
+    let mut recordings: Vec<Recording> = Vec::new();
+    let mut source = String::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if line.starts_with("Source Output #") {
+            source.clear();
+        } else if let Some(value) = line.strip_prefix("Source:") {
+            source = value.trim().to_string();
+        } else if let Some(name) = line.strip_prefix("application.name = ") {
+            recordings.push(Recording {
+                app: name.trim_matches('"').to_string(),
+                source: source.clone(),
+            });
+        }
+    }
+
+    recordings
+}
+
 #[cfg(target_os = "linux")]
 fn parse_source_outputs(text: &str) -> Vec<ConnectedApp> {
     let mut apps = Vec::new();
@@ -91,4 +342,147 @@ Source Output #43
         assert_eq!(apps.len(), 1);
         assert_eq!(apps[0].name, "Discord");
     }
+
+    #[test]
+    fn test_parse_sinks_classifies_physical() {
+        let sample = r#"
+Sink #47
+	State: RUNNING
+	Name: alsa_output.pci-0000_00_1f.3.analog-stereo
+	Description: Built-in Audio Analog Stereo
+	Driver: PipeWire
+	Properties:
+		alsa.card = "0"
+		device.api = "alsa"
+		device.description = "Built-in Audio Analog Stereo"
+
+Sink #62
+	State: IDLE
+	Name: VoidMic_Clean
+	Description: VoidMic_Clean
+	Driver: module-null-sink.c
+	Properties:
+		device.description = "VoidMic_Clean"
+		device.class = "abstract"
+"#;
+        let sinks = parse_sinks(sample);
+        assert_eq!(sinks.len(), 2);
+        assert_eq!(sinks[0].name, "alsa_output.pci-0000_00_1f.3.analog-stereo");
+        assert!(sinks[0].physical);
+        assert_eq!(sinks[1].name, "VoidMic_Clean");
+        assert!(!sinks[1].physical);
+
+        let speakers = Some(sinks[0].name.as_str());
+        assert!(is_physical_output(
+            "Built-in Audio Analog Stereo",
+            &sinks,
+            None
+        ));
+        assert!(is_physical_output("default", &sinks, speakers));
+        assert!(is_physical_output("hw:CARD=PCH,DEV=0", &sinks, None));
+        assert!(!is_physical_output("VoidMic_Clean", &sinks, speakers));
+        assert!(!is_physical_output(
+            "default",
+            &sinks,
+            Some("VoidMic_Clean")
+        ));
+        assert!(!is_physical_output("default", &sinks, None));
+        assert!(!is_physical_output("Some JACK port", &sinks, None));
+    }
+
+    #[test]
+    fn test_apps_using_input() {
+        let sources = parse_devices(
+            r#"
+Source #55
+	State: RUNNING
+	Name: alsa_input.usb-Blue_Yeti-00.analog-stereo
+	Description: Yeti Stereo Microphone Analog Stereo
+	Driver: PipeWire
+	Properties:
+		alsa.card = "2"
+		device.api = "alsa"
+
+Source #56
+	State: SUSPENDED
+	Name: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor
+	Description: Monitor of Built-in Audio Analog Stereo
+	Driver: PipeWire
+	Properties:
+		device.api = "alsa"
+		device.class = "monitor"
+
+Source #63
+	State: RUNNING
+	Name: VoidMic_Clean.monitor
+	Description: Monitor of VoidMic_Clean
+	Driver: module-null-sink.c
+"#,
+            "Source #",
+        );
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0].index, 55);
+        assert!(sources[0].physical);
+
+        let recordings = parse_recordings(
+            r#"
+Source Output #80
+	Driver: PipeWire
+	Client: 71
+	Source: 55
+	Properties:
+		media.name = "Recording"
+		application.name = "OBS Studio"
+
+Source Output #81
+	Source: 55
+	Properties:
+		application.name = "OBS Studio"
+
+Source Output #82
+	Source: 56
+	Properties:
+		application.name = "Audacity"
+
+Source Output #83
+	Source: 63
+	Properties:
+		application.name = "Discord"
+"#,
+        );
+        assert_eq!(recordings.len(), 4);
+        assert_eq!(recordings[0].source, "55");
+
+        let users = |device, default| apps_using_input(device, &sources, &recordings, default);
+        let yeti = Some("alsa_input.usb-Blue_Yeti-00.analog-stereo");
+        // By description, listed once per app
+        assert_eq!(
+            users("Yeti Stereo Microphone Analog Stereo", None),
+            ["OBS Studio"]
+        );
+        assert_eq!(users("default", yeti), ["OBS Studio"]);
+        // A raw ALSA card: whoever records from hardware, but not from monitors
+        assert_eq!(users("hw:CARD=Microphones,DEV=0", None), ["OBS Studio"]);
+        assert_eq!(users("Monitor of VoidMic_Clean", None), ["Discord"]);
+        assert!(users("default", None).is_empty());
+        assert!(users("Some JACK port", yeti).is_empty());
+    }
+
+    #[test]
+    fn test_parse_server_name() {
+        let sample = "Server String: /run/user/1000/pulse/native
+Library Protocol Version: 35
+Server Name: PulseAudio (on PipeWire 1.0.5)
+Server Version: 15.0.0
+Default Sink: alsa_output.pci-0000_00_1f.3.analog-stereo
+";
+        assert_eq!(
+            parse_server_name(sample).as_deref(),
+            Some("PulseAudio (on PipeWire 1.0.5)")
+        );
+        assert_eq!(
+            parse_server_name("Connection failure: Connection refused\n"),
+            None
+        );
+    }
 }
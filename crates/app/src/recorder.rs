@@ -0,0 +1,238 @@
+//! Records the processed mic to a WAV file while it is in live use.
+//!
+//! The audio thread hands each output frame to a writer thread over a bounded channel
+//! and never waits for it (see `AudioEngine::set_record_tap`). Recordings are mono
+//! 16-bit PCM at the engine rate.
+
+use anyhow::{anyhow, Context, Result};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use log::warn;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
+
+use crate::audio::RecordFrame;
+
+// Frames buffered between the audio thread and the writer (1 s)
+const RECORD_QUEUE: usize = 100;
+// How often an idle writer checks whether it was stopped
+const STOP_POLL: Duration = Duration::from_millis(50);
+const WAV_HEADER_BYTES: u64 = 44;
+const BYTES_PER_SAMPLE: u64 = 2;
+
+/// Where a recording goes when no path is set: `voidmic-<unix time>.wav` in the music
+/// directory (home as a fallback).
+pub fn default_record_path() -> PathBuf {
+    let dir = dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir);
+    dir.join(format!("voidmic-{}.wav", crate::daemon::unix_now()))
+}
+
+type WavFile = hound::WavWriter<BufWriter<File>>;
+
+/// A recording in progress. Dropping it stops and finalizes the file as well.
+pub struct Recording {
+    path: PathBuf,
+    sender: Sender<RecordFrame>,
+    frames_written: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    // First write error, e.g. a full disk; the writer stops at it
+    error: Arc<Mutex<Option<String>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Recording {
+    /// Creates (truncates) the WAV at `path` and starts its writer thread. Feed it frames
+    /// through `tap()`.
+    pub fn start(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Can't create {}", parent.display()))?;
+        }
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .with_context(|| format!("Can't write {}", path.display()))?;
+
+        let (sender, receiver) = crossbeam_channel::bounded(RECORD_QUEUE);
+        let frames_written = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let error = Arc::new(Mutex::new(None));
+        let worker = {
+            let (frames_written, stop, error) =
+                (frames_written.clone(), stop.clone(), error.clone());
+            std::thread::Builder::new()
+                .name("voidmic-recorder".into())
+                .spawn(move || {
+                    if let Err(e) = write_frames(writer, &receiver, &frames_written, &stop) {
+                        warn!("Recording stopped: {:#}", e);
+                        if let Ok(mut error) = error.lock() {
+                            *error = Some(format!("{:#}", e));
+                        }
+                    }
+                })
+                .context("Failed to spawn recorder thread")?
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            sender,
+            frames_written,
+            stop,
+            error,
+            worker: Some(worker),
+        })
+    }
+
+    /// Sender to attach to the engine.
+    pub fn tap(&self) -> Sender<RecordFrame> {
+        self.sender.clone()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Length of the audio written so far.
+    pub fn duration(&self) -> Duration {
+        let samples = self.frames_written.load(Ordering::Relaxed) * FRAME_SIZE as u64;
+        Duration::from_millis(samples * 1000 / SAMPLE_RATE as u64)
+    }
+
+    /// Current file size in bytes.
+    pub fn bytes_written(&self) -> u64 {
+        let samples = self.frames_written.load(Ordering::Relaxed) * FRAME_SIZE as u64;
+        WAV_HEADER_BYTES + samples * BYTES_PER_SAMPLE
+    }
+
+    /// Why writing stopped early, if it did; the file keeps what was written before.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().ok().and_then(|error| error.clone())
+    }
+
+    /// Writes out what is still queued, finalizes the file and returns its length.
+    pub fn stop(mut self) -> Result<Duration> {
+        self.finish();
+        match self.error() {
+            Some(e) => Err(anyhow!(e)),
+            None => Ok(self.duration()),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+fn write_frames(
+    mut writer: WavFile,
+    receiver: &Receiver<RecordFrame>,
+    frames_written: &AtomicU64,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let write = |writer: &mut WavFile, frame: &RecordFrame| -> Result<()> {
+        for &sample in frame {
+            writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)?;
+        }
+        frames_written.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    };
+    loop {
+        match receiver.recv_timeout(STOP_POLL) {
+            Ok(frame) => write(&mut writer, &frame)?,
+            Err(RecvTimeoutError::Timeout) if !stop.load(Ordering::Relaxed) => {}
+            // Stopped while idle (or every sender is gone): drain and finish
+            Err(_) => break,
+        }
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+    while let Ok(frame) = receiver.try_recv() {
+        write(&mut writer, &frame)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("voidmic_{}_{}.wav", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_recording_has_expected_duration() {
+        let path = temp_path("record");
+        let recording = Recording::start(&path).unwrap();
+        let tap = recording.tap();
+        // 1.5 s of a half-scale tone
+        for i in 0..150 {
+            let frame: RecordFrame =
+                std::array::from_fn(|n| if (i + n) % 2 == 0 { 0.5 } else { -0.5 });
+            tap.send(frame).unwrap();
+        }
+        let duration = recording.stop().unwrap();
+        assert_eq!(duration, Duration::from_millis(1500));
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let spec = reader.spec();
+        assert_eq!(
+            (spec.channels, spec.sample_rate, spec.bits_per_sample),
+            (1, SAMPLE_RATE, 16)
+        );
+        assert_eq!(reader.duration(), 150 * FRAME_SIZE as u32);
+        let first: i16 = reader.samples().next().unwrap().unwrap();
+        assert_eq!(first, (0.5 * i16::MAX as f32).round() as i16);
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            44 + 150 * FRAME_SIZE as u64 * 2
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_stop_without_audio_leaves_empty_wav() {
+        let path = temp_path("record_empty");
+        let recording = Recording::start(&path).unwrap();
+        assert_eq!(recording.stop().unwrap(), Duration::ZERO);
+        assert_eq!(hound::WavReader::open(&path).unwrap().duration(), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_full_disk_stops_with_error() {
+        // Every write to /dev/full fails with ENOSPC
+        let Ok(recording) = Recording::start(Path::new("/dev/full")) else {
+            return; // Refused up front, which is just as graceful
+        };
+        let tap = recording.tap();
+        for _ in 0..50 {
+            // The writer may already have given up and dropped the receiver
+            let _ = tap.send([0.1; FRAME_SIZE]);
+        }
+        let err = recording.stop().unwrap_err();
+        assert!(!err.to_string().is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+//! "Forgot to mute" watch: notices when the mic keeps sending sound but nobody has
+//! spoken for a long time, so background noise doesn't go out for the rest of a call.
+//!
+//! Speech comes from the processor's VAD counter (`AudioEngine::speech_frames`) and
+//! "sending sound" from the output peak, so time behind a closed gate never counts.
+
+use crate::audio::AudioEngine;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+pub const SILENCE_DEFAULT_MINS: u32 = 5;
+pub const SILENCE_MAX_MINS: u32 = 60;
+// Output peak (-50 dBFS) above which the mic counts as sending sound
+const SENDING_PEAK: f32 = 0.003;
+
+/// What happens once the mic has sent sound without speech for the configured time.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SilenceAction {
+    #[default]
+    Off,
+    /// Ask whether to mute
+    Notify,
+    /// Mute until speech is heard again
+    Mute,
+}
+
+impl SilenceAction {
+    pub const ALL: [SilenceAction; 3] = [
+        SilenceAction::Off,
+        SilenceAction::Notify,
+        SilenceAction::Mute,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SilenceAction::Off => tr!("app.silence_off"),
+            SilenceAction::Notify => tr!("app.silence_notify"),
+            SilenceAction::Mute => tr!("app.silence_mute"),
+        }
+    }
+}
+
+/// Reported by `SilenceWatch::update` when its state flips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SilenceEvent {
+    /// Sound without speech for the whole limit
+    Silent,
+    /// Speech again after `Silent`
+    Speech,
+}
+
+/// Time limit for `minutes`, clamped to 1..=`SILENCE_MAX_MINS`.
+pub fn silence_limit(minutes: u32) -> Duration {
+    Duration::from_secs(u64::from(minutes.clamp(1, SILENCE_MAX_MINS)) * 60)
+}
+
+/// Times how long the mic has been sending sound without speech.
+pub struct SilenceWatch {
+    limit: Duration,
+    silent_for: Duration,
+    fired: bool,
+    // What `poll` saw last time
+    last_poll: Option<Instant>,
+    last_speech_frames: u32,
+}
+
+impl SilenceWatch {
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            limit,
+            silent_for: Duration::ZERO,
+            fired: false,
+            last_poll: None,
+            last_speech_frames: 0,
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: Duration) {
+        self.limit = limit;
+    }
+
+    /// Advances the timer by `elapsed`, during which speech was or wasn't heard and the
+    /// mic was or wasn't sending sound. Speech restarts the timer; time without sound
+    /// neither counts nor restarts it. `Silent` is reported once per stretch without
+    /// speech, and `Speech` when it ends.
+    pub fn update(
+        &mut self,
+        elapsed: Duration,
+        speech: bool,
+        sending: bool,
+    ) -> Option<SilenceEvent> {
+        if speech {
+            self.silent_for = Duration::ZERO;
+            return std::mem::take(&mut self.fired).then_some(SilenceEvent::Speech);
+        }
+        if sending {
+            self.silent_for += elapsed;
+            if !self.fired && self.silent_for >= self.limit {
+                self.fired = true;
+                return Some(SilenceEvent::Silent);
+            }
+        }
+        None
+    }
+
+    /// Starts the timer over, e.g. once the user dismissed the warning.
+    pub fn reset(&mut self) {
+        self.silent_for = Duration::ZERO;
+        self.fired = false;
+    }
+
+    /// Updates the timer from what `engine` did since the last call. Paused in bypass,
+    /// where the VAD doesn't run.
+    pub fn poll(&mut self, engine: &AudioEngine) -> Option<SilenceEvent> {
+        let now = Instant::now();
+        let elapsed = self.last_poll.map_or(Duration::ZERO, |last| now - last);
+        self.last_poll = Some(now);
+        let speech_frames = engine.speech_frames.load(Ordering::Relaxed);
+        let speech = speech_frames != self.last_speech_frames;
+        self.last_speech_frames = speech_frames;
+        if engine.controls.bypass_enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let peak = f32::from_bits(engine.output_peak.load(Ordering::Relaxed));
+        self.update(elapsed, speech, peak > SENDING_PEAK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    // Feeds one-second steps of (speech, sending) and collects the events
+    fn run(watch: &mut SilenceWatch, steps: &[(bool, bool)]) -> Vec<(usize, SilenceEvent)> {
+        steps
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &(speech, sending))| {
+                watch
+                    .update(SECOND, speech, sending)
+                    .map(|event| (i, event))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fires_once_after_limit_of_sound_without_speech() {
+        let mut watch = SilenceWatch::new(10 * SECOND);
+        let events = run(&mut watch, &[(false, true); 30]);
+        assert_eq!(events, [(9, SilenceEvent::Silent)]);
+    }
+
+    #[test]
+    fn test_speech_restarts_timer_and_ends_silence() {
+        let mut watch = SilenceWatch::new(10 * SECOND);
+        // Speech every 8 s keeps it from ever firing
+        let mut steps = Vec::new();
+        for _ in 0..5 {
+            steps.extend([(false, true); 7]);
+            steps.push((true, true));
+        }
+        assert!(run(&mut watch, &steps).is_empty());
+
+        let events = run(&mut watch, &[(false, true); 10]);
+        assert_eq!(events, [(9, SilenceEvent::Silent)]);
+        let events = run(&mut watch, &[(false, true), (true, true), (true, true)]);
+        assert_eq!(events, [(1, SilenceEvent::Speech)]);
+        // Re-armed
+        let events = run(&mut watch, &[(false, true); 10]);
+        assert_eq!(events, [(9, SilenceEvent::Silent)]);
+    }
+
+    #[test]
+    fn test_quiet_time_is_skipped() {
+        let mut watch = SilenceWatch::new(10 * SECOND);
+        // 6 s of sound, a minute of nothing (gate closed), 4 more seconds of sound
+        let mut steps = vec![(false, true); 6];
+        steps.extend([(false, false); 60]);
+        steps.extend([(false, true); 4]);
+        let events = run(&mut watch, &steps);
+        assert_eq!(events, [(69, SilenceEvent::Silent)]);
+    }
+
+    #[test]
+    fn test_reset_starts_over() {
+        let mut watch = SilenceWatch::new(10 * SECOND);
+        assert_eq!(run(&mut watch, &[(false, true); 10]).len(), 1);
+        watch.reset();
+        // No `Speech` for a dismissed warning, and a fresh full period before the next
+        assert!(run(&mut watch, &[(false, true); 9]).is_empty());
+        assert_eq!(
+            run(&mut watch, &[(false, true)]),
+            [(0, SilenceEvent::Silent)]
+        );
+
+        watch.reset();
+        watch.set_limit(3 * SECOND);
+        assert_eq!(
+            run(&mut watch, &[(false, true); 3]),
+            [(2, SilenceEvent::Silent)]
+        );
+    }
+
+    #[test]
+    fn test_silence_limit_is_clamped() {
+        assert_eq!(silence_limit(0), Duration::from_secs(60));
+        assert_eq!(silence_limit(5), Duration::from_secs(300));
+        assert_eq!(
+            silence_limit(1000),
+            Duration::from_secs(u64::from(SILENCE_MAX_MINS) * 60)
+        );
+    }
+}
@@ -3,10 +3,35 @@
 //! Checks GitHub Releases API for newer versions and provides download links.
 
 use semver::Version;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const GITHUB_API_URL: &str = "https://api.github.com/repos/Detair/voidvoice/releases/latest";
+// Includes pre-releases, which `/latest` skips
+const GITHUB_RELEASES_URL: &str =
+    "https://api.github.com/repos/Detair/voidvoice/releases?per_page=20";
+
+/// Which releases count as updates.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Full releases only
+    #[default]
+    Stable,
+    /// Full releases and pre-releases
+    Beta,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Beta];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "Stable",
+            UpdateChannel::Beta => "Beta",
+        }
+    }
+}
 
 /// Information about an available update.
 #[derive(Clone, Debug)]
@@ -19,21 +44,43 @@ pub struct UpdateInfo {
 struct GitHubRelease {
     tag_name: String,
     html_url: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+fn parse_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.trim_start_matches('v')).ok()
+}
+
+/// Picks the newest release on `channel` from a releases listing.
+fn newest_release(releases: Vec<GitHubRelease>, channel: UpdateChannel) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft && (channel == UpdateChannel::Beta || !r.prerelease))
+        .filter_map(|r| parse_tag(&r.tag_name).map(|v| (v, r)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, r)| r)
 }
 
-/// Checks GitHub for available updates.
+/// Checks GitHub for available updates on the given channel.
 ///
 /// Returns `Some(UpdateInfo)` if a newer version is available, `None` otherwise.
 /// Returns `Err` on network or parsing errors.
-pub fn check_for_updates() -> Result<Option<UpdateInfo>, String> {
+pub fn check_for_updates(channel: UpdateChannel) -> Result<Option<UpdateInfo>, String> {
     let client = reqwest::blocking::Client::builder()
         .user_agent("VoidMic-Updater")
         .timeout(std::time::Duration::from_secs(10))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
+    let url = match channel {
+        UpdateChannel::Stable => GITHUB_API_URL,
+        UpdateChannel::Beta => GITHUB_RELEASES_URL,
+    };
     let response = client
-        .get(GITHUB_API_URL)
+        .get(url)
         .send()
         .map_err(|e| format!("Failed to fetch release info: {}", e))?;
 
@@ -41,9 +88,20 @@ pub fn check_for_updates() -> Result<Option<UpdateInfo>, String> {
         return Err(format!("GitHub API returned status: {}", response.status()));
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .map_err(|e| format!("Failed to parse release JSON: {}", e))?;
+    let release = match channel {
+        UpdateChannel::Stable => response
+            .json::<GitHubRelease>()
+            .map_err(|e| format!("Failed to parse release JSON: {}", e))?,
+        UpdateChannel::Beta => {
+            let releases: Vec<GitHubRelease> = response
+                .json()
+                .map_err(|e| format!("Failed to parse release JSON: {}", e))?;
+            match newest_release(releases, channel) {
+                Some(release) => release,
+                None => return Ok(None),
+            }
+        }
+    };
 
     // Parse versions (strip 'v' prefix if present)
     let remote_version_str = release.tag_name.trim_start_matches('v');
@@ -68,13 +126,14 @@ pub fn check_for_updates() -> Result<Option<UpdateInfo>, String> {
 
 /// Spawns a background thread to check for updates.
 ///
-/// Returns a receiver that will contain the update info when available.
-pub fn check_for_updates_async() -> std::sync::mpsc::Receiver<Option<UpdateInfo>> {
+/// Returns a receiver that will contain the check result when available.
+pub fn check_for_updates_async(
+    channel: UpdateChannel,
+) -> std::sync::mpsc::Receiver<Result<Option<UpdateInfo>, String>> {
     let (tx, rx) = std::sync::mpsc::channel();
 
     std::thread::spawn(move || {
-        let result = check_for_updates().ok().flatten();
-        let _ = tx.send(result);
+        let _ = tx.send(check_for_updates(channel));
     });
 
     rx
@@ -122,5 +181,23 @@ mod tests {
         let release: GitHubRelease = serde_json::from_str(json).unwrap();
         assert_eq!(release.tag_name, "v1.0.0");
         assert_eq!(release.html_url, "https://example.com");
+        assert!(!release.prerelease);
+    }
+
+    #[test]
+    fn test_newest_release_respects_channel() {
+        let releases = || {
+            let json = r#"[
+                {"tag_name":"v1.1.0-beta.1","html_url":"beta","prerelease":true},
+                {"tag_name":"v1.2.0","html_url":"draft","draft":true},
+                {"tag_name":"v1.0.0","html_url":"stable"}
+            ]"#;
+            serde_json::from_str::<Vec<GitHubRelease>>(json).unwrap()
+        };
+
+        let stable = newest_release(releases(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.html_url, "stable");
+        let beta = newest_release(releases(), UpdateChannel::Beta).unwrap();
+        assert_eq!(beta.html_url, "beta");
     }
 }
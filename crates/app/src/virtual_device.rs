@@ -8,6 +8,9 @@ use std::process::Command;
 /// Name of the virtual sink created by VoidMic
 pub const VIRTUAL_SINK_NAME: &str = "VoidMic_Clean";
 
+/// Name of the combine sink that fans VoidMic's output out to several sinks
+pub const COMBINE_SINK_NAME: &str = "VoidMic_Combined";
+
 /// Information about a created virtual device
 #[derive(Debug, Clone)]
 pub struct VirtualDevice {
@@ -82,6 +85,76 @@ pub fn create_virtual_sink() -> Result<VirtualDevice, String> {
     }
 }
 
+/// `pactl` arguments for loading a combine sink that forwards to `slaves`.
+#[cfg(any(target_os = "linux", test))]
+fn combine_sink_args(slaves: &[&str]) -> Vec<String> {
+    vec![
+        "load-module".to_string(),
+        "module-combine-sink".to_string(),
+        format!("sink_name={}", COMBINE_SINK_NAME),
+        format!("slaves={}", slaves.join(",")),
+        format!("sink_properties=device.description={}", COMBINE_SINK_NAME),
+    ]
+}
+
+/// Creates a `module-combine-sink` that plays everything sent to it on all of
+/// `slaves` (e.g. the virtual sink plus headphones, to monitor while streaming).
+///
+/// Linux only. Returns the module ID for later unloading.
+pub fn create_combine_sink(slaves: &[&str]) -> Result<VirtualDevice, String> {
+    if slaves.len() < 2 {
+        return Err("A combined sink needs at least two sinks".to_string());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let result = Command::new("pactl")
+            .args(combine_sink_args(slaves))
+            .output()
+            .map_err(|e| format!("Failed to create combined sink: {}", e))?;
+
+        if result.status.success() {
+            let module_id: u32 = String::from_utf8_lossy(&result.stdout)
+                .trim()
+                .parse()
+                .unwrap_or(0);
+
+            Ok(VirtualDevice {
+                module_id,
+                sink_name: COMBINE_SINK_NAME.to_string(),
+            })
+        } else {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            Err(format!("pactl failed: {}", stderr))
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Combined sinks are only supported on Linux".to_string())
+    }
+}
+
+/// Destroys the combine sink, if one is loaded.
+pub fn destroy_combine_sink() -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        let Some(module_id) = find_module_id("module-combine-sink", COMBINE_SINK_NAME) else {
+            return Ok(());
+        };
+        Command::new("pactl")
+            .args(["unload-module", &module_id.to_string()])
+            .output()
+            .map_err(|e| format!("Failed to unload module: {}", e))?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(())
+    }
+}
+
 /// Destroys a virtual sink by module ID.
 ///
 /// If `module_id` is 0 (unknown), looks up the specific module ID for VoidMic_Clean
@@ -91,7 +164,7 @@ pub fn destroy_virtual_sink(module_id: u32) -> Result<(), String> {
     {
         let effective_id = if module_id == 0 {
             // Find VoidMic_Clean's specific module ID instead of unloading all null-sinks
-            find_voidmic_module_id().unwrap_or(0)
+            find_module_id("module-null-sink", VIRTUAL_SINK_NAME).unwrap_or(0)
         } else {
             module_id
         };
@@ -120,9 +193,9 @@ pub fn destroy_virtual_sink(module_id: u32) -> Result<(), String> {
     }
 }
 
-/// Finds the PulseAudio module ID for the VoidMic_Clean null-sink.
+/// Finds the PulseAudio module ID of the `module` instance owning `sink_name`.
 #[cfg(target_os = "linux")]
-fn find_voidmic_module_id() -> Option<u32> {
+fn find_module_id(module: &str, sink_name: &str) -> Option<u32> {
     let output = Command::new("pactl")
         .args(["list", "short", "modules"])
         .output()
@@ -135,7 +208,7 @@ fn find_voidmic_module_id() -> Option<u32> {
     let text = String::from_utf8_lossy(&output.stdout);
     for line in text.lines() {
         // Format: "ID\tmodule-null-sink\tsink_name=VoidMic_Clean ..."
-        if line.contains("module-null-sink") && line.contains(VIRTUAL_SINK_NAME) {
+        if line.contains(module) && line.contains(sink_name) {
             return line.split_whitespace().next()?.parse().ok();
         }
     }
@@ -184,6 +257,19 @@ mod tests {
         assert!(monitor.starts_with(VIRTUAL_SINK_NAME));
     }
 
+    #[test]
+    fn test_combine_sink_args() {
+        let args = combine_sink_args(&[VIRTUAL_SINK_NAME, "alsa_output.headphones"]);
+        assert_eq!(args[1], "module-combine-sink");
+        assert!(args.contains(&"sink_name=VoidMic_Combined".to_string()));
+        assert!(args.contains(&"slaves=VoidMic_Clean,alsa_output.headphones".to_string()));
+    }
+
+    #[test]
+    fn test_combine_sink_needs_two_slaves() {
+        assert!(create_combine_sink(&[VIRTUAL_SINK_NAME]).is_err());
+    }
+
     #[test]
     fn test_virtual_device_struct_construction() {
         let device = VirtualDevice {
@@ -4,4 +4,8 @@
 pub const SAMPLE_RATE: u32 = 48000;
 
 /// Frame size in samples (10ms at 48kHz = 480 samples)
+///
+/// Fixed by RNNoise and the WebRTC VAD, which only take frames of this size, so it sets
+/// the processor's latency and how often settings are picked up. The level gate can
+/// decide more often than once per frame; see `VoidProcessor::gate_subblocks`.
 pub const FRAME_SIZE: usize = 480;
@@ -0,0 +1,229 @@
+//! Convolution with a measured impulse response, for room/headphone correction.
+//!
+//! Uses uniformly partitioned FFT convolution: the impulse response is cut into
+//! `FRAME_SIZE` blocks whose spectra are multiplied with those of the latest input
+//! frames, and each frame's result is overlap-added into the next, so responses far
+//! longer than a frame cost one FFT pair per frame and add no latency.
+
+use crate::constants::{FRAME_SIZE, SAMPLE_RATE};
+use crate::processor::{fft_in_place, fft_twiddles, sanitize_sample};
+
+// Holds one frame plus one impulse response block without circular wrap-around
+const CONVOLVER_FFT_SIZE: usize = 1024;
+const CONVOLVER_BINS: usize = CONVOLVER_FFT_SIZE / 2 + 1;
+
+/// Longest impulse response used (2s at 48kHz); anything past it is dropped.
+pub const MAX_IMPULSE_RESPONSE_LEN: usize = 2 * SAMPLE_RATE as usize;
+
+/// Convolves a mono signal with an impulse response, one frame at a time.
+pub struct PartitionedConvolver {
+    twiddles: Vec<(f32, f32)>,
+    // Spectrum of each impulse response block: partition `p` occupies
+    // `[p * CONVOLVER_BINS..(p + 1) * CONVOLVER_BINS]`
+    ir_re: Vec<f32>,
+    ir_im: Vec<f32>,
+    // Spectra of the latest input frames in the same layout, newest at `head`
+    input_re: Vec<f32>,
+    input_im: Vec<f32>,
+    head: usize,
+    partitions: usize,
+    // Tail of the last frame's result, added to the next output frame
+    overlap: Vec<f32>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+impl PartitionedConvolver {
+    /// Prepares `impulse_response` (48kHz samples, truncated to
+    /// `MAX_IMPULSE_RESPONSE_LEN`). An empty response outputs silence.
+    #[must_use]
+    pub fn new(impulse_response: &[f32]) -> Self {
+        let ir = &impulse_response[..impulse_response.len().min(MAX_IMPULSE_RESPONSE_LEN)];
+        let partitions = ir.len().div_ceil(FRAME_SIZE).max(1);
+        let twiddles = fft_twiddles(CONVOLVER_FFT_SIZE);
+
+        let mut re = vec![0.0; CONVOLVER_FFT_SIZE];
+        let mut im = vec![0.0; CONVOLVER_FFT_SIZE];
+        let mut ir_re = vec![0.0; partitions * CONVOLVER_BINS];
+        let mut ir_im = vec![0.0; partitions * CONVOLVER_BINS];
+        for (p, block) in ir.chunks(FRAME_SIZE).enumerate() {
+            re.fill(0.0);
+            im.fill(0.0);
+            for (dst, &sample) in re.iter_mut().zip(block) {
+                *dst = sanitize_sample(sample);
+            }
+            fft_in_place(&mut re, &mut im, &twiddles, false);
+            let bins = p * CONVOLVER_BINS..(p + 1) * CONVOLVER_BINS;
+            ir_re[bins.clone()].copy_from_slice(&re[..CONVOLVER_BINS]);
+            ir_im[bins].copy_from_slice(&im[..CONVOLVER_BINS]);
+        }
+
+        Self {
+            twiddles,
+            ir_re,
+            ir_im,
+            input_re: vec![0.0; partitions * CONVOLVER_BINS],
+            input_im: vec![0.0; partitions * CONVOLVER_BINS],
+            head: 0,
+            partitions,
+            overlap: vec![0.0; FRAME_SIZE],
+            re,
+            im,
+        }
+    }
+
+    /// Clears the input history, so the previous signal's tail doesn't ring on.
+    pub fn reset(&mut self) {
+        self.input_re.fill(0.0);
+        self.input_im.fill(0.0);
+        self.overlap.fill(0.0);
+    }
+
+    /// Convolves one frame in place (`FRAME_SIZE` samples).
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        if frame.len() != FRAME_SIZE {
+            return;
+        }
+        self.re.fill(0.0);
+        self.im.fill(0.0);
+        self.re[..FRAME_SIZE].copy_from_slice(frame);
+        fft_in_place(&mut self.re, &mut self.im, &self.twiddles, false);
+
+        // The slot of the oldest frame becomes the newest
+        self.head = (self.head + self.partitions - 1) % self.partitions;
+        let newest = self.head * CONVOLVER_BINS..(self.head + 1) * CONVOLVER_BINS;
+        self.input_re[newest.clone()].copy_from_slice(&self.re[..CONVOLVER_BINS]);
+        self.input_im[newest].copy_from_slice(&self.im[..CONVOLVER_BINS]);
+
+        // Frame k back in time meets impulse response block k
+        self.re[..CONVOLVER_BINS].fill(0.0);
+        self.im[..CONVOLVER_BINS].fill(0.0);
+        for p in 0..self.partitions {
+            let input = (self.head + p) % self.partitions * CONVOLVER_BINS;
+            let ir = p * CONVOLVER_BINS;
+            for bin in 0..CONVOLVER_BINS {
+                let (x_re, x_im) = (self.input_re[input + bin], self.input_im[input + bin]);
+                let (h_re, h_im) = (self.ir_re[ir + bin], self.ir_im[ir + bin]);
+                self.re[bin] += x_re * h_re - x_im * h_im;
+                self.im[bin] += x_re * h_im + x_im * h_re;
+            }
+        }
+        // Real output: the upper half mirrors the lower one
+        for bin in CONVOLVER_BINS..CONVOLVER_FFT_SIZE {
+            self.re[bin] = self.re[CONVOLVER_FFT_SIZE - bin];
+            self.im[bin] = -self.im[CONVOLVER_FFT_SIZE - bin];
+        }
+        fft_in_place(&mut self.re, &mut self.im, &self.twiddles, true);
+
+        let scale = 1.0 / CONVOLVER_FFT_SIZE as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            *sample = sanitize_sample(self.re[i].mul_add(scale, self.overlap[i]));
+        }
+        for (i, overlap) in self.overlap.iter_mut().enumerate() {
+            *overlap = self.re[FRAME_SIZE + i] * scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(convolver: &mut PartitionedConvolver, input: &[f32]) -> Vec<f32> {
+        let mut output = Vec::with_capacity(input.len());
+        for chunk in input.chunks(FRAME_SIZE) {
+            let mut frame = [0.0f32; FRAME_SIZE];
+            frame[..chunk.len()].copy_from_slice(chunk);
+            convolver.process_frame(&mut frame);
+            output.extend_from_slice(&frame[..chunk.len()]);
+        }
+        output
+    }
+
+    fn direct_convolution(input: &[f32], ir: &[f32]) -> Vec<f32> {
+        (0..input.len())
+            .map(|n| {
+                ir.iter()
+                    .enumerate()
+                    .take(n + 1)
+                    .map(|(k, &h)| h * input[n - k])
+                    .sum()
+            })
+            .collect()
+    }
+
+    // Deterministic noise-like test signal
+    fn signal(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unit_impulse_passes_signal() {
+        let input = signal(4 * FRAME_SIZE, 1);
+        let mut convolver = PartitionedConvolver::new(&[1.0]);
+        let output = run(&mut convolver, &input);
+        for (out, inp) in output.iter().zip(&input) {
+            assert!((out - inp).abs() < 1e-4, "{} vs {}", out, inp);
+        }
+    }
+
+    #[test]
+    fn test_long_impulse_response_matches_direct_convolution() {
+        // Spans four partitions, so every frame overlaps several earlier ones
+        let ir: Vec<f32> = signal(3 * FRAME_SIZE + 100, 7)
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s * (-(i as f32) / 400.0).exp())
+            .collect();
+        let input = signal(8 * FRAME_SIZE, 3);
+        let mut convolver = PartitionedConvolver::new(&ir);
+        let output = run(&mut convolver, &input);
+        let expected = direct_convolution(&input, &ir);
+        for (i, (out, exp)) in output.iter().zip(&expected).enumerate() {
+            assert!((out - exp).abs() < 1e-3, "sample {}: {} vs {}", i, out, exp);
+        }
+    }
+
+    #[test]
+    fn test_delay_crosses_frames() {
+        let mut ir = vec![0.0f32; FRAME_SIZE + 20];
+        ir[FRAME_SIZE + 10] = 0.5;
+        let mut input = vec![0.0f32; 3 * FRAME_SIZE];
+        input[5] = 1.0;
+        let mut convolver = PartitionedConvolver::new(&ir);
+        let output = run(&mut convolver, &input);
+        let peak = output
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .unwrap();
+        assert_eq!(peak.0, FRAME_SIZE + 15);
+        assert!((peak.1 - 0.5).abs() < 1e-4);
+
+        // Reset drops the ringing tail
+        convolver.reset();
+        let mut frame = [0.0f32; FRAME_SIZE];
+        convolver.process_frame(&mut frame);
+        assert!(frame.iter().all(|s| s.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_empty_and_oversized_responses() {
+        let mut silent = PartitionedConvolver::new(&[]);
+        let mut frame = [0.5f32; FRAME_SIZE];
+        silent.process_frame(&mut frame);
+        assert!(frame.iter().all(|s| s.abs() < 1e-6));
+
+        let long = PartitionedConvolver::new(&vec![0.0; MAX_IMPULSE_RESPONSE_LEN + 5000]);
+        assert_eq!(
+            long.partitions,
+            MAX_IMPULSE_RESPONSE_LEN.div_ceil(FRAME_SIZE)
+        );
+    }
+}
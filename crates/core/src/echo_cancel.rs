@@ -15,9 +15,7 @@ impl EchoCanceller {
     /// Creates a new echo canceller. Returns None if AEC3 initialization fails.
     #[must_use]
     pub fn new() -> Option<Self> {
-        let aec = VoipAec3::builder(SAMPLE_RATE as usize, 1, 1)
-            .build()
-            .ok()?;
+        let aec = VoipAec3::builder(SAMPLE_RATE as usize, 1, 1).build().ok()?;
         Some(Self {
             aec,
             output_buffer: [0.0; FRAME_SIZE],
@@ -33,15 +31,20 @@ impl EchoCanceller {
     ///
     /// # Returns
     /// `true` if processing succeeded, `false` if fallback to raw input was used
-    pub fn process_frame(&mut self, mic_input: &[f32], speaker_ref: &[f32], output: &mut [f32]) -> bool {
+    pub fn process_frame(
+        &mut self,
+        mic_input: &[f32],
+        speaker_ref: &[f32],
+        output: &mut [f32],
+    ) -> bool {
         // Clear output buffer
         self.output_buffer.fill(0.0);
 
         // Process with AEC3
         // level_change = false (we don't track volume changes yet)
-        if let Err(e) = self
-            .aec
-            .process(mic_input, Some(speaker_ref), false, &mut self.output_buffer)
+        if let Err(e) =
+            self.aec
+                .process(mic_input, Some(speaker_ref), false, &mut self.output_buffer)
         {
             log::warn!("AEC error: {e:?}");
             output.copy_from_slice(mic_input); // Fallback to raw input
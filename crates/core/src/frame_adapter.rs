@@ -7,18 +7,21 @@ use crate::processor::VoidProcessor;
 use ringbuf::traits::{Consumer, Observer, Producer};
 use ringbuf::HeapRb;
 
+/// Maximum channel count supported by `FrameAdapter`.
+pub const MAX_CHANNELS: usize = 8;
+
 /// Bridges variable-size audio buffers from plugin hosts to fixed-size
-/// `FRAME_SIZE` stereo frames expected by `VoidProcessor`.
+/// `FRAME_SIZE` frames expected by `VoidProcessor`.
 ///
-/// Internally uses two ring buffers (input and output) to accumulate/drain
-/// samples without blocking.
+/// Internally uses two interleaved ring buffers (input and output) to
+/// accumulate/drain samples without blocking.
 pub struct FrameAdapter {
+    channels: usize,
     rb_in: HeapRb<f32>,
     rb_out: HeapRb<f32>,
-    left_in: [f32; FRAME_SIZE],
-    right_in: [f32; FRAME_SIZE],
-    left_out: [f32; FRAME_SIZE],
-    right_out: [f32; FRAME_SIZE],
+    // Planar scratch frames: channel `c` occupies `[c * FRAME_SIZE..(c + 1) * FRAME_SIZE]`
+    frame_in: Box<[f32]>,
+    frame_out: Box<[f32]>,
 }
 
 impl Default for FrameAdapter {
@@ -28,20 +31,33 @@ impl Default for FrameAdapter {
 }
 
 impl FrameAdapter {
-    /// Creates a new adapter with ring buffers sized for the given channel count.
+    /// Creates a new stereo adapter.
     #[must_use]
     pub fn new() -> Self {
-        let buffer_size = FRAME_SIZE * 4 * 2; // Always stereo
+        Self::with_channels(2)
+    }
+
+    /// Creates a new adapter with ring buffers sized for the given channel count
+    /// (clamped to `1..=MAX_CHANNELS`).
+    #[must_use]
+    pub fn with_channels(channels: usize) -> Self {
+        let channels = channels.clamp(1, MAX_CHANNELS);
+        let buffer_size = FRAME_SIZE * 4 * channels;
         Self {
+            channels,
             rb_in: HeapRb::<f32>::new(buffer_size),
             rb_out: HeapRb::<f32>::new(buffer_size),
-            left_in: [0.0; FRAME_SIZE],
-            right_in: [0.0; FRAME_SIZE],
-            left_out: [0.0; FRAME_SIZE],
-            right_out: [0.0; FRAME_SIZE],
+            frame_in: vec![0.0; FRAME_SIZE * channels].into_boxed_slice(),
+            frame_out: vec![0.0; FRAME_SIZE * channels].into_boxed_slice(),
         }
     }
 
+    /// Number of channels this adapter interleaves.
+    #[must_use]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
     /// Pushes interleaved stereo sample pairs into the input ring buffer.
     pub fn push_stereo_interleaved(&mut self, left: &[f32], right: &[f32]) {
         let len = left.len().min(right.len());
@@ -51,15 +67,36 @@ impl FrameAdapter {
         }
     }
 
-    /// Pushes mono samples, duplicating each to both stereo channels.
+    /// Pushes mono samples, duplicating each to every channel.
     pub fn push_mono(&mut self, mono: &[f32]) {
         for &sample in mono {
-            let _ = self.rb_in.try_push(sample);
-            let _ = self.rb_in.try_push(sample);
+            for _ in 0..self.channels {
+                let _ = self.rb_in.try_push(sample);
+            }
         }
     }
 
-    /// Processes all complete stereo frames available in the input buffer
+    /// Pushes planar (one slice per channel) input. Missing channels are filled
+    /// from the first slice; only the length common to all slices is consumed.
+    pub fn push_planar(&mut self, input: &[&[f32]]) {
+        let Some(first) = input.first() else {
+            return;
+        };
+        let len = input
+            .iter()
+            .take(self.channels)
+            .map(|ch| ch.len())
+            .min()
+            .unwrap_or(0);
+        for i in 0..len {
+            for ch in 0..self.channels {
+                let sample = input.get(ch).unwrap_or(first)[i];
+                let _ = self.rb_in.try_push(sample);
+            }
+        }
+    }
+
+    /// Processes all complete frames available in the input buffer
     /// through the given `VoidProcessor`, pushing results to the output buffer.
     pub fn process_available(
         &mut self,
@@ -68,16 +105,25 @@ impl FrameAdapter {
         threshold: f32,
         dynamic_threshold: bool,
     ) {
-        // Need 2 * FRAME_SIZE samples for a full stereo frame
-        while self.rb_in.occupied_len() >= FRAME_SIZE * 2 {
+        let channels = self.channels;
+        while self.rb_in.occupied_len() >= FRAME_SIZE * channels {
             for j in 0..FRAME_SIZE {
-                self.left_in[j] = self.rb_in.try_pop().unwrap_or(0.0);
-                self.right_in[j] = self.rb_in.try_pop().unwrap_or(0.0);
+                for ch in 0..channels {
+                    self.frame_in[ch * FRAME_SIZE + j] = self.rb_in.try_pop().unwrap_or(0.0);
+                }
             }
 
+            // Build per-channel views on the stack (no allocation on the audio thread)
+            let mut in_chunks = self.frame_in.chunks_exact(FRAME_SIZE);
+            let inputs: [&[f32]; MAX_CHANNELS] =
+                std::array::from_fn(|_| in_chunks.next().unwrap_or(&[]));
+            let mut out_chunks = self.frame_out.chunks_exact_mut(FRAME_SIZE);
+            let mut outputs: [&mut [f32]; MAX_CHANNELS] =
+                std::array::from_fn(|_| out_chunks.next().unwrap_or(&mut []));
+
             processor.process_frame(
-                &[&self.left_in, &self.right_in],
-                &mut [&mut self.left_out, &mut self.right_out],
+                &inputs[..channels],
+                &mut outputs[..channels],
                 None,
                 suppression,
                 threshold,
@@ -85,8 +131,9 @@ impl FrameAdapter {
             );
 
             for j in 0..FRAME_SIZE {
-                let _ = self.rb_out.try_push(self.left_out[j]);
-                let _ = self.rb_out.try_push(self.right_out[j]);
+                for ch in 0..channels {
+                    let _ = self.rb_out.try_push(self.frame_out[ch * FRAME_SIZE + j]);
+                }
             }
         }
     }
@@ -108,14 +155,16 @@ impl FrameAdapter {
         count
     }
 
-    /// Pops processed output as mono (averages L+R). Returns number of samples written.
+    /// Pops processed output as mono (averages all channels). Returns number of samples written.
     pub fn pop_mono(&mut self, out: &mut [f32]) -> usize {
         let mut count = 0;
         for sample in out.iter_mut() {
-            if self.rb_out.occupied_len() >= 2 {
-                let l = self.rb_out.try_pop().unwrap_or(0.0);
-                let r = self.rb_out.try_pop().unwrap_or(0.0);
-                *sample = (l + r) * 0.5;
+            if self.rb_out.occupied_len() >= self.channels {
+                let mut sum = 0.0;
+                for _ in 0..self.channels {
+                    sum += self.rb_out.try_pop().unwrap_or(0.0);
+                }
+                *sample = sum / self.channels as f32;
                 count += 1;
             } else {
                 *sample = 0.0;
@@ -123,6 +172,34 @@ impl FrameAdapter {
         }
         count
     }
+
+    /// Pops processed planar output. Channels beyond the adapter's count are zeroed.
+    /// Returns the number of samples per channel written from processed audio;
+    /// the remainder (if the output buffer isn't primed yet) is zero-filled.
+    pub fn pop_planar(&mut self, output: &mut [&mut [f32]]) -> usize {
+        let len = output.iter().map(|ch| ch.len()).min().unwrap_or(0);
+        let mut count = 0;
+        for i in 0..len {
+            let available = self.rb_out.occupied_len() >= self.channels;
+            for ch in 0..self.channels {
+                let sample = if available {
+                    self.rb_out.try_pop().unwrap_or(0.0)
+                } else {
+                    0.0
+                };
+                if let Some(out_ch) = output.get_mut(ch) {
+                    out_ch[i] = sample;
+                }
+            }
+            for out_ch in output.iter_mut().skip(self.channels) {
+                out_ch[i] = 0.0;
+            }
+            if available {
+                count += 1;
+            }
+        }
+        count
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +235,22 @@ mod tests {
         assert_eq!(adapter.rb_in.occupied_len(), 8);
     }
 
+    #[test]
+    fn test_mono_adapter_planar_roundtrip() {
+        let mut adapter = FrameAdapter::with_channels(1);
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+
+        let input = [0.0f32; FRAME_SIZE];
+        adapter.push_planar(&[&input]);
+        assert_eq!(adapter.rb_in.occupied_len(), FRAME_SIZE);
+
+        adapter.process_available(&mut processor, 1.0, 0.015, false);
+
+        let mut out = [1.0f32; FRAME_SIZE];
+        let count = adapter.pop_planar(&mut [&mut out]);
+        assert_eq!(count, FRAME_SIZE);
+    }
+
     #[test]
     fn test_partial_frame_does_not_process() {
         let mut adapter = FrameAdapter::new();
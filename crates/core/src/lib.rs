@@ -1,8 +1,12 @@
 pub mod constants;
+pub mod convolver;
 pub mod echo_cancel;
 pub mod frame_adapter;
 pub mod processor;
+pub mod resampler;
 
+pub use convolver::PartitionedConvolver;
 pub use frame_adapter::FrameAdapter;
 pub use nnnoiseless::DenoiseState;
-pub use processor::VoidProcessor;
+pub use processor::{ProcessorControls, VoidProcessor};
+pub use resampler::{LinearResampler, RateBridge};
@@ -1,27 +1,309 @@
 use crate::constants::{FRAME_SIZE, SAMPLE_RATE};
 use crate::echo_cancel::EchoCanceller;
+use crate::frame_adapter::{FrameAdapter, MAX_CHANNELS};
 use anyhow::{anyhow, Result};
 use biquad::{Biquad, Coefficients, DirectForm2Transposed, ToHertz, Type};
-use crossbeam_channel::Sender;
+use crossbeam_channel::{Receiver, Sender};
 use nnnoiseless::DenoiseState;
 use spectrum_analyzer::scaling::divide_by_N_sqrt;
 use spectrum_analyzer::{samples_fft_to_spectrum, FrequencyLimit};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use webrtc_vad::{Vad, VadMode};
 
+// Speech-gated suppression: per-frame step of the speech/silence crossfade
+// (fast to let consonants through, slower to settle back into full suppression)
+const SPEECH_BLEND_ATTACK: f32 = 0.5;
+const SPEECH_BLEND_RELEASE: f32 = 0.05;
+
+// Time constants of the ramps that live changes to the suppression strength (per
+// sample) and the EQ gains (per frame) follow, so slider moves don't zipper
+const SUPPRESSION_RAMP_MS: f32 = 10.0;
+const EQ_RAMP_MS: f32 = 20.0;
+
+/// Gain reduction reported for a fully closed gate (silence), in dB.
+pub const GAIN_REDUCTION_MAX_DB: f32 = 60.0;
+
+/// Output magnitude counted as a clip by `clip_count`.
+pub const CLIP_LEVEL: f32 = 0.999;
+
+/// Attenuation (positive dB, capped at `GAIN_REDUCTION_MAX_DB`) of a linear gain;
+/// boosts count as 0.
+fn gain_reduction_db(gain: f32) -> f32 {
+    if gain.is_nan() || gain <= 0.0 {
+        return GAIN_REDUCTION_MAX_DB;
+    }
+    (-20.0 * gain.log10()).clamp(0.0, GAIN_REDUCTION_MAX_DB)
+}
+
+// Upper bound on spectrum bins for a FRAME_SIZE real FFT
+const SPECTRUM_MAX_BINS: usize = FRAME_SIZE / 2 + 1;
+// Spectrum/waveform buffer pairs circulating between the audio thread and the GUI
+const SPECTRUM_POOL_SIZE: usize = 4;
+// Pooled buffers hold a whole time-domain frame, which also covers the spectrum bins
+const VISUAL_BUFFER_LEN: usize = FRAME_SIZE;
+/// Frequency range of the published spectrum
+pub const SPECTRUM_MIN_HZ: f32 = 20.0;
+pub const SPECTRUM_MAX_HZ: f32 = 20_000.0;
+
+/// What a visualizer buffer pair carries.
+#[derive(Clone, Copy)]
+enum VisualKind {
+    Spectrum,
+    Waveform,
+}
+
+impl VisualKind {
+    fn max_len(self) -> usize {
+        match self {
+            VisualKind::Spectrum => SPECTRUM_MAX_BINS,
+            VisualKind::Waveform => FRAME_SIZE,
+        }
+    }
+}
+
+/// Centre frequency (Hz) of the `index`-th published spectrum bin: FFT bins are
+/// `SAMPLE_RATE / FRAME_SIZE` apart, starting at the first one inside the range.
+pub fn spectrum_bin_hz(index: usize) -> f32 {
+    let spacing = SAMPLE_RATE as f32 / FRAME_SIZE as f32;
+    ((SPECTRUM_MIN_HZ / spacing).ceil() + index as f32) * spacing
+}
+
 // Gate timing constants (all in milliseconds)
 const ATTACK_MS: u32 = 5;
 const RELEASE_MS: u32 = 200;
 const FADE_MS: u32 = 10;
+// Decay rate of the exponential gate fade over its length
+const FADE_EXP_RATE: f32 = 5.0;
+// Upper bound for the configurable hold time
+pub const GATE_HOLD_MAX_MS: u32 = 2000;
+/// `gate_hysteresis` default and lower bound: an open gate closes only once the level
+/// falls below threshold * ratio, so a level hovering at the threshold can't chatter.
+/// 1.0 disables hysteresis.
+pub const GATE_HYSTERESIS_DEFAULT: f32 = 0.8;
+pub const GATE_HYSTERESIS_MIN: f32 = 0.5;
+/// Most gate decisions per frame (`gate_subblocks`): 8 sub-blocks of 60 samples, 1.25 ms.
+/// RNNoise and the VAD need whole frames, but the level gate can decide more often.
+pub const GATE_MAX_SUBBLOCKS: u32 = 8;
+// Auto-suspend: default and longest gate-closed silence before the DSP chain is skipped
+pub const AUTO_SUSPEND_DEFAULT_SECS: u32 = 30;
+pub const AUTO_SUSPEND_MAX_SECS: u32 = 600;
+// Deepest attenuation the expander applies (-60 dB)
+const EXPANDER_FLOOR: f32 = 0.001;
+
+// Consecutive VAD errors (0.5s of frames) before giving up on the VAD for good
+const VAD_ERROR_LIMIT: u32 = 50;
+/// Longest VAD smoothing window (`vad_window_frames`), 160 ms.
+pub const VAD_MAX_WINDOW_FRAMES: u32 = 16;
+/// Longest VAD hangover (`vad_hangover_frames`), 500 ms.
+pub const VAD_MAX_HANGOVER_FRAMES: u32 = 50;
+
+// Silent frames (100ms) fed to a fresh RNNoise state so its fade-in isn't heard
+const DENOISE_PRIME_FRAMES: usize = 10;
+
+/// Creates an RNNoise state that has already run through `DENOISE_PRIME_FRAMES` of
+/// silence. A cold state fades in and sounds off for the first ~100ms of audio.
+fn primed_denoise_state() -> Box<DenoiseState<'static>> {
+    let mut state = DenoiseState::new();
+    let silence = [0.0f32; FRAME_SIZE];
+    let mut scratch = [0.0f32; FRAME_SIZE];
+    for _ in 0..DENOISE_PRIME_FRAMES {
+        state.process_frame(&mut scratch, &silence);
+    }
+    state
+}
+
+// Calibration listens for this long before suggesting a threshold
+const CALIBRATION_SECS: u32 = 3;
+// Hard cap on buffered calibration frames (3s at up to 192kHz) so a stuck calibration can't grow memory
+const CALIBRATION_MAX_FRAMES: usize = 1200;
+
+// Gain calibration aims typical speech at this RMS level
+const GAIN_CALIBRATION_TARGET_DBFS: f32 = -18.0;
+// Input gain range (also caps calibration suggestions)
+pub const INPUT_GAIN_MIN_DB: f32 = -20.0;
+pub const INPUT_GAIN_MAX_DB: f32 = 20.0;
+// Below this RMS (-60 dBFS) nobody spoke; don't suggest a huge boost for silence
+const GAIN_CALIBRATION_MIN_RMS: f32 = 0.001;
+
+/// Suggests an input gain (dB) that brings speech at `speech_rms` to
+/// `GAIN_CALIBRATION_TARGET_DBFS`, clamped to the input gain range. Returns 0 dB
+/// if the measured level is too low to be speech.
+pub fn suggest_input_gain_db(speech_rms: f32) -> f32 {
+    if !speech_rms.is_finite() || speech_rms < GAIN_CALIBRATION_MIN_RMS {
+        return 0.0;
+    }
+    let speech_dbfs = 20.0 * speech_rms.log10();
+    (GAIN_CALIBRATION_TARGET_DBFS - speech_dbfs).clamp(INPUT_GAIN_MIN_DB, INPUT_GAIN_MAX_DB)
+}
+
+/// Moves a gate threshold measured with `from_db` of input gain onto the level scale of
+/// `to_db`, since the gate compares against the level after input gain.
+#[must_use]
+pub fn rescale_threshold(threshold: f32, from_db: f32, to_db: f32) -> f32 {
+    threshold * 10f32.powf((to_db - from_db) / 20.0)
+}
+
+/// Typical speech level from per-frame RMS values: the mean of the louder half,
+/// so pauses between words don't drag the estimate down. Reorders `frames`.
+fn speech_level(frames: &mut [f32]) -> f32 {
+    if frames.is_empty() {
+        return 0.0;
+    }
+    frames.sort_unstable_by(|a, b| b.total_cmp(a));
+    let loud = &frames[..frames.len().div_ceil(2)];
+    loud.iter().sum::<f32>() / loud.len() as f32
+}
+
+/// Replaces NaN/Inf with silence so a single bad driver sample can't poison filter
+/// or gain state for the rest of the session.
+#[inline]
+pub(crate) fn sanitize_sample(sample: f32) -> f32 {
+    if sample.is_finite() {
+        sample
+    } else {
+        0.0
+    }
+}
+
+/// Mid-side stereo width: 0.0 = mono, 1.0 = unchanged, >1.0 = wider.
+fn apply_stereo_width(left: &mut [f32], right: &mut [f32], width: f32) {
+    if (width - 1.0).abs() < f32::EPSILON {
+        return;
+    }
+    for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+        let mid = (*l + *r) * 0.5;
+        let side = (*l - *r) * 0.5 * width;
+        *l = mid + side;
+        *r = mid - side;
+    }
+}
+
+/// One 16-bit step; the dither is scaled to quantization at that depth.
+const DITHER_LSB: f32 = 1.0 / 32768.0;
+
+/// TPDF dither: the sum of two uniform values, spanning ±1 LSB with a triangular
+/// distribution, which decorrelates quantization error from the signal.
+struct Dither {
+    // xorshift32, cheap enough for every output sample
+    state: u32,
+}
+
+impl Dither {
+    fn new() -> Self {
+        Self { state: 0x9E37_79B9 }
+    }
+
+    /// Uniform in [-0.5, 0.5) LSB.
+    fn uniform(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        ((self.state >> 8) as f32 / (1u32 << 24) as f32 - 0.5) * DITHER_LSB
+    }
+
+    /// Adds dither to every channel. Digital silence (a closed gate) is left silent, so
+    /// downstream silence detection still works.
+    fn process_frame(&mut self, frames: &mut [&mut [f32]]) {
+        for channel in frames.iter_mut() {
+            if channel.iter().all(|&sample| sample == 0.0) {
+                continue;
+            }
+            for sample in channel.iter_mut() {
+                let noise = self.uniform() + self.uniform();
+                *sample = (*sample + noise).clamp(-1.0, 1.0);
+            }
+        }
+    }
+}
+
+/// Longest noise floor window (10s at 100 frames/sec); bounds the tracker's ring buffer.
+pub const NOISE_FLOOR_MAX_WINDOW: usize = 1000;
+// Frames per smoothing time constant at the default window: 300 / 15 gives the original 0.05
+const NOISE_FLOOR_SMOOTHING_DIVISOR: f32 = 15.0;
+// Frames seen before the floor starts moving (and the shortest allowed window)
+const NOISE_FLOOR_WARMUP_FRAMES: usize = 10;
+
+/// Default range the dynamic gate threshold is clamped to.
+pub const DYNAMIC_THRESHOLD_DEFAULT_MIN: f32 = 0.005;
+pub const DYNAMIC_THRESHOLD_DEFAULT_MAX: f32 = 0.08;
+/// Widest range `dynamic_threshold_min`/`max` accept (about -66 to -14 dBFS).
+pub const DYNAMIC_THRESHOLD_LOWEST: f32 = 0.0005;
+pub const DYNAMIC_THRESHOLD_HIGHEST: f32 = 0.2;
+// Margin the dynamic threshold keeps above the noise floor, capped at the minimum
+const DYNAMIC_THRESHOLD_MARGIN: f32 = 0.003;
+
+/// Validates a dynamic threshold range: both ends are kept within
+/// `DYNAMIC_THRESHOLD_LOWEST..=DYNAMIC_THRESHOLD_HIGHEST`, and a maximum below the
+/// minimum is raised to it.
+#[must_use]
+pub fn dynamic_threshold_range(min: f32, max: f32) -> (f32, f32) {
+    let min = sanitize_sample(min).clamp(DYNAMIC_THRESHOLD_LOWEST, DYNAMIC_THRESHOLD_HIGHEST);
+    let max = sanitize_sample(max).clamp(min, DYNAMIC_THRESHOLD_HIGHEST);
+    (min, max)
+}
+
+/// How quickly the dynamic gate's noise floor follows changes in the room.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoiseFloorSpeed {
+    /// 3s window
+    #[default]
+    Normal,
+    /// 10s window; rides out intermittent noise
+    Slow,
+    /// 1s window; follows a changing environment
+    Fast,
+}
+
+impl NoiseFloorSpeed {
+    pub const ALL: [NoiseFloorSpeed; 3] = [
+        NoiseFloorSpeed::Slow,
+        NoiseFloorSpeed::Normal,
+        NoiseFloorSpeed::Fast,
+    ];
+
+    /// Decodes the value stored in `VoidProcessor::noise_floor_speed`; unknown values fall
+    /// back to `Normal`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => NoiseFloorSpeed::Slow,
+            2 => NoiseFloorSpeed::Fast,
+            _ => NoiseFloorSpeed::Normal,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoiseFloorSpeed::Slow => "Slow",
+            NoiseFloorSpeed::Normal => "Normal",
+            NoiseFloorSpeed::Fast => "Fast",
+        }
+    }
+
+    /// Window length in frames for `NoiseFloorTracker::with_window`.
+    pub fn window(self) -> usize {
+        match self {
+            NoiseFloorSpeed::Slow => 1000,
+            NoiseFloorSpeed::Normal => 300,
+            NoiseFloorSpeed::Fast => 100,
+        }
+    }
+}
 
 /// Tracks minimum RMS over a sliding window to estimate noise floor.
-/// Uses a fixed-size ring buffer (3s at 100 frames/sec) to avoid allocations.
+/// The minimum comes from a monotonic deque in a fixed-size ring (up to
+/// `NOISE_FLOOR_MAX_WINDOW` frames) to avoid allocations; the window length also sets
+/// how smoothly the floor moves.
 pub struct NoiseFloorTracker {
-    window: [f32; 300],
-    write_idx: usize,
-    count: usize,
+    // (frame, rms) with rms strictly increasing from the front, which is the window minimum
+    minima: [(u64, f32); NOISE_FLOOR_MAX_WINDOW],
+    head: usize,
+    queued: usize,
+    len: usize,
+    smoothing: f32,
+    frame: u64,
     current_floor: f32,
+    frozen: bool,
 }
 
 impl Default for NoiseFloorTracker {
@@ -31,42 +313,85 @@ impl Default for NoiseFloorTracker {
 }
 
 impl NoiseFloorTracker {
+    /// Tracker with the default 3s window.
     pub fn new() -> Self {
-        Self {
-            window: [0.0; 300],
-            write_idx: 0,
-            count: 0,
+        Self::with_window(NoiseFloorSpeed::Normal.window())
+    }
+
+    /// Tracker over the last `window` frames, capped at `NOISE_FLOOR_MAX_WINDOW`.
+    pub fn with_window(window: usize) -> Self {
+        let mut tracker = Self {
+            minima: [(0, 0.0); NOISE_FLOOR_MAX_WINDOW],
+            head: 0,
+            queued: 0,
+            len: 0,
+            smoothing: 0.0,
+            frame: 0,
             current_floor: 0.01,
+            frozen: false,
+        };
+        tracker.set_window(window);
+        tracker
+    }
+
+    /// Changes the window length. History is discarded; the current floor is kept so
+    /// the gate doesn't jump.
+    pub fn set_window(&mut self, window: usize) {
+        self.len = window.clamp(NOISE_FLOOR_WARMUP_FRAMES, NOISE_FLOOR_MAX_WINDOW);
+        self.smoothing = (NOISE_FLOOR_SMOOTHING_DIVISOR / self.len as f32).min(1.0);
+        self.head = 0;
+        self.queued = 0;
+        self.frame = 0;
+    }
+
+    /// Holds the current floor while set; `update` ignores new levels. Unfreezing
+    /// starts a fresh window from the held floor.
+    pub fn set_frozen(&mut self, frozen: bool) {
+        if self.frozen && !frozen {
+            self.head = 0;
+            self.queued = 0;
+            self.frame = 0;
         }
+        self.frozen = frozen;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
     }
 
     pub fn update(&mut self, rms: f32) {
-        self.window[self.write_idx] = rms;
-        self.write_idx = (self.write_idx + 1) % 300;
-        if self.count < 300 {
-            self.count += 1;
-        }
-
-        // Find 10th percentile without allocation
-        // Simple approach: track running minimum with decay
-        if self.count >= 10 {
-            // Find minimum in recent samples (last 30 = ~300ms)
-            let start = if self.count >= 30 {
-                (self.write_idx + 300 - 30) % 300
-            } else {
-                0
-            };
-            let mut min_val = f32::MAX;
-            for i in 0..30.min(self.count) {
-                let idx = (start + i) % 300;
-                if self.window[idx] < min_val && self.window[idx] > 0.0001 {
-                    min_val = self.window[idx];
+        if self.frozen {
+            return;
+        }
+        self.frame += 1;
+
+        // Drop minima that have slid out of the window
+        while self.queued > 0 && self.minima[self.head].0 + self.len as u64 <= self.frame {
+            self.head = (self.head + 1) % NOISE_FLOOR_MAX_WINDOW;
+            self.queued -= 1;
+        }
+
+        // Near-silence (muted/disconnected input) isn't a noise floor
+        if rms > 0.0001 {
+            // Anything louder than the new value can never be the minimum again
+            while self.queued > 0 {
+                let back = (self.head + self.queued - 1) % NOISE_FLOOR_MAX_WINDOW;
+                if self.minima[back].1 < rms {
+                    break;
                 }
+                self.queued -= 1;
             }
-            if min_val < f32::MAX {
-                // Smooth transition
-                self.current_floor = self.current_floor.mul_add(0.95, min_val * 0.05);
-            }
+            let tail = (self.head + self.queued) % NOISE_FLOOR_MAX_WINDOW;
+            self.minima[tail] = (self.frame, rms);
+            self.queued += 1;
+        }
+
+        if self.frame >= NOISE_FLOOR_WARMUP_FRAMES as u64 && self.queued > 0 {
+            // Smooth transition
+            let min_val = self.minima[self.head].1;
+            self.current_floor = self
+                .current_floor
+                .mul_add(1.0 - self.smoothing, min_val * self.smoothing);
         }
     }
 
@@ -75,43 +400,135 @@ impl NoiseFloorTracker {
     }
 }
 
+// Number of frequency points used to estimate the EQ's broadband gain
+const EQ_GAIN_ESTIMATE_POINTS: usize = 256;
+
+/// Shelf gain (dB) at full warmth/brightness on the tilt macro.
+pub const EQ_TILT_MAX_DB: f32 = 6.0;
+
+/// Low/high shelf offsets (dB) for a warmth/brightness tilt in `-1.0..=1.0`.
+/// Positive is warmer (lows up, highs down), negative brighter; the two shelves
+/// always move by the same amount in opposite directions.
+pub fn eq_tilt_gains(tilt: f32) -> (f32, f32) {
+    let tilt = sanitize_sample(tilt).clamp(-1.0, 1.0);
+    (tilt * EQ_TILT_MAX_DB, -tilt * EQ_TILT_MAX_DB)
+}
+
+/// `(sin w, cos w, sin 2w, cos 2w)` at each point of the makeup estimate's frequency
+/// grid. It doesn't depend on the gains, so it's built once (by the first EQ created).
+fn eq_response_grid() -> &'static [[f32; 4]; EQ_GAIN_ESTIMATE_POINTS] {
+    static GRID: OnceLock<[[f32; 4]; EQ_GAIN_ESTIMATE_POINTS]> = OnceLock::new();
+    GRID.get_or_init(|| {
+        let mut grid = [[0.0; 4]; EQ_GAIN_ESTIMATE_POINTS];
+        for (k, point) in grid.iter_mut().enumerate() {
+            let w = std::f32::consts::PI * (k as f32 + 0.5) / EQ_GAIN_ESTIMATE_POINTS as f32;
+            let (s1, c1) = w.sin_cos();
+            let (s2, c2) = (2.0 * w).sin_cos();
+            *point = [s1, c1, s2, c2];
+        }
+        grid
+    })
+}
+
+/// Squared magnitude response of a biquad at one point of `eq_response_grid`.
+fn biquad_power_response(c: &Coefficients<f32>, [s1, c1, s2, c2]: [f32; 4]) -> f32 {
+    let num_re = c.b0 + c.b1 * c1 + c.b2 * c2;
+    let num_im = -(c.b1 * s1 + c.b2 * s2);
+    let den_re = 1.0 + c.a1 * c1 + c.a2 * c2;
+    let den_im = -(c.a1 * s1 + c.a2 * s2);
+    (num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im).max(f32::EPSILON)
+}
+
+/// Trim that cancels the cascade's net gain on white noise (mean power over a
+/// linear frequency grid).
+fn eq_makeup_gain(bands: &[Coefficients<f32>; 3]) -> f32 {
+    let mut total = 0.0f32;
+    for &point in eq_response_grid() {
+        total += bands
+            .iter()
+            .map(|c| biquad_power_response(c, point))
+            .product::<f32>();
+    }
+    let mean_power = total / EQ_GAIN_ESTIMATE_POINTS as f32;
+    if mean_power.is_finite() && mean_power > 0.0 {
+        1.0 / mean_power.sqrt()
+    } else {
+        1.0
+    }
+}
+
+/// Low shelf (200 Hz), peaking (1 kHz) and high shelf (4 kHz) coefficients for the
+/// given gains.
+fn eq_band_coefficients(
+    low_gain_db: f32,
+    mid_gain_db: f32,
+    high_gain_db: f32,
+) -> Result<[Coefficients<f32>; 3]> {
+    let fs = SAMPLE_RATE.hz();
+
+    let low_coeffs =
+        Coefficients::<f32>::from_params(Type::LowShelf(low_gain_db), fs, 200.0.hz(), 0.707)
+            .map_err(|e| anyhow!("Failed to create low shelf filter: {:?}", e))?;
+
+    let mid_coeffs =
+        Coefficients::<f32>::from_params(Type::PeakingEQ(mid_gain_db), fs, 1000.0.hz(), 1.0)
+            .map_err(|e| anyhow!("Failed to create peaking filter: {:?}", e))?;
+
+    let high_coeffs =
+        Coefficients::<f32>::from_params(Type::HighShelf(high_gain_db), fs, 4000.0.hz(), 0.707)
+            .map_err(|e| anyhow!("Failed to create high shelf filter: {:?}", e))?;
+
+    Ok([low_coeffs, mid_coeffs, high_coeffs])
+}
+
 /// Three-band Equalizer using Biquad filters
 pub struct ThreeBandEq {
     low_shelf: DirectForm2Transposed<f32>,
     peaking: DirectForm2Transposed<f32>,
     high_shelf: DirectForm2Transposed<f32>,
+    makeup_gain: f32,
+    auto_gain: bool,
 }
 
 impl ThreeBandEq {
     pub fn new(low_gain_db: f32, mid_gain_db: f32, high_gain_db: f32) -> Result<Self> {
-        let fs = SAMPLE_RATE.hz();
-
-        // Low Shelf: 200 Hz
-        let low_coeffs =
-            Coefficients::<f32>::from_params(Type::LowShelf(low_gain_db), fs, 200.0.hz(), 0.707)
-                .map_err(|e| anyhow!("Failed to create low shelf filter: {:?}", e))?;
-
-        // Peaking: 1000 Hz
-        let mid_coeffs =
-            Coefficients::<f32>::from_params(Type::PeakingEQ(mid_gain_db), fs, 1000.0.hz(), 1.0)
-                .map_err(|e| anyhow!("Failed to create peaking filter: {:?}", e))?;
-
-        // High Shelf: 4000 Hz
-        let high_coeffs =
-            Coefficients::<f32>::from_params(Type::HighShelf(high_gain_db), fs, 4000.0.hz(), 0.707)
-                .map_err(|e| anyhow!("Failed to create high shelf filter: {:?}", e))?;
-
+        let bands = eq_band_coefficients(low_gain_db, mid_gain_db, high_gain_db)?;
+        let [low_coeffs, mid_coeffs, high_coeffs] = bands;
         Ok(Self {
             low_shelf: DirectForm2Transposed::<f32>::new(low_coeffs),
             peaking: DirectForm2Transposed::<f32>::new(mid_coeffs),
             high_shelf: DirectForm2Transposed::<f32>::new(high_coeffs),
+            makeup_gain: eq_makeup_gain(&bands),
+            auto_gain: false,
         })
     }
 
     pub fn process(&mut self, sample: f32) -> f32 {
         let l = self.low_shelf.run(sample);
         let m = self.peaking.run(l);
-        self.high_shelf.run(m)
+        let out = self.high_shelf.run(m);
+        if self.auto_gain {
+            out * self.makeup_gain
+        } else {
+            out
+        }
+    }
+
+    /// Linear trim that keeps broadband loudness constant for the current gains.
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    /// Enables applying `makeup_gain()` to the output.
+    pub fn set_auto_gain(&mut self, enabled: bool) {
+        self.auto_gain = enabled;
+    }
+
+    /// Clears the filter history (e.g. after it has been corrupted by non-finite input).
+    pub fn reset(&mut self) {
+        self.low_shelf.reset_state();
+        self.peaking.reset_state();
+        self.high_shelf.reset_state();
     }
 
     pub fn update_gains(
@@ -120,32 +537,252 @@ impl ThreeBandEq {
         mid_gain_db: f32,
         high_gain_db: f32,
     ) -> Result<()> {
-        let fs = SAMPLE_RATE.hz();
+        let bands = eq_band_coefficients(low_gain_db, mid_gain_db, high_gain_db)?;
+        self.set_bands(&bands, Some(eq_makeup_gain(&bands)));
+        Ok(())
+    }
 
-        let low_coeffs =
-            Coefficients::<f32>::from_params(Type::LowShelf(low_gain_db), fs, 200.0.hz(), 0.707)
-                .map_err(|e| anyhow!("Failed to update low shelf: {:?}", e))?;
+    /// Switches to coefficients from `eq_band_coefficients`, so several channels can
+    /// share one computation. The makeup gain is left as it was when `None`.
+    fn set_bands(&mut self, bands: &[Coefficients<f32>; 3], makeup_gain: Option<f32>) {
+        let [low_coeffs, mid_coeffs, high_coeffs] = *bands;
         self.low_shelf.update_coefficients(low_coeffs);
-
-        let mid_coeffs =
-            Coefficients::<f32>::from_params(Type::PeakingEQ(mid_gain_db), fs, 1000.0.hz(), 1.0)
-                .map_err(|e| anyhow!("Failed to update peaking: {:?}", e))?;
         self.peaking.update_coefficients(mid_coeffs);
-
-        let high_coeffs =
-            Coefficients::<f32>::from_params(Type::HighShelf(high_gain_db), fs, 4000.0.hz(), 0.707)
-                .map_err(|e| anyhow!("Failed to update high shelf: {:?}", e))?;
         self.high_shelf.update_coefficients(high_coeffs);
-        Ok(())
+        if let Some(gain) = makeup_gain {
+            self.makeup_gain = gain;
+        }
+    }
+}
+
+// AGC time constants matching the original per-frame coefficients (0.1 / 0.005)
+pub const AGC_DEFAULT_ATTACK_MS: f32 = 95.0;
+pub const AGC_DEFAULT_RELEASE_MS: f32 = 2000.0;
+
+/// Peak ceiling of the AGC output (dBFS); the default matches the original ±0.99 clamp.
+pub const AGC_CEILING_DEFAULT_DB: f32 = -0.09;
+pub const AGC_CEILING_MIN_DB: f32 = -12.0;
+
+/// Per-frame smoothing coefficient for a time constant of `ms`.
+fn frame_coeff(ms: f32) -> f32 {
+    let frame_ms = FRAME_SIZE as f32 * 1000.0 / SAMPLE_RATE as f32;
+    1.0 - (-frame_ms / ms.max(frame_ms)).exp()
+}
+
+// Frequencies above this keep their unsuppressed content when "protect highs" is on
+const PROTECT_HIGHS_HZ: f32 = 8000.0;
+
+fn protect_highs_filter() -> Result<DirectForm2Transposed<f32>> {
+    let coeffs = Coefficients::<f32>::from_params(
+        Type::HighPass,
+        SAMPLE_RATE.hz(),
+        PROTECT_HIGHS_HZ.hz(),
+        0.707,
+    )
+    .map_err(|e| anyhow!("Failed to create protect-highs filter: {:?}", e))?;
+    Ok(DirectForm2Transposed::<f32>::new(coeffs))
+}
+
+/// Adds back the part of `dry - processed` above the filter's cutoff, so the protected
+/// band keeps the original signal while lower bands stay processed.
+fn restore_high_band(filter: &mut DirectForm2Transposed<f32>, dry: &[f32], processed: &mut [f32]) {
+    for (out, &original) in processed.iter_mut().zip(dry) {
+        let restored = filter.run(original - *out);
+        *out = sanitize_sample(*out + restored);
+    }
+}
+
+// Spectral gate: two-frame sqrt-Hann windows (50% overlap, so analysis x synthesis sums
+// to one) zero-padded to a power-of-two FFT. Output lags the input by one frame.
+const SPECTRAL_GATE_WINDOW: usize = 2 * FRAME_SIZE;
+const SPECTRAL_GATE_FFT_SIZE: usize = 1024;
+const SPECTRAL_GATE_BINS: usize = SPECTRAL_GATE_FFT_SIZE / 2 + 1;
+// A bin opens once it is this far above its noise estimate (+6 dB)
+const SPECTRAL_GATE_MARGIN: f32 = 2.0;
+// Gain of a closed bin (-20 dB): noise bands are thinned out rather than cut to silence
+const SPECTRAL_GATE_FLOOR: f32 = 0.1;
+// Per-frame rise of a bin's noise estimate (~3 dB/s); it falls to any new minimum at once
+const SPECTRAL_GATE_NOISE_RISE: f32 = 1.0035;
+const SPECTRAL_GATE_NOISE_MIN: f32 = 1e-5;
+// Per-frame gain decay of a closing bin, so bins don't flicker (musical noise)
+const SPECTRAL_GATE_RELEASE: f32 = 0.7;
+
+/// `(cos, sin)` of `-2πk/n` for `k < n/2`, as `fft_in_place` takes them.
+pub(crate) fn fft_twiddles(n: usize) -> Vec<(f32, f32)> {
+    (0..n / 2)
+        .map(|k| {
+            let angle = -2.0 * std::f32::consts::PI * k as f32 / n as f32;
+            (angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+/// In-place radix-2 FFT over split real/imaginary buffers. `twiddles` holds
+/// `(cos, sin)` of `-2πk/n` for `k < n/2`. The inverse is left unscaled.
+pub(crate) fn fft_in_place(re: &mut [f32], im: &mut [f32], twiddles: &[(f32, f32)], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (cos, sin) = twiddles[k * stride];
+                let sin = if inverse { -sin } else { sin };
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Frequency-selective gate for one channel. Each FFT bin tracks its own noise floor
+/// and is attenuated while it stays below `SPECTRAL_GATE_MARGIN` times that floor, so
+/// steady noise bands are gated while whatever rises above them (voice) passes intact.
+pub struct SpectralGate {
+    window: Vec<f32>,
+    twiddles: Vec<(f32, f32)>,
+    // Previous frame followed by the current one
+    history: Vec<f32>,
+    // Second half of the last resynthesized window, added to the next output frame
+    overlap: Vec<f32>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    noise: Vec<f32>,
+    gains: Vec<f32>,
+}
+
+impl Default for SpectralGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpectralGate {
+    pub fn new() -> Self {
+        let window = (0..SPECTRAL_GATE_WINDOW)
+            .map(|i| {
+                let phase = std::f32::consts::PI * i as f32 / SPECTRAL_GATE_WINDOW as f32;
+                phase.sin()
+            })
+            .collect();
+        Self {
+            window,
+            twiddles: fft_twiddles(SPECTRAL_GATE_FFT_SIZE),
+            history: vec![0.0; SPECTRAL_GATE_WINDOW],
+            overlap: vec![0.0; FRAME_SIZE],
+            re: vec![0.0; SPECTRAL_GATE_FFT_SIZE],
+            im: vec![0.0; SPECTRAL_GATE_FFT_SIZE],
+            noise: vec![f32::MAX; SPECTRAL_GATE_BINS],
+            gains: vec![1.0; SPECTRAL_GATE_BINS],
+        }
+    }
+
+    /// Clears the audio history and the learned noise profile.
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.overlap.fill(0.0);
+        self.noise.fill(f32::MAX);
+        self.gains.fill(1.0);
+    }
+
+    /// Gates one frame in place (`FRAME_SIZE` samples; the output is one frame late).
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        if frame.len() != FRAME_SIZE {
+            return;
+        }
+        self.history.copy_within(FRAME_SIZE.., 0);
+        self.history[FRAME_SIZE..].copy_from_slice(frame);
+
+        for (i, (re, im)) in self.re.iter_mut().zip(self.im.iter_mut()).enumerate() {
+            *re = if i < SPECTRAL_GATE_WINDOW {
+                self.history[i] * self.window[i]
+            } else {
+                0.0
+            };
+            *im = 0.0;
+        }
+        fft_in_place(&mut self.re, &mut self.im, &self.twiddles, false);
+
+        for bin in 0..SPECTRAL_GATE_BINS {
+            let magnitude = self.re[bin].hypot(self.im[bin]);
+            let noise = &mut self.noise[bin];
+            *noise = if magnitude < *noise {
+                magnitude
+            } else {
+                *noise * SPECTRAL_GATE_NOISE_RISE
+            }
+            .max(SPECTRAL_GATE_NOISE_MIN);
+
+            let target = if magnitude > *noise * SPECTRAL_GATE_MARGIN {
+                1.0
+            } else {
+                SPECTRAL_GATE_FLOOR
+            };
+            let gain = &mut self.gains[bin];
+            *gain = if target >= *gain {
+                target
+            } else {
+                (*gain * SPECTRAL_GATE_RELEASE).max(target)
+            };
+
+            // Real input: the upper half mirrors the lower one
+            self.re[bin] *= *gain;
+            self.im[bin] *= *gain;
+            let mirror = (SPECTRAL_GATE_FFT_SIZE - bin) % SPECTRAL_GATE_FFT_SIZE;
+            if mirror != bin {
+                self.re[mirror] *= *gain;
+                self.im[mirror] *= *gain;
+            }
+        }
+        fft_in_place(&mut self.re, &mut self.im, &self.twiddles, true);
+
+        let scale = 1.0 / SPECTRAL_GATE_FFT_SIZE as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            *sample = sanitize_sample(self.re[i].mul_add(scale * self.window[i], self.overlap[i]));
+        }
+        for (i, overlap) in self.overlap.iter_mut().enumerate() {
+            let j = FRAME_SIZE + i;
+            *overlap = self.re[j] * scale * self.window[j];
+        }
     }
 }
 
-/// Simple lookahead limiter for Automatic Gain Control (AGC)
+/// Frame-based leveler for Automatic Gain Control (AGC), with a peak ceiling.
+///
+/// The gain is worked out once per frame and ramped to from the previous frame's gain
+/// across the samples, so a sudden cut for the ceiling doesn't click.
 pub struct LookaheadLimiter {
     pub target_level: f32,
     current_gain: f32,
+    // Gain the last frame ended at, where the next frame's ramp starts
+    applied_gain: f32,
+    attack_ms: f32,
+    release_ms: f32,
     attack_coeff: f32,
     release_coeff: f32,
+    ceiling_db: f32,
+    ceiling: f32,
 }
 
 impl LookaheadLimiter {
@@ -153,8 +790,33 @@ impl LookaheadLimiter {
         Self {
             target_level,
             current_gain: 1.0,
-            attack_coeff: 0.1,
-            release_coeff: 0.005,
+            applied_gain: 1.0,
+            attack_ms: AGC_DEFAULT_ATTACK_MS,
+            release_ms: AGC_DEFAULT_RELEASE_MS,
+            attack_coeff: frame_coeff(AGC_DEFAULT_ATTACK_MS),
+            release_coeff: frame_coeff(AGC_DEFAULT_RELEASE_MS),
+            ceiling_db: AGC_CEILING_DEFAULT_DB,
+            ceiling: 10f32.powf(AGC_CEILING_DEFAULT_DB / 20.0),
+        }
+    }
+
+    /// Sets the peak level (dBFS, at most 0) the output is kept under.
+    pub fn set_ceiling_db(&mut self, ceiling_db: f32) {
+        if ceiling_db != self.ceiling_db {
+            self.ceiling_db = ceiling_db;
+            self.ceiling = 10f32.powf(ceiling_db.min(0.0) / 20.0);
+        }
+    }
+
+    /// Sets how fast the gain falls (attack) and recovers (release), in ms.
+    pub fn set_times(&mut self, attack_ms: f32, release_ms: f32) {
+        if attack_ms != self.attack_ms {
+            self.attack_ms = attack_ms;
+            self.attack_coeff = frame_coeff(attack_ms);
+        }
+        if release_ms != self.release_ms {
+            self.release_ms = release_ms;
+            self.release_coeff = frame_coeff(release_ms);
         }
     }
 
@@ -163,6 +825,10 @@ impl LookaheadLimiter {
             return;
         }
 
+        if !self.current_gain.is_finite() {
+            self.current_gain = 1.0;
+        }
+
         // Calculate max RMS across all channels for linked limiting
         let frame_len = frames[0].len();
         let mut sum_sq = 0.0;
@@ -191,74 +857,711 @@ impl LookaheadLimiter {
             self.current_gain -= 0.001;
         }
 
-        // Apply gain to all channels
+        self.apply_gain(frames);
+    }
+
+    /// Applies the current gain without adapting it, e.g. while the gate is closed so
+    /// the gain doesn't wind up chasing silence and blast the next onset.
+    pub fn hold_frame(&mut self, frames: &mut [&mut [f32]]) {
+        if !self.current_gain.is_finite() {
+            self.current_gain = 1.0;
+        }
+        self.apply_gain(frames);
+    }
+
+    /// Gain applied to the last frame (above 1 while boosting quiet speech).
+    pub fn gain(&self) -> f32 {
+        self.current_gain
+    }
+
+    fn apply_gain(&mut self, frames: &mut [&mut [f32]]) {
+        if !self.applied_gain.is_finite() {
+            self.applied_gain = self.current_gain;
+        }
+
+        // Lower the gain for frames whose peak would cross the ceiling instead of
+        // clipping them
+        let peak = frames
+            .iter()
+            .flat_map(|channel| channel.iter())
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        if peak * self.current_gain > self.ceiling {
+            self.current_gain = self.ceiling / peak;
+        }
+
+        // Ramp from the last frame's gain to this one's on all channels. While a cut
+        // for the ceiling is still ramping in, the clamp holds the peaks down; it
+        // never widens a step between samples, so it can't click
+        let Some(frame_len) = frames.first().map(|channel| channel.len().max(1)) else {
+            return;
+        };
+        let step = (self.current_gain - self.applied_gain) / frame_len as f32;
         for channel in frames.iter_mut() {
-            for sample in channel.iter_mut() {
-                let val = *sample * self.current_gain;
-                *sample = val.clamp(-0.99, 0.99);
+            for (j, sample) in channel.iter_mut().enumerate() {
+                let gain = step.mul_add((j + 1) as f32, self.applied_gain);
+                let val = sanitize_sample(*sample * gain);
+                *sample = val.clamp(-self.ceiling, self.ceiling);
             }
         }
+        self.applied_gain = self.current_gain;
     }
 }
 
-pub enum BypassState {
-    Active,
-    Bypassed,
-    FadingOut,
-    FadingIn,
+/// Downward compressor for steady vocal dynamics, linked across channels.
+///
+/// Unlike `LookaheadLimiter` (a slow leveler toward a target), this reacts per sample:
+/// levels above `threshold_db` are reduced by `ratio`, then `makeup_db` is added back.
+pub struct Compressor {
+    pub threshold_db: f32,
+    pub ratio: f32,
+    pub makeup_db: f32,
+    attack_ms: f32,
+    release_ms: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    // Smoothed gain reduction in dB (>= 0)
+    reduction_db: f32,
 }
 
-pub struct VoidProcessor {
-    denoise: Vec<Box<DenoiseState<'static>>>,
-    echo_canceller: Vec<EchoCanceller>,
-    eq: Vec<ThreeBandEq>,
-    agc_limiter: LookaheadLimiter,
-    noise_floor_tracker: NoiseFloorTracker,
-    vad_instances: [Vad; 4], // Pre-created for all VadMode variants to avoid RT allocation
-    channels: usize,
+/// One-pole smoothing coefficient reaching ~63% of a step after `ms`.
+fn smoothing_coeff(ms: f32) -> f32 {
+    let samples = ms * 0.001 * SAMPLE_RATE as f32;
+    if samples <= 1.0 {
+        0.0
+    } else {
+        (-1.0 / samples).exp()
+    }
+}
 
-    // State
-    gate_open: bool,
-    samples_since_close: u32,
-    samples_since_open: u32,
-    fade_position: u32,
-    bypass_state: BypassState,
-    crossfade_pos: u32,
-    calibration_samples: Vec<f32>,
+impl Compressor {
+    pub fn new(
+        threshold_db: f32,
+        ratio: f32,
+        attack_ms: f32,
+        release_ms: f32,
+        makeup_db: f32,
+    ) -> Self {
+        Self {
+            threshold_db,
+            ratio,
+            makeup_db,
+            attack_ms,
+            release_ms,
+            attack_coeff: smoothing_coeff(attack_ms),
+            release_coeff: smoothing_coeff(release_ms),
+            reduction_db: 0.0,
+        }
+    }
 
-    // Current Settings (Locally cached to avoid atomic load every sample)
-    current_vad_mode: i32,
-    current_eq_enabled: bool,
-    current_agc_enabled: bool,
-    current_eq_low: f32,
-    current_eq_mid: f32,
-    current_eq_high: f32,
+    pub fn set_times(&mut self, attack_ms: f32, release_ms: f32) {
+        if attack_ms != self.attack_ms {
+            self.attack_ms = attack_ms;
+            self.attack_coeff = smoothing_coeff(attack_ms);
+        }
+        if release_ms != self.release_ms {
+            self.release_ms = release_ms;
+            self.release_coeff = smoothing_coeff(release_ms);
+        }
+    }
 
-    // Shared Atomics (Control Interface)
-    pub volume_level: Arc<AtomicU32>,
-    pub calibration_mode: Arc<AtomicBool>,
-    pub calibration_result: Arc<AtomicU32>,
-    pub vad_sensitivity: Arc<AtomicU32>,
-    pub eq_low_gain: Arc<AtomicU32>,
-    pub eq_mid_gain: Arc<AtomicU32>,
-    pub eq_high_gain: Arc<AtomicU32>,
-    pub eq_enabled: Arc<AtomicBool>,
+    /// Current gain reduction in dB (before makeup), e.g. for metering.
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.reduction_db
+    }
+
+    pub fn process_frame(&mut self, frames: &mut [&mut [f32]]) {
+        if frames.is_empty() {
+            return;
+        }
+
+        if !self.reduction_db.is_finite() {
+            self.reduction_db = 0.0;
+        }
+
+        let slope = 1.0 - 1.0 / self.ratio.max(1.0);
+        let frame_len = frames.iter().map(|ch| ch.len()).min().unwrap_or(0);
+        for k in 0..frame_len {
+            // Linked detection: the loudest channel drives all of them
+            let mut peak = 0.0f32;
+            for channel in frames.iter() {
+                peak = peak.max(channel[k].abs());
+            }
+            let level_db = 20.0 * peak.max(1e-6).log10();
+            let target = (level_db - self.threshold_db).max(0.0) * slope;
+
+            let coeff = if target > self.reduction_db {
+                self.attack_coeff
+            } else {
+                self.release_coeff
+            };
+            self.reduction_db = target + coeff * (self.reduction_db - target);
+
+            let gain = 10f32.powf((self.makeup_db - self.reduction_db) / 20.0);
+            for channel in frames.iter_mut() {
+                channel[k] = sanitize_sample(channel[k] * gain);
+            }
+        }
+    }
+}
+
+/// What happens to audio while the gate decision is "closed".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GateMode {
+    /// Fade to silence
+    #[default]
+    Gate,
+    /// Attenuate in proportion to how far the level is below threshold
+    Expander,
+    /// Leave the signal untouched
+    Off,
+}
+
+impl GateMode {
+    pub const ALL: [GateMode; 3] = [GateMode::Gate, GateMode::Expander, GateMode::Off];
+
+    /// Decodes the value stored in `VoidProcessor::gate_mode`; unknown values fall back to `Gate`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => GateMode::Expander,
+            2 => GateMode::Off,
+            _ => GateMode::Gate,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GateMode::Gate => "Gate",
+            GateMode::Expander => "Expander",
+            GateMode::Off => "Off",
+        }
+    }
+}
+
+/// Shape of the gate's fade to silence once it closes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FadeCurve {
+    /// Quarter-cosine, the same shape as the bypass crossfade
+    #[default]
+    Cosine,
+    /// Straight-line ramp (the original fade)
+    Linear,
+    /// Drops quickly, then tails off
+    Exponential,
+}
+
+impl FadeCurve {
+    pub const ALL: [FadeCurve; 3] = [FadeCurve::Cosine, FadeCurve::Linear, FadeCurve::Exponential];
+
+    /// Decodes the value stored in `VoidProcessor::gate_fade_curve`; unknown values fall
+    /// back to `Cosine`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => FadeCurve::Linear,
+            2 => FadeCurve::Exponential,
+            _ => FadeCurve::Cosine,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FadeCurve::Cosine => "Cosine",
+            FadeCurve::Linear => "Linear",
+            FadeCurve::Exponential => "Exponential",
+        }
+    }
+
+    /// Gain at fade progress `t` (0 = start, 1 = silent). Every curve starts at 1.0 and
+    /// ends at exactly 0.0, so switching curves never leaves a step at either end.
+    pub fn gain(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            FadeCurve::Cosine => (t * std::f32::consts::PI / 2.0).cos(),
+            FadeCurve::Linear => 1.0 - t,
+            FadeCurve::Exponential => {
+                // e^-5t, rescaled so the tail lands on zero instead of -43 dB
+                let end = (-FADE_EXP_RATE).exp();
+                ((-FADE_EXP_RATE * t).exp() - end) / (1.0 - end)
+            }
+        }
+    }
+}
+
+/// Which signal the gate (and VAD) measures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GateKeySource {
+    /// The denoised signal
+    #[default]
+    PostDenoise,
+    /// The signal going into RNNoise (after input gain and echo cancellation), whose
+    /// level doesn't drop on quiet speech the way the denoised one can
+    PreDenoise,
+}
+
+impl GateKeySource {
+    pub const ALL: [GateKeySource; 2] = [GateKeySource::PostDenoise, GateKeySource::PreDenoise];
+
+    /// Decodes the value stored in `VoidProcessor::gate_key_source`; unknown values fall
+    /// back to `PostDenoise`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => GateKeySource::PreDenoise,
+            _ => GateKeySource::PostDenoise,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GateKeySource::PostDenoise => "After Denoise",
+            GateKeySource::PreDenoise => "Before Denoise",
+        }
+    }
+}
+
+/// A gate open/close transition, for the optional event log (see
+/// `VoidProcessor::gate_event_sender`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GateEvent {
+    /// Wall-clock time of the frame that flipped the gate
+    pub time: std::time::SystemTime,
+    /// True when the gate opened, false when it closed
+    pub open: bool,
+    /// Frame RMS at the transition
+    pub rms: f32,
+    /// Threshold the RMS was compared against (the dynamic one with Auto-Gate)
+    pub threshold: f32,
+}
+
+/// Which signal the VAD listens to when there is more than one channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VadSource {
+    /// Average of all channels
+    #[default]
+    Mix,
+    /// Whichever channel has the most energy in the current frame
+    Loudest,
+    /// One fixed channel (0-based), e.g. the one the voice mic is on
+    Channel(usize),
+}
+
+impl VadSource {
+    /// Decodes the value stored in `VoidProcessor::vad_source_channel`: 0 = mix,
+    /// 1 = loudest, 2 and up = channel `value - 2`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => VadSource::Mix,
+            1 => VadSource::Loudest,
+            n => VadSource::Channel((n - 2) as usize),
+        }
+    }
+
+    pub fn to_u32(self) -> u32 {
+        match self {
+            VadSource::Mix => 0,
+            VadSource::Loudest => 1,
+            VadSource::Channel(ch) => ch as u32 + 2,
+        }
+    }
+
+    /// Channel to run the VAD on, given each channel's energy this frame. `None` means
+    /// the mix, which is also used when the chosen channel doesn't exist.
+    pub fn channel(self, energies: &[f32]) -> Option<usize> {
+        match self {
+            VadSource::Mix => None,
+            VadSource::Loudest => energies
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(ch, _)| ch),
+            VadSource::Channel(ch) => (ch < energies.len()).then_some(ch),
+        }
+    }
+}
+
+/// Steadies the per-frame VAD verdicts: speech starts once `onset` of the last `window`
+/// frames were voiced and carries on for `hangover` frames after that stops being true.
+/// The defaults (1 of 1, no hangover) pass every verdict through unchanged.
+pub struct VadSmoother {
+    // Latest verdict in bit 0
+    history: u32,
+    window: u32,
+    onset: u32,
+    hangover: u32,
+    hangover_remaining: u32,
+    speech: bool,
+}
+
+impl Default for VadSmoother {
+    fn default() -> Self {
+        Self::new(1, 1, 0)
+    }
+}
+
+impl VadSmoother {
+    pub fn new(window: u32, onset: u32, hangover: u32) -> Self {
+        let mut smoother = Self {
+            history: 0,
+            window: 1,
+            onset: 1,
+            hangover: 0,
+            hangover_remaining: 0,
+            speech: false,
+        };
+        smoother.configure(window, onset, hangover);
+        smoother
+    }
+
+    /// Window clamped to 1..=`VAD_MAX_WINDOW_FRAMES`, onset to 1..=window and hangover
+    /// to `VAD_MAX_HANGOVER_FRAMES`. History is kept.
+    pub fn configure(&mut self, window: u32, onset: u32, hangover: u32) {
+        self.window = window.clamp(1, VAD_MAX_WINDOW_FRAMES);
+        self.onset = onset.clamp(1, self.window);
+        self.hangover = hangover.min(VAD_MAX_HANGOVER_FRAMES);
+        self.hangover_remaining = self.hangover_remaining.min(self.hangover);
+    }
+
+    /// Adds one frame's raw verdict and returns the smoothed one.
+    pub fn update(&mut self, voiced: bool) -> bool {
+        self.history = (self.history << 1) | u32::from(voiced);
+        let voiced_frames = (self.history & ((1 << self.window) - 1)).count_ones();
+        if voiced_frames >= self.onset {
+            self.speech = true;
+            self.hangover_remaining = self.hangover;
+        } else if self.speech && self.hangover_remaining > 0 {
+            self.hangover_remaining -= 1;
+        } else {
+            self.speech = false;
+        }
+        self.speech
+    }
+}
+
+/// Sub-blocks per frame actually used for `requested`: clamped to 1..=`GATE_MAX_SUBBLOCKS`
+/// and rounded down to a divisor of `FRAME_SIZE`, so all sub-blocks are the same length.
+fn gate_subblock_count(requested: u32) -> usize {
+    let mut count = requested.clamp(1, GATE_MAX_SUBBLOCKS) as usize;
+    while !FRAME_SIZE.is_multiple_of(count) {
+        count -= 1;
+    }
+    count
+}
+
+/// Downward expander gain for a signal at `level` (RMS): below `threshold`, every dB
+/// under it becomes `ratio` dB at the output.
+pub fn expander_gain(level: f32, threshold: f32, ratio: f32) -> f32 {
+    if level >= threshold || threshold <= 0.0 {
+        return 1.0;
+    }
+    let gain = (level.max(0.0) / threshold).powf(ratio.max(1.0) - 1.0);
+    sanitize_sample(gain).clamp(EXPANDER_FLOOR, 1.0)
+}
+
+pub enum BypassState {
+    Active,
+    Bypassed,
+    FadingOut,
+    FadingIn,
+}
+
+/// The settings a frontend controls, as shared atomics. A `VoidProcessor` reads them
+/// in `process_updates`; clone this (the atomics are shared, not copied) to wire a GUI,
+/// host parameters or an FFI handle to the processor, see `VoidProcessor::controls`.
+#[derive(Clone)]
+pub struct ProcessorControls {
+    /// After `auto_suspend_secs` of closed gate (Gate mode only), skip denoise/EQ/AGC and
+    /// output silence until the input level crosses the threshold again
+    pub auto_suspend_enabled: Arc<AtomicBool>,
+    pub auto_suspend_secs: Arc<AtomicU32>,
+    pub input_gain_db: Arc<AtomicU32>,
+    /// Time (ms) the gate stays fully open after the signal drops, before release begins
+    pub gate_hold_ms: Arc<AtomicU32>,
+    /// `GateMode` as u32 (see `GateMode::from_u32`)
+    pub gate_mode: Arc<AtomicU32>,
+    /// `FadeCurve` as u32 (see `FadeCurve::from_u32`)
+    pub gate_fade_curve: Arc<AtomicU32>,
+    /// `NoiseFloorSpeed` as u32 (see `NoiseFloorSpeed::from_u32`); used by the dynamic gate
+    pub noise_floor_speed: Arc<AtomicU32>,
+    /// Holds the dynamic gate's noise floor at its current estimate
+    pub noise_floor_frozen: Arc<AtomicBool>,
+    /// Range (RMS) the dynamic gate's threshold is clamped to; see `dynamic_threshold_range`
+    pub dynamic_threshold_min: Arc<AtomicU32>,
+    pub dynamic_threshold_max: Arc<AtomicU32>,
+    pub expander_ratio: Arc<AtomicU32>,
+    /// Close threshold as a fraction of the open threshold; see `GATE_HYSTERESIS_DEFAULT`
+    pub gate_hysteresis: Arc<AtomicU32>,
+    /// Gate decisions per frame, 1..=`GATE_MAX_SUBBLOCKS` (1 = once per 10 ms frame)
+    pub gate_subblocks: Arc<AtomicU32>,
+    /// `GateKeySource` as u32 (see `GateKeySource::from_u32`)
+    pub gate_key_source: Arc<AtomicU32>,
+    pub vad_sensitivity: Arc<AtomicU32>,
+    /// `VadSource` as u32 (see `VadSource::from_u32`); only matters with several channels
+    pub vad_source_channel: Arc<AtomicU32>,
+    /// VAD smoothing (see `VadSmoother`): speech needs `vad_onset_frames` voiced frames
+    /// out of the last `vad_window_frames`, then holds for `vad_hangover_frames`
+    pub vad_window_frames: Arc<AtomicU32>,
+    pub vad_onset_frames: Arc<AtomicU32>,
+    pub vad_hangover_frames: Arc<AtomicU32>,
+    pub eq_low_gain: Arc<AtomicU32>,
+    pub eq_mid_gain: Arc<AtomicU32>,
+    pub eq_high_gain: Arc<AtomicU32>,
+    pub eq_enabled: Arc<AtomicBool>,
+    pub eq_auto_gain: Arc<AtomicBool>,
+    /// Warmth/brightness macro (-1..1), added on top of the band gains; applies even
+    /// with the detailed EQ disabled
+    pub eq_tilt: Arc<AtomicU32>,
     pub agc_enabled: Arc<AtomicBool>,
     pub agc_target: Arc<AtomicU32>,
+    pub agc_attack_ms: Arc<AtomicU32>,
+    pub agc_release_ms: Arc<AtomicU32>,
+    /// Freezes the AGC gain while the gate is closed (not in `GateMode::Off`)
+    pub agc_hold_when_gated: Arc<AtomicBool>,
+    /// Peak ceiling of the AGC output in dBFS (`AGC_CEILING_MIN_DB..=0`)
+    pub agc_ceiling_db: Arc<AtomicU32>,
+    /// Compressor stage between EQ and AGC; threshold/makeup in dB, times in ms
+    pub compressor_enabled: Arc<AtomicBool>,
+    pub compressor_threshold_db: Arc<AtomicU32>,
+    pub compressor_ratio: Arc<AtomicU32>,
+    pub compressor_attack_ms: Arc<AtomicU32>,
+    pub compressor_release_ms: Arc<AtomicU32>,
+    pub compressor_makeup_db: Arc<AtomicU32>,
     pub bypass_enabled: Arc<AtomicBool>,
-    pub jitter_ewma_us: Arc<AtomicU32>,
     pub gate_threshold: Arc<AtomicU32>,
     pub suppression_strength: Arc<AtomicU32>,
     pub dynamic_threshold_enabled: Arc<AtomicBool>,
+    pub clean_reference: Arc<AtomicBool>,
+    /// Keeps the unsuppressed signal above 8kHz so RNNoise doesn't dull music/sibilance
+    pub protect_highs: Arc<AtomicBool>,
+    /// Per-frequency gating of steady noise bands after denoising (adds one frame of latency)
+    pub spectral_gate: Arc<AtomicBool>,
+    pub echo_cancel_enabled: Arc<AtomicBool>,
+    pub stereo_width: Arc<AtomicU32>,
+    /// TPDF dither at 16-bit level on the final output, against quantization distortion
+    /// of quiet content when the host converts to integer samples
+    pub dither_enabled: Arc<AtomicBool>,
+    /// Silences the output (after bypass, so it holds in bypass too), fading over a frame
+    pub muted: Arc<AtomicBool>,
+    pub speech_gated_suppression: Arc<AtomicBool>,
+    pub speech_suppression_floor: Arc<AtomicU32>,
+    /// Selects which visualizer data is sent; only one kind goes out at a time
+    pub waveform_view: Arc<AtomicBool>,
+    /// Computes visualizer data; clear it while nothing is shown to skip the FFTs
+    pub spectrum_enabled: Arc<AtomicBool>,
+}
+
+impl ProcessorControls {
+    /// Controls at the processor defaults, with the settings `VoidProcessor::new` takes.
+    #[must_use]
+    pub fn new(
+        vad_sensitivity: i32,
+        eq_params: (f32, f32, f32),
+        agc_target_level: f32,
+        echo_cancel_enabled: bool,
+    ) -> Self {
+        Self {
+            auto_suspend_enabled: Arc::new(AtomicBool::new(false)),
+            auto_suspend_secs: Arc::new(AtomicU32::new(AUTO_SUSPEND_DEFAULT_SECS)),
+            input_gain_db: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            gate_hold_ms: Arc::new(AtomicU32::new(0)),
+            gate_mode: Arc::new(AtomicU32::new(GateMode::Gate as u32)),
+            gate_fade_curve: Arc::new(AtomicU32::new(FadeCurve::Cosine as u32)),
+            noise_floor_speed: Arc::new(AtomicU32::new(NoiseFloorSpeed::Normal as u32)),
+            noise_floor_frozen: Arc::new(AtomicBool::new(false)),
+            dynamic_threshold_min: Arc::new(AtomicU32::new(
+                DYNAMIC_THRESHOLD_DEFAULT_MIN.to_bits(),
+            )),
+            dynamic_threshold_max: Arc::new(AtomicU32::new(
+                DYNAMIC_THRESHOLD_DEFAULT_MAX.to_bits(),
+            )),
+            expander_ratio: Arc::new(AtomicU32::new(2.0f32.to_bits())),
+            gate_hysteresis: Arc::new(AtomicU32::new(GATE_HYSTERESIS_DEFAULT.to_bits())),
+            gate_subblocks: Arc::new(AtomicU32::new(1)),
+            gate_key_source: Arc::new(AtomicU32::new(GateKeySource::PostDenoise as u32)),
+            vad_sensitivity: Arc::new(AtomicU32::new(vad_sensitivity as u32)),
+            vad_source_channel: Arc::new(AtomicU32::new(VadSource::Mix.to_u32())),
+            vad_window_frames: Arc::new(AtomicU32::new(1)),
+            vad_onset_frames: Arc::new(AtomicU32::new(1)),
+            vad_hangover_frames: Arc::new(AtomicU32::new(0)),
+            eq_low_gain: Arc::new(AtomicU32::new(eq_params.0.to_bits())),
+            eq_mid_gain: Arc::new(AtomicU32::new(eq_params.1.to_bits())),
+            eq_high_gain: Arc::new(AtomicU32::new(eq_params.2.to_bits())),
+            eq_enabled: Arc::new(AtomicBool::new(true)),
+            eq_auto_gain: Arc::new(AtomicBool::new(false)),
+            eq_tilt: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            agc_enabled: Arc::new(AtomicBool::new(false)),
+            agc_target: Arc::new(AtomicU32::new(agc_target_level.to_bits())),
+            agc_attack_ms: Arc::new(AtomicU32::new(AGC_DEFAULT_ATTACK_MS.to_bits())),
+            agc_release_ms: Arc::new(AtomicU32::new(AGC_DEFAULT_RELEASE_MS.to_bits())),
+            agc_hold_when_gated: Arc::new(AtomicBool::new(true)),
+            agc_ceiling_db: Arc::new(AtomicU32::new(AGC_CEILING_DEFAULT_DB.to_bits())),
+            compressor_enabled: Arc::new(AtomicBool::new(false)),
+            compressor_threshold_db: Arc::new(AtomicU32::new((-20.0f32).to_bits())),
+            compressor_ratio: Arc::new(AtomicU32::new(3.0f32.to_bits())),
+            compressor_attack_ms: Arc::new(AtomicU32::new(10.0f32.to_bits())),
+            compressor_release_ms: Arc::new(AtomicU32::new(100.0f32.to_bits())),
+            compressor_makeup_db: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            bypass_enabled: Arc::new(AtomicBool::new(false)),
+            gate_threshold: Arc::new(AtomicU32::new(0.015f32.to_bits())),
+            suppression_strength: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            dynamic_threshold_enabled: Arc::new(AtomicBool::new(false)),
+            clean_reference: Arc::new(AtomicBool::new(false)),
+            protect_highs: Arc::new(AtomicBool::new(false)),
+            spectral_gate: Arc::new(AtomicBool::new(false)),
+            echo_cancel_enabled: Arc::new(AtomicBool::new(echo_cancel_enabled)),
+            stereo_width: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            dither_enabled: Arc::new(AtomicBool::new(false)),
+            muted: Arc::new(AtomicBool::new(false)),
+            speech_gated_suppression: Arc::new(AtomicBool::new(false)),
+            speech_suppression_floor: Arc::new(AtomicU32::new(0.5f32.to_bits())),
+            waveform_view: Arc::new(AtomicBool::new(false)),
+            spectrum_enabled: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+pub struct VoidProcessor {
+    denoise: Vec<Box<DenoiseState<'static>>>,
+    echo_canceller: Vec<EchoCanceller>,
+    reference_denoise: Vec<Box<DenoiseState<'static>>>,
+    eq: Vec<ThreeBandEq>,
+    // High-pass on (dry - denoised) per channel, for "protect highs"
+    protect_highs_filters: Vec<DirectForm2Transposed<f32>>,
+    spectral_gates: Vec<SpectralGate>,
+    // Each channel's signal before denoising, kept for the VAD when the gate keys off it
+    pre_denoise: Vec<[f32; FRAME_SIZE]>,
+    agc_limiter: LookaheadLimiter,
+    compressor: Compressor,
+    dither: Dither,
+    // Output gain the mute fades from: 1 while unmuted, 0 while muted
+    mute_gain: f32,
+    noise_floor_tracker: NoiseFloorTracker,
+    vad_instances: [Vad; 4], // Pre-created for all VadMode variants to avoid RT allocation
+    // Consecutive frames the VAD rejected; at VAD_ERROR_LIMIT we switch to level-only gating
+    vad_error_count: u32,
+    vad_failed: bool,
+    vad_smoother: VadSmoother,
+    channels: usize,
+
+    // State
+    gate_open: bool,
+    samples_since_close: u32,
+    samples_since_open: u32,
+    // Samples left in the hold phase (gate kept fully open before release starts)
+    hold_remaining: u32,
+    // Expander gain reached at the end of the previous frame (ramped across each frame)
+    expander_gain: f32,
+    fade_position: u32,
+    bypass_state: BypassState,
+    crossfade_pos: u32,
+    calibration_samples: Vec<f32>,
+    gain_calibration_samples: Vec<f32>,
+    // Rate the incoming frames are actually sampled at (differs from SAMPLE_RATE when resampled)
+    sample_rate: u32,
+
+    // Current Settings (Locally cached to avoid atomic load every sample)
+    current_vad_mode: i32,
+    current_eq_enabled: bool,
+    current_eq_tilt: f32,
+    current_eq_auto_gain: bool,
+    current_agc_enabled: bool,
+    current_agc_hold_when_gated: bool,
+    current_compressor_enabled: bool,
+    current_clean_reference: bool,
+    current_protect_highs: bool,
+    current_spectral_gate: bool,
+    current_waveform_view: bool,
+    current_spectrum_enabled: bool,
+    current_echo_cancel: bool,
+    current_stereo_width: f32,
+    current_dither: bool,
+    current_muted: bool,
+    current_input_gain: f32,
+    current_hold_samples: u32,
+    current_gate_mode: GateMode,
+    current_vad_source: VadSource,
+    current_fade_curve: FadeCurve,
+    current_noise_floor_speed: NoiseFloorSpeed,
+    current_dynamic_min: f32,
+    current_dynamic_max: f32,
+    current_expander_ratio: f32,
+    current_gate_hysteresis: f32,
+    current_gate_subblocks: usize,
+    current_gate_key_source: GateKeySource,
+    current_speech_gated: bool,
+    current_speech_floor: f32,
+    // Auto-suspend timeout in samples (`None` = off), gate-closed samples counted toward
+    // it, and the threshold a suspended processor watches for activity
+    current_auto_suspend_samples: Option<u32>,
+    silent_samples: u32,
+    suspended: bool,
+    suspend_threshold: f32,
+    // VAD decision of the previous frame and the smoothed 0..1 "speech-ness" derived from it
+    last_frame_speech: bool,
+    speech_blend: f32,
+    // Target EQ gains (dB) and the gains the filters use, ramping toward them
+    current_eq_low: f32,
+    current_eq_mid: f32,
+    current_eq_high: f32,
+    eq_ramp_gains: [f32; 3],
+    // Suppression strength at the end of the last frame; `None` until the first one
+    suppression_ramp: Option<f32>,
+
+    /// Settings, shared with the frontends; applied by `process_updates`
+    controls: ProcessorControls,
+
+    // Shared Atomics (meters and results)
+    pub volume_level: Arc<AtomicU32>,
+    /// Per-channel RMS (f32 bits), one entry per channel, measured where `volume_level`
+    /// reads the mix; shows a dead or unbalanced channel on stereo inputs
+    pub channel_levels: Arc<[AtomicU32]>,
+    /// Gate decision of the last processed frame (read-only for the GUI)
+    pub gate_is_open: Arc<AtomicBool>,
+    /// How far the gate or expander turned the end of the last frame down (positive dB,
+    /// f32 bits; `GAIN_REDUCTION_MAX_DB` when closed), for gain-reduction meters
+    pub gate_reduction_db: Arc<AtomicU32>,
+    /// Same for the compressor and AGC together; AGC boosts count as 0
+    pub level_reduction_db: Arc<AtomicU32>,
+    /// Frames whose output reached `CLIP_LEVEL` on any channel; only ever counts up, so
+    /// it shows clipping anywhere in a session. Store 0 to reset it
+    pub clip_count: Arc<AtomicU32>,
+    /// Frames the (smoothed) VAD took for speech; only counts up (wrapping), so a reader
+    /// comparing two readings learns whether anyone spoke in between
+    pub speech_frames: Arc<AtomicU32>,
+    /// Threshold the last gate decision used; differs from `gate_threshold` with Auto-Gate
+    pub effective_threshold: Arc<AtomicU32>,
+    /// Noise floor estimate (f32 bits) Auto-Gate derives its threshold from; 0 while
+    /// Auto-Gate is off, since the floor is only tracked for it
+    pub noise_floor: Arc<AtomicU32>,
+    /// Whether the DSP chain is currently skipped by auto-suspend (read-only for the GUI)
+    pub dsp_suspended: Arc<AtomicBool>,
+    pub calibration_mode: Arc<AtomicBool>,
+    pub calibration_result: Arc<AtomicU32>,
+    /// Second calibration phase: measures speech and suggests `input_gain_db`
+    pub gain_calibration_mode: Arc<AtomicBool>,
+    pub gain_calibration_result: Arc<AtomicU32>,
+    pub jitter_ewma_us: Arc<AtomicU32>,
+    /// Average time (µs) one `process_frame` call takes, for hosts that measure it
+    pub process_time_us: Arc<AtomicU32>,
     pub spectrum_sender: Option<Sender<(Vec<f32>, Vec<f32>)>>,
+    /// Receives raw mono (input, output) frames instead of spectra while `waveform_view` is set
+    pub waveform_sender: Option<Sender<(Vec<f32>, Vec<f32>)>>,
+    /// Receives every gate open/close transition; `None` (the default) skips the logging
+    pub gate_event_sender: Option<Sender<GateEvent>>,
+    /// Receives the mono mix of every processed frame, exactly as output (e.g. to feed a
+    /// transcription engine). Sent from the audio thread with `try_send`: use a bounded
+    /// channel (an unbounded one allocates as it grows) and drain it off the audio
+    /// thread; frames are dropped while it is full. `None` (the default) skips the mix
+    pub frame_sender: Option<Sender<[f32; FRAME_SIZE]>>,
 
-    // Pre-allocated spectrum buffers (avoid allocations in audio thread)
-    spectrum_in_buf: Vec<f32>,
-    spectrum_out_buf: Vec<f32>,
+    // Pre-allocated spectrum/waveform buffers, recycled back from the GUI via `spectrum_recycler()`
+    // so steady-state sends never allocate
+    spectrum_pool_tx: Sender<(Vec<f32>, Vec<f32>)>,
+    spectrum_pool_rx: Receiver<(Vec<f32>, Vec<f32>)>,
     spectrum_frame_counter: u32,
     hann_coefficients: [f32; FRAME_SIZE],
     windowed_in: [f32; FRAME_SIZE],
     windowed_out: [f32; FRAME_SIZE],
+
+    // Buffering for `process_chunk` (taken out while processing to avoid self-borrow)
+    chunk_adapter: Option<FrameAdapter>,
 }
 
 // SAFETY: VoidProcessor owns all its mutable state (Vad, EchoCanceller, DenoiseState)
@@ -287,105 +1590,183 @@ impl VoidProcessor {
 
         let mut denoise = Vec::with_capacity(channels);
         let mut echo_canceller = Vec::with_capacity(channels);
+        let mut reference_denoise = Vec::with_capacity(channels);
         let mut eq = Vec::with_capacity(channels);
+        let mut protect_highs_filters = Vec::with_capacity(channels);
+        let mut spectral_gates = Vec::with_capacity(channels);
 
         // Pre-compute Hann window coefficients (periodic form matching spectrum-analyzer crate)
         let mut hann_coefficients = [0.0f32; FRAME_SIZE];
         for (i, coeff) in hann_coefficients.iter_mut().enumerate() {
-            *coeff = 0.5
-                * (1.0
-                    - (2.0 * std::f32::consts::PI * i as f32 / FRAME_SIZE as f32).cos());
+            *coeff =
+                0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / FRAME_SIZE as f32).cos());
         }
 
         for _ in 0..channels {
-            denoise.push(DenoiseState::new());
-            if echo_cancel_enabled {
-                if let Some(aec) = EchoCanceller::new() {
-                    echo_canceller.push(aec);
-                }
+            denoise.push(primed_denoise_state());
+            // Always created so echo cancellation can be switched on without a rebuild
+            if let Some(aec) = EchoCanceller::new() {
+                echo_canceller.push(aec);
             }
+            reference_denoise.push(primed_denoise_state());
             if let Ok(e) = ThreeBandEq::new(eq_params.0, eq_params.1, eq_params.2) {
                 eq.push(e);
             }
+            if let Ok(filter) = protect_highs_filter() {
+                protect_highs_filters.push(filter);
+            }
+            spectral_gates.push(SpectralGate::new());
+        }
+
+        let (spectrum_pool_tx, spectrum_pool_rx) = crossbeam_channel::bounded(SPECTRUM_POOL_SIZE);
+        for _ in 0..SPECTRUM_POOL_SIZE {
+            let _ = spectrum_pool_tx.try_send((
+                Vec::with_capacity(VISUAL_BUFFER_LEN),
+                Vec::with_capacity(VISUAL_BUFFER_LEN),
+            ));
         }
 
         Self {
             denoise,
             echo_canceller,
+            reference_denoise,
             eq,
+            protect_highs_filters,
+            spectral_gates,
+            pre_denoise: vec![[0.0; FRAME_SIZE]; channels],
             agc_limiter: LookaheadLimiter::new(agc_target_level),
+            compressor: Compressor::new(-20.0, 3.0, 10.0, 100.0, 0.0),
+            dither: Dither::new(),
+            mute_gain: 1.0,
             noise_floor_tracker: NoiseFloorTracker::new(),
             vad_instances,
+            vad_error_count: 0,
+            vad_failed: false,
+            vad_smoother: VadSmoother::default(),
             channels,
 
             gate_open: false,
             samples_since_close: 0,
             samples_since_open: 0,
+            hold_remaining: 0,
+            expander_gain: 1.0,
             fade_position: 0,
             bypass_state: BypassState::Active,
             crossfade_pos: 0,
-            calibration_samples: Vec::with_capacity(300), // Pre-alloc for ~3s calibration
+            calibration_samples: Vec::with_capacity(CALIBRATION_MAX_FRAMES),
+            gain_calibration_samples: Vec::with_capacity(CALIBRATION_MAX_FRAMES),
+            sample_rate: SAMPLE_RATE,
 
             current_vad_mode: vad_sensitivity,
             current_eq_enabled: true,
+            current_eq_tilt: 0.0,
+            current_eq_auto_gain: false,
             current_agc_enabled: false,
+            current_agc_hold_when_gated: true,
+            current_compressor_enabled: false,
+            current_clean_reference: false,
+            current_protect_highs: false,
+            current_spectral_gate: false,
+            current_waveform_view: false,
+            current_spectrum_enabled: true,
+            current_echo_cancel: echo_cancel_enabled,
+            current_stereo_width: 1.0,
+            current_dither: false,
+            current_muted: false,
+            current_input_gain: 1.0,
+            current_hold_samples: 0,
+            current_gate_mode: GateMode::Gate,
+            current_vad_source: VadSource::Mix,
+            current_fade_curve: FadeCurve::Cosine,
+            current_noise_floor_speed: NoiseFloorSpeed::Normal,
+            current_dynamic_min: DYNAMIC_THRESHOLD_DEFAULT_MIN,
+            current_dynamic_max: DYNAMIC_THRESHOLD_DEFAULT_MAX,
+            current_expander_ratio: 2.0,
+            current_gate_hysteresis: GATE_HYSTERESIS_DEFAULT,
+            current_gate_subblocks: 1,
+            current_gate_key_source: GateKeySource::PostDenoise,
+            current_speech_gated: false,
+            current_speech_floor: 0.5,
+            current_auto_suspend_samples: None,
+            silent_samples: 0,
+            suspended: false,
+            suspend_threshold: 0.0,
+            last_frame_speech: false,
+            speech_blend: 0.0,
             current_eq_low: eq_params.0,
             current_eq_mid: eq_params.1,
             current_eq_high: eq_params.2,
+            eq_ramp_gains: [eq_params.0, eq_params.1, eq_params.2],
+            suppression_ramp: None,
 
+            controls: ProcessorControls::new(
+                vad_sensitivity,
+                eq_params,
+                agc_target_level,
+                echo_cancel_enabled,
+            ),
             volume_level: Arc::new(AtomicU32::new(0)),
+            channel_levels: (0..channels).map(|_| AtomicU32::new(0)).collect(),
+            gate_is_open: Arc::new(AtomicBool::new(false)),
+            gate_reduction_db: Arc::new(AtomicU32::new(0)),
+            level_reduction_db: Arc::new(AtomicU32::new(0)),
+            clip_count: Arc::new(AtomicU32::new(0)),
+            speech_frames: Arc::new(AtomicU32::new(0)),
+            effective_threshold: Arc::new(AtomicU32::new(0)),
+            noise_floor: Arc::new(AtomicU32::new(0)),
+            dsp_suspended: Arc::new(AtomicBool::new(false)),
             calibration_mode: Arc::new(AtomicBool::new(false)),
             calibration_result: Arc::new(AtomicU32::new(0)),
-            vad_sensitivity: Arc::new(AtomicU32::new(vad_sensitivity as u32)),
-            eq_low_gain: Arc::new(AtomicU32::new(eq_params.0.to_bits())),
-            eq_mid_gain: Arc::new(AtomicU32::new(eq_params.1.to_bits())),
-            eq_high_gain: Arc::new(AtomicU32::new(eq_params.2.to_bits())),
-            eq_enabled: Arc::new(AtomicBool::new(true)),
-            agc_enabled: Arc::new(AtomicBool::new(false)),
-            agc_target: Arc::new(AtomicU32::new(agc_target_level.to_bits())),
-            bypass_enabled: Arc::new(AtomicBool::new(false)),
+            gain_calibration_mode: Arc::new(AtomicBool::new(false)),
+            gain_calibration_result: Arc::new(AtomicU32::new(0)),
             jitter_ewma_us: Arc::new(AtomicU32::new(0)),
-            gate_threshold: Arc::new(AtomicU32::new(0.015f32.to_bits())),
-            suppression_strength: Arc::new(AtomicU32::new(1.0f32.to_bits())),
-            dynamic_threshold_enabled: Arc::new(AtomicBool::new(false)),
+            process_time_us: Arc::new(AtomicU32::new(0)),
             spectrum_sender: None,
-            // Pre-allocate spectrum buffers (FRAME_SIZE/2 bins typical for FFT)
-            spectrum_in_buf: Vec::with_capacity(FRAME_SIZE / 2),
-            spectrum_out_buf: Vec::with_capacity(FRAME_SIZE / 2),
+            gate_event_sender: None,
+            frame_sender: None,
+            waveform_sender: None,
+            spectrum_pool_tx,
+            spectrum_pool_rx,
             spectrum_frame_counter: 0,
             hann_coefficients,
             windowed_in: [0.0; FRAME_SIZE],
             windowed_out: [0.0; FRAME_SIZE],
+            chunk_adapter: Some(FrameAdapter::with_channels(channels)),
         }
     }
 
     pub fn process_updates(&mut self) {
         // Check for settings updates
-        let new_vad = self.vad_sensitivity.load(Ordering::Relaxed) as i32;
+        let new_vad = self.controls.vad_sensitivity.load(Ordering::Relaxed) as i32;
         if new_vad != self.current_vad_mode {
             self.current_vad_mode = new_vad.clamp(0, 3);
         }
 
-        if !self.eq.is_empty() {
-            let new_low = f32::from_bits(self.eq_low_gain.load(Ordering::Relaxed));
-            let new_mid = f32::from_bits(self.eq_mid_gain.load(Ordering::Relaxed));
-            let new_high = f32::from_bits(self.eq_high_gain.load(Ordering::Relaxed));
+        // Cache EQ and AGC enabled state
+        self.current_eq_enabled = self.controls.eq_enabled.load(Ordering::Relaxed);
+        let tilt = f32::from_bits(self.controls.eq_tilt.load(Ordering::Relaxed));
+        self.current_eq_tilt = sanitize_sample(tilt).clamp(-1.0, 1.0);
 
-            if (new_low - self.current_eq_low).abs() > 0.01
-                || (new_mid - self.current_eq_mid).abs() > 0.01
-                || (new_high - self.current_eq_high).abs() > 0.01
-            {
-                self.current_eq_low = new_low;
-                self.current_eq_mid = new_mid;
-                self.current_eq_high = new_high;
-                for eq_instance in &mut self.eq {
-                    let _ = eq_instance.update_gains(new_low, new_mid, new_high);
-                }
+        if !self.eq.is_empty() {
+            // Band gains only count while the detailed EQ is on; the tilt always does
+            let (mut new_low, mut new_mid, mut new_high) = (0.0, 0.0, 0.0);
+            if self.current_eq_enabled {
+                new_low = f32::from_bits(self.controls.eq_low_gain.load(Ordering::Relaxed));
+                new_mid = f32::from_bits(self.controls.eq_mid_gain.load(Ordering::Relaxed));
+                new_high = f32::from_bits(self.controls.eq_high_gain.load(Ordering::Relaxed));
             }
+            let (tilt_low, tilt_high) = eq_tilt_gains(self.current_eq_tilt);
+            new_low += tilt_low;
+            new_high += tilt_high;
+
+            // Only the target moves here; `process_frame` ramps the filters toward it
+            self.current_eq_low = new_low;
+            self.current_eq_mid = new_mid;
+            self.current_eq_high = new_high;
         }
 
         // Check Bypass Toggle
-        let bypass_requested = self.bypass_enabled.load(Ordering::Relaxed);
+        let bypass_requested = self.controls.bypass_enabled.load(Ordering::Relaxed);
         match self.bypass_state {
             BypassState::Active if bypass_requested => {
                 self.bypass_state = BypassState::FadingOut;
@@ -398,16 +1779,330 @@ impl VoidProcessor {
             _ => {}
         }
 
-        // Cache EQ and AGC enabled state
-        self.current_eq_enabled = self.eq_enabled.load(Ordering::Relaxed);
-        self.current_agc_enabled = self.agc_enabled.load(Ordering::Relaxed);
+        let eq_auto_gain = self.controls.eq_auto_gain.load(Ordering::Relaxed);
+        if eq_auto_gain != self.current_eq_auto_gain {
+            self.current_eq_auto_gain = eq_auto_gain;
+            for eq_instance in &mut self.eq {
+                eq_instance.set_auto_gain(eq_auto_gain);
+            }
+            // The makeup gain isn't kept up to date while auto-gain is off
+            if eq_auto_gain {
+                self.apply_eq_gains();
+            }
+        }
+        self.current_agc_enabled = self.controls.agc_enabled.load(Ordering::Relaxed);
+        self.current_agc_hold_when_gated =
+            self.controls.agc_hold_when_gated.load(Ordering::Relaxed);
+        self.current_clean_reference = self.controls.clean_reference.load(Ordering::Relaxed);
+        let protect_highs = self.controls.protect_highs.load(Ordering::Relaxed);
+        if protect_highs && !self.current_protect_highs {
+            // Don't resume from filter state left over from the last time it was on
+            for filter in &mut self.protect_highs_filters {
+                filter.reset_state();
+            }
+        }
+        self.current_protect_highs = protect_highs;
+        let spectral_gate = self.controls.spectral_gate.load(Ordering::Relaxed);
+        if spectral_gate && !self.current_spectral_gate {
+            // Relearn the noise profile rather than reuse one from an older environment
+            for gate in &mut self.spectral_gates {
+                gate.reset();
+            }
+        }
+        self.current_spectral_gate = spectral_gate;
+        self.current_waveform_view = self.controls.waveform_view.load(Ordering::Relaxed);
+        self.current_spectrum_enabled = self.controls.spectrum_enabled.load(Ordering::Relaxed);
+        // AEC state is kept while disabled; AEC3 re-converges quickly on re-enable
+        self.current_echo_cancel = self.controls.echo_cancel_enabled.load(Ordering::Relaxed);
+        let width = f32::from_bits(self.controls.stereo_width.load(Ordering::Relaxed));
+        self.current_stereo_width = sanitize_sample(width).clamp(0.0, 2.0);
+        self.current_dither = self.controls.dither_enabled.load(Ordering::Relaxed);
+        self.current_muted = self.controls.muted.load(Ordering::Relaxed);
+        let gain_db = f32::from_bits(self.controls.input_gain_db.load(Ordering::Relaxed));
+        let gain_db = sanitize_sample(gain_db).clamp(INPUT_GAIN_MIN_DB, INPUT_GAIN_MAX_DB);
+        self.current_input_gain = 10f32.powf(gain_db / 20.0);
+        let hold_ms = self
+            .controls
+            .gate_hold_ms
+            .load(Ordering::Relaxed)
+            .min(GATE_HOLD_MAX_MS);
+        self.current_hold_samples = self.sample_rate * hold_ms / 1000;
+        self.current_gate_mode =
+            GateMode::from_u32(self.controls.gate_mode.load(Ordering::Relaxed));
+        self.current_vad_source =
+            VadSource::from_u32(self.controls.vad_source_channel.load(Ordering::Relaxed));
+        self.vad_smoother.configure(
+            self.controls.vad_window_frames.load(Ordering::Relaxed),
+            self.controls.vad_onset_frames.load(Ordering::Relaxed),
+            self.controls.vad_hangover_frames.load(Ordering::Relaxed),
+        );
+        self.current_fade_curve =
+            FadeCurve::from_u32(self.controls.gate_fade_curve.load(Ordering::Relaxed));
+        let speed =
+            NoiseFloorSpeed::from_u32(self.controls.noise_floor_speed.load(Ordering::Relaxed));
+        if speed != self.current_noise_floor_speed {
+            self.noise_floor_tracker.set_window(speed.window());
+            self.current_noise_floor_speed = speed;
+        }
+        self.noise_floor_tracker
+            .set_frozen(self.controls.noise_floor_frozen.load(Ordering::Relaxed));
+        (self.current_dynamic_min, self.current_dynamic_max) = dynamic_threshold_range(
+            f32::from_bits(self.controls.dynamic_threshold_min.load(Ordering::Relaxed)),
+            f32::from_bits(self.controls.dynamic_threshold_max.load(Ordering::Relaxed)),
+        );
+        let ratio = f32::from_bits(self.controls.expander_ratio.load(Ordering::Relaxed));
+        self.current_expander_ratio = sanitize_sample(ratio).clamp(1.0, 10.0);
+        let hysteresis = f32::from_bits(self.controls.gate_hysteresis.load(Ordering::Relaxed));
+        self.current_gate_hysteresis = sanitize_sample(hysteresis).clamp(GATE_HYSTERESIS_MIN, 1.0);
+        let subblocks = self.controls.gate_subblocks.load(Ordering::Relaxed);
+        self.current_gate_subblocks = gate_subblock_count(subblocks);
+        self.current_gate_key_source =
+            GateKeySource::from_u32(self.controls.gate_key_source.load(Ordering::Relaxed));
+        let suspend_secs = self.controls.auto_suspend_secs.load(Ordering::Relaxed);
+        self.current_auto_suspend_samples = self
+            .controls
+            .auto_suspend_enabled
+            .load(Ordering::Relaxed)
+            .then_some(suspend_secs.clamp(1, AUTO_SUSPEND_MAX_SECS) * self.sample_rate);
+        self.current_speech_gated = self
+            .controls
+            .speech_gated_suppression
+            .load(Ordering::Relaxed);
+        let floor = f32::from_bits(
+            self.controls
+                .speech_suppression_floor
+                .load(Ordering::Relaxed),
+        );
+        self.current_speech_floor = sanitize_sample(floor).clamp(0.0, 1.0);
 
         // Check AGC settings
-        let target_bits = self.agc_target.load(Ordering::Relaxed);
+        let target_bits = self.controls.agc_target.load(Ordering::Relaxed);
         let new_target = f32::from_bits(target_bits);
         if (new_target - self.agc_limiter.target_level).abs() > 0.01 {
             self.agc_limiter.target_level = new_target;
         }
+        let attack_ms = f32::from_bits(self.controls.agc_attack_ms.load(Ordering::Relaxed));
+        let release_ms = f32::from_bits(self.controls.agc_release_ms.load(Ordering::Relaxed));
+        self.agc_limiter.set_times(
+            sanitize_sample(attack_ms).clamp(10.0, 1000.0),
+            sanitize_sample(release_ms).clamp(100.0, 10_000.0),
+        );
+        let ceiling_db = f32::from_bits(self.controls.agc_ceiling_db.load(Ordering::Relaxed));
+        self.agc_limiter
+            .set_ceiling_db(sanitize_sample(ceiling_db).clamp(AGC_CEILING_MIN_DB, 0.0));
+
+        // Compressor settings
+        self.current_compressor_enabled = self.controls.compressor_enabled.load(Ordering::Relaxed);
+        let load_f32 = |atomic: &AtomicU32, min: f32, max: f32| {
+            sanitize_sample(f32::from_bits(atomic.load(Ordering::Relaxed))).clamp(min, max)
+        };
+        self.compressor.threshold_db = load_f32(&self.controls.compressor_threshold_db, -60.0, 0.0);
+        self.compressor.ratio = load_f32(&self.controls.compressor_ratio, 1.0, 20.0);
+        self.compressor.makeup_db = load_f32(&self.controls.compressor_makeup_db, 0.0, 24.0);
+        let attack_ms = load_f32(&self.controls.compressor_attack_ms, 0.1, 100.0);
+        let release_ms = load_f32(&self.controls.compressor_release_ms, 10.0, 1000.0);
+        self.compressor.set_times(attack_ms, release_ms);
+    }
+
+    /// Moves the EQ straight to the gains the last `process_updates` targeted instead
+    /// of ramping there. For offline rendering, which applies its settings only once.
+    pub fn snap_eq(&mut self) {
+        self.eq_ramp_gains = [
+            self.current_eq_low,
+            self.current_eq_mid,
+            self.current_eq_high,
+        ];
+        self.apply_eq_gains();
+    }
+
+    /// Steps the EQ filters one frame toward the target gains rather than jumping,
+    /// snapping once within 0.01 dB.
+    fn advance_eq_ramp(&mut self) {
+        let targets = [
+            self.current_eq_low,
+            self.current_eq_mid,
+            self.current_eq_high,
+        ];
+        if self.eq.is_empty() || self.eq_ramp_gains == targets {
+            return;
+        }
+        let coeff = frame_coeff(EQ_RAMP_MS);
+        for (gain, target) in self.eq_ramp_gains.iter_mut().zip(targets) {
+            *gain += (target - *gain) * coeff;
+            if (target - *gain).abs() <= 0.01 {
+                *gain = target;
+            }
+        }
+        self.apply_eq_gains();
+    }
+
+    /// Moves every channel's EQ to `eq_ramp_gains`. The coefficients are shared by all
+    /// channels, and the makeup gain is only worked out while auto-gain uses it.
+    fn apply_eq_gains(&mut self) {
+        let [low, mid, high] = self.eq_ramp_gains;
+        let Ok(bands) = eq_band_coefficients(low, mid, high) else {
+            return;
+        };
+        let makeup_gain = self.current_eq_auto_gain.then(|| eq_makeup_gain(&bands));
+        for eq_instance in &mut self.eq {
+            eq_instance.set_bands(&bands, makeup_gain);
+        }
+    }
+
+    /// Low, mid and high gains (dB) the EQ filters currently use.
+    pub fn eq_gains(&self) -> [f32; 3] {
+        self.eq_ramp_gains
+    }
+
+    /// Processes buffers of any length (one slice per channel), buffering internally to
+    /// whole `FRAME_SIZE` frames. Suppression, threshold, and dynamic threshold are read
+    /// from the control atomics.
+    ///
+    /// Output lags input by up to one frame; until enough input has accumulated the
+    /// output is zero-filled. Returns the number of samples per channel that carry
+    /// processed audio.
+    pub fn process_chunk(&mut self, input: &[&[f32]], output: &mut [&mut [f32]]) -> usize {
+        let Some(mut adapter) = self.chunk_adapter.take() else {
+            return 0;
+        };
+
+        let suppression =
+            f32::from_bits(self.controls.suppression_strength.load(Ordering::Relaxed));
+        let threshold = f32::from_bits(self.controls.gate_threshold.load(Ordering::Relaxed));
+        let dynamic_threshold = self
+            .controls
+            .dynamic_threshold_enabled
+            .load(Ordering::Relaxed);
+
+        let len = input
+            .iter()
+            .map(|ch| ch.len())
+            .chain(output.iter().map(|ch| ch.len()))
+            .min()
+            .unwrap_or(0);
+        let in_channels = input.len().min(MAX_CHANNELS);
+        let out_channels = output.len().min(MAX_CHANNELS);
+
+        // Work in frame-sized slices so large host buffers never overflow the adapter rings
+        let mut written = 0;
+        let mut offset = 0;
+        while offset < len {
+            let end = (offset + FRAME_SIZE).min(len);
+
+            let mut in_iter = input.iter();
+            let inputs: [&[f32]; MAX_CHANNELS] =
+                std::array::from_fn(|_| in_iter.next().map_or(&[][..], |ch| &ch[offset..end]));
+            adapter.push_planar(&inputs[..in_channels]);
+
+            adapter.process_available(self, suppression, threshold, dynamic_threshold);
+
+            let mut out_iter = output.iter_mut();
+            let mut outputs: [&mut [f32]; MAX_CHANNELS] = std::array::from_fn(|_| {
+                out_iter
+                    .next()
+                    .map_or(&mut [][..], |ch| &mut ch[offset..end])
+            });
+            written += adapter.pop_planar(&mut outputs[..out_channels]);
+
+            offset = end;
+        }
+
+        self.chunk_adapter = Some(adapter);
+        written
+    }
+
+    /// Sets the rate the frames passed to `process_frame` are sampled at.
+    /// Defaults to `SAMPLE_RATE`; callers feeding non-48kHz audio must set this.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate.max(1);
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Number of frames to collect before a calibration finalizes, always within
+    /// `1..=CALIBRATION_MAX_FRAMES` so `calibration_mode` is guaranteed to self-clear.
+    fn calibration_target_frames(&self) -> usize {
+        let frames = (self.sample_rate * CALIBRATION_SECS) as usize / FRAME_SIZE;
+        frames.clamp(1, CALIBRATION_MAX_FRAMES)
+    }
+
+    fn finish_gain_calibration(&mut self) {
+        let level = speech_level(&mut self.gain_calibration_samples);
+        self.gain_calibration_result
+            .store(suggest_input_gain_db(level).to_bits(), Ordering::Relaxed);
+        self.gain_calibration_mode.store(false, Ordering::Relaxed);
+        self.gain_calibration_samples.clear();
+    }
+
+    fn finish_calibration(&mut self) {
+        let max_rms = self
+            .calibration_samples
+            .iter()
+            .cloned()
+            .fold(0.0f32, f32::max);
+        let suggested = (max_rms * 1.2).max(0.005);
+        self.calibration_result
+            .store(suggested.to_bits(), Ordering::Relaxed);
+        self.calibration_mode.store(false, Ordering::Relaxed);
+        self.calibration_samples.clear();
+    }
+
+    /// Runs the VAD on `frame` and smooths its verdict (`VadSmoother`). If the VAD
+    /// rejects the frame, `above_threshold` (the level-only decision) stands in for it,
+    /// so VAD errors never hold the gate closed. After `VAD_ERROR_LIMIT` consecutive
+    /// errors the VAD is skipped for good.
+    fn detect_speech(&mut self, frame: &[i16], above_threshold: bool) -> bool {
+        if self.vad_failed {
+            return above_threshold;
+        }
+
+        let vad_idx = self.current_vad_mode.clamp(0, 3) as usize;
+        match self.vad_instances[vad_idx].is_voice_segment(frame) {
+            Ok(is_speech) => {
+                self.vad_error_count = 0;
+                self.vad_smoother.update(is_speech)
+            }
+            Err(_) => {
+                self.vad_error_count += 1;
+                if self.vad_error_count >= VAD_ERROR_LIMIT {
+                    log::error!(
+                        "VAD rejected {} consecutive {}-sample frames, using level-only gating",
+                        self.vad_error_count,
+                        frame.len()
+                    );
+                    self.vad_failed = true;
+                }
+                above_threshold
+            }
+        }
+    }
+
+    fn store_gain_reduction(&self, gate_db: f32, level_db: f32) {
+        self.gate_reduction_db
+            .store(gate_db.to_bits(), Ordering::Relaxed);
+        self.level_reduction_db.store(
+            level_db.min(GAIN_REDUCTION_MAX_DB).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Suppression to use for this frame. With speech-gated suppression, frames
+    /// following a VAD-positive frame ease down to the speech floor (more raw signal,
+    /// fewer RNNoise artifacts on consonants) and silence returns to `base`.
+    fn speech_gated_strength(&mut self, base: f32) -> f32 {
+        if !self.current_speech_gated {
+            self.speech_blend = 0.0;
+            return base;
+        }
+        self.speech_blend = if self.last_frame_speech {
+            (self.speech_blend + SPEECH_BLEND_ATTACK).min(1.0)
+        } else {
+            (self.speech_blend - SPEECH_BLEND_RELEASE).max(0.0)
+        };
+        let speech_level = self.current_speech_floor.min(base);
+        base + (speech_level - base) * self.speech_blend
     }
 
     pub fn process_frame(
@@ -434,47 +2129,160 @@ impl VoidProcessor {
             return;
         }
 
+        // Suspended: only a level check runs until the input crosses the threshold again
+        if self.suspended {
+            let threshold = if dynamic_threshold_enabled {
+                self.suspend_threshold
+            } else {
+                gate_threshold
+            };
+            if self.stays_suspended(input_frames, threshold) {
+                for out_ch in output_frames.iter_mut() {
+                    out_ch.fill(0.0);
+                }
+                self.store_gain_reduction(GAIN_REDUCTION_MAX_DB, 0.0);
+                self.send_processed_frame(output_frames);
+                return;
+            }
+            self.suspended = false;
+            self.silent_samples = 0;
+            self.dsp_suspended.store(false, Ordering::Relaxed);
+        }
+
         let mut mono_mix = [0.0f32; FRAME_SIZE];
+        // The same mix of the signal the gate keys off (`gate_key_source`), for the VAD
+        let mut key_mix = [0.0f32; FRAME_SIZE];
+        // Per-sample power of the key signal summed over channels: the gate's level, which
+        // unlike the mono mix doesn't depend on how correlated the channels are
+        let mut mix_power = [0.0f32; FRAME_SIZE];
+        // Per-channel energy of the key signal, for picking the VAD channel
+        let mut channel_energies = [0.0f32; MAX_CHANNELS];
+        let key_source = self.current_gate_key_source;
+        // Per-sample suppression, ramping from the last frame's toward this one's
+        let suppression_target = self.speech_gated_strength(suppression_strength);
+        let mut suppression_ramp = [suppression_target; FRAME_SIZE];
+        if let Some(mut strength) = self.suppression_ramp.filter(|s| *s != suppression_target) {
+            let decay = smoothing_coeff(SUPPRESSION_RAMP_MS);
+            for value in &mut suppression_ramp {
+                strength = suppression_target + (strength - suppression_target) * decay;
+                *value = strength;
+            }
+        }
+        // Settled: stop ramping once inaudibly close
+        let end = suppression_ramp[FRAME_SIZE - 1];
+        self.suppression_ramp = Some(if (end - suppression_target).abs() < 1e-4 {
+            suppression_target
+        } else {
+            end
+        });
+        self.advance_eq_ramp();
+        let input_gain = self.current_input_gain;
+        // Raw (pre-gain) input energy, for the gain calibration phase
+        let mut input_energy = 0.0f32;
 
         // 1. Process Per-Channel Logic (Echo Cancel, Denoise)
         for i in 0..channels {
             let input_ch = input_frames[i];
             let output_ch = &mut output_frames[i];
 
-            // Convert input to temp buffer for processing
+            // Convert input to temp buffer for processing (dropping NaN/Inf from the device)
             let mut temp_input = [0.0f32; FRAME_SIZE];
-            temp_input.copy_from_slice(input_ch);
+            for (dst, &src) in temp_input.iter_mut().zip(input_ch.iter()) {
+                let sample = sanitize_sample(src);
+                input_energy += sample * sample;
+                *dst = sample * input_gain;
+            }
 
             // A. Echo Cancellation
-            if let Some(aec_instance) = self.echo_canceller.get_mut(i) {
+            if !self.current_echo_cancel {
+                // Disabled: reference frames (if any) are ignored
+            } else if let Some(aec_instance) = self.echo_canceller.get_mut(i) {
                 if let Some(refs) = ref_frames {
                     // Try to match channel, or use channel 0 if fewer refs
                     if let Some(ref_ch) = refs.get(i).or_else(|| refs.first()) {
                         let mut aec_output = [0.0f32; FRAME_SIZE];
-                        aec_instance.process_frame(&temp_input, ref_ch, &mut aec_output);
+                        // Optionally strip noise from the reference so AEC models only the echo
+                        match self.reference_denoise.get_mut(i) {
+                            Some(ref_denoise) if self.current_clean_reference => {
+                                let mut clean_ref = [0.0f32; FRAME_SIZE];
+                                ref_denoise.process_frame(&mut clean_ref, ref_ch);
+                                aec_instance.process_frame(
+                                    &temp_input,
+                                    &clean_ref,
+                                    &mut aec_output,
+                                );
+                            }
+                            _ => {
+                                aec_instance.process_frame(&temp_input, ref_ch, &mut aec_output);
+                            }
+                        }
                         temp_input.copy_from_slice(&aec_output);
                     }
                 }
             }
 
-            // B. Denoise (RNNoise)
+            if key_source == GateKeySource::PreDenoise {
+                if let Some(frame) = self.pre_denoise.get_mut(i) {
+                    frame.copy_from_slice(&temp_input);
+                }
+            }
+
+            // B. Denoise (RNNoise)
             if let Some(denoise_instance) = self.denoise.get_mut(i) {
                 denoise_instance.process_frame(output_ch, &temp_input);
             }
 
             // C. Blend (Suppression Strength)
             for j in 0..FRAME_SIZE {
-                output_ch[j] = temp_input[j].mul_add(1.0 - suppression_strength, output_ch[j] * suppression_strength);
+                let strength = suppression_ramp[j];
+                output_ch[j] = temp_input[j].mul_add(1.0 - strength, output_ch[j] * strength);
+            }
+
+            // Protected band: put back what suppression removed above the cutoff
+            if self.current_protect_highs {
+                if let Some(filter) = self.protect_highs_filters.get_mut(i) {
+                    restore_high_band(filter, &temp_input, output_ch);
+                }
+            }
 
-                // Accumulate to Mono Mix for Gate/VAD analysis
+            // D. Spectral Gate (per-bin, ahead of the full-band gate)
+            if self.current_spectral_gate {
+                if let Some(gate) = self.spectral_gates.get_mut(i) {
+                    gate.process_frame(output_ch);
+                }
+            }
+
+            let key: &[f32] = match key_source {
+                GateKeySource::PostDenoise => output_ch,
+                GateKeySource::PreDenoise => &temp_input,
+            };
+            let mut channel_energy = 0.0f32;
+            let mut key_energy = 0.0f32;
+            for j in 0..FRAME_SIZE {
+                // Accumulate to Mono Mix for the visualizer, and the key signal's mix and
+                // power for the VAD and gate
                 mono_mix[j] += output_ch[j];
+                channel_energy += output_ch[j] * output_ch[j];
+                let power = key[j] * key[j];
+                key_mix[j] += key[j];
+                mix_power[j] += power;
+                key_energy += power;
+            }
+            if let Some(energy) = channel_energies.get_mut(i) {
+                *energy = key_energy;
+            }
+            if let Some(level) = self.channel_levels.get(i) {
+                let channel_rms = sanitize_sample((channel_energy / FRAME_SIZE as f32).sqrt());
+                level.store(channel_rms.to_bits(), Ordering::Relaxed);
             }
         }
 
-        // 2. Normalize Mono Mix
+        // 2. Normalize Mono Mix and power (averages over channels)
         let norm_factor = 1.0 / (channels as f32);
-        for sample in mono_mix.iter_mut() {
-            *sample *= norm_factor;
+        for j in 0..FRAME_SIZE {
+            mono_mix[j] *= norm_factor;
+            key_mix[j] *= norm_factor;
+            mix_power[j] *= norm_factor;
         }
 
         // 3. Linked Gate Analysis (Runs on Mono Mix)
@@ -484,112 +2292,276 @@ impl VoidProcessor {
         match self.bypass_state {
             BypassState::Bypassed => {
                 for i in 0..channels {
-                    output_frames[i].copy_from_slice(input_frames[i]);
+                    for (out, &inp) in output_frames[i].iter_mut().zip(input_frames[i].iter()) {
+                        *out = sanitize_sample(inp);
+                    }
                 }
+                self.store_gain_reduction(0.0, 0.0);
             }
             _ => {
-                // Analysis
-                let sum: f32 = mono_mix.iter().map(|x| x * x).sum();
-                let rms = (sum / FRAME_SIZE as f32).sqrt();
+                // Analysis: mean power of the key signal over channels, so a stereo pair
+                // reads like either of its channels whether the two are identical or
+                // unrelated
+                let sum: f32 = mix_power.iter().sum();
+                let rms = sanitize_sample((sum / FRAME_SIZE as f32).sqrt());
                 self.volume_level.store(rms.to_bits(), Ordering::Relaxed);
 
                 // Calibration mode
                 if self.calibration_mode.load(Ordering::Relaxed) {
-                    self.calibration_samples.push(rms);
-                    let calibration_duration_samples = SAMPLE_RATE * 3;
-                    if self.calibration_samples.len()
-                        >= (calibration_duration_samples / FRAME_SIZE as u32) as usize
-                    {
-                        let max_rms = self
-                            .calibration_samples
-                            .iter()
-                            .cloned()
-                            .fold(0.0f32, f32::max);
-                        let suggested = (max_rms * 1.2).max(0.005);
-                        self.calibration_result
-                            .store(suggested.to_bits(), Ordering::Relaxed);
-                        self.calibration_mode.store(false, Ordering::Relaxed);
-                        self.calibration_samples.clear();
+                    if self.calibration_samples.len() < CALIBRATION_MAX_FRAMES {
+                        self.calibration_samples.push(rms);
+                    }
+                    if self.calibration_samples.len() >= self.calibration_target_frames() {
+                        self.finish_calibration();
+                    }
+                } else if !self.calibration_samples.is_empty() {
+                    // Calibration was cancelled externally; drop the partial measurement
+                    self.calibration_samples.clear();
+                }
+
+                // Gain calibration (speech level, measured before input gain)
+                if self.gain_calibration_mode.load(Ordering::Relaxed) {
+                    let input_rms = (input_energy / (FRAME_SIZE * channels) as f32).sqrt();
+                    if self.gain_calibration_samples.len() < CALIBRATION_MAX_FRAMES {
+                        self.gain_calibration_samples
+                            .push(sanitize_sample(input_rms));
                     }
+                    if self.gain_calibration_samples.len() >= self.calibration_target_frames() {
+                        self.finish_gain_calibration();
+                    }
+                } else if !self.gain_calibration_samples.is_empty() {
+                    self.gain_calibration_samples.clear();
                 }
 
                 // Gate decision
                 let effective_threshold = if dynamic_threshold_enabled {
                     self.noise_floor_tracker.update(rms);
-                    let dynamic = self.noise_floor_tracker.floor().mul_add(1.5, 0.003);
-                    dynamic.clamp(0.005, 0.08)
+                    let floor = self.noise_floor_tracker.floor();
+                    self.noise_floor.store(floor.to_bits(), Ordering::Relaxed);
+                    // A fixed margin would put minimums below it out of reach
+                    let margin = DYNAMIC_THRESHOLD_MARGIN.min(self.current_dynamic_min);
+                    let dynamic = floor.mul_add(1.5, margin);
+                    dynamic.clamp(self.current_dynamic_min, self.current_dynamic_max)
                 } else {
+                    self.noise_floor.store(0, Ordering::Relaxed);
                     gate_threshold
                 };
 
+                // Mixing dilutes a voice that is only on one channel, so the VAD can
+                // listen to a single channel instead
+                let vad_channel = self
+                    .current_vad_source
+                    .channel(&channel_energies[..channels.min(MAX_CHANNELS)]);
+                let vad_source: &[f32] = match vad_channel {
+                    Some(ch) if key_source == GateKeySource::PreDenoise => self
+                        .pre_denoise
+                        .get(ch)
+                        .map_or(&key_mix[..], |frame| &frame[..]),
+                    Some(ch) => &output_frames[ch][..],
+                    None => &key_mix,
+                };
                 let mut vad_buffer = [0i16; FRAME_SIZE];
-                for i in 0..FRAME_SIZE {
-                    vad_buffer[i] = (mono_mix[i] * 32767.0).clamp(-32768.0, 32767.0) as i16;
+                for (dst, &src) in vad_buffer.iter_mut().zip(vad_source) {
+                    *dst = (src * 32767.0).clamp(-32768.0, 32767.0) as i16;
+                }
+                // Gate decisions run per sub-block; the VAD only sees whole frames, so its
+                // verdict covers every sub-block of this one
+                let subblocks = self.current_gate_subblocks;
+                let block_len = FRAME_SIZE / subblocks;
+                let mut block_rms = [rms; GATE_MAX_SUBBLOCKS as usize];
+                if subblocks > 1 {
+                    let blocks = mix_power.chunks_exact(block_len);
+                    for (level, block) in block_rms.iter_mut().zip(blocks) {
+                        let sum: f32 = block.iter().sum();
+                        *level = sanitize_sample((sum / block_len as f32).sqrt());
+                    }
+                }
+                // Two thresholds: open above the threshold, stay open down to the lower
+                // close threshold
+                let close_threshold = effective_threshold * self.current_gate_hysteresis;
+                let above_threshold = if self.gate_open {
+                    rms > close_threshold
+                } else {
+                    rms > effective_threshold
+                };
+                let is_speech = self.detect_speech(&vad_buffer, above_threshold);
+                self.last_frame_speech = is_speech;
+                if is_speech {
+                    self.speech_frames.fetch_add(1, Ordering::Relaxed);
                 }
-                let vad_idx = self.current_vad_mode.clamp(0, 3) as usize;
-                let is_speech = self.vad_instances[vad_idx].is_voice_segment(&vad_buffer).unwrap_or(false);
 
+                let was_open = self.gate_open;
                 let attack_samples = (SAMPLE_RATE / 1000) * ATTACK_MS;
                 let release_samples = (SAMPLE_RATE / 1000) * RELEASE_MS;
                 let fade_samples = (SAMPLE_RATE / 1000) * FADE_MS;
 
-                if rms > effective_threshold || is_speech {
-                    self.samples_since_close += FRAME_SIZE as u32;
-                    if self.samples_since_close >= attack_samples {
-                        self.gate_open = true;
-                        self.samples_since_open = 0;
-                        self.fade_position = 0;
+                // Gate state and expander target at the end of each sub-block. Sub-blocks
+                // before a mid-frame open still fade on from where the last frame left off.
+                let fade_start = self.fade_position;
+                let mut block_open = [false; GATE_MAX_SUBBLOCKS as usize];
+                let mut block_expander = [1.0f32; GATE_MAX_SUBBLOCKS as usize];
+                for b in 0..subblocks {
+                    let level = block_rms[b];
+                    let above = if self.gate_open {
+                        level > close_threshold
+                    } else {
+                        level > effective_threshold
+                    };
+                    if above || is_speech {
+                        self.samples_since_close += block_len as u32;
+                        if self.samples_since_close >= attack_samples {
+                            self.gate_open = true;
+                            self.samples_since_open = 0;
+                            self.hold_remaining = self.current_hold_samples;
+                            self.fade_position = 0;
+                        }
+                    } else {
+                        self.samples_since_close = 0;
+                        if self.gate_open {
+                            if self.hold_remaining > 0 {
+                                // Hold: brief pauses between words don't count toward release
+                                self.hold_remaining =
+                                    self.hold_remaining.saturating_sub(block_len as u32);
+                            } else {
+                                self.samples_since_open += block_len as u32;
+                                if self.samples_since_open > release_samples {
+                                    self.gate_open = false;
+                                }
+                            }
+                        }
                     }
-                } else {
-                    self.samples_since_close = 0;
-                    if self.gate_open {
-                        self.samples_since_open += FRAME_SIZE as u32;
-                        if self.samples_since_open > release_samples {
-                            self.gate_open = false;
+                    block_open[b] = self.gate_open;
+                    if !self.gate_open {
+                        block_expander[b] =
+                            expander_gain(level, effective_threshold, self.current_expander_ratio);
+                    }
+                }
+                self.gate_is_open.store(self.gate_open, Ordering::Relaxed);
+                if self.gate_open != was_open {
+                    self.send_gate_event(rms, effective_threshold);
+                }
+                self.effective_threshold
+                    .store(effective_threshold.to_bits(), Ordering::Relaxed);
+                self.track_silence(effective_threshold);
+
+                // Expander targets per sub-block, each ramped from the one before
+                let expander_start = self.expander_gain;
+                self.expander_gain = block_expander[subblocks - 1];
+                let expander_end = &block_expander[..subblocks];
+                let expander_active = self.current_gate_mode == GateMode::Expander
+                    && (expander_start < 1.0 || expander_end.iter().any(|&g| g < 1.0));
+                let any_closed = block_open[..subblocks].iter().any(|&open| !open);
+
+                // Gate envelope, worked out once so every channel fades in lockstep; an
+                // open sub-block passes and restarts the fade for the next close
+                let gate_active = self.current_gate_mode == GateMode::Gate && any_closed;
+                let mut gate_gains = [1.0f32; FRAME_SIZE];
+                let mut fade_end = fade_start;
+                if gate_active {
+                    let blocks = gate_gains.chunks_exact_mut(block_len);
+                    for (block, &open) in blocks.zip(&block_open) {
+                        if open {
+                            fade_end = 0;
+                            continue;
+                        }
+                        for gain in block.iter_mut() {
+                            if fade_end < fade_samples {
+                                *gain = self
+                                    .current_fade_curve
+                                    .gain(fade_end as f32 / fade_samples as f32);
+                                fade_end += 1;
+                            } else {
+                                *gain = 0.0;
+                            }
                         }
                     }
                 }
 
                 // 4. Apply Gate & EQ & AGC to ALL channels
-                let mut final_fade = self.fade_position;
                 for (i, output_ch) in output_frames.iter_mut().enumerate().take(channels) {
-
-                    // Gate (each channel uses same fade envelope)
-                    if !self.gate_open {
-                        let mut local_fade = self.fade_position;
-                        for sample in output_ch.iter_mut() {
-                            if local_fade < fade_samples {
-                                let fade_gain = 1.0 - (local_fade as f32 / fade_samples as f32);
-                                *sample *= fade_gain;
-                                local_fade += 1;
-                            } else {
-                                *sample = 0.0;
+                    // Expander (same gain ramp on every channel)
+                    if expander_active {
+                        let mut from = expander_start;
+                        let blocks = output_ch.chunks_exact_mut(block_len);
+                        for (block, &to) in blocks.zip(expander_end) {
+                            let step = (to - from) / block_len as f32;
+                            for (k, sample) in block.iter_mut().enumerate() {
+                                *sample *= step.mul_add(k as f32, from);
                             }
+                            from = to;
+                        }
+                    }
+
+                    // Gate (the same envelope on every channel)
+                    if gate_active {
+                        for (sample, &gain) in output_ch.iter_mut().zip(&gate_gains) {
+                            // Fully closed is exact silence
+                            *sample = if gain == 0.0 { 0.0 } else { *sample * gain };
                         }
-                        final_fade = local_fade;
                     }
 
                     // Equalizer
-                    if self.current_eq_enabled {
+                    if self.current_eq_enabled || self.current_eq_tilt != 0.0 {
                         if let Some(eq) = self.eq.get_mut(i) {
+                            let mut corrupted = false;
                             for sample in output_ch.iter_mut() {
-                                *sample = eq.process(*sample);
+                                let val = eq.process(*sample);
+                                corrupted |= !val.is_finite();
+                                *sample = sanitize_sample(val);
+                            }
+                            if corrupted {
+                                eq.reset();
                             }
                         }
                     }
                 }
 
-                // Update global fade position from per-sample tracking
-                if !self.gate_open {
-                    self.fade_position = final_fade;
+                // Carry the fade over to the next frame while the gate stays closed
+                if self.current_gate_mode == GateMode::Gate && !self.gate_open {
+                    self.fade_position = fade_end;
                 } else {
                     self.fade_position = 0;
                 }
 
+                // Compressor (Linked)
+                if self.current_compressor_enabled {
+                    self.compressor.process_frame(output_frames);
+                }
+
                 // AGC (Linked)
                 if self.current_agc_enabled {
-                    self.agc_limiter.process_frame(output_frames);
+                    if self.current_agc_hold_when_gated
+                        && !self.gate_open
+                        && self.current_gate_mode != GateMode::Off
+                    {
+                        self.agc_limiter.hold_frame(output_frames);
+                    } else {
+                        self.agc_limiter.process_frame(output_frames);
+                    }
+                }
+
+                // Stereo Width (Mid-Side)
+                if channels == 2 {
+                    let (left, right) = output_frames.split_at_mut(1);
+                    apply_stereo_width(left[0], right[0], self.current_stereo_width);
+                }
+
+                let mut gate_gain = if gate_active {
+                    gate_gains[FRAME_SIZE - 1]
+                } else {
+                    1.0
+                };
+                if expander_active {
+                    gate_gain *= self.expander_gain;
+                }
+                let mut level_reduction = 0.0;
+                if self.current_compressor_enabled {
+                    level_reduction += self.compressor.gain_reduction_db();
+                }
+                if self.current_agc_enabled {
+                    level_reduction += gain_reduction_db(self.agc_limiter.gain());
                 }
+                self.store_gain_reduction(gain_reduction_db(gate_gain), level_reduction);
             }
         }
 
@@ -603,8 +2575,8 @@ impl VoidProcessor {
                     let gain_dry = (t * std::f32::consts::PI / 2.0).sin();
 
                     for i in 0..channels {
-                        output_frames[i][j] =
-                            output_frames[i][j].mul_add(gain_wet, input_frames[i][j] * gain_dry);
+                        output_frames[i][j] = output_frames[i][j]
+                            .mul_add(gain_wet, sanitize_sample(input_frames[i][j]) * gain_dry);
                     }
                     if t_start < crossfade_len {
                         t_start += 1;
@@ -622,8 +2594,8 @@ impl VoidProcessor {
                     let gain_wet = (t * std::f32::consts::PI / 2.0).sin();
 
                     for i in 0..channels {
-                        output_frames[i][j] =
-                            output_frames[i][j].mul_add(gain_wet, input_frames[i][j] * gain_dry);
+                        output_frames[i][j] = output_frames[i][j]
+                            .mul_add(gain_wet, sanitize_sample(input_frames[i][j]) * gain_dry);
                     }
 
                     if t_start < crossfade_len {
@@ -638,71 +2610,238 @@ impl VoidProcessor {
             _ => {}
         }
 
-        // Spectrum Analysis (On Mono Mix) - throttled to every 4th frame (~25fps)
+        // Mute, faded across the frame so switching it doesn't click
+        let mute_target = if self.current_muted { 0.0 } else { 1.0 };
+        if self.mute_gain != 1.0 || mute_target != 1.0 {
+            let step = (mute_target - self.mute_gain) / FRAME_SIZE as f32;
+            for output_ch in output_frames.iter_mut() {
+                for (j, sample) in output_ch.iter_mut().enumerate() {
+                    *sample *= step.mul_add((j + 1) as f32, self.mute_gain);
+                }
+            }
+            self.mute_gain = mute_target;
+        }
+
+        // Final safety net: never hand non-finite or wildly out-of-range samples to the host
+        let mut peak = 0.0f32;
+        for output_ch in output_frames.iter_mut() {
+            for sample in output_ch.iter_mut() {
+                *sample = sanitize_sample(*sample).clamp(-1.0, 1.0);
+                peak = peak.max(sample.abs());
+            }
+        }
+        if peak >= CLIP_LEVEL {
+            self.clip_count.fetch_add(1, Ordering::Relaxed);
+        }
+        if self.current_dither {
+            self.dither.process_frame(output_frames);
+        }
+        self.send_processed_frame(output_frames);
+
+        // Spectrum Analysis / Waveform (On Mono Mix) - throttled to every 4th frame (~25fps)
         self.spectrum_frame_counter += 1;
         if self.spectrum_frame_counter >= 4 {
             self.spectrum_frame_counter = 0;
         }
-        if self.spectrum_frame_counter == 0 {
-        if let Some(sender) = &self.spectrum_sender {
+        let send_waveform = self.current_waveform_view && self.waveform_sender.is_some();
+        let send_spectrum = !self.current_waveform_view && self.spectrum_sender.is_some();
+        let visual_due = self.spectrum_frame_counter == 0 && self.current_spectrum_enabled;
+        if visual_due && (send_waveform || send_spectrum) {
             // Need Input Mono Mix too
             let mut input_mono = [0.0f32; FRAME_SIZE];
             for j in 0..FRAME_SIZE {
                 for input_ch in input_frames.iter().take(channels) {
-                    input_mono[j] += input_ch[j];
+                    input_mono[j] += sanitize_sample(input_ch[j]);
                 }
                 input_mono[j] *= norm_factor;
             }
 
-            // Apply Hann window using pre-computed coefficients (avoids Vec allocation)
-            for j in 0..FRAME_SIZE {
-                self.windowed_in[j] = input_mono[j] * self.hann_coefficients[j];
-                self.windowed_out[j] = mono_mix[j] * self.hann_coefficients[j];
+            if send_waveform {
+                self.publish_visual(
+                    VisualKind::Waveform,
+                    input_mono.iter().copied(),
+                    mono_mix.iter().copied(),
+                );
+            } else {
+                // Apply Hann window using pre-computed coefficients (avoids Vec allocation)
+                for j in 0..FRAME_SIZE {
+                    self.windowed_in[j] = input_mono[j] * self.hann_coefficients[j];
+                    self.windowed_out[j] = mono_mix[j] * self.hann_coefficients[j];
+                }
+
+                let input_spectrum = samples_fft_to_spectrum(
+                    &self.windowed_in,
+                    SAMPLE_RATE,
+                    FrequencyLimit::Range(SPECTRUM_MIN_HZ, SPECTRUM_MAX_HZ),
+                    Some(&divide_by_N_sqrt),
+                )
+                .ok();
+
+                let output_spectrum = samples_fft_to_spectrum(
+                    &self.windowed_out,
+                    SAMPLE_RATE,
+                    FrequencyLimit::Range(SPECTRUM_MIN_HZ, SPECTRUM_MAX_HZ),
+                    Some(&divide_by_N_sqrt),
+                )
+                .ok();
+
+                if let (Some(in_spec), Some(out_spec)) = (input_spectrum, output_spectrum) {
+                    self.publish_visual(
+                        VisualKind::Spectrum,
+                        in_spec.data().iter().map(|(_, val)| val.val()),
+                        out_spec.data().iter().map(|(_, val)| val.val()),
+                    );
+                }
             }
+        }
+    }
 
-            let input_spectrum = samples_fft_to_spectrum(
-                &self.windowed_in,
-                SAMPLE_RATE,
-                FrequencyLimit::Range(20.0, 20_000.0),
-                Some(&divide_by_N_sqrt),
-            )
-            .ok();
+    /// Counts gate-closed time toward auto-suspend and suspends once it reaches the timeout.
+    /// Only Gate mode qualifies: its output is already silent, so skipping the chain
+    /// doesn't change what listeners hear.
+    fn track_silence(&mut self, threshold: f32) {
+        let Some(limit) = self.current_auto_suspend_samples else {
+            self.silent_samples = 0;
+            return;
+        };
+        if self.gate_open || self.current_gate_mode != GateMode::Gate {
+            self.silent_samples = 0;
+            return;
+        }
+        self.silent_samples = self.silent_samples.saturating_add(FRAME_SIZE as u32);
+        if self.silent_samples >= limit {
+            self.suspended = true;
+            self.suspend_threshold = threshold;
+            self.dsp_suspended.store(true, Ordering::Relaxed);
+        }
+    }
 
-            let output_spectrum = samples_fft_to_spectrum(
-                &self.windowed_out,
-                SAMPLE_RATE,
-                FrequencyLimit::Range(20.0, 20_000.0),
-                Some(&divide_by_N_sqrt),
-            )
-            .ok();
+    /// Cheap activity check while suspended: the gained input level against `threshold`.
+    /// Anything that needs the full chain (auto-suspend turned off, another gate mode,
+    /// bypass, calibration) ends the suspension too.
+    fn stays_suspended(&self, input_frames: &[&[f32]], threshold: f32) -> bool {
+        if self.current_auto_suspend_samples.is_none()
+            || self.current_gate_mode != GateMode::Gate
+            || !matches!(self.bypass_state, BypassState::Active)
+            || self.calibration_mode.load(Ordering::Relaxed)
+            || self.gain_calibration_mode.load(Ordering::Relaxed)
+        {
+            return false;
+        }
+        let mut energy = 0.0f32;
+        for input_ch in input_frames {
+            for &sample in input_ch.iter() {
+                let sample = sanitize_sample(sample) * self.current_input_gain;
+                energy += sample * sample;
+            }
+        }
+        let samples = (FRAME_SIZE * input_frames.len().max(1)) as f32;
+        let rms = sanitize_sample((energy / samples).sqrt());
+        // Keep the meter alive
+        self.volume_level.store(rms.to_bits(), Ordering::Relaxed);
+        rms <= threshold
+    }
 
-            if let (Some(in_spec), Some(out_spec)) = (input_spectrum, output_spectrum) {
-                // Reuse pre-allocated buffers
-                self.spectrum_in_buf.clear();
-                self.spectrum_out_buf.clear();
+    /// The settings this processor applies on each `process_updates`. Clone it to keep a
+    /// handle for a GUI or host parameters; stores through any clone reach the processor.
+    pub fn controls(&self) -> &ProcessorControls {
+        &self.controls
+    }
 
-                for (_, val) in in_spec.data().iter() {
-                    self.spectrum_in_buf.push(val.val());
-                }
-                for (_, val) in out_spec.data().iter() {
-                    self.spectrum_out_buf.push(val.val());
-                }
+    /// Sender the GUI uses to hand spectrum/waveform buffers back once it has replaced them.
+    /// Without recycling, sends simply stop once the pool is drained.
+    pub fn spectrum_recycler(&self) -> Sender<(Vec<f32>, Vec<f32>)> {
+        self.spectrum_pool_tx.clone()
+    }
 
-                // Only clone when channel has room to avoid wasted Vec allocations
-                if !sender.is_full() {
-                    if let Err(crossbeam_channel::TrySendError::Disconnected(_)) =
-                        sender.try_send((
-                            self.spectrum_in_buf.clone(),
-                            self.spectrum_out_buf.clone(),
-                        ))
-                    {
-                        log::warn!("Spectrum receiver disconnected, disabling sender");
-                        self.spectrum_sender = None;
-                    }
-                }
+    /// Reports a gate transition to the event log, if one is attached. Events are dropped
+    /// rather than blocking when the log falls behind.
+    fn send_gate_event(&mut self, rms: f32, threshold: f32) {
+        let Some(sender) = &self.gate_event_sender else {
+            return;
+        };
+        let event = GateEvent {
+            time: std::time::SystemTime::now(),
+            open: self.gate_open,
+            rms,
+            threshold,
+        };
+        if let Err(crossbeam_channel::TrySendError::Disconnected(_)) = sender.try_send(event) {
+            log::warn!("Gate event receiver disconnected, disabling sender");
+            self.gate_event_sender = None;
+        }
+    }
+
+    /// Hands the mono mix of the finished output to `frame_sender`, if one is attached.
+    fn send_processed_frame(&mut self, output_frames: &[&mut [f32]]) {
+        let Some(sender) = &self.frame_sender else {
+            return;
+        };
+        let mut mix = [0.0f32; FRAME_SIZE];
+        for output_ch in output_frames {
+            for (sum, &sample) in mix.iter_mut().zip(output_ch.iter()) {
+                *sum += sample;
+            }
+        }
+        let norm_factor = 1.0 / output_frames.len().max(1) as f32;
+        for sample in &mut mix {
+            *sample *= norm_factor;
+        }
+        if let Err(crossbeam_channel::TrySendError::Disconnected(_)) = sender.try_send(mix) {
+            log::warn!("Processed frame receiver disconnected, disabling sender");
+            self.frame_sender = None;
+        }
+    }
+
+    /// Fills a pooled buffer pair and sends it; skips the update if no buffer is free.
+    fn publish_visual(
+        &mut self,
+        kind: VisualKind,
+        input: impl Iterator<Item = f32>,
+        output: impl Iterator<Item = f32>,
+    ) {
+        let sender = match kind {
+            VisualKind::Spectrum => &self.spectrum_sender,
+            VisualKind::Waveform => &self.waveform_sender,
+        };
+        let Some(sender) = sender else {
+            return;
+        };
+        if sender.is_full() {
+            return;
+        }
+        // Buffers that weren't pre-sized by us (e.g. a GUI's initial empty Vecs) would
+        // grow on push; drop them instead (free for empty Vecs)
+        let (mut in_buf, mut out_buf) = loop {
+            let Ok(bufs) = self.spectrum_pool_rx.try_recv() else {
+                return;
+            };
+            if bufs.0.capacity() >= VISUAL_BUFFER_LEN && bufs.1.capacity() >= VISUAL_BUFFER_LEN {
+                break bufs;
+            }
+        };
+
+        in_buf.clear();
+        out_buf.clear();
+        in_buf.extend(input.take(kind.max_len()));
+        out_buf.extend(output.take(kind.max_len()));
+
+        match sender.try_send((in_buf, out_buf)) {
+            Ok(()) => {}
+            Err(crossbeam_channel::TrySendError::Full(bufs)) => {
+                let _ = self.spectrum_pool_tx.try_send(bufs);
             }
+            Err(crossbeam_channel::TrySendError::Disconnected(_)) => match kind {
+                VisualKind::Spectrum => {
+                    log::warn!("Spectrum receiver disconnected, disabling sender");
+                    self.spectrum_sender = None;
+                }
+                VisualKind::Waveform => {
+                    log::warn!("Waveform receiver disconnected, disabling sender");
+                    self.waveform_sender = None;
+                }
+            },
         }
-        } // spectrum throttle
     }
 }
 
@@ -772,6 +2911,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shorter_window_adapts_faster() {
+        let settle = |speed: NoiseFloorSpeed| {
+            let mut tracker = NoiseFloorTracker::with_window(speed.window());
+            for _ in 0..1500 {
+                tracker.update(0.005);
+            }
+            // The room gets louder; count frames until the floor is halfway there
+            (0..2000)
+                .position(|_| {
+                    tracker.update(0.02);
+                    tracker.floor() > 0.0125
+                })
+                .unwrap_or(usize::MAX)
+        };
+        let fast = settle(NoiseFloorSpeed::Fast);
+        let normal = settle(NoiseFloorSpeed::Normal);
+        let slow = settle(NoiseFloorSpeed::Slow);
+        assert!(
+            fast < normal && normal < slow,
+            "Expected fast < normal < slow, got {} / {} / {}",
+            fast,
+            normal,
+            slow
+        );
+        assert!(slow < 2000, "Slow window should still adapt eventually");
+    }
+
+    #[test]
+    fn test_window_is_bounded() {
+        let mut tracker = NoiseFloorTracker::with_window(NOISE_FLOOR_MAX_WINDOW * 10);
+        for i in 0..3 * NOISE_FLOOR_MAX_WINDOW {
+            tracker.update(0.01 + (i % 7) as f32 * 0.001);
+        }
+        assert!(tracker.floor() > 0.0);
+
+        // Live change keeps the floor instead of restarting from scratch
+        let before = tracker.floor();
+        tracker.set_window(0);
+        assert_eq!(tracker.floor(), before);
+        tracker.update(0.01);
+    }
+
+    #[test]
+    fn test_floor_holds_through_loud_stretch_within_window() {
+        let mut tracker = NoiseFloorTracker::new();
+        for _ in 0..300 {
+            tracker.update(0.005);
+        }
+        // 1.5s of loud room: still inside the 3s window, so the quiet minimum holds
+        // (a 300ms scan would have climbed most of the way to 0.05 by now)
+        for _ in 0..150 {
+            tracker.update(0.05);
+        }
+        assert!(
+            tracker.floor() < 0.006,
+            "Floor should stay at the window minimum: got {}",
+            tracker.floor()
+        );
+
+        // Once the quiet frames leave the window the floor follows the room
+        for _ in 0..300 {
+            tracker.update(0.05);
+        }
+        assert!(
+            tracker.floor() > 0.03,
+            "Floor should rise after the window passes: got {}",
+            tracker.floor()
+        );
+    }
+
+    #[test]
+    fn test_frozen_floor_ignores_new_levels() {
+        let mut tracker = NoiseFloorTracker::new();
+        for _ in 0..300 {
+            tracker.update(0.005);
+        }
+        let held = tracker.floor();
+
+        tracker.set_frozen(true);
+        assert!(tracker.is_frozen());
+        for level in [0.05, 0.2, 0.0005, 0.02] {
+            for _ in 0..500 {
+                tracker.update(level);
+            }
+            assert_eq!(tracker.floor(), held);
+        }
+
+        // Unfrozen, it adapts again starting from the held value
+        tracker.set_frozen(false);
+        for _ in 0..500 {
+            tracker.update(0.05);
+        }
+        assert!(tracker.floor() > held * 2.0);
+    }
+
+    #[test]
+    fn test_dynamic_threshold_range_is_validated() {
+        assert_eq!(
+            dynamic_threshold_range(DYNAMIC_THRESHOLD_DEFAULT_MIN, DYNAMIC_THRESHOLD_DEFAULT_MAX),
+            (0.005, 0.08)
+        );
+        assert_eq!(dynamic_threshold_range(0.0, 1.0), (0.0005, 0.2));
+        assert_eq!(
+            dynamic_threshold_range(f32::NAN, f32::INFINITY),
+            (0.0005, 0.0005)
+        );
+        // An inverted range collapses to the minimum
+        assert_eq!(dynamic_threshold_range(0.02, 0.01), (0.02, 0.02));
+    }
+
+    #[test]
+    fn test_dynamic_threshold_follows_configured_range() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let hiss: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.7).sin() * 0.0004)
+            .collect();
+        let mut out = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor| {
+            processor.process_updates();
+            for _ in 0..100 {
+                processor.process_frame(&[&hiss], &mut [&mut out], None, 0.0, 0.05, true);
+            }
+            f32::from_bits(processor.effective_threshold.load(Ordering::Relaxed))
+        };
+
+        // A very quiet room hits the default floor
+        assert_eq!(run(&mut processor), DYNAMIC_THRESHOLD_DEFAULT_MIN);
+
+        processor
+            .controls()
+            .dynamic_threshold_min
+            .store(0.001f32.to_bits(), Ordering::Relaxed);
+        let threshold = run(&mut processor);
+        // Below the margin a minimum of 0.003 or more would keep
+        assert!(
+            threshold > 0.001 && threshold < DYNAMIC_THRESHOLD_MARGIN,
+            "Lower minimum should let the threshold follow the floor: got {}",
+            threshold
+        );
+        // The published floor is the one the threshold was derived from
+        let floor = f32::from_bits(processor.noise_floor.load(Ordering::Relaxed));
+        assert!((floor.mul_add(1.5, 0.001) - threshold).abs() < 1e-6);
+
+        processor
+            .controls()
+            .dynamic_threshold_min
+            .store(0.01f32.to_bits(), Ordering::Relaxed);
+        assert_eq!(run(&mut processor), 0.01);
+
+        // With Auto-Gate off there's no floor to report
+        processor.process_frame(&[&hiss], &mut [&mut out], None, 0.0, 0.05, false);
+        assert_eq!(processor.noise_floor.load(Ordering::Relaxed), 0);
+    }
+
     #[test]
     fn test_ring_buffer_wraps() {
         let mut tracker = NoiseFloorTracker::new();
@@ -815,6 +3109,157 @@ mod tests {
         assert!(eq.update_gains(-10.0, 0.0, 10.0).is_ok());
     }
 
+    #[test]
+    fn test_eq_tilt_is_symmetric() {
+        assert_eq!(eq_tilt_gains(0.0), (0.0, 0.0));
+
+        // Warm: lows up, highs down by the same amount
+        let (low, high) = eq_tilt_gains(0.5);
+        assert!(low > 0.0 && high < 0.0);
+        assert_eq!(low, -high);
+        assert_eq!(eq_tilt_gains(1.0), (EQ_TILT_MAX_DB, -EQ_TILT_MAX_DB));
+
+        // Bright is the mirror image
+        let (low, high) = eq_tilt_gains(-0.5);
+        assert_eq!((low, high), (-0.5 * EQ_TILT_MAX_DB, 0.5 * EQ_TILT_MAX_DB));
+
+        // Out of range or garbage input stays bounded
+        assert_eq!(eq_tilt_gains(3.0), (EQ_TILT_MAX_DB, -EQ_TILT_MAX_DB));
+        assert_eq!(eq_tilt_gains(f32::NAN), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_eq_tilt_drives_shelves_without_detailed_eq() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .eq_enabled
+            .store(false, Ordering::Relaxed);
+        processor
+            .controls()
+            .eq_low_gain
+            .store(9.0f32.to_bits(), Ordering::Relaxed);
+        processor
+            .controls()
+            .eq_tilt
+            .store(1.0f32.to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+
+        // Band gains are ignored while the EQ is off, the tilt is not
+        assert_eq!(processor.current_eq_low, EQ_TILT_MAX_DB);
+        assert_eq!(processor.current_eq_mid, 0.0);
+        assert_eq!(processor.current_eq_high, -EQ_TILT_MAX_DB);
+
+        // With the EQ on, the tilt stacks on the band gains
+        processor
+            .controls()
+            .eq_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        assert_eq!(processor.current_eq_low, 9.0 + EQ_TILT_MAX_DB);
+    }
+
+    #[test]
+    fn test_eq_gain_changes_ramp() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .eq_low_gain
+            .store(12.0f32.to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+
+        // The target moves at once, the filters don't until a frame is processed
+        assert_eq!(processor.current_eq_low, 12.0);
+        assert_eq!(processor.eq_ramp_gains, [0.0, 0.0, 0.0]);
+
+        // Extra update calls don't speed the ramp up: it advances once per frame
+        let input = vec![0.0f32; FRAME_SIZE];
+        let mut out = vec![0.0f32; FRAME_SIZE];
+        for _ in 0..5 {
+            processor.process_updates();
+        }
+        processor.process_frame(&[&input], &mut [&mut out], None, 1.0, 0.0, false);
+        let first = processor.eq_ramp_gains[0];
+        assert!(first > 0.0 && first < 6.0, "first step {}", first);
+        for _ in 0..20 {
+            processor.process_updates();
+            processor.process_frame(&[&input], &mut [&mut out], None, 1.0, 0.0, false);
+        }
+        assert_eq!(processor.eq_ramp_gains, [12.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_snap_eq_skips_ramp() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .eq_mid_gain
+            .store((-6.0f32).to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+        processor.snap_eq();
+        assert_eq!(processor.eq_gains(), [0.0, -6.0, 0.0]);
+    }
+
+    #[test]
+    fn test_eq_makeup_is_only_computed_with_auto_gain() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let flat = ThreeBandEq::new(0.0, 0.0, 0.0).unwrap().makeup_gain();
+        let boosted = ThreeBandEq::new(6.0, 0.0, 0.0).unwrap().makeup_gain();
+        processor
+            .controls()
+            .eq_low_gain
+            .store(6.0f32.to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+        processor.snap_eq();
+        for eq in &processor.eq {
+            assert_eq!(eq.makeup_gain(), flat);
+        }
+
+        // Turning auto-gain on catches the trim up, the same for every channel
+        processor
+            .controls()
+            .eq_auto_gain
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        for eq in &processor.eq {
+            assert_eq!(eq.makeup_gain(), boosted);
+        }
+    }
+
+    #[test]
+    fn test_eq_auto_gain_preserves_white_noise_rms() {
+        // Deterministic white noise (LCG)
+        let mut seed = 0x1234_5678u32;
+        let noise: Vec<f32> = (0..SAMPLE_RATE as usize)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1u32 << 24) as f32 * 0.2 - 0.1
+            })
+            .collect();
+        let rms =
+            |data: &[f32]| (data.iter().map(|s| s * s).sum::<f32>() / data.len() as f32).sqrt();
+        let input_rms = rms(&noise);
+
+        for gains in [(6.0, 6.0, 6.0), (-6.0, 3.0, 9.0), (10.0, -10.0, -10.0)] {
+            let mut eq = ThreeBandEq::new(gains.0, gains.1, gains.2).unwrap();
+            eq.set_auto_gain(true);
+            let out: Vec<f32> = noise.iter().map(|&s| eq.process(s)).collect();
+            let ratio = rms(&out) / input_rms;
+            assert!(
+                (ratio - 1.0).abs() < 0.1,
+                "Auto-gain should preserve RMS for {:?}: ratio {}",
+                gains,
+                ratio
+            );
+        }
+
+        // Without auto-gain the boost is audible
+        let mut eq = ThreeBandEq::new(6.0, 6.0, 6.0).unwrap();
+        let out: Vec<f32> = noise.iter().map(|&s| eq.process(s)).collect();
+        assert!(rms(&out) / input_rms > 1.5);
+        assert!(eq.makeup_gain() < 1.0);
+    }
+
     // ── LookaheadLimiter ─────────────────────────────────────────
 
     #[test]
@@ -868,47 +3313,209 @@ mod tests {
     }
 
     #[test]
-    fn test_empty_frames_no_panic() {
+    fn test_output_stays_under_ceiling() {
         let mut limiter = LookaheadLimiter::new(0.7);
-        let mut frames: Vec<&mut [f32]> = vec![];
-        limiter.process_frame(&mut frames); // Should not panic
+        limiter.set_ceiling_db(-6.0);
+        let ceiling = 10f32.powf(-6.0 / 20.0);
+        // Quiet stretches let the gain climb, then loud bursts arrive on top of it
+        for i in 0..200 {
+            let amp = if i % 40 < 30 { 0.05 } else { 0.9 };
+            let mut data: Vec<f32> = (0..FRAME_SIZE)
+                .map(|k| amp * (k as f32 * 0.05).sin())
+                .collect();
+            let mut frames: Vec<&mut [f32]> = vec![data.as_mut_slice()];
+            limiter.process_frame(&mut frames);
+            for sample in frames[0].iter() {
+                assert!(
+                    sample.abs() <= ceiling,
+                    "Frame {}: {} exceeds the {} ceiling",
+                    i,
+                    sample,
+                    ceiling
+                );
+            }
+        }
     }
 
-    // ── VoidProcessor ────────────────────────────────────────────
+    #[test]
+    fn test_ceiling_cut_ramps_without_clicking() {
+        // A 480 Hz tone whose gain has climbed, then the ceiling drops under it
+        let tone = |i: usize| 0.3 * (i as f32 * std::f32::consts::TAU / 100.0).sin();
+        let mut limiter = LookaheadLimiter::new(0.7);
+        let mut previous = 0.0f32;
+        let mut largest_step = 0.0f32;
+        for n in 0..200 {
+            // Mid-cycle at this frame boundary, where a gain step shows the most
+            if n == 151 {
+                limiter.set_ceiling_db(-12.0);
+            }
+            let mut data: Vec<f32> = (0..FRAME_SIZE).map(|k| tone(n * FRAME_SIZE + k)).collect();
+            limiter.process_frame(&mut [data.as_mut_slice()]);
+            for &sample in &data {
+                if n >= 140 {
+                    largest_step = largest_step.max((sample - previous).abs());
+                }
+                previous = sample;
+            }
+        }
+        // The tone itself moves at most 0.3 * 2π / 100 per sample, times the gain of 3;
+        // switching gains at the boundary would step by about 0.6
+        assert!(
+            largest_step < 0.07,
+            "Gain change stepped the output by {}",
+            largest_step
+        );
+        assert!(limiter.gain() < 1.0, "Ceiling should have cut the gain");
+    }
 
     #[test]
-    fn test_processor_creation() {
-        let _p1 = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
-        let _p2 = VoidProcessor::new(2, 0, (-3.0, 0.0, 3.0), 0.5, false);
+    fn test_default_times_match_original_coefficients() {
+        let limiter = LookaheadLimiter::new(0.7);
+        assert!((limiter.attack_coeff - 0.1).abs() < 0.001);
+        assert!((limiter.release_coeff - 0.005).abs() < 0.0001);
     }
 
     #[test]
-    fn test_silence_produces_silence() {
-        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
-        let input = [0.0f32; FRAME_SIZE];
-        let mut output = [0.0f32; FRAME_SIZE];
+    fn test_faster_attack_reduces_overshoot() {
+        // Let the gain climb on quiet input, then step up and sum the excess over target
+        let overshoot = |attack_ms: f32| {
+            let mut limiter = LookaheadLimiter::new(0.3);
+            limiter.set_times(attack_ms, AGC_DEFAULT_RELEASE_MS);
+            let mut quiet = vec![0.05f32; FRAME_SIZE];
+            for _ in 0..500 {
+                quiet.fill(0.05);
+                limiter.process_frame(&mut [quiet.as_mut_slice()]);
+            }
+            let mut excess = 0.0;
+            let mut loud = vec![0.3f32; FRAME_SIZE];
+            for _ in 0..30 {
+                loud.fill(0.3);
+                limiter.process_frame(&mut [loud.as_mut_slice()]);
+                excess += (loud[0] - 0.3).max(0.0);
+            }
+            excess
+        };
+
+        let default = overshoot(AGC_DEFAULT_ATTACK_MS);
+        let fast = overshoot(10.0);
+        assert!(
+            default > 0.0,
+            "Step should overshoot with the default attack"
+        );
+        assert!(
+            fast < default * 0.5,
+            "Faster attack should cut overshoot: fast={} default={}",
+            fast,
+            default
+        );
+    }
+
+    #[test]
+    fn test_empty_frames_no_panic() {
+        let mut limiter = LookaheadLimiter::new(0.7);
+        let mut frames: Vec<&mut [f32]> = vec![];
+        limiter.process_frame(&mut frames); // Should not panic
+    }
+
+    // ── Compressor ───────────────────────────────────────────────
+
+    fn settled_compressor_output(compressor: &mut Compressor, level: f32) -> f32 {
+        let mut data = vec![level; FRAME_SIZE];
+        for _ in 0..20 {
+            data.fill(level);
+            let mut frames: Vec<&mut [f32]> = vec![data.as_mut_slice()];
+            compressor.process_frame(&mut frames);
+        }
+        data[FRAME_SIZE - 1]
+    }
+
+    #[test]
+    fn test_compressor_passes_signal_below_threshold() {
+        let mut compressor = Compressor::new(-20.0, 4.0, 5.0, 50.0, 0.0);
+        let out = settled_compressor_output(&mut compressor, 0.05); // -26 dBFS
+        assert!(
+            (out - 0.05).abs() < 1e-4,
+            "Below threshold should be untouched: {}",
+            out
+        );
+        assert!(compressor.gain_reduction_db() < 0.01);
+    }
+
+    #[test]
+    fn test_compressor_reduces_at_ratio() {
+        // 0.5 is ~-6 dBFS: 14 dB over a -20 dB threshold, 4:1 leaves 3.5 dB over
+        let mut compressor = Compressor::new(-20.0, 4.0, 5.0, 50.0, 0.0);
+        let out = settled_compressor_output(&mut compressor, 0.5);
+        let in_db = 20.0 * 0.5f32.log10();
+        let out_db = 20.0 * out.log10();
+        let expected_db = -20.0 + (in_db + 20.0) / 4.0;
+        assert!(
+            (out_db - expected_db).abs() < 0.1,
+            "Expected {:.2} dBFS, got {:.2}",
+            expected_db,
+            out_db
+        );
+        assert!((compressor.gain_reduction_db() - (in_db - expected_db)).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_compressor_makeup_restores_level() {
+        let in_db = 20.0 * 0.5f32.log10();
+        let reduction_db = (in_db + 20.0) * (1.0 - 1.0 / 4.0);
+        let mut compressor = Compressor::new(-20.0, 4.0, 5.0, 50.0, reduction_db);
+        let out = settled_compressor_output(&mut compressor, 0.5);
+        assert!(
+            (out - 0.5).abs() < 0.01,
+            "Makeup should restore the input level: {}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_compressor_releases_when_signal_drops() {
+        let mut compressor = Compressor::new(-20.0, 4.0, 5.0, 50.0, 0.0);
+        settled_compressor_output(&mut compressor, 0.5);
+        assert!(compressor.gain_reduction_db() > 5.0);
+        // 200ms of quiet is four release time constants
+        settled_compressor_output(&mut compressor, 0.01);
+        assert!(compressor.gain_reduction_db() < 0.5);
+    }
+
+    // ── VoidProcessor ────────────────────────────────────────────
+
+    #[test]
+    fn test_processor_creation() {
+        let _p1 = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let _p2 = VoidProcessor::new(2, 0, (-3.0, 0.0, 3.0), 0.5, false);
+    }
+
+    #[test]
+    fn test_silence_produces_silence() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let input = [0.0f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
 
         // Process enough frames for the gate to fully close
         for _ in 0..100 {
-            processor.process_frame(
-                &[&input],
-                &mut [&mut output],
-                None,
-                1.0,
-                0.015,
-                false,
-            );
+            processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
         }
 
         // After many silent frames, output should be all zeros
         let max = output.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
-        assert!(max < 0.001, "Silent input should produce silent output: max={}", max);
+        assert!(
+            max < 0.001,
+            "Silent input should produce silent output: max={}",
+            max
+        );
     }
 
     #[test]
     fn test_bypass_passes_through() {
         let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
-        processor.bypass_enabled.store(true, Ordering::Relaxed);
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
         processor.process_updates();
 
         // Generate a non-zero signal
@@ -920,14 +3527,7 @@ mod tests {
 
         // Process enough frames for bypass crossfade to complete
         for _ in 0..20 {
-            processor.process_frame(
-                &[&input],
-                &mut [&mut output],
-                None,
-                1.0,
-                0.015,
-                false,
-            );
+            processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
         }
 
         // After crossfade settles, output should match input
@@ -950,33 +3550,176 @@ mod tests {
         let loud = [0.3f32; FRAME_SIZE];
         let mut output = [0.0f32; FRAME_SIZE];
         for _ in 0..10 {
-            processor.process_frame(
-                &[&loud],
-                &mut [&mut output],
-                None,
-                1.0,
-                0.015,
-                false,
-            );
+            processor.process_frame(&[&loud], &mut [&mut output], None, 1.0, 0.015, false);
         }
 
         // Now feed silence - gate should close after release period
         let silence = [0.0f32; FRAME_SIZE];
         for _ in 0..200 {
-            processor.process_frame(
-                &[&silence],
-                &mut [&mut output],
-                None,
-                1.0,
-                0.015,
-                false,
-            );
+            processor.process_frame(&[&silence], &mut [&mut output], None, 1.0, 0.015, false);
         }
 
         let max = output.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
         assert!(max < 0.001, "Gate should close after silence: max={}", max);
     }
 
+    #[test]
+    fn test_gate_hold_bridges_pauses() {
+        // Open the gate, then pause for 300ms: longer than the 200ms release alone
+        let run = |hold_ms: u32| {
+            let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+            processor
+                .controls()
+                .gate_hold_ms
+                .store(hold_ms, Ordering::Relaxed);
+            processor.process_updates();
+
+            let loud = [0.3f32; FRAME_SIZE];
+            let silence = [0.0f32; FRAME_SIZE];
+            let mut output = [0.0f32; FRAME_SIZE];
+            for _ in 0..10 {
+                processor.process_frame(&[&loud], &mut [&mut output], None, 1.0, 0.015, false);
+            }
+            for _ in 0..30 {
+                processor.process_frame(&[&silence], &mut [&mut output], None, 1.0, 0.015, false);
+            }
+            processor.gate_open
+        };
+
+        assert!(
+            !run(0),
+            "Without hold the gate should release during a 300ms pause"
+        );
+        assert!(
+            run(200),
+            "A 200ms hold should keep the gate open through the pause"
+        );
+    }
+
+    #[test]
+    fn test_gate_hold_is_capped() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .gate_hold_ms
+            .store(u32::MAX, Ordering::Relaxed);
+        processor.process_updates();
+        assert_eq!(
+            processor.current_hold_samples,
+            (SAMPLE_RATE / 1000) * GATE_HOLD_MAX_MS
+        );
+    }
+
+    #[test]
+    fn test_gate_hold_follows_sample_rate() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor.set_sample_rate(16_000);
+        processor
+            .controls()
+            .gate_hold_ms
+            .store(250, Ordering::Relaxed);
+        processor.process_updates();
+        assert_eq!(processor.current_hold_samples, 4_000);
+    }
+
+    #[test]
+    fn test_expander_gain_is_proportional() {
+        assert_eq!(expander_gain(0.2, 0.1, 2.0), 1.0);
+        // 6 dB under threshold at 2:1 becomes 12 dB under, i.e. 6 dB of attenuation
+        assert!((expander_gain(0.05, 0.1, 2.0) - 0.5).abs() < 1e-6);
+        assert!((expander_gain(0.05, 0.1, 3.0) - 0.25).abs() < 1e-6);
+        assert_eq!(expander_gain(0.05, 0.1, 1.0), 1.0);
+        assert_eq!(expander_gain(0.0, 0.1, 2.0), EXPANDER_FLOOR);
+    }
+
+    #[test]
+    fn test_gate_mode_below_threshold() {
+        // Quiet tone (RMS ~0.05) under a 0.1 threshold, processed dry. 6 kHz is above the
+        // band the VAD listens to, so it can't take the tone for speech and open the gate.
+        let tone: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (2.0 * std::f32::consts::PI * 6000.0 * i as f32 / 48_000.0).sin() * 0.0707)
+            .collect();
+        let run = |mode: GateMode| {
+            let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+            processor
+                .controls()
+                .eq_enabled
+                .store(false, Ordering::Relaxed);
+            processor
+                .controls()
+                .gate_mode
+                .store(mode as u32, Ordering::Relaxed);
+            processor.process_updates();
+            let mut output = [0.0f32; FRAME_SIZE];
+            for _ in 0..50 {
+                processor.process_frame(&[&tone], &mut [&mut output], None, 0.0, 0.1, false);
+            }
+            assert!(!processor.gate_open);
+            rms(&output) / rms(&tone)
+        };
+
+        assert!(
+            run(GateMode::Gate) < 0.001,
+            "Gate should mute below threshold"
+        );
+        let expanded = run(GateMode::Expander);
+        assert!(
+            (expanded - 0.5).abs() < 0.05,
+            "Expander at 2:1 should attenuate ~6 dB, got {}",
+            expanded
+        );
+        assert!((run(GateMode::Off) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_gate_mode_round_trips_through_u32() {
+        for mode in GateMode::ALL {
+            assert_eq!(GateMode::from_u32(mode as u32), mode);
+        }
+        assert_eq!(GateMode::from_u32(99), GateMode::Gate);
+    }
+
+    #[test]
+    fn test_vad_source_picks_channel() {
+        let sources = [
+            VadSource::Mix,
+            VadSource::Loudest,
+            VadSource::Channel(0),
+            VadSource::Channel(5),
+        ];
+        for source in sources {
+            assert_eq!(VadSource::from_u32(source.to_u32()), source);
+        }
+
+        // Voice on the right channel only
+        let energies = [0.01, 4.0];
+        assert_eq!(VadSource::Mix.channel(&energies), None);
+        assert_eq!(VadSource::Loudest.channel(&energies), Some(1));
+        assert_eq!(VadSource::Channel(0).channel(&energies), Some(0));
+        // A channel the input doesn't have falls back to the mix
+        assert_eq!(VadSource::Channel(2).channel(&energies), None);
+        assert_eq!(VadSource::Loudest.channel(&[]), None);
+    }
+
+    #[test]
+    fn test_fade_curves_are_monotonic_and_end_silent() {
+        for curve in FadeCurve::ALL {
+            assert_eq!(FadeCurve::from_u32(curve as u32), curve);
+            assert!((curve.gain(0.0) - 1.0).abs() < 1e-6, "{:?} start", curve);
+            assert!(curve.gain(1.0).abs() < 1e-6, "{:?} end", curve);
+            let mut prev = 1.0;
+            for step in 1..=100 {
+                let gain = curve.gain(step as f32 / 100.0);
+                assert!(gain <= prev, "{:?} rises at step {}", curve, step);
+                prev = gain;
+            }
+        }
+        // Cosine holds level longer than linear at the start, exponential drops faster
+        assert!(FadeCurve::Cosine.gain(0.25) > FadeCurve::Linear.gain(0.25));
+        assert!(FadeCurve::Exponential.gain(0.25) < FadeCurve::Linear.gain(0.25));
+        assert_eq!(FadeCurve::from_u32(99), FadeCurve::Cosine);
+    }
+
     #[test]
     fn test_channel_mismatch_does_not_panic() {
         let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
@@ -985,7 +3728,7 @@ mod tests {
 
         // Pass 1 channel to a 2-channel processor — should not panic
         processor.process_frame(
-            &[&input],        // 1 channel, expected 2
+            &[&input], // 1 channel, expected 2
             &mut [&mut output],
             None,
             1.0,
@@ -997,6 +3740,1804 @@ mod tests {
         assert_eq!(output[0], 0.0, "Mismatch should produce silence");
     }
 
+    #[test]
+    fn test_duplicated_mono_gates_like_true_mono() {
+        // A mono mic copied to both channels (as plugin hosts do) must reach the gate at
+        // its full level, not halved
+        let mut mono = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+        let mut stereo = VoidProcessor::new(2, 0, (0.0, 0.0, 0.0), 0.7, false);
+        // Just above the threshold: halving the level would keep the gate shut
+        let (amplitude, threshold) = (0.03f32, 0.015f32);
+        for frame in 0..50 {
+            let mut input = [0.0f32; FRAME_SIZE];
+            for (j, sample) in input.iter_mut().enumerate() {
+                *sample = sine(440.0, amplitude, frame * FRAME_SIZE + j);
+            }
+            let mut mono_out = [0.0f32; FRAME_SIZE];
+            let (mut left, mut right) = ([0.0f32; FRAME_SIZE], [0.0f32; FRAME_SIZE]);
+            mono.process_frame(&[&input], &mut [&mut mono_out], None, 0.0, threshold, false);
+            stereo.process_frame(
+                &[&input, &input],
+                &mut [&mut left, &mut right],
+                None,
+                0.0,
+                threshold,
+                false,
+            );
+
+            let mono_level = f32::from_bits(mono.volume_level.load(Ordering::Relaxed));
+            let stereo_level = f32::from_bits(stereo.volume_level.load(Ordering::Relaxed));
+            assert!((mono_level - stereo_level).abs() < 1e-6, "frame {}", frame);
+            assert_eq!(mono.gate_open, stereo.gate_open, "frame {}", frame);
+            assert_eq!(mono_out, left, "frame {}", frame);
+            assert_eq!(left, right, "frame {}", frame);
+        }
+        assert!(stereo.gate_open);
+    }
+
+    #[test]
+    fn test_uncorrelated_stereo_gates_like_mono() {
+        // Different tones per channel: averaging them would read 3 dB low (RMS 0.015) and
+        // keep the gate shut, but each channel alone is over the threshold
+        let (amplitude, threshold) = (0.03f32, 0.02f32);
+        let mut mono = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+        let mut stereo = VoidProcessor::new(2, 0, (0.0, 0.0, 0.0), 0.7, false);
+        for frame in 0..20 {
+            let (mut left, mut right) = ([0.0f32; FRAME_SIZE], [0.0f32; FRAME_SIZE]);
+            for j in 0..FRAME_SIZE {
+                left[j] = sine(400.0, amplitude, frame * FRAME_SIZE + j);
+                right[j] = sine(600.0, amplitude, frame * FRAME_SIZE + j);
+            }
+            let mut out = [[0.0f32; FRAME_SIZE]; 3];
+            let [mono_out, out_l, out_r] = &mut out;
+            mono.process_frame(&[&left], &mut [mono_out], None, 0.0, threshold, false);
+            let stereo_out: &mut [&mut [f32]] = &mut [out_l, out_r];
+            stereo.process_frame(&[&left, &right], stereo_out, None, 0.0, threshold, false);
+        }
+        let mono_level = f32::from_bits(mono.volume_level.load(Ordering::Relaxed));
+        let stereo_level = f32::from_bits(stereo.volume_level.load(Ordering::Relaxed));
+        assert!(
+            (mono_level - amplitude / 2f32.sqrt()).abs() < 1e-3,
+            "mono {}",
+            mono_level
+        );
+        assert!(
+            (stereo_level - mono_level).abs() < 1e-3,
+            "stereo {}",
+            stereo_level
+        );
+        assert!(mono.gate_open && stereo.gate_open);
+    }
+
+    #[test]
+    fn test_speech_gated_suppression() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+
+        // Disabled: base strength passes through untouched
+        processor.last_frame_speech = true;
+        assert_eq!(processor.speech_gated_strength(0.9), 0.9);
+
+        processor
+            .controls()
+            .speech_gated_suppression
+            .store(true, Ordering::Relaxed);
+        processor
+            .controls()
+            .speech_suppression_floor
+            .store(0.2f32.to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+
+        // Speech eases down to the floor within a few frames
+        let mut strength = 0.0;
+        for _ in 0..3 {
+            strength = processor.speech_gated_strength(0.9);
+        }
+        assert!(
+            (strength - 0.2).abs() < 1e-6,
+            "Speech should reach the floor: {}",
+            strength
+        );
+
+        // Silence releases gradually back to full strength
+        processor.last_frame_speech = false;
+        let first = processor.speech_gated_strength(0.9);
+        assert!(
+            first > 0.2 && first < 0.9,
+            "Release should be gradual: {}",
+            first
+        );
+        for _ in 0..30 {
+            strength = processor.speech_gated_strength(0.9);
+        }
+        assert!(
+            (strength - 0.9).abs() < 1e-6,
+            "Silence should restore base: {}",
+            strength
+        );
+
+        // The floor never raises suppression above the user's setting
+        processor.last_frame_speech = true;
+        for _ in 0..3 {
+            strength = processor.speech_gated_strength(0.1);
+        }
+        assert!((strength - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_suppression_changes_ramp() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let input = vec![0.0f32; FRAME_SIZE];
+        let mut out = vec![0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor, strength: f32| {
+            processor.process_frame(&[&input], &mut [&mut out], None, strength, 0.0, false);
+            processor.suppression_ramp.unwrap()
+        };
+
+        // The first frame starts at the setting, with nothing to ramp from
+        assert_eq!(run(&mut processor, 1.0), 1.0);
+
+        // A jump to 0 decays with the ramp's time constant: 10 ms is one frame
+        let after_one = run(&mut processor, 0.0);
+        let expected = smoothing_coeff(SUPPRESSION_RAMP_MS).powi(FRAME_SIZE as i32);
+        assert!(
+            (after_one - expected).abs() < 1e-3,
+            "{} vs {}",
+            after_one,
+            expected
+        );
+        for _ in 0..10 {
+            run(&mut processor, 0.0);
+        }
+        assert_eq!(run(&mut processor, 0.0), 0.0);
+    }
+
+    // ── Spectrum ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_spectrum_bin_frequencies() {
+        // 100Hz bins: 20Hz rounds up to the 100Hz bin, 20kHz is bin 199
+        assert_eq!(spectrum_bin_hz(0), 100.0);
+        assert_eq!(spectrum_bin_hz(1), 200.0);
+        assert_eq!(spectrum_bin_hz(199), SPECTRUM_MAX_HZ);
+        assert!(spectrum_bin_hz(0) >= SPECTRUM_MIN_HZ);
+    }
+
+    // ── Protect Highs ────────────────────────────────────────────
+
+    #[test]
+    fn test_restore_high_band_keeps_only_highs() {
+        let mut seed = 0x1234_5678u32;
+        let dry: Vec<f32> = (0..FRAME_SIZE * 100)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1u32 << 24) as f32 * 0.2 - 0.1
+            })
+            .collect();
+        // Energy above/below the cutoff, measured well away from it
+        let filtered_rms = |ty: Type<f32>, cutoff: f32, signal: &[f32]| {
+            let coeffs =
+                Coefficients::<f32>::from_params(ty, SAMPLE_RATE.hz(), cutoff.hz(), 0.707).unwrap();
+            let mut filter = DirectForm2Transposed::<f32>::new(coeffs);
+            let out: Vec<f32> = signal.iter().map(|&s| filter.run(s)).collect();
+            rms(&out[out.len() / 2..])
+        };
+
+        // Fully suppressed signal: without protection nothing is left in any band
+        let mut protected = vec![0.0f32; dry.len()];
+        let mut filter = protect_highs_filter().unwrap();
+        for (d, p) in dry.chunks(FRAME_SIZE).zip(protected.chunks_mut(FRAME_SIZE)) {
+            restore_high_band(&mut filter, d, p);
+        }
+
+        let dry_high = filtered_rms(Type::HighPass, 14_000.0, &dry);
+        let kept_high = filtered_rms(Type::HighPass, 14_000.0, &protected);
+        let dry_low = filtered_rms(Type::LowPass, 2_000.0, &dry);
+        let kept_low = filtered_rms(Type::LowPass, 2_000.0, &protected);
+        assert!(
+            kept_high > dry_high * 0.8,
+            "Highs should be restored: {} of {}",
+            kept_high,
+            dry_high
+        );
+        assert!(
+            kept_low < dry_low * 0.1,
+            "Lows should stay suppressed: {} of {}",
+            kept_low,
+            dry_low
+        );
+    }
+
+    #[test]
+    fn test_protect_highs_retains_high_band() {
+        // A low and a high tone, fully suppressed with the gate out of the way
+        let input: Vec<f32> = (0..FRAME_SIZE)
+            .map(|n| sine(300.0, 0.2, n) + sine(16_000.0, 0.2, n))
+            .collect();
+        let run = |protect: bool| {
+            let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+            processor
+                .controls()
+                .protect_highs
+                .store(protect, Ordering::Relaxed);
+            processor
+                .controls()
+                .gate_mode
+                .store(GateMode::Off as u32, Ordering::Relaxed);
+            processor.process_updates();
+            let mut output = vec![0.0f32; FRAME_SIZE];
+            for _ in 0..50 {
+                processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.0, false);
+            }
+            (
+                tone_amplitude(&output, 300.0),
+                tone_amplitude(&output, 16_000.0),
+            )
+        };
+
+        let (low_off, high_off) = run(false);
+        let (low_on, high_on) = run(true);
+        assert!(high_on > 0.16, "Highs should come back: {}", high_on);
+        assert!(
+            high_on > high_off * 1.5,
+            "Protection should keep more highs: {} vs {}",
+            high_on,
+            high_off
+        );
+        assert!(
+            (low_on - low_off).abs() < 0.01,
+            "Lows should stay as processed: {} vs {}",
+            low_on,
+            low_off
+        );
+    }
+
+    // ── Spectral Gate ────────────────────────────────────────────
+
+    fn sine(hz: f64, amplitude: f32, n: usize) -> f32 {
+        (2.0 * std::f64::consts::PI * hz * n as f64 / SAMPLE_RATE as f64).sin() as f32 * amplitude
+    }
+
+    // Amplitude of the `hz` component (signal should hold whole cycles of it)
+    fn tone_amplitude(signal: &[f32], hz: f64) -> f32 {
+        let (mut i, mut q) = (0.0f64, 0.0f64);
+        for (n, &s) in signal.iter().enumerate() {
+            let phase = 2.0 * std::f64::consts::PI * hz * n as f64 / SAMPLE_RATE as f64;
+            i += s as f64 * phase.cos();
+            q += s as f64 * phase.sin();
+        }
+        (2.0 * i.hypot(q) / signal.len() as f64) as f32
+    }
+
+    #[test]
+    fn test_fft_round_trip() {
+        let gate = SpectralGate::new();
+        let original: Vec<f32> = (0..SPECTRAL_GATE_FFT_SIZE)
+            .map(|n| sine(1000.0, 0.5, n) + sine(7_300.0, 0.1, n))
+            .collect();
+        let mut re = original.clone();
+        let mut im = vec![0.0f32; SPECTRAL_GATE_FFT_SIZE];
+        fft_in_place(&mut re, &mut im, &gate.twiddles, false);
+        fft_in_place(&mut re, &mut im, &gate.twiddles, true);
+        for (out, orig) in re.iter().zip(&original) {
+            assert!((out / SPECTRAL_GATE_FFT_SIZE as f32 - orig).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_spectral_gate_keeps_tone_and_gates_noise_band() {
+        let mut gate = SpectralGate::new();
+        let mut output = Vec::new();
+        // A steady 3kHz hum on its own first, then a loud 500Hz tone on top of it
+        for frame in 0..150 {
+            let mut buf = [0.0f32; FRAME_SIZE];
+            for (j, sample) in buf.iter_mut().enumerate() {
+                let n = frame * FRAME_SIZE + j;
+                *sample = sine(3_000.0, 0.02, n);
+                if frame >= 100 {
+                    *sample += sine(500.0, 0.3, n);
+                }
+            }
+            gate.process_frame(&mut buf);
+            if frame >= 110 {
+                output.extend_from_slice(&buf);
+            }
+        }
+
+        let tone = tone_amplitude(&output, 500.0);
+        let hum = tone_amplitude(&output, 3_000.0);
+        assert!(
+            tone > 0.27,
+            "Tone above the noise profile should pass: {}",
+            tone
+        );
+        assert!(hum < 0.004, "Hum should be gated: {}", hum);
+    }
+
+    #[test]
+    fn test_dither_is_small_zero_mean_and_in_range() {
+        let mut dither = Dither::new();
+        let (mut sum, mut sum_sq, mut peak) = (0.0f64, 0.0f64, 0.0f32);
+        for _ in 0..1000 {
+            let mut quiet = [0.001f32; FRAME_SIZE];
+            let mut full = [1.0f32; FRAME_SIZE];
+            let mut silence = [0.0f32; FRAME_SIZE];
+            dither.process_frame(&mut [&mut quiet, &mut full, &mut silence]);
+            for &sample in &quiet {
+                let noise = (sample - 0.001) / DITHER_LSB;
+                sum += noise as f64;
+                sum_sq += (noise * noise) as f64;
+                peak = peak.max(noise.abs());
+            }
+            assert!(full
+                .iter()
+                .all(|&s| (1.0 - 2.0 * DITHER_LSB..=1.0).contains(&s)));
+            // A closed gate stays digitally silent
+            assert!(silence.iter().all(|&s| s == 0.0));
+        }
+        let count = (1000 * FRAME_SIZE) as f64;
+        let mean = sum / count;
+        let variance = sum_sq / count - mean * mean;
+        // Triangular over ±1 LSB: variance 1/6 LSB²
+        assert!(peak < 1.0, "Peak {} LSB", peak);
+        assert!(mean.abs() < 0.01, "Mean {} LSB", mean);
+        assert!(
+            (variance - 1.0 / 6.0).abs() < 0.01,
+            "Variance {} LSB²",
+            variance
+        );
+
+        // Off by default; on, it reaches the processor's output
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let input = [0.25f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+        let mut plain = [0.0f32; FRAME_SIZE];
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        for _ in 0..5 {
+            processor.process_frame(&[&input], &mut [&mut plain], None, 0.0, 0.0, false);
+        }
+        assert!(plain.iter().all(|&s| s == 0.25));
+        processor
+            .controls()
+            .dither_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        processor.process_frame(&[&input], &mut [&mut output], None, 0.0, 0.0, false);
+        assert!(output.iter().any(|&s| s != 0.25));
+        assert!(output.iter().all(|&s| (s - 0.25).abs() < DITHER_LSB));
+    }
+
+    #[test]
+    fn test_clip_count() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        let quiet = [0.25f32; FRAME_SIZE];
+        let mut clipped = quiet;
+        clipped[100] = -1.0;
+        let mut left = [0.0f32; FRAME_SIZE];
+        let mut right = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor, right_in: &[f32]| {
+            processor.process_frame(
+                &[&quiet, right_in],
+                &mut [&mut left, &mut right],
+                None,
+                0.0,
+                0.0,
+                false,
+            );
+        };
+        for _ in 0..5 {
+            run(&mut processor, &quiet);
+        }
+        assert_eq!(processor.clip_count.load(Ordering::Relaxed), 0);
+
+        // One full-scale sample on either channel counts the frame once
+        for _ in 0..3 {
+            run(&mut processor, &clipped);
+        }
+        run(&mut processor, &quiet);
+        assert_eq!(processor.clip_count.load(Ordering::Relaxed), 3);
+
+        processor.clip_count.store(0, Ordering::Relaxed);
+        run(&mut processor, &quiet);
+        assert_eq!(processor.clip_count.load(Ordering::Relaxed), 0);
+        run(&mut processor, &clipped);
+        assert_eq!(processor.clip_count.load(Ordering::Relaxed), 1);
+    }
+
+    // ── Stereo ───────────────────────────────────────────────────
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_stereo_linked_gate_follows_mix() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let loud = [0.2f32; FRAME_SIZE];
+        let silence = [0.0f32; FRAME_SIZE];
+        let mut out_l = [0.0f32; FRAME_SIZE];
+        let mut out_r = [0.0f32; FRAME_SIZE];
+
+        // Only the left channel carries signal; the level is the mean power over both
+        // channels, so 1/sqrt(2) of it
+        for _ in 0..10 {
+            processor.process_frame(
+                &[&loud, &silence],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                0.0,
+                0.05,
+                false,
+            );
+        }
+        let level = f32::from_bits(processor.volume_level.load(Ordering::Relaxed));
+        assert!(
+            (level - 0.2 / 2f32.sqrt()).abs() < 0.005,
+            "Meter should read the mix: got {}",
+            level
+        );
+        assert!(processor.gate_open, "Gate should open on the combined mix");
+        assert!(processor.gate_is_open.load(Ordering::Relaxed));
+        // Per-channel meters show which side the signal is on
+        let channel_level =
+            |ch: usize| f32::from_bits(processor.channel_levels[ch].load(Ordering::Relaxed));
+        assert_eq!(processor.channel_levels.len(), 2);
+        assert!(
+            (channel_level(0) - 0.2).abs() < 0.01,
+            "Left: {}",
+            channel_level(0)
+        );
+        assert!(channel_level(1) < 0.001, "Right: {}", channel_level(1));
+        assert!(rms(&out_l) > 0.15, "Open gate should pass the loud channel");
+        assert!(rms(&out_r) < 0.001, "Silent channel must stay silent");
+
+        // Silence on both channels closes the gate for both
+        for _ in 0..200 {
+            processor.process_frame(
+                &[&silence, &silence],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                0.0,
+                0.05,
+                false,
+            );
+        }
+        assert!(!processor.gate_open, "Gate should close after release");
+        assert!(!processor.gate_is_open.load(Ordering::Relaxed));
+        assert!(rms(&out_l) < 0.001 && rms(&out_r) < 0.001);
+    }
+
+    #[test]
+    fn test_gain_reduction_is_reported() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor.vad_failed = true;
+        processor
+            .controls()
+            .compressor_enabled
+            .store(true, Ordering::Relaxed);
+        processor
+            .controls()
+            .compressor_threshold_db
+            .store((-30.0f32).to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+
+        let mut n = 0;
+        let mut run = |processor: &mut VoidProcessor, amplitude: f32| {
+            let input: Vec<f32> = (n..n + FRAME_SIZE)
+                .map(|k| sine(440.0, amplitude, k))
+                .collect();
+            n += FRAME_SIZE;
+            let mut out = [0.0f32; FRAME_SIZE];
+            processor.process_frame(&[&input], &mut [&mut out], None, 0.0, 0.05, false);
+            let read = |db: &AtomicU32| f32::from_bits(db.load(Ordering::Relaxed));
+            (
+                read(&processor.gate_reduction_db),
+                read(&processor.level_reduction_db),
+            )
+        };
+
+        // Open gate, compressor working on a loud tone
+        let mut reduction = (0.0, 0.0);
+        for _ in 0..20 {
+            reduction = run(&mut processor, 0.4);
+        }
+        assert_eq!(reduction.0, 0.0);
+        assert!(reduction.1 > 5.0, "compressor reduction {}", reduction.1);
+        assert_eq!(reduction.1, processor.compressor.gain_reduction_db());
+
+        // Closed gate reads as fully down once the fade is over
+        for _ in 0..50 {
+            reduction = run(&mut processor, 0.0);
+        }
+        assert!(!processor.gate_open);
+        assert_eq!(reduction.0, GAIN_REDUCTION_MAX_DB);
+
+        // Bypassed audio isn't reduced at all
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        for _ in 0..3 {
+            reduction = run(&mut processor, 0.4);
+        }
+        assert_eq!(reduction, (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_stereo_gate_fades_in_lockstep() {
+        const THRESHOLD: f32 = 0.05;
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        // Level-only gating, so the quiet tones can't count as speech
+        processor.vad_failed = true;
+        // 120-sample steps and a 5 ms hold put the close in the middle of a frame
+        processor
+            .controls()
+            .gate_subblocks
+            .store(4, Ordering::Relaxed);
+        processor
+            .controls()
+            .gate_hold_ms
+            .store(5, Ordering::Relaxed);
+        processor.process_updates();
+
+        // Different tones and levels on each side; quiet is 34 dB down, under the close
+        // threshold
+        let mut n = 0;
+        let mut run = |processor: &mut VoidProcessor, level: f32| {
+            let left: Vec<f32> = (n..n + FRAME_SIZE)
+                .map(|k| sine(440.0, 0.4 * level, k))
+                .collect();
+            let right: Vec<f32> = (n..n + FRAME_SIZE)
+                .map(|k| sine(1000.0, 0.2 * level, k))
+                .collect();
+            n += FRAME_SIZE;
+            let mut out_l = [0.0f32; FRAME_SIZE];
+            let mut out_r = [0.0f32; FRAME_SIZE];
+            processor.process_frame(
+                &[&left, &right],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                0.0,
+                THRESHOLD,
+                false,
+            );
+            (left, right, out_l, out_r)
+        };
+        for _ in 0..10 {
+            run(&mut processor, 1.0);
+        }
+        assert!(processor.gate_open);
+
+        // Hold runs out after two sub-blocks, then the 200 ms release takes 81 more: the
+        // gate closes at sample 240 of the 21st quiet frame and fades over the next 480
+        let closed_at = 20 * FRAME_SIZE + 240;
+        let fade_samples = (SAMPLE_RATE / 1000 * FADE_MS) as usize;
+        for frame in 0..25 {
+            let (left, right, out_l, out_r) = run(&mut processor, 0.02);
+            for k in 0..FRAME_SIZE {
+                let t = frame * FRAME_SIZE + k;
+                let gain = if t < closed_at {
+                    1.0
+                } else {
+                    FadeCurve::Cosine.gain((t - closed_at) as f32 / fade_samples as f32)
+                };
+                assert!(
+                    (out_l[k] - left[k] * gain).abs() < 1e-5
+                        && (out_r[k] - right[k] * gain).abs() < 1e-5,
+                    "Frame {} sample {}: ({}, {}) should both be faded to {}",
+                    frame,
+                    k,
+                    out_l[k],
+                    out_r[k],
+                    gain
+                );
+                if t >= closed_at + fade_samples {
+                    assert!(out_l[k] == 0.0 && out_r[k] == 0.0);
+                }
+            }
+            // The fade carries over by exactly the samples it covered
+            let expected_position = (frame * FRAME_SIZE + FRAME_SIZE)
+                .saturating_sub(closed_at)
+                .min(fade_samples);
+            assert_eq!(
+                processor.fade_position as usize, expected_position,
+                "Frame {}",
+                frame
+            );
+            assert_eq!(processor.gate_open, frame < 20, "Frame {}", frame);
+        }
+    }
+
+    #[test]
+    fn test_gate_keys_off_pre_denoise_signal() {
+        // A steady hum, which the spectral gate learns and removes: only the signal going
+        // into the chain stays over the threshold (RMS 0.042)
+        const THRESHOLD: f32 = 0.03;
+        let run = |source: GateKeySource| {
+            let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+            processor.vad_failed = true;
+            processor
+                .controls()
+                .spectral_gate
+                .store(true, Ordering::Relaxed);
+            processor
+                .controls()
+                .vad_source_channel
+                .store(VadSource::Channel(1).to_u32(), Ordering::Relaxed);
+            processor
+                .controls()
+                .gate_key_source
+                .store(source as u32, Ordering::Relaxed);
+            processor.process_updates();
+            let mut out_l = [0.0f32; FRAME_SIZE];
+            let mut out_r = [0.0f32; FRAME_SIZE];
+            for frame in 0..150 {
+                let hum: Vec<f32> = (0..FRAME_SIZE)
+                    .map(|j| sine(3_000.0, 0.06, frame * FRAME_SIZE + j))
+                    .collect();
+                processor.process_frame(
+                    &[&hum, &hum],
+                    &mut [&mut out_l, &mut out_r],
+                    None,
+                    0.0,
+                    THRESHOLD,
+                    false,
+                );
+            }
+            let level = f32::from_bits(processor.volume_level.load(Ordering::Relaxed));
+            (processor.gate_open, level)
+        };
+
+        let (open, level) = run(GateKeySource::PostDenoise);
+        assert!(
+            !open,
+            "Processed level {} should stay under the threshold",
+            level
+        );
+        let (open, level) = run(GateKeySource::PreDenoise);
+        assert!(open, "Unprocessed level {} should open the gate", level);
+        assert!((level - 0.06 / 2f32.sqrt()).abs() < 1e-3, "Level {}", level);
+
+        for source in GateKeySource::ALL {
+            assert_eq!(GateKeySource::from_u32(source as u32), source);
+        }
+        assert_eq!(GateKeySource::from_u32(7), GateKeySource::PostDenoise);
+    }
+
+    #[test]
+    fn test_stereo_eq_is_per_channel() {
+        // +12 dB high shelf, applied independently to each channel
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 12.0), 0.7, false);
+        let tone: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                (2.0 * std::f32::consts::PI * 10_000.0 * t).sin() * 0.05
+            })
+            .collect();
+        let silence = [0.0f32; FRAME_SIZE];
+        let mut out_l = [0.0f32; FRAME_SIZE];
+        let mut out_r = [0.0f32; FRAME_SIZE];
+
+        for _ in 0..10 {
+            processor.process_frame(
+                &[&tone, &silence],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                0.0,
+                0.0,
+                false,
+            );
+        }
+
+        assert!(
+            rms(&out_l) > rms(&tone) * 2.0,
+            "High shelf should boost the left channel: in={} out={}",
+            rms(&tone),
+            rms(&out_l)
+        );
+        assert!(
+            rms(&out_r) < 0.001,
+            "EQ state must not leak across channels"
+        );
+    }
+
+    #[test]
+    fn test_bypass_crossfade_is_click_free() {
+        const AMPLITUDE: f32 = 0.5;
+        const WARMUP_FRAMES: usize = 20;
+        // 440 Hz, continuous across frames
+        let step = 2.0 * std::f32::consts::PI * 440.0 / SAMPLE_RATE as f32;
+        let sine_step = AMPLITUDE * step;
+        let period = (2.0 * std::f32::consts::PI / step).ceil() as usize;
+
+        // No suppression and an open gate, so the wet path is the sine scaled by the input
+        // gain (which bypass skips): equal levels, then wet 6 dB below dry, where cutting
+        // straight over would jump
+        for wet_gain in [1.0f32, 0.5] {
+            let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+            processor
+                .controls()
+                .input_gain_db
+                .store((20.0 * wet_gain.log10()).to_bits(), Ordering::Relaxed);
+            processor.process_updates();
+            let mut phase = 0.0f32;
+            let mut played = Vec::new();
+            let mut run = |processor: &mut VoidProcessor, frames: usize| {
+                for _ in 0..frames {
+                    let input: Vec<f32> = (0..FRAME_SIZE)
+                        .map(|_| {
+                            phase += step;
+                            phase.sin() * AMPLITUDE
+                        })
+                        .collect();
+                    let mut output = [0.0f32; FRAME_SIZE];
+                    processor.process_frame(&[&input], &mut [&mut output], None, 0.0, 0.0, false);
+                    played.extend_from_slice(&output);
+                }
+            };
+
+            run(&mut processor, WARMUP_FRAMES);
+            for bypass in [true, false] {
+                processor
+                    .controls()
+                    .bypass_enabled
+                    .store(bypass, Ordering::Relaxed);
+                processor.process_updates();
+                run(&mut processor, 5);
+            }
+            assert!(matches!(processor.bypass_state, BypassState::Active));
+
+            // Both fades plus a frame either side
+            let transitions = &played[(WARMUP_FRAMES - 1) * FRAME_SIZE..];
+            let max_delta = transitions
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .fold(0.0f32, f32::max);
+            assert!(
+                max_delta < 1.5 * sine_step,
+                "Wet gain {}: crossfade jumps by {} (the sine moves at most {} per sample)",
+                wet_gain,
+                max_delta,
+                sine_step
+            );
+
+            // Peak over every stretch of one period stays between the quieter side and
+            // the equal-power sum of both (+3 dB mid-fade when they are identical)
+            let (wet, dry) = (wet_gain * AMPLITUDE, AMPLITUDE);
+            let ceiling = wet.hypot(dry) + 0.01;
+            for window in transitions.windows(period) {
+                let peak = window.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+                assert!(
+                    peak > 0.95 * wet.min(dry),
+                    "Wet gain {}: dips to {}",
+                    wet_gain,
+                    peak
+                );
+                assert!(peak < ceiling, "Wet gain {}: peaks at {}", wet_gain, peak);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stereo_bypass_crossfade_converges() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let left: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| i as f32 / FRAME_SIZE as f32 * 0.5)
+            .collect();
+        let right: Vec<f32> = left.iter().map(|s| -s).collect();
+        let mut out_l = [0.0f32; FRAME_SIZE];
+        let mut out_r = [0.0f32; FRAME_SIZE];
+
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        for _ in 0..5 {
+            processor.process_frame(
+                &[&left, &right],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                1.0,
+                0.015,
+                false,
+            );
+        }
+        assert!(matches!(processor.bypass_state, BypassState::Bypassed));
+        assert_eq!(out_l.as_slice(), left.as_slice());
+        assert_eq!(out_r.as_slice(), right.as_slice());
+
+        processor
+            .controls()
+            .bypass_enabled
+            .store(false, Ordering::Relaxed);
+        processor.process_updates();
+        for _ in 0..5 {
+            processor.process_frame(
+                &[&left, &right],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                1.0,
+                0.015,
+                false,
+            );
+        }
+        assert!(matches!(processor.bypass_state, BypassState::Active));
+    }
+
+    #[test]
+    fn test_stereo_width() {
+        let left_in: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.05).sin() * 0.3)
+            .collect();
+        let right_in: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.11).cos() * 0.2)
+            .collect();
+
+        // width = 0 collapses to mono
+        let mut left = left_in.clone();
+        let mut right = right_in.clone();
+        apply_stereo_width(&mut left, &mut right, 0.0);
+        assert_eq!(left, right);
+
+        // width = 1 leaves the image untouched
+        let mut left = left_in.clone();
+        let mut right = right_in.clone();
+        apply_stereo_width(&mut left, &mut right, 1.0);
+        assert_eq!(left, left_in);
+        assert_eq!(right, right_in);
+
+        // Through the full chain, width = 0 still yields identical channels
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .stereo_width
+            .store(0.0f32.to_bits(), Ordering::Relaxed);
+        processor.process_updates();
+        let mut out_l = [0.0f32; FRAME_SIZE];
+        let mut out_r = [0.0f32; FRAME_SIZE];
+        for _ in 0..5 {
+            processor.process_frame(
+                &[&left_in, &right_in],
+                &mut [&mut out_l, &mut out_r],
+                None,
+                1.0,
+                0.0,
+                false,
+            );
+        }
+        assert!(out_l.iter().any(|&s| s != 0.0), "Gate should be open");
+        assert_eq!(out_l, out_r);
+    }
+
+    #[test]
+    fn test_clean_reference_echo_path() {
+        // Far end: a voiced tone under loud hiss, heard back at half level
+        let mut seed = 0x1234_5678u32;
+        let frames: Vec<([f32; FRAME_SIZE], [f32; FRAME_SIZE])> = (0..200)
+            .map(|f| {
+                let mut input = [0.0f32; FRAME_SIZE];
+                let mut reference = [0.0f32; FRAME_SIZE];
+                for i in 0..FRAME_SIZE {
+                    let n = f * FRAME_SIZE + i;
+                    let voice: f32 = (1..8)
+                        .map(|h| sine(180.0 * h as f64, 0.2 / h as f32, n))
+                        .sum::<f32>()
+                        * sine(3.0, 1.0, n).abs();
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    let hiss = (seed >> 8) as f32 / (1u32 << 24) as f32 * 0.6 - 0.3;
+                    reference[i] = voice + hiss;
+                    input[i] = reference[i] * 0.5;
+                }
+                (input, reference)
+            })
+            .collect();
+        // Echo energy and what's left of it over the second half, suppression off
+        let run = |clean: bool| {
+            let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, true);
+            processor
+                .controls()
+                .clean_reference
+                .store(clean, Ordering::Relaxed);
+            processor
+                .controls()
+                .gate_mode
+                .store(GateMode::Off as u32, Ordering::Relaxed);
+            processor.process_updates();
+            let mut output = [0.0f32; FRAME_SIZE];
+            let (mut echo, mut residual) = (0.0f32, 0.0f32);
+            for (f, (input, reference)) in frames.iter().enumerate() {
+                processor.process_frame(
+                    &[input],
+                    &mut [&mut output],
+                    Some(&[reference]),
+                    0.0,
+                    0.0,
+                    false,
+                );
+                if f >= frames.len() / 2 {
+                    echo += input.iter().map(|s| s * s).sum::<f32>();
+                    residual += output.iter().map(|s| s * s).sum::<f32>();
+                }
+            }
+            (echo, residual, output)
+        };
+
+        let (echo, residual, output) = run(true);
+        let (_, _, raw_output) = run(false);
+        assert!(output.iter().all(|s| s.is_finite()));
+        assert_ne!(output, raw_output, "AEC should get the denoised reference");
+        assert!(
+            residual < echo * 0.1,
+            "Echo should still be cancelled: {} left of {}",
+            residual,
+            echo
+        );
+    }
+
+    /// Runs both processors on the same input/reference and returns the largest
+    /// sample difference in the final frame.
+    fn max_diff_after(
+        a: &mut VoidProcessor,
+        b: &mut VoidProcessor,
+        input: &[f32],
+        reference: &[f32],
+        frames: usize,
+    ) -> f32 {
+        let mut out_a = [0.0f32; FRAME_SIZE];
+        let mut out_b = [0.0f32; FRAME_SIZE];
+        a.process_updates();
+        b.process_updates();
+        for _ in 0..frames {
+            a.process_frame(
+                &[input],
+                &mut [&mut out_a],
+                Some(&[reference]),
+                0.0,
+                0.0,
+                false,
+            );
+            b.process_frame(
+                &[input],
+                &mut [&mut out_b],
+                Some(&[reference]),
+                0.0,
+                0.0,
+                false,
+            );
+        }
+        out_a
+            .iter()
+            .zip(out_b.iter())
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0f32, f32::max)
+    }
+
+    #[test]
+    fn test_echo_cancel_toggles_live() {
+        // `toggled` switches AEC on/off mid-stream; `plain` never runs it
+        let mut toggled = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let mut plain = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+
+        let mut input = [0.0f32; FRAME_SIZE];
+        let mut reference = [0.0f32; FRAME_SIZE];
+        for i in 0..FRAME_SIZE {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            reference[i] = (2.0 * std::f32::consts::PI * 440.0 * t).sin() * 0.3;
+            input[i] = reference[i] * 0.5;
+        }
+
+        let diff = max_diff_after(&mut toggled, &mut plain, &input, &reference, 10);
+        assert_eq!(diff, 0.0, "AEC off must ignore the reference");
+
+        toggled
+            .controls()
+            .echo_cancel_enabled
+            .store(true, Ordering::Relaxed);
+        let diff = max_diff_after(&mut toggled, &mut plain, &input, &reference, 10);
+        assert!(
+            diff > 1e-3,
+            "Enabling AEC should alter the output: {}",
+            diff
+        );
+
+        toggled
+            .controls()
+            .echo_cancel_enabled
+            .store(false, Ordering::Relaxed);
+        let diff = max_diff_after(&mut toggled, &mut plain, &input, &reference, 50);
+        assert!(
+            diff < 1e-3,
+            "Disabling AEC should restore passthrough: {}",
+            diff
+        );
+    }
+
+    #[test]
+    fn test_non_finite_input_is_sanitized() {
+        let mut processor = VoidProcessor::new(1, 2, (3.0, 0.0, 3.0), 0.7, false);
+        processor
+            .controls()
+            .agc_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+
+        let mut corrupt = [0.3f32; FRAME_SIZE];
+        corrupt[0] = f32::NAN;
+        corrupt[100] = f32::INFINITY;
+        corrupt[200] = f32::NEG_INFINITY;
+        let mut output = [0.0f32; FRAME_SIZE];
+
+        for _ in 0..5 {
+            processor.process_frame(&[&corrupt], &mut [&mut output], None, 1.0, 0.015, false);
+            assert!(
+                output.iter().all(|s| s.is_finite()),
+                "Output must stay finite with NaN/Inf input"
+            );
+            let level = f32::from_bits(processor.volume_level.load(Ordering::Relaxed));
+            assert!(level.is_finite(), "Meter must stay finite: got {}", level);
+        }
+
+        // Meter should recover and report the clean signal afterwards (passed through dry,
+        // since RNNoise would take the DC for noise and remove it)
+        let clean = [0.3f32; FRAME_SIZE];
+        for _ in 0..10 {
+            processor.process_frame(&[&clean], &mut [&mut output], None, 0.0, 0.015, false);
+        }
+        let level = f32::from_bits(processor.volume_level.load(Ordering::Relaxed));
+        assert!(
+            level.is_finite() && level > 0.1,
+            "Meter should recover after corrupt frames: got {}",
+            level
+        );
+        assert!(output.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn test_calibration_is_bounded_and_completes() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let input = [0.01f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+        let initial_capacity = processor.calibration_samples.capacity();
+
+        // Simulate a GUI that keeps re-arming calibration forever
+        for _ in 0..5000 {
+            processor.calibration_mode.store(true, Ordering::Relaxed);
+            processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+            assert!(processor.calibration_samples.len() <= CALIBRATION_MAX_FRAMES);
+        }
+        assert_eq!(
+            processor.calibration_samples.capacity(),
+            initial_capacity,
+            "Calibration buffer must not reallocate"
+        );
+
+        // A single calibration run must self-clear and produce a result
+        processor.calibration_mode.store(false, Ordering::Relaxed);
+        processor.calibration_result.store(0, Ordering::Relaxed);
+        processor.calibration_mode.store(true, Ordering::Relaxed);
+        for _ in 0..CALIBRATION_MAX_FRAMES {
+            processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+        }
+        assert!(!processor.calibration_mode.load(Ordering::Relaxed));
+        let result = f32::from_bits(processor.calibration_result.load(Ordering::Relaxed));
+        assert!(
+            result > 0.0,
+            "Calibration should produce a result: got {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_suggest_input_gain_db() {
+        // Already at target: no change
+        let at_target = 10f32.powf(GAIN_CALIBRATION_TARGET_DBFS / 20.0);
+        assert!(suggest_input_gain_db(at_target).abs() < 0.01);
+
+        // Quiet speaker (-30 dBFS) gets a +12 dB boost
+        let quiet = 10f32.powf(-30.0 / 20.0);
+        assert!((suggest_input_gain_db(quiet) - 12.0).abs() < 0.01);
+
+        // Loud speaker (-6 dBFS) gets attenuated
+        let loud = 10f32.powf(-6.0 / 20.0);
+        assert!((suggest_input_gain_db(loud) + 12.0).abs() < 0.01);
+
+        // Very quiet speaker: boost is capped
+        let whisper = 10f32.powf(-55.0 / 20.0);
+        assert_eq!(suggest_input_gain_db(whisper), INPUT_GAIN_MAX_DB);
+
+        // Clipping-level input: attenuation is capped too
+        assert!(suggest_input_gain_db(4.0) >= INPUT_GAIN_MIN_DB);
+
+        // Silence or garbage: no suggestion
+        assert_eq!(suggest_input_gain_db(0.0), 0.0);
+        assert_eq!(suggest_input_gain_db(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_rescale_threshold_follows_gain_change() {
+        assert_eq!(rescale_threshold(0.02, 3.0, 3.0), 0.02);
+        // +20 dB makes everything ten times louder, the threshold included
+        assert!((rescale_threshold(0.01, 0.0, 20.0) - 0.1).abs() < 1e-6);
+        assert!((rescale_threshold(0.01, 6.0, -14.0) - 0.001).abs() < 1e-7);
+
+        // A threshold calibrated at 0 dB stays just above noise that the new gain raised
+        let noise = 0.004;
+        let threshold = 0.006;
+        let gain = 10f32.powf(12.0 / 20.0);
+        let rescaled = rescale_threshold(threshold, 0.0, 12.0);
+        assert!(noise * gain < rescaled && rescaled < threshold * gain * 1.001);
+    }
+
+    #[test]
+    fn test_speech_level_ignores_pauses() {
+        // Half the frames are speech at 0.1, the rest near-silent pauses
+        let mut frames: Vec<f32> = (0..100)
+            .map(|i| if i % 2 == 0 { 0.1 } else { 0.001 })
+            .collect();
+        assert!((speech_level(&mut frames) - 0.1).abs() < 1e-6);
+        assert_eq!(speech_level(&mut []), 0.0);
+    }
+
+    #[test]
+    fn test_gain_calibration_completes() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        // Constant -30 dBFS RMS input
+        let level = 10f32.powf(-30.0 / 20.0);
+        let input = [level; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+
+        processor
+            .gain_calibration_mode
+            .store(true, Ordering::Relaxed);
+        for _ in 0..CALIBRATION_MAX_FRAMES {
+            processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+        }
+
+        assert!(!processor.gain_calibration_mode.load(Ordering::Relaxed));
+        let gain = f32::from_bits(processor.gain_calibration_result.load(Ordering::Relaxed));
+        assert!((gain - 12.0).abs() < 0.1, "Expected ~+12 dB, got {}", gain);
+    }
+
+    #[test]
+    fn test_vad_errors_fall_back_to_level_gating() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+
+        // A frame length webrtc-vad rejects (not 10/20/30ms)
+        let bad_frame = [0i16; 100];
+        for _ in 0..VAD_ERROR_LIMIT - 1 {
+            assert!(processor.detect_speech(&bad_frame, true));
+            assert!(!processor.detect_speech(&bad_frame, false));
+        }
+        // Alternating calls above pushed the error count past the limit
+        assert!(processor.vad_failed);
+
+        // Gate still opens on level alone once the VAD is out of the picture
+        let input: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.05).sin() * 0.3)
+            .collect();
+        let mut output = [0.0f32; FRAME_SIZE];
+        for _ in 0..20 {
+            processor.process_frame(&[&input], &mut [&mut output], None, 0.0, 0.015, false);
+        }
+        assert!(processor.gate_open);
+        assert!(
+            output.iter().any(|&s| s.abs() > 0.01),
+            "Gate muted after VAD failure"
+        );
+    }
+
+    #[test]
+    fn test_vad_error_count_resets_on_success() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let bad_frame = [0i16; 100];
+        let good_frame = [0i16; FRAME_SIZE];
+        for _ in 0..VAD_ERROR_LIMIT * 2 {
+            processor.detect_speech(&bad_frame, false);
+            processor.detect_speech(&good_frame, false);
+        }
+        // Sporadic errors never disable the VAD
+        assert!(!processor.vad_failed);
+    }
+
+    #[test]
+    fn test_vad_smoother_defaults_pass_through() {
+        let mut smoother = VadSmoother::default();
+        for voiced in [true, false, false, true, true, false] {
+            assert_eq!(smoother.update(voiced), voiced);
+        }
+    }
+
+    #[test]
+    fn test_vad_smoother_onset_and_hangover() {
+        // 3 of the last 5 frames, then 2 frames of hangover
+        let mut smoother = VadSmoother::new(5, 3, 2);
+        // Isolated voiced frames never reach the onset count
+        for voiced in [true, false, false, true, false, false, false] {
+            assert!(!smoother.update(voiced));
+        }
+        assert!(!smoother.update(true));
+        assert!(!smoother.update(true));
+        assert!(smoother.update(true));
+
+        // Unvoiced from here: the window keeps 3 voiced frames for 2 more frames, then
+        // the hangover covers 2 further frames
+        let held: Vec<bool> = (0..6).map(|_| smoother.update(false)).collect();
+        assert_eq!(held, [true, true, true, true, false, false]);
+    }
+
+    #[test]
+    fn test_vad_smoother_clamps_settings() {
+        let smoother = VadSmoother::new(100, 0, 1000);
+        assert_eq!(smoother.window, VAD_MAX_WINDOW_FRAMES);
+        assert_eq!(smoother.onset, 1);
+        assert_eq!(smoother.hangover, VAD_MAX_HANGOVER_FRAMES);
+        let smoother = VadSmoother::new(4, 9, 0);
+        assert_eq!(smoother.onset, 4);
+
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let controls = processor.controls();
+        controls.vad_window_frames.store(6, Ordering::Relaxed);
+        controls.vad_onset_frames.store(4, Ordering::Relaxed);
+        controls.vad_hangover_frames.store(20, Ordering::Relaxed);
+        processor.process_updates();
+        let smoother = &processor.vad_smoother;
+        assert_eq!(
+            (smoother.window, smoother.onset, smoother.hangover),
+            (6, 4, 20)
+        );
+    }
+
+    #[test]
+    fn test_calibration_duration_tracks_sample_rate() {
+        let input = [0.01f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+
+        for rate in [16_000u32, 44_100, 48_000, 96_000, 192_000] {
+            let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+            processor.set_sample_rate(rate);
+            processor.calibration_mode.store(true, Ordering::Relaxed);
+
+            let mut frames = 0usize;
+            while processor.calibration_mode.load(Ordering::Relaxed) {
+                processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+                frames += 1;
+                assert!(
+                    frames <= CALIBRATION_MAX_FRAMES,
+                    "Calibration never finished at {rate}Hz"
+                );
+            }
+
+            let seconds = (frames * FRAME_SIZE) as f32 / rate as f32;
+            assert!(
+                (seconds - CALIBRATION_SECS as f32).abs() < 0.05,
+                "Calibration at {rate}Hz took {seconds}s"
+            );
+        }
+    }
+
+    #[test]
+    fn test_process_chunk_arbitrary_lengths() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+
+        let mut total_written = 0;
+        let mut total_pushed = 0;
+        // Odd-sized chunks that never line up with FRAME_SIZE
+        for &len in [1usize, 37, 128, 479, 481, 1000, 64, 999, 4096]
+            .iter()
+            .cycle()
+            .take(40)
+        {
+            let left = vec![0.25f32; len];
+            let right = vec![-0.25f32; len];
+            let mut out_l = vec![0.0f32; len];
+            let mut out_r = vec![0.0f32; len];
+            total_written +=
+                processor.process_chunk(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+            total_pushed += len;
+        }
+
+        // Everything except at most one frame of latency must have come back out
+        assert!(
+            total_pushed - total_written < FRAME_SIZE * 2,
+            "Chunked processing lost audio: pushed {} got {}",
+            total_pushed,
+            total_written
+        );
+    }
+
+    #[test]
+    fn test_gate_events_report_transitions() {
+        let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+        let (tx, rx) = crossbeam_channel::unbounded();
+        processor.gate_event_sender = Some(tx);
+
+        let tone: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (2.0 * std::f32::consts::PI * 200.0 * i as f32 / 48_000.0).sin() * 0.3)
+            .collect();
+        let silence = [0.0f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+        for input in [&tone[..], &silence[..]] {
+            for _ in 0..50 {
+                processor.process_frame(&[input], &mut [&mut output], None, 0.0, 0.05, false);
+            }
+        }
+
+        // One event per transition, not per frame
+        let events: Vec<GateEvent> = rx.try_iter().collect();
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert!(events[0].open && !events[1].open);
+        assert!(events[0].rms > 0.05 && events[1].rms < 0.05);
+        assert!(events.iter().all(|e| e.threshold == 0.05));
+        assert!(events[0].time <= events[1].time);
+
+        // A dropped receiver turns logging off instead of failing every frame
+        drop(rx);
+        for input in [&tone[..], &silence[..]] {
+            for _ in 0..50 {
+                processor.process_frame(&[input], &mut [&mut output], None, 0.0, 0.05, false);
+            }
+        }
+        assert!(processor.gate_event_sender.is_none());
+    }
+
+    #[test]
+    fn test_frame_sender_receives_output_mix() {
+        let mut processor = VoidProcessor::new(2, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, Ordering::Relaxed);
+        processor.process_updates();
+        let (tx, rx) = crossbeam_channel::bounded(4);
+        processor.frame_sender = Some(tx);
+
+        let left = [0.5f32; FRAME_SIZE];
+        let right = [0.1f32; FRAME_SIZE];
+        let mut out_left = [0.0f32; FRAME_SIZE];
+        let mut out_right = [0.0f32; FRAME_SIZE];
+        // More frames than the channel holds: the extra ones are dropped, not waited for
+        for _ in 0..10 {
+            processor.process_frame(
+                &[&left, &right],
+                &mut [&mut out_left, &mut out_right],
+                None,
+                0.0,
+                0.0,
+                false,
+            );
+        }
+        let frames: Vec<[f32; FRAME_SIZE]> = rx.try_iter().collect();
+        assert_eq!(frames.len(), 4);
+        // Past the bypass crossfade every frame is the same, so the last one sent is the
+        // mix of the current output
+        let last = frames.last().unwrap();
+        for (j, &sample) in last.iter().enumerate() {
+            assert!((sample - (out_left[j] + out_right[j]) / 2.0).abs() < 1e-6);
+        }
+
+        drop(rx);
+        processor.process_frame(
+            &[&left, &right],
+            &mut [&mut out_left, &mut out_right],
+            None,
+            0.0,
+            0.0,
+            false,
+        );
+        assert!(processor.frame_sender.is_none());
+    }
+
+    #[test]
+    fn test_gate_opens_mid_frame_with_subblocks() {
+        // Silence, then a tone from the middle of the frame: too quiet over the whole frame
+        // (RMS 0.0125) but over the threshold in each sub-block it fills (RMS 0.0177)
+        let threshold = 0.015;
+        let mut frame = [0.0f32; FRAME_SIZE];
+        for (n, sample) in frame.iter_mut().enumerate().skip(FRAME_SIZE / 2) {
+            *sample = sine(1000.0, 0.025, n);
+        }
+        let run = |subblocks: u32| {
+            let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+            processor
+                .controls()
+                .gate_subblocks
+                .store(subblocks, Ordering::Relaxed);
+            processor.process_updates();
+            let silence = [0.0f32; FRAME_SIZE];
+            let mut out = [0.0f32; FRAME_SIZE];
+            // Let the initial fade finish
+            for _ in 0..3 {
+                processor.process_frame(&[&silence], &mut [&mut out], None, 0.0, threshold, false);
+            }
+            processor.process_frame(&[&frame], &mut [&mut out], None, 0.0, threshold, false);
+            (out, processor.gate_open)
+        };
+
+        // Once per frame, the gate never sees the tone's level
+        let (out, open) = run(1);
+        assert!(!open);
+        assert!(out.iter().all(|&s| s == 0.0));
+
+        // In 120-sample sub-blocks it opens once the 5 ms attack has passed: on the second
+        // sub-block of the tone, so everything from sample 360 on is let through
+        let (out, open) = run(4);
+        assert!(open);
+        let opened_at = FRAME_SIZE / 2 + FRAME_SIZE / 4;
+        assert!(out[..opened_at].iter().all(|&s| s == 0.0));
+        for n in opened_at..FRAME_SIZE {
+            assert!((out[n] - frame[n]).abs() < 1e-4, "sample {}", n);
+        }
+    }
+
+    #[test]
+    fn test_gate_subblock_count_divides_frame() {
+        assert_eq!(gate_subblock_count(0), 1);
+        assert_eq!(gate_subblock_count(4), 4);
+        // 480 isn't divisible by 7
+        assert_eq!(gate_subblock_count(7), 6);
+        assert_eq!(gate_subblock_count(100), GATE_MAX_SUBBLOCKS as usize);
+        for requested in 1..=GATE_MAX_SUBBLOCKS {
+            assert_eq!(FRAME_SIZE % gate_subblock_count(requested), 0);
+        }
+    }
+
+    #[test]
+    fn test_gate_hysteresis_keeps_borderline_signal_open() {
+        let tone = |amplitude: f32| -> Vec<f32> {
+            (0..FRAME_SIZE)
+                .map(|i| {
+                    (2.0 * std::f32::consts::PI * 200.0 * i as f32 / 48_000.0).sin() * amplitude
+                })
+                .collect()
+        };
+        // RMS about 0.045: below the 0.05 threshold, above 0.8 * 0.05
+        let (loud, borderline) = (tone(0.3), tone(0.064));
+        let run = |hysteresis: f32| {
+            let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+            // Level-only gating, so the VAD's opinion of a sine doesn't matter
+            processor.vad_failed = true;
+            processor
+                .controls()
+                .gate_hysteresis
+                .store(hysteresis.to_bits(), Ordering::Relaxed);
+            processor.process_updates();
+            let mut output = [0.0f32; FRAME_SIZE];
+            for input in [&loud, &borderline] {
+                for _ in 0..50 {
+                    processor.process_frame(&[input], &mut [&mut output], None, 0.0, 0.05, false);
+                }
+            }
+            processor.gate_is_open.load(Ordering::Relaxed)
+        };
+
+        assert!(
+            run(GATE_HYSTERESIS_DEFAULT),
+            "Borderline level should keep the gate open"
+        );
+        assert!(
+            !run(1.0),
+            "Without hysteresis the gate closes below the threshold"
+        );
+    }
+
+    #[test]
+    fn test_auto_suspend_skips_processing_until_activity() {
+        let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+        processor.vad_failed = true;
+        processor
+            .controls()
+            .auto_suspend_enabled
+            .store(true, Ordering::Relaxed);
+        processor
+            .controls()
+            .auto_suspend_secs
+            .store(1, Ordering::Relaxed);
+        processor.process_updates();
+
+        let tone: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (2.0 * std::f32::consts::PI * 200.0 * i as f32 / 48_000.0).sin() * 0.3)
+            .collect();
+        let silence = [0.0f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor, input: &[f32], frames: usize| {
+            for _ in 0..frames {
+                processor.process_frame(&[input], &mut [&mut output], None, 0.0, 0.05, false);
+            }
+        };
+
+        // Just under a second of closed gate: still processing
+        run(&mut processor, &silence, 90);
+        assert!(!processor.dsp_suspended.load(Ordering::Relaxed));
+        run(&mut processor, &silence, 20);
+        assert!(processor.dsp_suspended.load(Ordering::Relaxed));
+        // The chain is skipped, so the effective threshold stops being refreshed
+        processor.effective_threshold.store(0, Ordering::Relaxed);
+        run(&mut processor, &silence, 5);
+        assert_eq!(processor.effective_threshold.load(Ordering::Relaxed), 0);
+
+        // Speaking resumes the full chain and opens the gate
+        run(&mut processor, &tone, 5);
+        assert!(!processor.dsp_suspended.load(Ordering::Relaxed));
+        assert!(processor.gate_is_open.load(Ordering::Relaxed));
+        assert_eq!(
+            f32::from_bits(processor.effective_threshold.load(Ordering::Relaxed)),
+            0.05
+        );
+
+        // Turning the feature off never suspends, however long the silence
+        processor
+            .controls()
+            .auto_suspend_enabled
+            .store(false, Ordering::Relaxed);
+        processor.process_updates();
+        run(&mut processor, &silence, 300);
+        assert!(!processor.dsp_suspended.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_agc_gain_holds_while_gated() {
+        let mut processor = VoidProcessor::new(1, 0, (0.0, 0.0, 0.0), 0.7, false);
+        processor.vad_failed = true;
+        processor
+            .controls()
+            .agc_enabled
+            .store(true, Ordering::Relaxed);
+        processor
+            .controls()
+            .gate_mode
+            .store(GateMode::Expander as u32, Ordering::Relaxed);
+        processor.process_updates();
+
+        let tone = |amp: f32| -> Vec<f32> {
+            (0..FRAME_SIZE)
+                .map(|i| (2.0 * std::f32::consts::PI * 200.0 * i as f32 / 48_000.0).sin() * amp)
+                .collect()
+        };
+        let (speech, hum) = (tone(0.3), tone(0.01));
+        let mut output = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor, input: &[f32], frames: usize| {
+            for _ in 0..frames {
+                processor.process_frame(&[input], &mut [&mut output], None, 0.0, 0.05, false);
+            }
+        };
+
+        run(&mut processor, &speech, 100);
+        // Past the gate's hold and release
+        run(&mut processor, &hum, 50);
+        assert!(!processor.gate_open);
+        let gated_gain = processor.agc_limiter.current_gain;
+        // The expander lets quiet hum through, which the AGC would chase toward 3x
+        run(&mut processor, &hum, 1000);
+        assert!(
+            processor.agc_limiter.current_gain <= gated_gain,
+            "AGC gain drifted from {} to {} while gated",
+            gated_gain,
+            processor.agc_limiter.current_gain
+        );
+
+        processor
+            .controls()
+            .agc_hold_when_gated
+            .store(false, Ordering::Relaxed);
+        processor.process_updates();
+        run(&mut processor, &hum, 1000);
+        assert!(processor.agc_limiter.current_gain > gated_gain + 0.5);
+    }
+
+    #[test]
+    fn test_spectrum_buffers_are_recycled() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let (tx, rx) = crossbeam_channel::bounded(2);
+        processor.spectrum_sender = Some(tx);
+        let recycler = processor.spectrum_recycler();
+
+        let bins = || (0..200).map(|i| i as f32);
+        let mut seen = Vec::new();
+        let mut last: (Vec<f32>, Vec<f32>) = (Vec::new(), Vec::new());
+        for _ in 0..50 {
+            processor.publish_visual(VisualKind::Spectrum, bins(), bins());
+            let data = rx
+                .try_recv()
+                .expect("Pool should never run dry when recycled");
+            assert_eq!(data.0.len(), 200);
+            let ptr = data.0.as_ptr();
+            if !seen.contains(&ptr) {
+                seen.push(ptr);
+            }
+            let old = std::mem::replace(&mut last, data);
+            let _ = recycler.try_send(old);
+        }
+        assert!(
+            seen.len() <= SPECTRUM_POOL_SIZE,
+            "Only pooled buffers should circulate, saw {}",
+            seen.len()
+        );
+
+        // Without recycling, sends stop once the pool is drained
+        let mut received = 0;
+        for _ in 0..20 {
+            processor.publish_visual(VisualKind::Spectrum, bins(), bins());
+            if rx.try_recv().is_ok() {
+                received += 1;
+            }
+        }
+        assert!(received <= SPECTRUM_POOL_SIZE);
+    }
+
+    #[test]
+    fn test_waveform_view_replaces_spectrum_sends() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let (spectrum_tx, spectrum_rx) = crossbeam_channel::bounded(2);
+        let (waveform_tx, waveform_rx) = crossbeam_channel::bounded(2);
+        processor.spectrum_sender = Some(spectrum_tx);
+        processor.waveform_sender = Some(waveform_tx);
+        let input: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.05).sin() * 0.3)
+            .collect();
+        let mut output = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor| {
+            processor.process_updates();
+            for _ in 0..4 {
+                processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+            }
+        };
+
+        run(&mut processor);
+        assert!(waveform_rx.try_recv().is_err());
+        while spectrum_rx.try_recv().is_ok() {}
+
+        processor
+            .controls()
+            .waveform_view
+            .store(true, Ordering::Relaxed);
+        run(&mut processor);
+        assert!(spectrum_rx.try_recv().is_err());
+        let (wave_in, wave_out) = waveform_rx.try_recv().expect("Waveform frame expected");
+        assert_eq!(wave_in.len(), FRAME_SIZE);
+        assert_eq!(wave_out.len(), FRAME_SIZE);
+        assert!((wave_in[100] - input[100]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spectrum_disabled_skips_visual_sends() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let (spectrum_tx, spectrum_rx) = crossbeam_channel::bounded(4);
+        let (waveform_tx, waveform_rx) = crossbeam_channel::bounded(4);
+        processor.spectrum_sender = Some(spectrum_tx);
+        processor.waveform_sender = Some(waveform_tx);
+        let input: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| (i as f32 * 0.05).sin() * 0.3)
+            .collect();
+        let mut output = [0.0f32; FRAME_SIZE];
+        let mut run = |processor: &mut VoidProcessor| {
+            processor.process_updates();
+            for _ in 0..8 {
+                processor.process_frame(&[&input], &mut [&mut output], None, 1.0, 0.015, false);
+            }
+        };
+
+        // Neither view computes anything while disabled
+        processor
+            .controls()
+            .spectrum_enabled
+            .store(false, Ordering::Relaxed);
+        run(&mut processor);
+        assert!(spectrum_rx.try_recv().is_err());
+        processor
+            .controls()
+            .waveform_view
+            .store(true, Ordering::Relaxed);
+        run(&mut processor);
+        assert!(waveform_rx.try_recv().is_err());
+
+        processor
+            .controls()
+            .spectrum_enabled
+            .store(true, Ordering::Relaxed);
+        run(&mut processor);
+        assert!(waveform_rx.try_recv().is_ok(), "Sends resume once enabled");
+    }
+
+    #[test]
+    fn test_cloned_controls_drive_processing() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        // A frontend keeps its own handle, as AudioEngine and the plugins do
+        let controls = processor.controls().clone();
+        assert!(Arc::ptr_eq(
+            &controls.bypass_enabled,
+            &processor.controls().bypass_enabled
+        ));
+
+        let input: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| 0.05 * (i as f32 * 0.05).sin())
+            .collect();
+        let mut output = [0.0f32; FRAME_SIZE];
+        let run = |processor: &mut VoidProcessor, output: &mut [f32; FRAME_SIZE]| {
+            for _ in 0..30 {
+                processor.process_updates();
+                processor.process_frame(&[&input], &mut [output], None, 1.0, 0.5, false);
+            }
+        };
+
+        // Far below the threshold: the gate closes
+        run(&mut processor, &mut output);
+        assert!(output.iter().all(|s| s.abs() < 1e-3));
+
+        // Bypass set through the clone reaches the processor
+        controls.bypass_enabled.store(true, Ordering::Relaxed);
+        run(&mut processor, &mut output);
+        for (out, inp) in output.iter().zip(&input) {
+            assert!((out - inp).abs() < 0.01, "{} vs {}", out, inp);
+        }
+    }
+
+    #[test]
+    fn test_mute_fades_out_and_back_in() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let controls = processor.controls().clone();
+        // Bypass passes the input straight through, so only the mute shapes the output
+        controls.bypass_enabled.store(true, Ordering::Relaxed);
+        let input = [0.5f32; FRAME_SIZE];
+        let mut output = [0.0f32; FRAME_SIZE];
+        let run = |processor: &mut VoidProcessor, output: &mut [f32; FRAME_SIZE]| {
+            processor.process_updates();
+            processor.process_frame(&[&input], &mut [output], None, 1.0, 0.015, false);
+        };
+        for _ in 0..10 {
+            run(&mut processor, &mut output);
+        }
+        assert!((output[FRAME_SIZE - 1] - 0.5).abs() < 1e-3);
+
+        controls.muted.store(true, Ordering::Relaxed);
+        run(&mut processor, &mut output);
+        // One frame of fade, no step
+        assert!(output[0] > 0.49);
+        assert!(output.windows(2).all(|w| w[1] <= w[0]));
+        assert!(output[FRAME_SIZE - 1].abs() < 1e-6);
+        run(&mut processor, &mut output);
+        assert!(output.iter().all(|s| s.abs() < 1e-6));
+
+        controls.muted.store(false, Ordering::Relaxed);
+        run(&mut processor, &mut output);
+        assert!(output[0] < 0.01);
+        assert!((output[FRAME_SIZE - 1] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_speech_frames_count_speech_only() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        // Level-only gating: anything above the threshold counts as speech
+        processor.vad_failed = true;
+        let silence = [0.0f32; FRAME_SIZE];
+        let tone: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| 0.3 * (i as f32 * 0.1).sin())
+            .collect();
+        let mut output = [0.0f32; FRAME_SIZE];
+        for _ in 0..20 {
+            processor.process_frame(&[&silence], &mut [&mut output], None, 1.0, 0.015, false);
+        }
+        assert_eq!(processor.speech_frames.load(Ordering::Relaxed), 0);
+        for _ in 0..20 {
+            processor.process_frame(&[&tone], &mut [&mut output], None, 1.0, 0.015, false);
+        }
+        assert!(processor.speech_frames.load(Ordering::Relaxed) > 0);
+    }
+
     #[test]
     fn test_process_updates_does_not_panic() {
         let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
@@ -0,0 +1,305 @@
+//! Streaming linear-interpolation resampler for hosts that don't run at `SAMPLE_RATE`.
+//!
+//! The processor (RNNoise, VAD, gate timing) only works at 48kHz, so plugin frontends
+//! convert host-rate audio in and back out around it.
+
+use crate::constants::SAMPLE_RATE;
+use crate::frame_adapter::MAX_CHANNELS;
+use crate::processor::VoidProcessor;
+use std::collections::VecDeque;
+
+// Host samples handled per pass through `RateBridge`, so its scratch is fixed-size
+const BRIDGE_BLOCK: usize = 1024;
+// Silence queued ahead of the bridge output so resampler rounding never underruns it
+const BRIDGE_PRIME: usize = 4;
+
+/// Converts planar audio between two rates, one block at a time.
+///
+/// State carries over between calls, so blocks of any size can be fed in and the
+/// long-run output count tracks `input * to_rate / from_rate` to within one sample.
+pub struct LinearResampler {
+    // Input samples advanced per output sample
+    step: f64,
+    // Read position of the next output sample; 0.0 is the last sample of the previous block
+    pos: f64,
+    last: [f32; MAX_CHANNELS],
+}
+
+impl LinearResampler {
+    #[must_use]
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            step: from_rate.max(1) as f64 / to_rate.max(1) as f64,
+            pos: 0.0,
+            last: [0.0; MAX_CHANNELS],
+        }
+    }
+
+    /// Upper bound on the samples per channel `process` writes for `input_len` input samples.
+    #[must_use]
+    pub fn max_output_len(&self, input_len: usize) -> usize {
+        (input_len as f64 / self.step).ceil() as usize + 1
+    }
+
+    /// Clears the interpolation history.
+    pub fn reset(&mut self) {
+        self.pos = 0.0;
+        self.last = [0.0; MAX_CHANNELS];
+    }
+
+    /// Resamples one block (one slice per channel, equal lengths) and returns the number
+    /// of samples written per channel. `output` slices must hold `max_output_len` samples.
+    pub fn process(&mut self, input: &[&[f32]], output: &mut [&mut [f32]]) -> usize {
+        let channels = input.len().min(output.len()).min(MAX_CHANNELS);
+        let Some(len) = input[..channels].iter().map(|ch| ch.len()).min() else {
+            return 0;
+        };
+        if len == 0 {
+            return 0;
+        }
+        let capacity = output[..channels]
+            .iter()
+            .map(|ch| ch.len())
+            .min()
+            .unwrap_or(0);
+
+        // Index 0 is the previous block's last sample, index `j + 1` is input[j]
+        let mut written = 0;
+        while self.pos < len as f64 && written < capacity {
+            let index = self.pos as usize;
+            let frac = (self.pos - index as f64) as f32;
+            for ch in 0..channels {
+                let a = if index == 0 {
+                    self.last[ch]
+                } else {
+                    input[ch][index - 1]
+                };
+                let b = input[ch][index];
+                output[ch][written] = a + (b - a) * frac;
+            }
+            written += 1;
+            self.pos += self.step;
+        }
+
+        self.pos -= len as f64;
+        for (last, ch) in self.last.iter_mut().zip(&input[..channels]) {
+            *last = ch[len - 1];
+        }
+        written
+    }
+}
+
+/// Runs a `VoidProcessor` for a host at another rate: each host block is resampled to
+/// `SAMPLE_RATE`, processed, and resampled back into the host's buffer.
+pub struct RateBridge {
+    channels: usize,
+    to_internal: LinearResampler,
+    from_internal: LinearResampler,
+    internal_in: Vec<Vec<f32>>,
+    internal_out: Vec<Vec<f32>>,
+    host_out: Vec<Vec<f32>>,
+    pending: Vec<VecDeque<f32>>,
+}
+
+impl RateBridge {
+    /// Creates a bridge for `channels` channels (clamped to `1..=MAX_CHANNELS`).
+    #[must_use]
+    pub fn new(host_rate: u32, channels: usize) -> Self {
+        let channels = channels.clamp(1, MAX_CHANNELS);
+        let to_internal = LinearResampler::new(host_rate, SAMPLE_RATE);
+        let from_internal = LinearResampler::new(SAMPLE_RATE, host_rate);
+        let internal_len = to_internal.max_output_len(BRIDGE_BLOCK);
+        let host_len = from_internal.max_output_len(internal_len);
+        let pending_len = host_len + BRIDGE_BLOCK + BRIDGE_PRIME;
+
+        let pending = (0..channels)
+            .map(|_| {
+                let mut channel = VecDeque::with_capacity(pending_len);
+                channel.resize(BRIDGE_PRIME, 0.0);
+                channel
+            })
+            .collect();
+
+        Self {
+            channels,
+            to_internal,
+            from_internal,
+            internal_in: vec![vec![0.0; internal_len]; channels],
+            internal_out: vec![vec![0.0; internal_len]; channels],
+            host_out: vec![vec![0.0; host_len]; channels],
+            pending,
+        }
+    }
+
+    /// Processes one host block in place of `processor.process_chunk`; `input` and
+    /// `output` hold one slice per channel.
+    pub fn process(
+        &mut self,
+        processor: &mut VoidProcessor,
+        input: &[&[f32]],
+        output: &mut [&mut [f32]],
+    ) {
+        let channels = self.channels.min(input.len()).min(output.len());
+        let len = input[..channels]
+            .iter()
+            .map(|ch| ch.len())
+            .chain(output[..channels].iter().map(|ch| ch.len()))
+            .min()
+            .unwrap_or(0);
+
+        let mut offset = 0;
+        while offset < len {
+            let end = (offset + BRIDGE_BLOCK).min(len);
+            let host_in: [&[f32]; MAX_CHANNELS] = std::array::from_fn(|ch| {
+                input
+                    .get(ch)
+                    .map_or(&[][..], |samples| &samples[offset..end])
+            });
+            let internal = self.to_internal.process(
+                &host_in[..channels],
+                &mut planar_mut(&mut self.internal_in, usize::MAX)[..channels],
+            );
+
+            processor.process_chunk(
+                &planar(&self.internal_in, internal)[..channels],
+                &mut planar_mut(&mut self.internal_out, internal)[..channels],
+            );
+
+            let host = self.from_internal.process(
+                &planar(&self.internal_out, internal)[..channels],
+                &mut planar_mut(&mut self.host_out, usize::MAX)[..channels],
+            );
+
+            for (pending, produced) in self.pending.iter_mut().zip(&self.host_out) {
+                // Never grow past the preallocated capacity on the audio thread
+                let room = pending.capacity() - pending.len();
+                pending.extend(produced[..host.min(room)].iter().copied());
+            }
+            for (pending, out) in self.pending.iter_mut().zip(output.iter_mut()) {
+                for sample in out[offset..end].iter_mut() {
+                    *sample = pending.pop_front().unwrap_or(0.0);
+                }
+            }
+            offset = end;
+        }
+    }
+}
+
+/// The first `len` samples of each buffer, as the planar slices the processor takes.
+fn planar(buffers: &[Vec<f32>], len: usize) -> [&[f32]; MAX_CHANNELS] {
+    let mut buffers = buffers.iter();
+    std::array::from_fn(|_| {
+        buffers
+            .next()
+            .map_or(&[][..], |buffer| &buffer[..len.min(buffer.len())])
+    })
+}
+
+fn planar_mut(buffers: &mut [Vec<f32>], len: usize) -> [&mut [f32]; MAX_CHANNELS] {
+    let mut buffers = buffers.iter_mut();
+    std::array::from_fn(|_| {
+        buffers.next().map_or(&mut [][..], |buffer| {
+            let len = len.min(buffer.len());
+            &mut buffer[..len]
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_blocks(resampler: &mut LinearResampler, input: &[f32], block: usize) -> Vec<f32> {
+        let mut out = Vec::new();
+        let mut scratch = vec![0.0f32; resampler.max_output_len(block)];
+        for chunk in input.chunks(block) {
+            let n = resampler.process(&[chunk], &mut [&mut scratch[..]]);
+            out.extend_from_slice(&scratch[..n]);
+        }
+        out
+    }
+
+    #[test]
+    fn test_output_count_tracks_ratio() {
+        for (from, to) in [(44_100, 48_000), (48_000, 44_100), (96_000, 48_000)] {
+            let mut resampler = LinearResampler::new(from, to);
+            let input = vec![0.0f32; from as usize];
+            let out = run_blocks(&mut resampler, &input, 256);
+            let diff = out.len() as i64 - to as i64;
+            assert!(
+                diff.abs() <= 1,
+                "{}->{}: got {} samples",
+                from,
+                to,
+                out.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_rate_is_passthrough_delayed_by_one() {
+        let mut resampler = LinearResampler::new(48_000, 48_000);
+        let input: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+        let out = run_blocks(&mut resampler, &input, 100);
+        assert_eq!(out.len(), input.len());
+        assert_eq!(out[0], 0.0);
+        assert_eq!(&out[1..], &input[..input.len() - 1]);
+    }
+
+    #[test]
+    fn test_tone_survives_round_trip() {
+        let tone: Vec<f32> = (0..44_100)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44_100.0).sin() * 0.5)
+            .collect();
+        let mut up = LinearResampler::new(44_100, 48_000);
+        let mut down = LinearResampler::new(48_000, 44_100);
+        let internal = run_blocks(&mut up, &tone, 512);
+        let back = run_blocks(&mut down, &internal, 480);
+
+        let peak = back[1000..].iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(
+            (peak - 0.5).abs() < 0.02,
+            "Tone level changed: peak {}",
+            peak
+        );
+    }
+
+    #[test]
+    fn test_rate_bridge_passes_audio_at_host_rate() {
+        let mut processor = VoidProcessor::new(1, 2, (0.0, 0.0, 0.0), 0.7, false);
+        processor
+            .controls()
+            .bypass_enabled
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        processor.process_updates();
+        let mut bridge = RateBridge::new(44_100, 1);
+
+        let tone: Vec<f32> = (0..44_100)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44_100.0).sin() * 0.5)
+            .collect();
+        let mut out = vec![1.0f32; tone.len()];
+        for (input, output) in tone.chunks(512).zip(out.chunks_mut(512)) {
+            bridge.process(&mut processor, &[input], &mut [output]);
+        }
+
+        // Bypassed audio comes back at the same pitch and level, only delayed, and
+        // without dropouts once the frame buffering has settled
+        let second_half = &out[out.len() / 2..];
+        let peak = second_half.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(
+            (peak - 0.5).abs() < 0.02,
+            "Tone level changed: peak {}",
+            peak
+        );
+        let crossings = second_half
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count();
+        assert!((219..=221).contains(&crossings), "{} cycles", crossings);
+        let max_step = second_half
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_step < 0.04, "Discontinuity of {}", max_step);
+    }
+}
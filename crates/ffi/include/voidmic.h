@@ -0,0 +1,44 @@
+/* C interface to the VoidMic DSP (crates/ffi). */
+#ifndef VOIDMIC_H
+#define VOIDMIC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Samples per channel per void_processor_process_frame call (480 = 10ms). */
+extern const uint32_t VOIDMIC_FRAME_SIZE;
+/* All audio must be at this rate (48000 Hz). */
+extern const uint32_t VOIDMIC_SAMPLE_RATE;
+
+#define VOIDMIC_OK 0
+#define VOIDMIC_ERR_NULL (-1)
+#define VOIDMIC_ERR_LENGTH (-2)
+#define VOIDMIC_ERR_PANIC (-3)
+
+typedef struct VoidProcessorHandle VoidProcessorHandle;
+
+/* Returns NULL if channels is not in 1..=8. */
+VoidProcessorHandle *void_processor_new(uint32_t channels);
+
+/* NULL is ignored. */
+void void_processor_free(VoidProcessorHandle *handle);
+
+/* Interleaved float audio; len must be VOIDMIC_FRAME_SIZE * channels.
+ * input and output may be the same buffer. */
+int32_t void_processor_process_frame(VoidProcessorHandle *handle,
+                                     const float *input,
+                                     float *output,
+                                     size_t len);
+
+int32_t void_processor_set_threshold(VoidProcessorHandle *handle, float threshold);
+int32_t void_processor_set_suppression(VoidProcessorHandle *handle, float suppression);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VOIDMIC_H */
@@ -0,0 +1,261 @@
+//! C ABI for `VoidProcessor`, so the DSP can be used outside Rust.
+//!
+//! See `include/voidmic.h` for the C declarations. All audio is 48kHz `float`,
+//! interleaved, exactly `VOIDMIC_FRAME_SIZE` samples per channel per call.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::Ordering;
+use voidmic_core::constants::{FRAME_SIZE, SAMPLE_RATE};
+use voidmic_core::frame_adapter::MAX_CHANNELS;
+use voidmic_core::VoidProcessor;
+
+/// Samples per channel per `void_processor_process_frame` call.
+#[no_mangle]
+pub static VOIDMIC_FRAME_SIZE: u32 = FRAME_SIZE as u32;
+
+/// Sample rate the processor runs at.
+#[no_mangle]
+pub static VOIDMIC_SAMPLE_RATE: u32 = SAMPLE_RATE;
+
+pub const VOIDMIC_OK: i32 = 0;
+pub const VOIDMIC_ERR_NULL: i32 = -1;
+pub const VOIDMIC_ERR_LENGTH: i32 = -2;
+pub const VOIDMIC_ERR_PANIC: i32 = -3;
+
+/// Opaque handle given to C callers.
+pub struct VoidProcessorHandle {
+    processor: VoidProcessor,
+    channels: usize,
+    // Deinterleaved per-channel scratch, allocated once at creation
+    input: Vec<[f32; FRAME_SIZE]>,
+    output: Vec<[f32; FRAME_SIZE]>,
+}
+
+/// Creates a processor for `channels` interleaved channels (1..=8).
+///
+/// Returns null if `channels` is out of range. Free with `void_processor_free`.
+#[no_mangle]
+pub extern "C" fn void_processor_new(channels: u32) -> *mut VoidProcessorHandle {
+    let channels = channels as usize;
+    if channels == 0 || channels > MAX_CHANNELS {
+        return std::ptr::null_mut();
+    }
+
+    let created = catch_unwind(|| VoidProcessorHandle {
+        processor: VoidProcessor::new(channels, 2, (0.0, 0.0, 0.0), 0.7, false),
+        channels,
+        input: vec![[0.0; FRAME_SIZE]; channels],
+        output: vec![[0.0; FRAME_SIZE]; channels],
+    });
+    match created {
+        Ok(handle) => Box::into_raw(Box::new(handle)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Frees a processor. Null is ignored.
+///
+/// # Safety
+/// `handle` must be null or a pointer returned by `void_processor_new` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn void_processor_free(handle: *mut VoidProcessorHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Processes one frame of interleaved audio.
+///
+/// `len` is the total sample count of each buffer and must equal
+/// `VOIDMIC_FRAME_SIZE * channels`. `input` and `output` may point to the same buffer.
+///
+/// # Safety
+/// `handle` must be a live handle from `void_processor_new`, and `input`/`output`
+/// must each be valid for `len` floats.
+#[no_mangle]
+pub unsafe extern "C" fn void_processor_process_frame(
+    handle: *mut VoidProcessorHandle,
+    input: *const f32,
+    output: *mut f32,
+    len: usize,
+) -> i32 {
+    let Some(handle) = handle.as_mut() else {
+        return VOIDMIC_ERR_NULL;
+    };
+    if input.is_null() || output.is_null() {
+        return VOIDMIC_ERR_NULL;
+    }
+    if len != FRAME_SIZE * handle.channels {
+        return VOIDMIC_ERR_LENGTH;
+    }
+
+    // Deinterleave before taking the output slice, so in-place calls never alias
+    let input = std::slice::from_raw_parts(input, len);
+    for (i, frame) in input.chunks_exact(handle.channels).enumerate() {
+        for (ch, &sample) in frame.iter().enumerate() {
+            handle.input[ch][i] = sample;
+        }
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| handle.process()));
+    if result.is_err() {
+        return VOIDMIC_ERR_PANIC;
+    }
+
+    let output = std::slice::from_raw_parts_mut(output, len);
+    for (i, frame) in output.chunks_exact_mut(handle.channels).enumerate() {
+        for (ch, sample) in frame.iter_mut().enumerate() {
+            *sample = handle.output[ch][i];
+        }
+    }
+    VOIDMIC_OK
+}
+
+/// Sets the gate threshold (RMS, typically 0.005..0.05).
+///
+/// # Safety
+/// `handle` must be null or a live handle from `void_processor_new`.
+#[no_mangle]
+pub unsafe extern "C" fn void_processor_set_threshold(
+    handle: *mut VoidProcessorHandle,
+    threshold: f32,
+) -> i32 {
+    let Some(handle) = handle.as_ref() else {
+        return VOIDMIC_ERR_NULL;
+    };
+    handle
+        .processor
+        .controls()
+        .gate_threshold
+        .store(threshold.to_bits(), Ordering::Relaxed);
+    VOIDMIC_OK
+}
+
+/// Sets the suppression strength (0.0 = dry, 1.0 = fully denoised).
+///
+/// # Safety
+/// `handle` must be null or a live handle from `void_processor_new`.
+#[no_mangle]
+pub unsafe extern "C" fn void_processor_set_suppression(
+    handle: *mut VoidProcessorHandle,
+    suppression: f32,
+) -> i32 {
+    let Some(handle) = handle.as_ref() else {
+        return VOIDMIC_ERR_NULL;
+    };
+    handle
+        .processor
+        .controls()
+        .suppression_strength
+        .store(suppression.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    VOIDMIC_OK
+}
+
+impl VoidProcessorHandle {
+    fn process(&mut self) {
+        self.processor.process_updates();
+        let controls = self.processor.controls();
+        let suppression = f32::from_bits(controls.suppression_strength.load(Ordering::Relaxed));
+        let threshold = f32::from_bits(controls.gate_threshold.load(Ordering::Relaxed));
+        let dynamic_threshold = controls.dynamic_threshold_enabled.load(Ordering::Relaxed);
+
+        // Fixed-size slice tables keep the audio path allocation-free
+        let mut inputs = self.input.iter();
+        let input: [&[f32]; MAX_CHANNELS] =
+            std::array::from_fn(|_| inputs.next().map_or(&[][..], |ch| &ch[..]));
+        let mut outputs = self.output.iter_mut();
+        let mut output: [&mut [f32]; MAX_CHANNELS] =
+            std::array::from_fn(|_| outputs.next().map_or(&mut [][..], |ch| &mut ch[..]));
+        self.processor.process_frame(
+            &input[..self.channels],
+            &mut output[..self.channels],
+            None,
+            suppression,
+            threshold,
+            dynamic_threshold,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_bad_channel_counts() {
+        assert!(void_processor_new(0).is_null());
+        assert!(void_processor_new(MAX_CHANNELS as u32 + 1).is_null());
+    }
+
+    #[test]
+    fn test_null_and_length_guards() {
+        let mut buf = vec![0.0f32; FRAME_SIZE];
+        unsafe {
+            assert_eq!(
+                void_processor_process_frame(
+                    std::ptr::null_mut(),
+                    buf.as_ptr(),
+                    buf.as_mut_ptr(),
+                    buf.len()
+                ),
+                VOIDMIC_ERR_NULL
+            );
+            assert_eq!(
+                void_processor_set_threshold(std::ptr::null_mut(), 0.01),
+                VOIDMIC_ERR_NULL
+            );
+            assert_eq!(
+                void_processor_set_suppression(std::ptr::null_mut(), 1.0),
+                VOIDMIC_ERR_NULL
+            );
+            void_processor_free(std::ptr::null_mut());
+
+            let handle = void_processor_new(2);
+            assert!(!handle.is_null());
+            let input = vec![0.0f32; FRAME_SIZE * 2];
+            let mut output = vec![0.0f32; FRAME_SIZE * 2];
+            assert_eq!(
+                void_processor_process_frame(handle, std::ptr::null(), output.as_mut_ptr(), 960),
+                VOIDMIC_ERR_NULL
+            );
+            // Mono-sized buffer on a stereo handle
+            assert_eq!(
+                void_processor_process_frame(
+                    handle,
+                    input.as_ptr(),
+                    output.as_mut_ptr(),
+                    FRAME_SIZE
+                ),
+                VOIDMIC_ERR_LENGTH
+            );
+            void_processor_free(handle);
+        }
+    }
+
+    #[test]
+    fn test_process_through_opaque_pointer() {
+        unsafe {
+            let handle = void_processor_new(2);
+            assert!(!handle.is_null());
+            assert_eq!(void_processor_set_threshold(handle, 0.015), VOIDMIC_OK);
+            assert_eq!(void_processor_set_suppression(handle, 0.0), VOIDMIC_OK);
+
+            // Interleaved stereo tone, processed in place
+            let mut buf: Vec<f32> = (0..FRAME_SIZE * 2)
+                .map(|i| (i / 2) as f32 * 0.05)
+                .map(|t| t.sin() * 0.3)
+                .collect();
+            for _ in 0..50 {
+                let ptr = buf.as_mut_ptr();
+                assert_eq!(
+                    void_processor_process_frame(handle, ptr, ptr, buf.len()),
+                    VOIDMIC_OK
+                );
+            }
+            assert!(buf.iter().all(|s| s.is_finite()));
+
+            void_processor_free(handle);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use lv2::prelude::*;
 use std::sync::atomic::Ordering;
 use voidmic_core::constants::SAMPLE_RATE;
-use voidmic_core::{FrameAdapter, VoidProcessor};
+use voidmic_core::{RateBridge, VoidProcessor};
 
 #[derive(PortCollection)]
 struct VoidMicPorts {
@@ -12,12 +12,16 @@ struct VoidMicPorts {
     threshold: InputPort<Control>,
     suppression: InputPort<Control>,
     bypass: InputPort<Control>,
+    agc: InputPort<Control>,
+    eq: InputPort<Control>,
+    compressor: InputPort<Control>,
 }
 
 #[uri("https://github.com/Detair/voidvoice/lv2/voidmic")]
 struct VoidMic {
     processor: VoidProcessor,
-    adapter: FrameAdapter,
+    // Present when the host doesn't run at SAMPLE_RATE
+    bridge: Option<RateBridge>,
 }
 
 // Safety: LV2 hosts guarantee that Plugin::run() is called from a single audio thread.
@@ -30,16 +34,22 @@ impl Plugin for VoidMic {
     type InitFeatures = ();
     type AudioFeatures = ();
 
-    fn new(_info: &PluginInfo, _features: &mut ()) -> Option<Self> {
-        // Validate sample rate - VoidMic requires 48kHz
-        if _info.sample_rate() as u32 != SAMPLE_RATE {
-            eprintln!(
-                "VoidMic LV2: requires {}Hz sample rate, host is using {}Hz",
-                SAMPLE_RATE,
-                _info.sample_rate()
-            );
+    fn new(info: &PluginInfo, _features: &mut ()) -> Option<Self> {
+        // The processor runs at 48kHz; other host rates go through the resampling bridge
+        let host_rate = info.sample_rate().round() as u32;
+        if host_rate == 0 {
+            eprintln!("VoidMic LV2: host reported an invalid sample rate");
             return None;
         }
+        let bridge = if host_rate != SAMPLE_RATE {
+            eprintln!(
+                "VoidMic LV2: host is using {}Hz, resampling to {}Hz internally",
+                host_rate, SAMPLE_RATE
+            );
+            Some(RateBridge::new(host_rate, 2))
+        } else {
+            None
+        };
 
         let processor = VoidProcessor::new(
             2,               // Channels: Stereo
@@ -49,10 +59,7 @@ impl Plugin for VoidMic {
             false,           // Echo Cancel disabled
         );
 
-        Some(Self {
-            processor,
-            adapter: FrameAdapter::new(),
-        })
+        Some(Self { processor, bridge })
     }
 
     fn run(&mut self, ports: &mut VoidMicPorts, _features: &mut (), _sample_count: u32) {
@@ -60,36 +67,38 @@ impl Plugin for VoidMic {
         let threshold = *ports.threshold;
         let suppression = *ports.suppression;
         let bypass = *ports.bypass > 0.5;
+        let agc = *ports.agc > 0.5;
+        let eq = *ports.eq > 0.5;
+        let compressor = *ports.compressor > 0.5;
 
-        self.processor
-            .bypass_enabled
-            .store(bypass, Ordering::Relaxed);
+        let controls = self.processor.controls();
+        controls
+            .gate_threshold
+            .store(threshold.to_bits(), Ordering::Relaxed);
+        controls
+            .suppression_strength
+            .store(suppression.to_bits(), Ordering::Relaxed);
+        controls.bypass_enabled.store(bypass, Ordering::Relaxed);
+        controls.agc_enabled.store(agc, Ordering::Relaxed);
+        controls.eq_enabled.store(eq, Ordering::Relaxed);
+        controls
+            .compressor_enabled
+            .store(compressor, Ordering::Relaxed);
         self.processor.process_updates();
 
-        // 2. Push Input (stack-allocated, avoids heap per-callback)
-        let num_samples = ports.input_l.len();
-        let mut input_l = [0.0f32; 8192];
-        let mut input_r = [0.0f32; 8192];
-        let n = num_samples.min(8192);
-        input_l[..n].copy_from_slice(&ports.input_l[..n]);
-        input_r[..n].copy_from_slice(&ports.input_r[..n]);
-        self.adapter.push_stereo_interleaved(&input_l[..n], &input_r[..n]);
-
-        // 3. Process available frames
-        self.adapter.process_available(
-            &mut self.processor,
-            suppression,
-            threshold,
-            false,
-        );
-
-        // 4. Fill Output
-        let mut out_l = [0.0f32; 8192];
-        let mut out_r = [0.0f32; 8192];
-        self.adapter.pop_stereo(&mut out_l[..n], &mut out_r[..n]);
-
-        ports.output_l[..n].copy_from_slice(&out_l[..n]);
-        ports.output_r[..n].copy_from_slice(&out_r[..n]);
+        // 2. Process (the processor buffers any host block size internally)
+        if let Some(bridge) = &mut self.bridge {
+            bridge.process(
+                &mut self.processor,
+                &[&ports.input_l[..], &ports.input_r[..]],
+                &mut [&mut ports.output_l[..], &mut ports.output_r[..]],
+            );
+        } else {
+            self.processor.process_chunk(
+                &[&ports.input_l[..], &ports.input_r[..]],
+                &mut [&mut ports.output_l[..], &mut ports.output_r[..]],
+            );
+        }
     }
 }
 
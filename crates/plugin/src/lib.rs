@@ -1,11 +1,12 @@
-use crossbeam_channel::Receiver;
+use crossbeam_channel::{Receiver, Sender};
 use nih_plug::prelude::*;
 use nih_plug_egui::{create_egui_editor, widgets, EguiState};
 use std::num::NonZeroU32;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
 use voidmic_core::constants::SAMPLE_RATE;
-use voidmic_core::{FrameAdapter, VoidProcessor};
+use voidmic_core::processor::VadSource;
+use voidmic_core::{RateBridge, VoidProcessor};
 use voidmic_ui::{theme, visualizer, widgets as ui_widgets};
 
 struct VoidMicPlugin {
@@ -13,11 +14,21 @@ struct VoidMicPlugin {
 
     // Audio Processing State
     processor: Option<VoidProcessor>,
-    adapter: Option<FrameAdapter>,
+    // Present when the host doesn't run at SAMPLE_RATE
+    bridge: Option<RateBridge>,
+    // Copy of the host input per channel (the host buffer is processed in place)
+    scratch: Vec<Vec<f32>>,
 
-    // GUI Data Bridging
+    // GUI Data Bridging: created once and handed to each processor, so an editor opened
+    // before `initialize` still reads what the processor publishes
     volume_level: Arc<AtomicU32>,
+    channel_levels: Arc<[AtomicU32]>,
+    gate_reduction_db: Arc<AtomicU32>,
+    level_reduction_db: Arc<AtomicU32>,
     spectrum_receiver: Option<Receiver<(Vec<f32>, Vec<f32>)>>,
+    spectrum_recycler: Option<Sender<(Vec<f32>, Vec<f32>)>>,
+    // Active layout channel count (stereo-only controls are hidden otherwise)
+    channels: Arc<AtomicUsize>,
 }
 
 #[derive(Params)]
@@ -36,13 +47,31 @@ struct VoidMicParams {
 
     #[id = "agc"]
     pub agc_enabled: BoolParam,
+
+    #[id = "eq"]
+    pub eq_enabled: BoolParam,
+
+    #[id = "compressor"]
+    pub compressor_enabled: BoolParam,
+
+    #[id = "width"]
+    pub stereo_width: FloatParam,
+
+    /// `VadSource` as u32: mix, loudest, left or right
+    #[id = "vad_source_channel"]
+    pub vad_source_channel: IntParam,
 }
 
 struct GuiData {
     params: Arc<VoidMicParams>,
     volume_level: Arc<AtomicU32>,
+    channel_levels: Arc<[AtomicU32]>,
+    gate_reduction_db: Arc<AtomicU32>,
+    level_reduction_db: Arc<AtomicU32>,
     spectrum_receiver: Option<Receiver<(Vec<f32>, Vec<f32>)>>,
+    spectrum_recycler: Option<Sender<(Vec<f32>, Vec<f32>)>>,
     last_spectrum_data: (Vec<f32>, Vec<f32>),
+    channels: Arc<AtomicUsize>,
 }
 
 impl Default for VoidMicPlugin {
@@ -50,9 +79,16 @@ impl Default for VoidMicPlugin {
         Self {
             params: Arc::new(VoidMicParams::default()),
             processor: None,
-            adapter: None,
+            bridge: None,
+            scratch: Vec::new(),
             volume_level: Arc::new(AtomicU32::new(0)),
+            // One per channel of the widest layout (stereo)
+            channel_levels: (0..2).map(|_| AtomicU32::new(0)).collect(),
+            gate_reduction_db: Arc::new(AtomicU32::new(0)),
+            level_reduction_db: Arc::new(AtomicU32::new(0)),
             spectrum_receiver: None,
+            spectrum_recycler: None,
+            channels: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
@@ -81,6 +117,33 @@ impl Default for VoidMicParams {
 
             bypass: BoolParam::new("Bypass", false),
             agc_enabled: BoolParam::new("AGC", false),
+            eq_enabled: BoolParam::new("EQ", true),
+            compressor_enabled: BoolParam::new("Compressor", false),
+
+            stereo_width: FloatParam::new(
+                "Stereo Width",
+                1.0,
+                FloatRange::Linear { min: 0.0, max: 2.0 },
+            )
+            .with_step_size(0.01)
+            .with_unit("x"),
+
+            vad_source_channel: IntParam::new(
+                "VAD Channel",
+                VadSource::Mix.to_u32() as i32,
+                IntRange::Linear {
+                    min: VadSource::Mix.to_u32() as i32,
+                    max: VadSource::Channel(1).to_u32() as i32,
+                },
+            )
+            .with_value_to_string(Arc::new(|value| {
+                match VadSource::from_u32(value.max(0) as u32) {
+                    VadSource::Mix => "Mix".to_string(),
+                    VadSource::Loudest => "Loudest".to_string(),
+                    VadSource::Channel(0) => "Left".to_string(),
+                    VadSource::Channel(_) => "Right".to_string(),
+                }
+            })),
         }
     }
 }
@@ -119,8 +182,13 @@ impl Plugin for VoidMicPlugin {
         let gui_data = GuiData {
             params: self.params.clone(),
             volume_level: self.volume_level.clone(),
+            channel_levels: self.channel_levels.clone(),
+            gate_reduction_db: self.gate_reduction_db.clone(),
+            level_reduction_db: self.level_reduction_db.clone(),
             spectrum_receiver: self.spectrum_receiver.clone(),
+            spectrum_recycler: self.spectrum_recycler.clone(),
             last_spectrum_data: (Vec::new(), Vec::new()),
+            channels: self.channels.clone(),
         };
 
         create_egui_editor(
@@ -130,7 +198,6 @@ impl Plugin for VoidMicPlugin {
                 theme::setup_custom_style(egui_ctx, true);
             },
             move |egui_ctx, setter, state| {
-
                 egui::CentralPanel::default().show(egui_ctx, |ui| {
                     ui.heading("VoidMic Plugin");
                     ui.separator();
@@ -155,12 +222,49 @@ impl Plugin for VoidMicPlugin {
                     ui.label("Suppression:");
                     ui.add(widgets::ParamSlider::for_param(&params.suppression, setter));
 
+                    if state.channels.load(Ordering::Relaxed) == 2 {
+                        ui.label("Stereo Width:");
+                        ui.add(widgets::ParamSlider::for_param(
+                            &params.stereo_width,
+                            setter,
+                        ));
+                        ui.label("VAD Channel:");
+                        ui.add(widgets::ParamSlider::for_param(
+                            &params.vad_source_channel,
+                            setter,
+                        ));
+                    }
+
                     ui.separator();
 
                     // Volume Meter
                     let vol = f32::from_bits(state.volume_level.load(Ordering::Relaxed));
                     let thresh = params.gate_threshold.value();
-                    ui_widgets::render_volume_meter(ui, vol, thresh);
+                    let ballistics = ui_widgets::MeterBallistics::default();
+                    ui_widgets::render_volume_meter(ui, vol, thresh, ballistics);
+                    if state.channels.load(Ordering::Relaxed) > 1 {
+                        let levels: Vec<f32> = state
+                            .channel_levels
+                            .iter()
+                            .map(|level| f32::from_bits(level.load(Ordering::Relaxed)))
+                            .collect();
+                        ui_widgets::render_channel_meters(ui, &levels, thresh, ballistics);
+                    }
+
+                    // Gain reduction: what the gate and the AGC are taking off
+                    let reduction = |db: &AtomicU32| f32::from_bits(db.load(Ordering::Relaxed));
+                    ui_widgets::render_gain_reduction_meter(
+                        ui,
+                        "Gate",
+                        reduction(&state.gate_reduction_db),
+                        ballistics,
+                    );
+                    ui_widgets::render_gain_reduction_meter(
+                        ui,
+                        "AGC",
+                        reduction(&state.level_reduction_db),
+                        ballistics,
+                    );
 
                     // Visualizer
                     ui.add_space(10.0);
@@ -168,13 +272,17 @@ impl Plugin for VoidMicPlugin {
 
                     if let Some(rx) = &state.spectrum_receiver {
                         while let Ok(data) = rx.try_recv() {
-                            state.last_spectrum_data = data;
+                            let old = std::mem::replace(&mut state.last_spectrum_data, data);
+                            if let Some(recycler) = &state.spectrum_recycler {
+                                let _ = recycler.try_send(old);
+                            }
                         }
                     }
                     visualizer::render_spectrum(
                         ui,
                         &state.last_spectrum_data.0,
                         &state.last_spectrum_data.1,
+                        &visualizer::SpectrumDisplayConfig::default(),
                     );
                 });
             },
@@ -183,35 +291,57 @@ impl Plugin for VoidMicPlugin {
 
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
+        audio_io_layout: &AudioIOLayout,
         buffer_config: &BufferConfig,
         _context: &mut impl InitContext<Self>,
     ) -> bool {
-        if buffer_config.sample_rate != SAMPLE_RATE as f32 {
-            nih_log!(
-                "VoidMic requires 48kHz sample rate. Host is using {:.0}Hz. Plugin initialization rejected.",
-                buffer_config.sample_rate
-            );
+        // The processor runs at 48kHz; other host rates go through the resampling bridge
+        let host_rate = buffer_config.sample_rate.round() as u32;
+        if host_rate == 0 {
+            nih_log!("VoidMic: host reported an invalid sample rate");
             return false;
         }
 
-        // process() always interleaves to stereo internally, so the processor
-        // and ring buffers must always be sized for 2 channels regardless of layout.
+        let channels = audio_io_layout
+            .main_input_channels
+            .map_or(2, |c| c.get() as usize)
+            .clamp(1, 2);
+
+        self.bridge = if host_rate != SAMPLE_RATE {
+            nih_log!(
+                "VoidMic: host is using {}Hz, resampling to {}Hz internally",
+                host_rate,
+                SAMPLE_RATE
+            );
+            Some(RateBridge::new(host_rate, channels))
+        } else {
+            None
+        };
+
         let (tx, rx) = crossbeam_channel::bounded(2);
         self.spectrum_receiver = Some(rx);
 
         let mut processor = VoidProcessor::new(
-            2, // Always stereo: process() duplicates mono to stereo
+            channels,
             2, // VAD Sensitivity (Aggressive)
             (0.0, 0.0, 0.0),
             0.7,
             false,
         );
         processor.spectrum_sender = Some(tx);
-
-        self.volume_level = processor.volume_level.clone();
+        self.spectrum_recycler = Some(processor.spectrum_recycler());
+        processor
+            .controls()
+            .dynamic_threshold_enabled
+            .store(true, Ordering::Relaxed);
+
+        processor.volume_level = self.volume_level.clone();
+        processor.channel_levels = self.channel_levels.clone();
+        processor.gate_reduction_db = self.gate_reduction_db.clone();
+        processor.level_reduction_db = self.level_reduction_db.clone();
         self.processor = Some(processor);
-        self.adapter = Some(FrameAdapter::new());
+        self.channels.store(channels, Ordering::Relaxed);
+        self.scratch = vec![vec![0.0; buffer_config.max_buffer_size as usize]; channels];
 
         true
     }
@@ -226,55 +356,119 @@ impl Plugin for VoidMicPlugin {
             Some(p) => p,
             None => return ProcessStatus::Normal,
         };
-        let adapter = match self.adapter.as_mut() {
-            Some(a) => a,
-            None => return ProcessStatus::Normal,
-        };
 
-        processor
+        let controls = processor.controls();
+        controls
             .bypass_enabled
             .store(self.params.bypass.value(), Ordering::Relaxed);
-        processor
+        controls
             .agc_enabled
             .store(self.params.agc_enabled.value(), Ordering::Relaxed);
+        controls
+            .eq_enabled
+            .store(self.params.eq_enabled.value(), Ordering::Relaxed);
+        controls
+            .compressor_enabled
+            .store(self.params.compressor_enabled.value(), Ordering::Relaxed);
+        controls.gate_threshold.store(
+            self.params.gate_threshold.value().to_bits(),
+            Ordering::Relaxed,
+        );
+        controls
+            .suppression_strength
+            .store(self.params.suppression.value().to_bits(), Ordering::Relaxed);
+        controls.stereo_width.store(
+            self.params.stereo_width.value().to_bits(),
+            Ordering::Relaxed,
+        );
+        controls.vad_source_channel.store(
+            self.params.vad_source_channel.value().max(0) as u32,
+            Ordering::Relaxed,
+        );
+        // No spectrum FFTs while the editor is closed
+        controls
+            .spectrum_enabled
+            .store(self.params.editor_state.is_open(), Ordering::Relaxed);
 
         processor.process_updates();
 
-        let channel_data = buffer.as_slice();
-        let num_channels = channel_data.len();
-        if num_channels == 0 {
-            return ProcessStatus::Normal;
-        }
-        let num_samples = channel_data[0].len();
-
-        // 1. Push Input
-        if num_channels == 2 {
-            adapter.push_stereo_interleaved(&channel_data[0][..num_samples], &channel_data[1][..num_samples]);
-        } else if num_channels == 1 {
-            adapter.push_mono(&channel_data[0][..num_samples]);
-        }
-
-        // 2. Process available frames
-        adapter.process_available(
+        process_block(
             processor,
-            self.params.suppression.value(),
-            self.params.gate_threshold.value(),
-            true,
+            self.bridge.as_mut(),
+            &mut self.scratch,
+            buffer.as_slice(),
         );
 
-        // 3. Output
-        if num_channels == 1 {
-            adapter.pop_mono(&mut channel_data[0][..num_samples]);
-        } else {
-            // Split borrows: we need mutable references to two different slices
-            let (left_slice, rest) = channel_data.split_at_mut(1);
-            adapter.pop_stereo(
-                &mut left_slice[0][..num_samples],
-                &mut rest[0][..num_samples],
-            );
+        ProcessStatus::Normal
+    }
+}
+
+/// Processes the host's channels in place, using `scratch` (one buffer per processor
+/// channel) to keep a copy of the input.
+///
+/// Only the channels the processor was set up for are processed; any extra channels
+/// are left as they are. Channels of different lengths are processed up to the
+/// shortest, and blocks longer than `scratch` in pieces. A host that delivers fewer
+/// channels than the layout promised gets its audio back unprocessed.
+fn process_block(
+    processor: &mut VoidProcessor,
+    mut bridge: Option<&mut RateBridge>,
+    scratch: &mut [Vec<f32>],
+    channel_data: &mut [&mut [f32]],
+) {
+    let channels = scratch.len();
+    if channels == 0 || channel_data.len() < channels {
+        return;
+    }
+    let channel_data = &mut channel_data[..channels];
+    let num_samples = channel_data.iter().map(|ch| ch.len()).min().unwrap_or(0);
+    let chunk_len = scratch.iter().map(Vec::len).min().unwrap_or(0);
+    if num_samples == 0 || chunk_len == 0 {
+        return;
+    }
+
+    let mut start = 0;
+    while start < num_samples {
+        let end = (start + chunk_len).min(num_samples);
+        let len = end - start;
+
+        // 1. Keep a copy of the input, since output is written over the same buffer
+        for (scratch, host) in scratch.iter_mut().zip(channel_data.iter()) {
+            scratch[..len].copy_from_slice(&host[start..end]);
         }
 
-        ProcessStatus::Normal
+        // 2. Process (the processor buffers any host block size internally)
+        let bridge = bridge.as_deref_mut();
+        match (&scratch[..], &mut channel_data[..]) {
+            ([left_in, right_in], [left_out, right_out]) => process_at_host_rate(
+                processor,
+                bridge,
+                &[&left_in[..len], &right_in[..len]],
+                &mut [&mut left_out[start..end], &mut right_out[start..end]],
+            ),
+            (inputs, outputs) => process_at_host_rate(
+                processor,
+                bridge,
+                &[&inputs[0][..len]],
+                &mut [&mut outputs[0][start..end]],
+            ),
+        }
+        start = end;
+    }
+}
+
+/// Runs `processor` directly, or through `bridge` when the host isn't at `SAMPLE_RATE`.
+fn process_at_host_rate(
+    processor: &mut VoidProcessor,
+    bridge: Option<&mut RateBridge>,
+    input: &[&[f32]],
+    output: &mut [&mut [f32]],
+) {
+    match bridge {
+        Some(bridge) => bridge.process(processor, input, output),
+        None => {
+            processor.process_chunk(input, output);
+        }
     }
 }
 
@@ -294,3 +488,69 @@ impl Vst3Plugin for VoidMicPlugin {
 
 nih_export_clap!(VoidMicPlugin);
 nih_export_vst3!(VoidMicPlugin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Marks samples the plugin must leave alone; processed ones come out as the
+    // processor's silent start-up latency instead
+    const MARK: f32 = 0.5;
+
+    fn run(channels: usize, scratch_len: usize, lengths: &[usize]) -> Vec<Vec<f32>> {
+        let mut processor = VoidProcessor::new(channels, 2, (0.0, 0.0, 0.0), 0.7, false);
+        let mut scratch = vec![vec![0.0; scratch_len]; channels];
+        // Fake host buffer: one slice per channel, as `Buffer::as_slice` hands them out
+        let mut data: Vec<Vec<f32>> = lengths.iter().map(|&len| vec![MARK; len]).collect();
+        let mut slices: Vec<&mut [f32]> = data.iter_mut().map(Vec::as_mut_slice).collect();
+        process_block(&mut processor, None, &mut scratch, &mut slices);
+        data
+    }
+
+    fn untouched(samples: &[f32]) -> bool {
+        samples.iter().all(|&s| s == MARK)
+    }
+
+    fn processed(samples: &[f32]) -> bool {
+        samples.iter().all(|&s| s != MARK)
+    }
+
+    #[test]
+    fn test_mismatched_channel_lengths_process_the_shortest() {
+        let data = run(2, 256, &[200, 120]);
+        assert!(processed(&data[0][..120]) && processed(&data[1]));
+        assert!(untouched(&data[0][120..]));
+    }
+
+    #[test]
+    fn test_empty_buffers_are_ignored() {
+        assert_eq!(run(2, 256, &[0, 0]), vec![Vec::<f32>::new(); 2]);
+        assert!(run(2, 256, &[]).is_empty());
+        let data = run(2, 256, &[64, 0]);
+        assert!(untouched(&data[0]));
+        // Not prepared yet (no scratch space)
+        assert!(untouched(&run(1, 0, &[64])[0]));
+    }
+
+    #[test]
+    fn test_extra_channels_are_left_alone() {
+        let data = run(2, 256, &[128, 128, 128, 128]);
+        assert!(processed(&data[0]) && processed(&data[1]));
+        assert!(untouched(&data[2]) && untouched(&data[3]));
+
+        let data = run(1, 256, &[128, 128]);
+        assert!(processed(&data[0]) && untouched(&data[1]));
+    }
+
+    #[test]
+    fn test_missing_channels_pass_through() {
+        let data = run(2, 256, &[128]);
+        assert!(untouched(&data[0]));
+    }
+
+    #[test]
+    fn test_block_longer_than_scratch_is_processed_in_pieces() {
+        let data = run(2, 100, &[450, 450]);
+        assert!(processed(&data[0]) && processed(&data[1]));
+    }
+}
@@ -5,6 +5,8 @@ pub const ACCENT_BLUE: Color32 = Color32::from_rgb(88, 166, 255);
 pub const SUCCESS_GREEN: Color32 = Color32::from_rgb(46, 160, 67);
 pub const WARNING_YELLOW: Color32 = Color32::from_rgb(255, 193, 7);
 pub const DANGER_RED: Color32 = Color32::from_rgb(248, 81, 73);
+// Darker accent that keeps contrast on light backgrounds
+pub const ACCENT_BLUE_LIGHT: Color32 = Color32::from_rgb(9, 105, 218);
 
 pub fn setup_custom_style(ctx: &egui::Context, dark_mode: bool) {
     if dark_mode {
@@ -37,27 +39,41 @@ pub fn setup_custom_style(ctx: &egui::Context, dark_mode: bool) {
 
         ctx.set_visuals(visuals);
     } else {
-        // Clean Light Mode
         let mut visuals = Visuals::light();
-        
-        let panel_color = Color32::from_rgb(248, 249, 250);
-        let text_color = Color32::from_rgb(36, 41, 47);
-        
+
+        // Clean Light Palette (mirror of the dark one)
+        let bg_color = Color32::from_rgb(255, 255, 255);
+        let panel_color = Color32::from_rgb(246, 248, 250);
+        let text_color = Color32::from_rgb(31, 35, 40);
+
+        visuals.window_fill = bg_color;
         visuals.panel_fill = panel_color;
+        visuals.extreme_bg_color = bg_color;
+        visuals.faint_bg_color = Color32::from_rgb(234, 238, 242);
         visuals.override_text_color = Some(text_color);
-        
-        visuals.widgets.inactive.bg_fill = Color32::from_rgb(235, 237, 240);
+
+        // Widgets
+        visuals.widgets.noninteractive.bg_fill = panel_color;
+        visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, text_color);
+        visuals.widgets.noninteractive.bg_stroke =
+            Stroke::new(1.0, Color32::from_rgb(208, 215, 222));
+
+        visuals.widgets.inactive.bg_fill = Color32::from_rgb(234, 238, 242);
+        visuals.widgets.inactive.weak_bg_fill = Color32::from_rgb(234, 238, 242);
         visuals.widgets.inactive.corner_radius = CornerRadius::same(6);
-        
-        visuals.widgets.hovered.bg_fill = Color32::from_rgb(220, 223, 228);
+
+        visuals.widgets.hovered.bg_fill = Color32::from_rgb(220, 226, 232);
+        visuals.widgets.hovered.weak_bg_fill = Color32::from_rgb(220, 226, 232);
         visuals.widgets.hovered.corner_radius = CornerRadius::same(6);
-        
-        visuals.widgets.active.bg_fill = Color32::from_rgb(0, 120, 215);
+
+        visuals.widgets.active.bg_fill = ACCENT_BLUE_LIGHT;
         visuals.widgets.active.fg_stroke = Stroke::new(1.0, Color32::WHITE);
         visuals.widgets.active.corner_radius = CornerRadius::same(6);
-        
-        visuals.selection.bg_fill = Color32::from_rgb(0, 120, 215);
-        
+
+        visuals.selection.bg_fill = Color32::from_rgb(84, 174, 255);
+        visuals.selection.stroke = Stroke::new(1.0, ACCENT_BLUE_LIGHT);
+        visuals.hyperlink_color = ACCENT_BLUE_LIGHT;
+
         ctx.set_visuals(visuals);
     }
 }
@@ -1,27 +1,221 @@
-use egui_plot::{Line, Plot, PlotPoints};
+use egui_plot::{HLine, Line, LineStyle, Plot, PlotPoints};
+use std::collections::VecDeque;
+use voidmic_core::processor::spectrum_bin_hz;
 
-pub fn render_spectrum(ui: &mut egui::Ui, input_data: &[f32], output_data: &[f32]) {
+// Per-frame fall-off of held peaks (~20 dB per second at the ~25fps spectrum rate)
+const PEAK_DECAY: f32 = 0.9;
+// Bottom of the gate activity chart
+const GATE_HISTORY_FLOOR_DB: f32 = -60.0;
+
+/// How `render_spectrum` draws the spectrum. The default is the original plot:
+/// linear frequency axis, linear magnitude, no peak hold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectrumDisplayConfig {
+    /// Log-scaled frequency axis (gives the voice range more room)
+    pub log_frequency: bool,
+    /// Plot magnitudes in dB instead of linear
+    pub db_scale: bool,
+    /// Bottom of the dB scale; quieter bins are clamped to it
+    pub db_floor: f32,
+    /// Overlay a slowly falling trace of recent output peaks
+    pub peak_hold: bool,
+}
+
+impl Default for SpectrumDisplayConfig {
+    fn default() -> Self {
+        Self {
+            log_frequency: false,
+            db_scale: false,
+            db_floor: -80.0,
+            peak_hold: false,
+        }
+    }
+}
+
+impl SpectrumDisplayConfig {
+    fn x(&self, bin: usize) -> f64 {
+        let hz = spectrum_bin_hz(bin) as f64;
+        if self.log_frequency {
+            hz.log10()
+        } else {
+            hz
+        }
+    }
+
+    fn y(&self, magnitude: f32) -> f64 {
+        if self.db_scale {
+            (20.0 * magnitude.max(1e-9).log10()).max(self.db_floor) as f64
+        } else {
+            magnitude as f64
+        }
+    }
+
+    fn baseline(&self) -> f32 {
+        if self.db_scale {
+            self.db_floor
+        } else {
+            0.0
+        }
+    }
+
+    fn points(&self, data: &[f32]) -> Vec<[f64; 2]> {
+        data.iter()
+            .enumerate()
+            .map(|(bin, &magnitude)| [self.x(bin), self.y(magnitude)])
+            .collect()
+    }
+}
+
+pub fn render_spectrum(
+    ui: &mut egui::Ui,
+    input_data: &[f32],
+    output_data: &[f32],
+    display: &SpectrumDisplayConfig,
+) {
     if input_data.is_empty() {
         ui.label("Waiting for audio...");
         return;
     }
 
-    let red_line = Line::new(PlotPoints::from_ys_f32(input_data))
+    let red_line = Line::new(PlotPoints::from(display.points(input_data)))
         .color(egui::Color32::from_rgba_unmultiplied(220, 53, 69, 180)) // Clearer red
-        .fill(0.0); // Fill input (noise)
+        .fill(display.baseline()); // Fill input (noise)
 
-    let green_line = Line::new(PlotPoints::from_ys_f32(output_data))
+    let green_line = Line::new(PlotPoints::from(display.points(output_data)))
         .color(egui::Color32::GREEN)
         .width(2.0); // Clean output
 
-    Plot::new("spectrum")
+    // Held peaks live in egui memory so callers don't have to carry the state
+    let peaks_id = ui.id().with("spectrum_peaks");
+    let peak_line = if display.peak_hold {
+        let points = ui.ctx().data_mut(|data| {
+            let peaks = data.get_temp_mut_or_default::<Vec<f32>>(peaks_id);
+            peaks.resize(output_data.len(), 0.0);
+            for (peak, &magnitude) in peaks.iter_mut().zip(output_data) {
+                *peak = magnitude.max(*peak * PEAK_DECAY);
+            }
+            display.points(peaks)
+        });
+        Some(
+            Line::new(PlotPoints::from(points))
+                .color(egui::Color32::from_rgb(255, 193, 7))
+                .width(1.0),
+        )
+    } else {
+        ui.ctx().data_mut(|data| data.remove::<Vec<f32>>(peaks_id));
+        None
+    };
+
+    let mut plot = Plot::new("spectrum")
+        .height(100.0)
+        .show_axes([false, false])
+        .show_grid([false, false])
+        .allow_drag(false)
+        .allow_zoom(false);
+    if display.db_scale {
+        plot = plot.include_y(display.db_floor).include_y(0.0);
+    }
+    plot.show(ui, |plot_ui| {
+        plot_ui.line(red_line);
+        plot_ui.line(green_line);
+        if let Some(line) = peak_line {
+            plot_ui.line(line);
+        }
+    });
+}
+
+/// Oscilloscope view of mono input vs. output samples; the dashed lines mark full scale,
+/// so clipping and gate closures are easy to spot.
+pub fn render_waveform(ui: &mut egui::Ui, input_data: &[f32], output_data: &[f32]) {
+    if input_data.is_empty() {
+        ui.label("Waiting for audio...");
+        return;
+    }
+
+    let red_line = Line::new(PlotPoints::from_ys_f32(input_data))
+        .color(egui::Color32::from_rgba_unmultiplied(220, 53, 69, 180));
+    let green_line = Line::new(PlotPoints::from_ys_f32(output_data))
+        .color(egui::Color32::GREEN)
+        .width(1.5);
+    let full_scale = egui::Color32::from_gray(120);
+
+    Plot::new("waveform")
         .height(100.0)
         .show_axes([false, false])
         .show_grid([false, false])
         .allow_drag(false)
         .allow_zoom(false)
+        .include_y(-1.0)
+        .include_y(1.0)
         .show(ui, |plot_ui| {
+            for y in [1.0, -1.0] {
+                plot_ui.hline(
+                    HLine::new(y)
+                        .color(full_scale)
+                        .style(LineStyle::dashed_loose()),
+                );
+            }
             plot_ui.line(red_line);
             plot_ui.line(green_line);
         });
 }
+
+/// One snapshot of the gate for `render_gate_history`.
+#[derive(Clone, Copy, Debug)]
+pub struct GateSample {
+    /// Seconds, on any monotonic clock (e.g. egui's `input.time`)
+    pub time: f64,
+    pub rms: f32,
+    pub threshold: f32,
+    pub open: bool,
+}
+
+/// Scrolling strip chart of the gate's input level against its threshold (dashed),
+/// shaded green while the gate is open. Shows the last `span_secs` seconds.
+pub fn render_gate_history(ui: &mut egui::Ui, history: &VecDeque<GateSample>, span_secs: f64) {
+    let Some(latest) = history.back() else {
+        ui.label("Waiting for audio...");
+        return;
+    };
+    let now = latest.time;
+    let to_db = |level: f32| (20.0 * level.max(1e-9).log10()).max(GATE_HISTORY_FLOOR_DB) as f64;
+    let series = |value: &dyn Fn(&GateSample) -> f64| -> Vec<[f64; 2]> {
+        history
+            .iter()
+            .map(|sample| [sample.time - now, value(sample)])
+            .collect()
+    };
+
+    let open_band = Line::new(PlotPoints::from(series(&|sample| {
+        if sample.open {
+            0.0
+        } else {
+            GATE_HISTORY_FLOOR_DB as f64
+        }
+    })))
+    .color(egui::Color32::from_rgba_unmultiplied(40, 167, 69, 60))
+    .fill(GATE_HISTORY_FLOOR_DB)
+    .width(0.0);
+    let level_line = Line::new(PlotPoints::from(series(&|sample| to_db(sample.rms))))
+        .color(ui.visuals().strong_text_color())
+        .width(1.5);
+    let threshold_line = Line::new(PlotPoints::from(series(&|sample| to_db(sample.threshold))))
+        .color(egui::Color32::from_rgb(255, 152, 0))
+        .style(LineStyle::dashed_loose());
+
+    Plot::new("gate_history")
+        .height(80.0)
+        .show_axes([false, false])
+        .show_grid([false, false])
+        .allow_drag(false)
+        .allow_zoom(false)
+        .include_x(-span_secs)
+        .include_x(0.0)
+        .include_y(GATE_HISTORY_FLOOR_DB)
+        .include_y(0.0)
+        .show(ui, |plot_ui| {
+            plot_ui.line(open_band);
+            plot_ui.line(level_line);
+            plot_ui.line(threshold_line);
+        });
+}
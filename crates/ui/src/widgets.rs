@@ -1,32 +1,218 @@
 use egui::{Pos2, Sense, Stroke};
 
+// Height of the main meter and of each per-channel meter
+const METER_HEIGHT: f32 = 20.0;
+const CHANNEL_METER_HEIGHT: f32 = 12.0;
+
+// Bottom of the meter scale
+const METER_FLOOR_DB: f32 = -60.0;
+
+/// PPM (IEC 60268-10 style): time constant of the rise, and the fall rate (20 dB in 1.5 s).
+pub const PPM_RISE_MS: f32 = 10.0;
+pub const PPM_FALL_DB_PER_SEC: f32 = 20.0 / 1.5;
+/// VU: time to reach 99% of a step, rising and falling alike.
+pub const VU_RESPONSE_MS: f32 = 300.0;
+
+/// How the level meters follow the signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MeterBallistics {
+    /// Peak programme meter: fast rise, slow linear fall
+    #[default]
+    Ppm,
+    /// Volume unit meter: averages over ~300 ms both ways
+    Vu,
+    /// Raw level every frame, no smoothing
+    Instant,
+}
+
+impl MeterBallistics {
+    pub const ALL: [MeterBallistics; 3] = [
+        MeterBallistics::Ppm,
+        MeterBallistics::Vu,
+        MeterBallistics::Instant,
+    ];
+
+    /// Decodes a stored value; unknown values fall back to `Ppm`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => MeterBallistics::Vu,
+            2 => MeterBallistics::Instant,
+            _ => MeterBallistics::Ppm,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MeterBallistics::Ppm => "PPM",
+            MeterBallistics::Vu => "VU",
+            MeterBallistics::Instant => "Instant",
+        }
+    }
+
+    /// Moves the displayed level (dB) `dt` seconds toward `target_db`.
+    pub fn step(self, shown_db: f32, target_db: f32, dt: f32) -> f32 {
+        let dt = dt.max(0.0);
+        // Exponential approach with time constant `tau` (seconds)
+        let approach = |tau: f32| shown_db + (target_db - shown_db) * (1.0 - (-dt / tau).exp());
+        match self {
+            MeterBallistics::Instant => target_db,
+            MeterBallistics::Ppm if target_db >= shown_db => approach(PPM_RISE_MS / 1000.0),
+            MeterBallistics::Ppm => (shown_db - PPM_FALL_DB_PER_SEC * dt).max(target_db),
+            // 99% of a step takes ln(100) time constants
+            MeterBallistics::Vu => approach(VU_RESPONSE_MS / 1000.0 / 100f32.ln()),
+        }
+    }
+}
+
 /// Renders a horizontal volume meter with a threshold indicator.
-pub fn render_volume_meter(ui: &mut egui::Ui, volume: f32, gate_threshold: f32) {
-    // Calculate DB for current volume
-    let volume_db = if volume > 0.0001 {
-        20.0 * volume.log10()
-    } else {
-        -60.0
-    };
-    let bar_len = ((volume_db + 60.0) / 60.0).clamp(0.0, 1.0);
+pub fn render_volume_meter(
+    ui: &mut egui::Ui,
+    volume: f32,
+    gate_threshold: f32,
+    ballistics: MeterBallistics,
+) {
+    paint_meter(ui, volume, Some(gate_threshold), METER_HEIGHT, ballistics);
 
-    // Calculate DB for threshold
-    let threshold_db = if gate_threshold > 0.0001 {
-        20.0 * gate_threshold.log10()
-    } else {
-        -60.0
-    };
-    let threshold_pos = ((threshold_db + 60.0) / 60.0).clamp(0.0, 1.0);
+    ui.label(
+        egui::RichText::new(
+            "White Line = Gate Threshold. Keep noise to the left, voice to the right.",
+        )
+        .size(10.0),
+    );
+}
 
-    let color = if volume > gate_threshold {
-        egui::Color32::GREEN
+/// Renders a slim meter per channel (L/R for stereo) on the same scale as
+/// `render_volume_meter`, to catch a dead or unbalanced channel.
+pub fn render_channel_meters(
+    ui: &mut egui::Ui,
+    levels: &[f32],
+    gate_threshold: f32,
+    ballistics: MeterBallistics,
+) {
+    for (ch, &level) in levels.iter().enumerate() {
+        let name = match (levels.len(), ch) {
+            (2, 0) => "L".to_string(),
+            (2, _) => "R".to_string(),
+            _ => (ch + 1).to_string(),
+        };
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(name).monospace().size(10.0));
+            paint_meter(
+                ui,
+                level,
+                Some(gate_threshold),
+                CHANNEL_METER_HEIGHT,
+                ballistics,
+            );
+        });
+    }
+}
+
+/// Renders a slim meter of the level reaching the output device, on the same scale as
+/// `render_volume_meter` but without a threshold, shown as `label` with `hint` on hover.
+pub fn render_output_meter(
+    ui: &mut egui::Ui,
+    label: &str,
+    hint: &str,
+    level: f32,
+    ballistics: MeterBallistics,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(label).monospace().size(10.0))
+            .on_hover_text(hint);
+        paint_meter(ui, level, None, CHANNEL_METER_HEIGHT, ballistics);
+    });
+}
+
+/// Renders a slim gain-reduction meter: a bar growing leftward from the right edge by
+/// `reduction_db` (positive dB), on the same 60 dB scale as the level meters.
+pub fn render_gain_reduction_meter(
+    ui: &mut egui::Ui,
+    label: &str,
+    reduction_db: f32,
+    ballistics: MeterBallistics,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(label).monospace().size(10.0));
+        let (rect, response) = ui.allocate_at_least(
+            egui::vec2(ui.available_width(), CHANNEL_METER_HEIGHT),
+            Sense::hover(),
+        );
+        let shown_db = follow(ui, response.id, reduction_db, ballistics);
+        let bar_len = (shown_db / -METER_FLOOR_DB).clamp(0.0, 1.0);
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter();
+            painter.rect_filled(rect, 2.0, egui::Color32::from_gray(40));
+            if bar_len > 0.0 {
+                let mut fill_rect = rect;
+                fill_rect.set_left(rect.max.x - rect.width() * bar_len);
+                painter.rect_filled(fill_rect, 2.0, egui::Color32::from_rgb(255, 140, 0));
+            }
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("-{shown_db:.1} dB"),
+                egui::FontId::proportional(CHANNEL_METER_HEIGHT * 0.6),
+                egui::Color32::WHITE,
+            );
+        }
+    });
+}
+
+/// Sets what screen readers announce for a button: `label` instead of its text, and for
+/// toggles (`on` is `Some`) whether it is on.
+pub fn describe_button(response: &egui::Response, label: &str, on: Option<bool>) {
+    let enabled = response.enabled();
+    response.widget_info(|| match on {
+        Some(on) => egui::WidgetInfo::selected(egui::WidgetType::Button, enabled, on, label),
+        None => egui::WidgetInfo::labeled(egui::WidgetType::Button, enabled, label),
+    });
+}
+
+fn level_db(level: f32) -> f32 {
+    if level > 0.0001 {
+        20.0 * level.log10()
     } else {
-        egui::Color32::DARK_GRAY
-    };
+        METER_FLOOR_DB
+    }
+}
+
+/// Moves meter `id`'s displayed value (dB) toward `target_db` by `ballistics` and
+/// returns it. The value carries over between frames, kept in egui's memory per meter.
+fn follow(ui: &egui::Ui, id: egui::Id, target_db: f32, ballistics: MeterBallistics) -> f32 {
+    let dt = ui.input(|i| i.stable_dt).min(0.1);
+    let shown_db = ui.data_mut(|data| {
+        let shown = data.get_temp_mut_or_insert_with(id, || target_db);
+        *shown = ballistics.step(*shown, target_db, dt);
+        *shown
+    });
+    if (shown_db - target_db).abs() > 0.1 {
+        ui.ctx().request_repaint();
+    }
+    shown_db
+}
 
+/// Paints one meter; `gate_threshold`, if given, is marked and colors the bar.
+fn paint_meter(
+    ui: &mut egui::Ui,
+    volume: f32,
+    gate_threshold: Option<f32>,
+    height: f32,
+    ballistics: MeterBallistics,
+) {
     // Custom painting
-    let (rect, _response) =
-        ui.allocate_at_least(egui::vec2(ui.available_width(), 20.0), Sense::hover());
+    let (rect, response) =
+        ui.allocate_at_least(egui::vec2(ui.available_width(), height), Sense::hover());
+
+    let volume_db = follow(ui, response.id, level_db(volume), ballistics);
+    let bar_len = ((volume_db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0);
+
+    let color = match gate_threshold {
+        Some(threshold) if volume <= threshold => egui::Color32::DARK_GRAY,
+        Some(_) => egui::Color32::GREEN,
+        None => egui::Color32::LIGHT_BLUE,
+    };
 
     if ui.is_rect_visible(rect) {
         let painter = ui.painter();
@@ -42,14 +228,18 @@ pub fn render_volume_meter(ui: &mut egui::Ui, volume: f32, gate_threshold: f32)
         }
 
         // Threshold Marker
-        let marker_x = rect.width().mul_add(threshold_pos, rect.min.x);
-        painter.line_segment(
-            [
-                Pos2::new(marker_x, rect.min.y),
-                Pos2::new(marker_x, rect.max.y),
-            ],
-            Stroke::new(2.0, egui::Color32::WHITE),
-        );
+        if let Some(threshold) = gate_threshold {
+            let threshold_db = level_db(threshold);
+            let threshold_pos = ((threshold_db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0);
+            let marker_x = rect.width().mul_add(threshold_pos, rect.min.x);
+            painter.line_segment(
+                [
+                    Pos2::new(marker_x, rect.min.y),
+                    Pos2::new(marker_x, rect.max.y),
+                ],
+                Stroke::new(2.0, egui::Color32::WHITE),
+            );
+        }
 
         // Text overlay
         let text = format!("{volume_db:.1} dB");
@@ -57,15 +247,72 @@ pub fn render_volume_meter(ui: &mut egui::Ui, volume: f32, gate_threshold: f32)
             rect.center(),
             egui::Align2::CENTER_CENTER,
             text,
-            egui::FontId::proportional(12.0),
+            egui::FontId::proportional(height * 0.6),
             egui::Color32::WHITE,
         );
     }
+}
 
-    ui.label(
-        egui::RichText::new(
-            "White Line = Gate Threshold. Keep noise to the left, voice to the right.",
-        )
-        .size(10.0),
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs `ballistics` on a constant `target_db` for `secs`, in steps of `dt`
+    fn settle(
+        ballistics: MeterBallistics,
+        from_db: f32,
+        target_db: f32,
+        secs: f32,
+        dt: f32,
+    ) -> f32 {
+        let mut shown = from_db;
+        for _ in 0..(secs / dt).round() as usize {
+            shown = ballistics.step(shown, target_db, dt);
+        }
+        shown
+    }
+
+    #[test]
+    fn test_ppm_step_response() {
+        // One rise time constant covers 63% of a 60 dB step
+        let rise = settle(MeterBallistics::Ppm, -60.0, 0.0, 0.01, 0.001);
+        assert!(
+            (rise - -60.0 * (-1.0f32).exp()).abs() < 0.5,
+            "rise {}",
+            rise
+        );
+
+        for dt in [0.001, 1.0 / 60.0] {
+            let settled = settle(MeterBallistics::Ppm, -60.0, 0.0, 0.1, dt);
+            assert!(settled > -0.5, "dt {}: {} after 100 ms", dt, settled);
+
+            // Falls 20 dB in 1.5 s, whatever the frame rate
+            let fall = settle(MeterBallistics::Ppm, 0.0, -60.0, 1.5, dt);
+            assert!(
+                (fall - -20.0).abs() < 0.5,
+                "dt {}: {} after 1.5 s",
+                dt,
+                fall
+            );
+        }
+    }
+
+    #[test]
+    fn test_vu_step_response() {
+        for dt in [0.001, 1.0 / 60.0] {
+            // 99% of the step in 300 ms, in both directions
+            let rise = settle(MeterBallistics::Vu, -60.0, 0.0, 0.3, dt);
+            assert!((rise - -0.6).abs() < 0.1, "dt {}: {}", dt, rise);
+            let fall = settle(MeterBallistics::Vu, 0.0, -60.0, 0.3, dt);
+            assert!((fall - -59.4).abs() < 0.1, "dt {}: {}", dt, fall);
+            // ...and well short of it a third of the way in
+            assert!(settle(MeterBallistics::Vu, -60.0, 0.0, 0.1, dt) < -10.0);
+        }
+    }
+
+    #[test]
+    fn test_instant_follows_level() {
+        assert_eq!(MeterBallistics::Instant.step(-60.0, -3.0, 0.001), -3.0);
+        assert_eq!(MeterBallistics::Instant.step(0.0, -40.0, 0.001), -40.0);
+    }
 }